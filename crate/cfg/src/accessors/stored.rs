@@ -9,7 +9,7 @@ use peace_data::{
         resman::{BorrowFail, Ref},
         DataAccess, DataAccessDyn, Resources, TypeIds,
     },
-    Data,
+    Data, DataBorrowInfo,
 };
 use peace_item_model::ItemId;
 use peace_resource_rt::{states::StatesCurrentStored, type_reg::untagged::DataType};
@@ -58,6 +58,10 @@ where
             marker: PhantomData,
         }
     }
+
+    fn borrow_infos() -> Vec<DataBorrowInfo> {
+        vec![DataBorrowInfo::new::<StatesCurrentStored>(false, false)]
+    }
 }
 
 impl<T> DataAccess for Stored<'_, T> {
@@ -2,6 +2,8 @@ use std::marker::PhantomData;
 
 use peace_item_model::ItemId;
 
+use crate::InterruptToken;
+
 #[cfg(feature = "output_progress")]
 use peace_progress_model::ProgressSender;
 
@@ -13,6 +15,9 @@ pub struct FnCtx<'exec> {
     /// For items to submit progress updates.
     #[cfg(feature = "output_progress")]
     pub progress_sender: ProgressSender<'exec>,
+    /// Signals that the command execution has been interrupted, if the
+    /// execution is interruptible.
+    pub interrupt_token: Option<&'exec InterruptToken>,
     /// Marker.
     pub marker: PhantomData<&'exec ()>,
 }
@@ -27,10 +32,38 @@ impl<'exec> FnCtx<'exec> {
             item_id,
             #[cfg(feature = "output_progress")]
             progress_sender,
+            interrupt_token: None,
             marker: PhantomData,
         }
     }
 
+    /// Sets the `InterruptToken` for items to observe interruptions.
+    #[must_use]
+    pub fn with_interrupt_token(mut self, interrupt_token: &'exec InterruptToken) -> Self {
+        self.interrupt_token = Some(interrupt_token);
+        self
+    }
+
+    /// Returns whether the command execution has been interrupted.
+    ///
+    /// Long running item functions may check this to stop early, and return
+    /// the state they reached.
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupt_token
+            .is_some_and(InterruptToken::is_interrupted)
+    }
+
+    /// Completes when the command execution is interrupted.
+    ///
+    /// This never completes if the execution is not interruptible, so it is
+    /// intended to be used in a `select!` with the item's work.
+    pub async fn interrupted(&self) {
+        match self.interrupt_token {
+            Some(interrupt_token) => interrupt_token.interrupted().await,
+            None => std::future::pending().await,
+        }
+    }
+
     /// Returns the `ProgressTracker` for items to send progress to.
     #[cfg(feature = "output_progress")]
     pub fn progress_sender(&self) -> &ProgressSender<'exec> {
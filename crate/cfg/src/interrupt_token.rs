@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Signals to items that the command execution has been interrupted.
+///
+/// This is inserted into `Resources` by the framework for each command
+/// execution, and is accessible to items through [`FnCtx`]. Long running item
+/// functions may poll [`is_interrupted`] or await [`interrupted`] to stop
+/// early, and return the state they reached.
+///
+/// [`FnCtx`]: crate::FnCtx
+/// [`interrupted`]: Self::interrupted
+/// [`is_interrupted`]: Self::is_interrupted
+#[derive(Clone, Debug)]
+pub struct InterruptToken(Arc<watch::Sender<bool>>);
+
+impl InterruptToken {
+    /// Returns a new `InterruptToken` that is not interrupted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks this token as interrupted.
+    ///
+    /// All clones of this token observe the interruption.
+    pub fn interrupt(&self) {
+        self.0.send_replace(true);
+    }
+
+    /// Returns whether this token has been interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Completes when this token is interrupted.
+    ///
+    /// This completes immediately if the token is already interrupted.
+    pub async fn interrupted(&self) {
+        let mut interrupt_rx = self.0.subscribe();
+        // The sender is held by `self`, so this only returns an error if the
+        // sender is dropped, which cannot happen while `self` is borrowed.
+        let _result = interrupt_rx.wait_for(|interrupted| *interrupted).await;
+    }
+}
+
+impl Default for InterruptToken {
+    fn default() -> Self {
+        Self(Arc::new(watch::Sender::new(false)))
+    }
+}
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use dyn_clone::DynClone;
 use peace_data::Data;
 use peace_item_model::ItemId;
-use peace_params::{Params, ParamsSpec};
+use peace_params::{ItemOutputs, Params, ParamsSpec};
 use peace_resource_rt::{resources::ts::Empty, Resources};
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -471,6 +471,29 @@ pub trait Item: DynClone {
         diff: &Self::StateDiff,
     ) -> Result<Self::State, Self::Error>;
 
+    /// Returns the values that this item publishes from its state, such as a
+    /// bucket ARN or a download path.
+    ///
+    /// This is called with the state returned from [`apply`], and the outputs
+    /// are stored alongside the current states. Other items can use them in
+    /// their params specs through `ValueSpec::ItemOutput`, even when this item
+    /// is not applied in the same command.
+    ///
+    /// # Implementors
+    ///
+    /// The default implementation publishes no outputs. Outputs should only be
+    /// derived from `state`, so that they are the same each time the state is
+    /// the same.
+    ///
+    /// Items should provide a type whose fields are the output names, so that
+    /// consumers can read the outputs with [`ItemOutputs::to_typed`] instead
+    /// of by name, e.g. `FileDownloadOutputs`.
+    ///
+    /// [`apply`]: Self::apply
+    fn outputs(_state: &Self::State) -> ItemOutputs {
+        ItemOutputs::new()
+    }
+
     /// Returns the physical resources that this item interacts with.
     ///
     /// # Examples
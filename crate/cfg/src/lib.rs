@@ -7,7 +7,10 @@
 pub use async_trait::async_trait;
 pub use peace_core::*;
 
-pub use crate::{apply_check::ApplyCheck, fn_ctx::FnCtx, item::Item, state::State};
+pub use crate::{
+    apply_check::ApplyCheck, fn_ctx::FnCtx, interrupt_token::InterruptToken, item::Item,
+    state::State,
+};
 
 #[cfg(feature = "output_progress")]
 pub use crate::ref_into::RefInto;
@@ -17,6 +20,7 @@ pub mod state;
 
 mod apply_check;
 mod fn_ctx;
+mod interrupt_token;
 mod item;
 
 #[cfg(feature = "output_progress")]
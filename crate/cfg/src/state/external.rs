@@ -1,5 +1,6 @@
 use std::fmt::{self, Display};
 
+use peace_diff::{Equality, MaybeEq};
 use serde::{Deserialize, Serialize};
 
 /// Physical state that is externally defined -- computed, generated, or
@@ -39,6 +40,19 @@ where
     }
 }
 
+impl<V> MaybeEq for External<V>
+where
+    V: Clone + MaybeEq,
+{
+    /// Values that are not yet determined have [`Equality::Unknown`].
+    fn maybe_eq(&self, other: &Self) -> Equality {
+        match (self, other) {
+            (Self::Value(v_self), Self::Value(v_other)) => v_self.maybe_eq(v_other),
+            (Self::Tbd, _) | (_, Self::Tbd) => Equality::Unknown,
+        }
+    }
+}
+
 /// Physical state that is computed or generated externally, e.g. a server ID.
 pub type Generated<V> = External<V>;
 
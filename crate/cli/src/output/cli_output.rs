@@ -346,6 +346,7 @@ where
             ProgressStatus::UserPending => "👤",
             ProgressStatus::Complete(ProgressComplete::Success) => "✅",
             ProgressStatus::Complete(ProgressComplete::Fail) => "❌",
+            ProgressStatus::Complete(ProgressComplete::Interrupted) => "🟡",
         };
 
         // These are used to tell `indicatif` how to style the computed bar.
@@ -368,6 +369,7 @@ where
         const GREEN_LIGHT: u8 = 35;
         const PURPLE: u8 = 128;
         const RED_DIM: u8 = 160;
+        const YELLOW: u8 = 220;
 
         let bar_or_spinner = match self.colorize {
             CliColorize::Colored => {
@@ -394,6 +396,7 @@ where
                                 console::style(BAR_FULL).color256(GREEN_LIGHT)
                             }
                             ProgressComplete::Fail => console::style("{bar:40.160}"),
+                            ProgressComplete::Interrupted => console::style("{bar:40.220}"),
                         },
                     }
                 } else {
@@ -420,6 +423,9 @@ where
                             ProgressComplete::Fail => {
                                 console::style(SPINNER_FULL).color256(RED_DIM)
                             }
+                            ProgressComplete::Interrupted => {
+                                console::style(SPINNER_FULL).color256(YELLOW)
+                            }
                         },
                     }
                 }
@@ -681,7 +687,7 @@ where
                             let progress_bar = progress_tracker.progress_bar();
                             progress_bar.finish();
                         }
-                        ProgressComplete::Fail => {
+                        ProgressComplete::Fail | ProgressComplete::Interrupted => {
                             self.progress_bar_style_update(progress_tracker);
 
                             let progress_bar = progress_tracker.progress_bar();
@@ -1,12 +1,20 @@
+use std::any::TypeId;
+
 use futures::{StreamExt, TryStreamExt};
+use own::OwnedOrRef;
+use peace_flow_model::FlowId;
 use peace_flow_rt::{Flow, ItemGraph};
 use peace_item_model::ItemId;
-use peace_params::{MappingFnReg, MappingFns, ParamsKey, ParamsSpecs};
+use peace_params::{
+    FieldTemplate, FlowsStatesCurrentStored, MappingFnId, MappingFnReg, MappingFns, ParamsKey,
+    ParamsOverride, ParamsSpecs,
+};
 use peace_profile_model::Profile;
 use peace_resource_rt::{
     internal::{FlowParamsFile, ProfileParamsFile, WorkspaceParamsFile},
-    paths::ParamsSpecsFile,
+    paths::{FlowDir, ParamsSpecsFile, ProfileDir, StatesCurrentFile},
     resources::ts::{Empty, SetUp},
+    states::StatesCurrentStored,
     Resource, Resources,
 };
 use peace_rt_model::{
@@ -14,11 +22,13 @@ use peace_rt_model::{
         FlowParams, FlowParamsOpt, ProfileParams, ProfileParamsOpt, WorkspaceParams,
         WorkspaceParamsOpt,
     },
-    ParamsSpecsSerializer, ParamsSpecsTypeReg, StatesTypeReg, Storage, WorkspaceInitializer,
+    ItemIdsDisabled, ParamsSpecsSerializer, ParamsSpecsTypeReg, StatesTypeReg, Storage,
+    TemplateTypeError, WorkspaceInitializer,
 };
+use peace_state_rt::StatesSerializer;
 use type_reg::untagged::{BoxDt, TypeReg};
 
-use crate::ProfileSelection;
+use crate::{ItemGraphEdgesCheck, ProfileSelection};
 
 /// Common code used to build different `CmdCtx*` types.
 pub(crate) struct CmdCtxBuilderSupport;
@@ -430,6 +440,260 @@ impl CmdCtxBuilderSupport {
         }
     }
 
+    /// Returns an error if any params spec uses a mapping function that is not
+    /// registered in the `MappingFnReg`.
+    ///
+    /// This includes the placeholders in `ValueSpec::Template`s, so that
+    /// mistyped placeholders are detected when the `CmdCtx` is built, instead
+    /// of when the item's params are resolved. The types of the mapping
+    /// functions are checked by [`params_specs_templates_check`].
+    ///
+    /// [`params_specs_templates_check`]: Self::params_specs_templates_check
+    pub(crate) fn params_specs_mapping_fns_check(
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+    ) -> Result<(), peace_rt_model::Error> {
+        let item_mapping_fn_ids = params_specs
+            .iter()
+            .flat_map(|(item_id, params_spec)| {
+                params_spec
+                    .mapping_fn_ids()
+                    .into_iter()
+                    .filter(|mapping_fn_id| !mapping_fn_reg.contains_key(mapping_fn_id))
+                    .map(|mapping_fn_id| (item_id.clone(), mapping_fn_id))
+            })
+            .collect::<Vec<(ItemId, MappingFnId)>>();
+
+        if item_mapping_fn_ids.is_empty() {
+            Ok(())
+        } else {
+            Err(peace_rt_model::Error::ParamsSpecsMappingFnsNotRegistered {
+                item_mapping_fn_ids,
+            })
+        }
+    }
+
+    /// Returns an error if any placeholder in a `ValueSpec::Template` uses a
+    /// mapping function whose types do not match.
+    ///
+    /// Each mapping function's arguments must be an item's state, be written
+    /// by an item's data, or be in `resources`, and its output must be able to
+    /// be interpolated into the field's type.
+    ///
+    /// Mapping functions that are not registered are reported by
+    /// [`params_specs_mapping_fns_check`], and are ignored here.
+    ///
+    /// [`params_specs_mapping_fns_check`]: Self::params_specs_mapping_fns_check
+    pub(crate) fn params_specs_templates_check<E>(
+        item_graph: &ItemGraph<E>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Result<(), peace_rt_model::Error>
+    where
+        E: 'static,
+    {
+        let type_id_is_available = |type_id: TypeId| {
+            resources.contains_key(&type_id)
+                || item_graph.iter_insertion().any(|item| {
+                    item.state_type_id() == type_id || item.borrow_muts().contains(&type_id)
+                })
+        };
+
+        let item_template_type_errors = params_specs
+            .iter()
+            .flat_map(|(item_id, params_spec)| {
+                params_spec
+                    .field_templates()
+                    .into_iter()
+                    .flat_map(|field_template| {
+                        let FieldTemplate {
+                            field_type_name,
+                            field_kind,
+                            template,
+                            mapping_fn_ids,
+                        } = field_template;
+
+                        mapping_fn_ids
+                            .into_iter()
+                            .filter_map(|mapping_fn_id| {
+                                mapping_fn_reg
+                                    .get(&mapping_fn_id)
+                                    .map(|mapping_fn| (mapping_fn_id, mapping_fn))
+                            })
+                            .flat_map(|(mapping_fn_id, mapping_fn)| {
+                                let arg_type_names = mapping_fn
+                                    .arg_type_ids()
+                                    .into_iter()
+                                    .zip(mapping_fn.arg_type_names())
+                                    .filter(|(type_id, _arg_type_name)| {
+                                        !type_id_is_available(*type_id)
+                                    })
+                                    .map(|(_type_id, arg_type_name)| arg_type_name)
+                                    .collect::<Vec<String>>();
+                                let args_not_found = (!arg_type_names.is_empty()).then(|| {
+                                    TemplateTypeError::ArgsNotFound {
+                                        template: template.clone(),
+                                        mapping_fn_id: mapping_fn_id.clone(),
+                                        arg_type_names,
+                                    }
+                                });
+
+                                let output_kind = mapping_fn.output_kind();
+                                let value_kind_mismatch = (!output_kind
+                                    .is_interpolatable_into(field_kind))
+                                .then(|| TemplateTypeError::ValueKindMismatch {
+                                    template: template.clone(),
+                                    mapping_fn_id,
+                                    output_type_name: mapping_fn.output_type_name(),
+                                    output_kind,
+                                    field_type_name: field_type_name.clone(),
+                                    field_kind,
+                                });
+
+                                args_not_found.into_iter().chain(value_kind_mismatch)
+                            })
+                            .collect::<Vec<TemplateTypeError>>()
+                    })
+                    .map(|template_type_error| (item_id.clone(), template_type_error))
+                    .collect::<Vec<(ItemId, TemplateTypeError)>>()
+            })
+            .collect::<Vec<(ItemId, TemplateTypeError)>>();
+
+        if item_template_type_errors.is_empty() {
+            Ok(())
+        } else {
+            Err(peace_rt_model::Error::ParamsSpecsTemplateTypesMismatch {
+                item_template_type_errors,
+            })
+        }
+    }
+
+    /// Reads the stored current states of the other flows that are read by
+    /// `ValueSpec::FlowState`s in the params specs.
+    ///
+    /// Flows that have not been applied in this profile have no entry, so
+    /// resolving a value from them returns an error.
+    pub(crate) async fn flows_states_current_stored_read(
+        params_specs: &ParamsSpecs,
+        storage: &Storage,
+        profile_dir: &ProfileDir,
+    ) -> Result<FlowsStatesCurrentStored, peace_rt_model::Error> {
+        let flow_ids = params_specs
+            .values()
+            .flat_map(|params_spec| params_spec.flow_state_refs())
+            .fold(Vec::<FlowId>::new(), |mut flow_ids, flow_state_ref| {
+                if !flow_ids.contains(&flow_state_ref.flow_id) {
+                    flow_ids.push(flow_state_ref.flow_id);
+                }
+                flow_ids
+            });
+
+        let mut flows_states_current_stored = FlowsStatesCurrentStored::new();
+        for flow_id in flow_ids {
+            let flow_dir = FlowDir::from((profile_dir, &flow_id));
+            let states_current_file = StatesCurrentFile::from(&flow_dir);
+            let states_untyped =
+                StatesSerializer::<peace_rt_model::Error>::deserialize_untyped_opt(
+                    storage,
+                    &states_current_file,
+                )
+                .await?;
+            if let Some(states_untyped) = states_untyped {
+                flows_states_current_stored.insert(flow_id, states_untyped.into_iter().collect());
+            }
+        }
+
+        Ok(flows_states_current_stored)
+    }
+
+    /// Checks that each item whose params spec reads data from another item
+    /// is a successor of that item in the flow's graph.
+    ///
+    /// Depending on `item_graph_edges_check`, missing edges either return an
+    /// error, are added to a copy of the flow, are logged as warnings, or are
+    /// ignored.
+    pub(crate) fn item_graph_edges_check<'ctx, E>(
+        flow: OwnedOrRef<'ctx, Flow<E>>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        item_graph_edges_check: ItemGraphEdgesCheck,
+    ) -> Result<OwnedOrRef<'ctx, Flow<E>>, peace_rt_model::Error>
+    where
+        E: 'static,
+    {
+        if item_graph_edges_check == ItemGraphEdgesCheck::Ignore {
+            return Ok(flow);
+        }
+
+        let item_refs = flow.params_specs_item_refs_missing(params_specs, mapping_fn_reg);
+        if item_refs.is_empty() {
+            return Ok(flow);
+        }
+
+        match item_graph_edges_check {
+            ItemGraphEdgesCheck::Error => {
+                Err(peace_rt_model::Error::ItemGraphEdgesMissing { item_refs })
+            }
+            ItemGraphEdgesCheck::Infer => match flow.with_logic_edges(&item_refs) {
+                Ok(flow) => Ok(OwnedOrRef::from(flow)),
+                Err(_would_cycle) => {
+                    Err(peace_rt_model::Error::ItemGraphEdgesInferCycle { item_refs })
+                }
+            },
+            ItemGraphEdgesCheck::Warn => {
+                item_refs.iter().for_each(|(item_id_src, item_id_dest)| {
+                    tracing::warn!(
+                        flow_id = %flow.flow_id(),
+                        item_id_src = %item_id_src,
+                        item_id_dest = %item_id_dest,
+                        "`{item_id_dest}`'s params read data from `{item_id_src}`, \
+                        but `{item_id_src}` is not a predecessor of `{item_id_dest}` in the item graph."
+                    );
+                });
+                Ok(flow)
+            }
+            ItemGraphEdgesCheck::Ignore => Ok(flow),
+        }
+    }
+
+    /// Applies params overrides on top of the merged params specs.
+    ///
+    /// Each override sets one field of an item's params spec, and later
+    /// overrides for the same field take precedence.
+    pub(crate) fn params_specs_overrides_apply(
+        params_specs: &mut ParamsSpecs,
+        params_overrides: &[ParamsOverride],
+    ) -> Result<(), peace_rt_model::Error> {
+        params_overrides.iter().try_for_each(|params_override| {
+            let ParamsOverride {
+                item_id,
+                field_name,
+                value,
+            } = params_override;
+
+            let params_spec =
+                params_specs
+                    .iter_mut()
+                    .find_map(|(item_id_existing, params_spec)| {
+                        (item_id_existing == item_id).then_some(params_spec)
+                    });
+            let Some(params_spec) = params_spec else {
+                return Err(peace_rt_model::Error::ParamsOverrideItemNotFound {
+                    params_override: params_override.clone(),
+                    item_ids: params_specs.keys().cloned().collect::<Vec<ItemId>>(),
+                });
+            };
+
+            params_spec
+                .field_value_set(field_name, value)
+                .map_err(|error| peace_rt_model::Error::ParamsOverrideApply {
+                    params_override: Box::new(params_override.clone()),
+                    error,
+                })
+        })
+    }
+
     /// Registers each mapping function with the `MappingFnReg` and inserts it
     /// into `resources`.
     ///
@@ -445,6 +709,68 @@ impl CmdCtxBuilderSupport {
         mapping_fn_reg
     }
 
+    /// Returns the IDs of items whose enable condition evaluates to `false`.
+    pub(crate) fn item_ids_disabled<E>(
+        item_graph: &ItemGraph<E>,
+        resources: &Resources<Empty>,
+    ) -> Vec<ItemId>
+    where
+        E: 'static,
+    {
+        item_graph
+            .iter_insertion()
+            .filter(|item| !item.is_enabled(resources))
+            .map(|item| item.id().clone())
+            .collect::<Vec<ItemId>>()
+    }
+
+    /// Returns the [`ItemIdsDisabled`] for the disabled items, flagging the
+    /// ones whose stored state is not clean.
+    ///
+    /// If the clean state of an item cannot be determined, its stored state
+    /// is treated as not clean, so that it is flagged.
+    pub(crate) async fn item_ids_disabled_stored_not_clean<E>(
+        item_graph: &ItemGraph<E>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+        item_ids_disabled: Vec<ItemId>,
+        items_disabled_clean: bool,
+    ) -> Result<ItemIdsDisabled, E>
+    where
+        E: std::error::Error + 'static,
+    {
+        let mut item_ids_stored_not_clean = Vec::<ItemId>::new();
+        let items_disabled = item_graph
+            .iter_insertion()
+            .filter(|item| item_ids_disabled.contains(item.id()));
+        for item in items_disabled {
+            let state_stored = resources.try_borrow::<StatesCurrentStored>().ok().and_then(
+                |states_current_stored| states_current_stored.get_raw(item.id()).cloned(),
+            );
+            let Some(state_stored) = state_stored else {
+                continue;
+            };
+
+            let state_stored_is_clean = match item
+                .state_clean(params_specs, mapping_fn_reg, resources)
+                .await
+            {
+                Ok(state_clean) => item.state_eq(&state_stored, &state_clean)?,
+                Err(_error) => false,
+            };
+            if !state_stored_is_clean {
+                item_ids_stored_not_clean.push(item.id().clone());
+            }
+        }
+
+        Ok(ItemIdsDisabled::new(
+            item_ids_disabled,
+            item_ids_stored_not_clean,
+            items_disabled_clean,
+        ))
+    }
+
     pub(crate) async fn item_graph_setup<E>(
         item_graph: &ItemGraph<E>,
         resources: Resources<Empty>,
@@ -5,21 +5,27 @@ use interruptible::Interruptibility;
 use own::{OwnedOrMutRef, OwnedOrRef};
 use peace_flow_rt::Flow;
 use peace_item_model::ItemId;
-use peace_params::{ParamsSpecs, ParamsValue};
+use peace_params::{ParamsOverride, ParamsSpecs, ParamsValue};
 use peace_resource_rt::{
     internal::{FlowParamsFile, ProfileParamsFile, WorkspaceParamsFile},
-    paths::{FlowDir, ParamsSpecsFile, ProfileDir, ProfileHistoryDir, StatesCurrentFile},
+    paths::{
+        FlowDir, OutputsFile, ParamsSpecsFile, ProfileDir, ProfileHistoryDir, StatesCurrentFile,
+    },
     resources::ts::Empty,
     Resources,
 };
 use peace_rt_model::{
     params::{FlowParamsOpt, ProfileParamsOpt, WorkspaceParamsOpt},
-    ParamsSpecsSerializer, Workspace, WorkspaceInitializer,
+    ItemIdsOrphaned, ItemsOutputsSerializer, ParamsSpecsSerializer, Workspace,
+    WorkspaceInitializer,
 };
 use peace_state_rt::StatesSerializer;
 use typed_builder::TypedBuilder;
 
-use crate::{CmdCtxBuilderSupport, CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes, ProfileSelection};
+use crate::{
+    CmdCtxBuilderSupport, CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes, ItemGraphEdgesCheck,
+    ProfileSelection,
+};
 
 /// Context for a command that works with one profile and one flow.
 ///
@@ -189,6 +195,79 @@ where
         )
     )]
     pub params_specs: ParamsSpecs,
+    /// What to do when an item's params spec reads data from an item that is
+    /// not its predecessor in the flow's graph.
+    ///
+    /// Defaults to [`ItemGraphEdgesCheck::Warn`].
+    #[builder(
+        via_mutators(init = ItemGraphEdgesCheck::default()),
+        mutators(
+            /// Sets what to do when an item's params spec reads data from an
+            /// item that is not its predecessor in the flow's graph.
+            pub fn with_item_graph_edges_check(
+                &mut self,
+                item_graph_edges_check: ItemGraphEdgesCheck,
+            ) {
+                self.item_graph_edges_check = item_graph_edges_check;
+            }
+        )
+    )]
+    pub item_graph_edges_check: ItemGraphEdgesCheck,
+    /// Params overrides to apply on top of the item params specs.
+    ///
+    /// These are applied after the provided params specs are merged with the
+    /// stored params specs, and are only used for this execution unless
+    /// [`with_params_overrides_persist`] is set to `true`.
+    ///
+    /// [`with_params_overrides_persist`]: CmdCtxSpsfParamsBuilder::with_params_overrides_persist
+    #[builder(
+        via_mutators(init = Vec::new()),
+        mutators(
+            /// Overrides one field of an item's params, e.g. from a
+            /// `--set item_id.field=value` command line argument.
+            ///
+            /// Overrides for the same field that are added later take
+            /// precedence.
+            pub fn with_params_override(&mut self, params_override: ParamsOverride) {
+                self.params_overrides.push(params_override);
+            }
+        )
+    )]
+    pub params_overrides: Vec<ParamsOverride>,
+    /// Whether params overrides are stored in the params specs file.
+    ///
+    /// Defaults to `false`.
+    #[builder(
+        via_mutators(init = false),
+        mutators(
+            /// Sets whether params overrides are stored in the params specs
+            /// file, so that they are used in subsequent executions.
+            pub fn with_params_overrides_persist(&mut self, params_overrides_persist: bool) {
+                self.params_overrides_persist = params_overrides_persist;
+            }
+        )
+    )]
+    pub params_overrides_persist: bool,
+    /// Whether disabled items whose stored state is not clean are cleaned up.
+    ///
+    /// Defaults to `false`, which skips disabled items, and flags the ones
+    /// whose stored state is not clean in [`ItemIdsDisabled`].
+    ///
+    /// [`ItemIdsDisabled`]: peace_rt_model::ItemIdsDisabled
+    #[builder(
+        via_mutators(init = false),
+        mutators(
+            /// Sets whether disabled items whose stored state is not clean
+            /// are cleaned up when the flow is applied.
+            ///
+            /// Items are disabled when their `ItemEnableCondition` evaluates
+            /// to `false`.
+            pub fn with_items_disabled_clean(&mut self, items_disabled_clean: bool) {
+                self.items_disabled_clean = items_disabled_clean;
+            }
+        )
+    )]
+    pub items_disabled_clean: bool,
     /// `Resources` for flow execution.
     ///
     /// A "resource" is any object, and `resources` is a map where each object
@@ -243,6 +322,10 @@ impl<'ctx, CmdCtxTypesT, __interruptibility>
             (ProfileParamsOpt<<CmdCtxTypesT as CmdCtxTypes>::ProfileParamsKey>,),
             (FlowParamsOpt<<CmdCtxTypesT as CmdCtxTypes>::FlowParamsKey>,),
             (ParamsSpecs,),
+            (ItemGraphEdgesCheck,),
+            (Vec<ParamsOverride>,),
+            (bool,),
+            (bool,),
             (Resources<Empty>,),
         ),
     >
@@ -268,6 +351,10 @@ where
             profile_params: profile_params_provided,
             flow_params: flow_params_provided,
             params_specs: params_specs_provided,
+            item_graph_edges_check,
+            params_overrides,
+            params_overrides_persist,
+            items_disabled_clean,
             resources: resources_override,
         } = self.build_partial();
 
@@ -429,14 +516,35 @@ where
         )
         .await?;
 
-        let params_specs = CmdCtxBuilderSupport::params_specs_merge(
+        let mut params_specs = CmdCtxBuilderSupport::params_specs_merge(
             flow_ref,
             params_specs_provided,
             params_specs_stored,
         )?;
 
-        CmdCtxBuilderSupport::params_specs_serialize(&params_specs, storage, &params_specs_file)
-            .await?;
+        // Overrides are only used for this execution unless they are persisted.
+        let params_specs_to_store = (!params_overrides_persist && !params_overrides.is_empty())
+            .then(|| params_specs.clone());
+        CmdCtxBuilderSupport::params_specs_overrides_apply(&mut params_specs, &params_overrides)?;
+        CmdCtxBuilderSupport::params_specs_mapping_fns_check(&params_specs, &mapping_fn_reg)?;
+
+        // Check or infer edges for items that read data from other items.
+        let flow = CmdCtxBuilderSupport::item_graph_edges_check(
+            flow,
+            &params_specs,
+            &mapping_fn_reg,
+            item_graph_edges_check,
+        )?;
+        let flow_ref = &flow;
+        let flow_id = flow_ref.flow_id();
+        let item_graph = flow_ref.graph();
+
+        CmdCtxBuilderSupport::params_specs_serialize(
+            params_specs_to_store.as_ref().unwrap_or(&params_specs),
+            storage,
+            &params_specs_file,
+        )
+        .await?;
 
         // States loading and storage.
         let states_type_reg_ref = &states_type_reg;
@@ -452,6 +560,41 @@ where
         if let Some(states_current_stored) = states_current_stored {
             resources.insert(states_current_stored);
         }
+        let item_ids_orphaned =
+            StatesSerializer::<CmdCtxTypesT::AppError>::deserialize_item_ids_orphaned_opt(
+                storage,
+                item_graph,
+                &states_current_file,
+            )
+            .await?
+            .unwrap_or_default();
+        resources.insert(ItemIdsOrphaned::new(
+            item_ids_orphaned,
+            item_graph.item_ids_retired().to_vec(),
+        ));
+
+        // Other flows' stored states that items' params are read from.
+        let flows_states_current_stored = CmdCtxBuilderSupport::flows_states_current_stored_read(
+            &params_specs,
+            storage,
+            &profile_dir,
+        )
+        .await?;
+        resources.insert(flows_states_current_stored);
+
+        // Outputs published by items when they were last applied.
+        let outputs_file = OutputsFile::from(&flow_dir);
+        let items_outputs = ItemsOutputsSerializer::<CmdCtxTypesT::AppError>::deserialize_opt(
+            storage,
+            &outputs_file,
+        )
+        .await?
+        .unwrap_or_default();
+        resources.insert(items_outputs);
+
+        // Evaluate each `Item`'s enable condition against the workspace, profile, and
+        // flow params.
+        let item_ids_disabled = CmdCtxBuilderSupport::item_ids_disabled(item_graph, &resources);
 
         // Call each `Item`'s initialization function.
         let mut resources = CmdCtxBuilderSupport::item_graph_setup(item_graph, resources).await?;
@@ -477,6 +620,25 @@ where
         // Needs to come before `state_example`, because params resolution may need
         // some resources to be inserted for `state_example` to work.
         resources.merge(resources_override.into_inner());
+        CmdCtxBuilderSupport::params_specs_templates_check(
+            item_graph,
+            &params_specs,
+            &mapping_fn_reg,
+            &resources,
+        )?;
+
+        // Disabled items with stored state that is not clean are flagged, and
+        // cleaned up if requested.
+        let item_ids_disabled = CmdCtxBuilderSupport::item_ids_disabled_stored_not_clean(
+            item_graph,
+            &params_specs,
+            &mapping_fn_reg,
+            &resources,
+            item_ids_disabled,
+            items_disabled_clean,
+        )
+        .await?;
+        resources.insert(item_ids_disabled);
 
         // Fetching state example inserts it into resources.
         #[cfg(feature = "item_state_example")]
@@ -533,6 +695,10 @@ impl<'ctx, CmdCtxTypesT, __interruptibility> IntoFuture
             (ProfileParamsOpt<<CmdCtxTypesT as CmdCtxTypes>::ProfileParamsKey>,),
             (FlowParamsOpt<<CmdCtxTypesT as CmdCtxTypes>::FlowParamsKey>,),
             (ParamsSpecs,),
+            (ItemGraphEdgesCheck,),
+            (Vec<ParamsOverride>,),
+            (bool,),
+            (bool,),
             (Resources<Empty>,),
         ),
     >
@@ -0,0 +1,29 @@
+/// What the `CmdCtx` does when an item's params spec reads data from another
+/// item that is not its predecessor in the item graph.
+///
+/// Params specs read data from other items through `MappingFn`s and
+/// `InMemory` values. If the item that writes the data is not a predecessor
+/// of the item that reads it, the two items may run concurrently, and the
+/// reading item's params may fail to resolve.
+///
+/// See [`Flow::params_specs_item_refs_missing`].
+///
+/// [`Flow::params_specs_item_refs_missing`]: peace_flow_rt::Flow::params_specs_item_refs_missing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ItemGraphEdgesCheck {
+    /// Returns an error when building the `CmdCtx`.
+    Error,
+    /// Adds the missing edges as logic edges to the `CmdCtx`'s flow.
+    ///
+    /// The flow passed to the builder is not modified.
+    Infer,
+    /// Logs a warning for each missing edge through `tracing::warn!`, and
+    /// continues to build the `CmdCtx`.
+    ///
+    /// This is the default, so that existing flows continue to build, while
+    /// still surfacing edges that should be added.
+    #[default]
+    Warn,
+    /// Does not check for missing edges.
+    Ignore,
+}
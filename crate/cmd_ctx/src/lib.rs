@@ -19,6 +19,7 @@ pub use crate::{
     cmd_ctx_spsf::{CmdCtxSpsf, CmdCtxSpsfFields},
     cmd_ctx_spsf_params::{CmdCtxSpsfParams, CmdCtxSpsfParamsBuilder},
     cmd_ctx_types::CmdCtxTypes,
+    item_graph_edges_check::ItemGraphEdgesCheck,
     profile_filter_fn::ProfileFilterFn,
     profile_selection::ProfileSelection,
 };
@@ -41,5 +42,6 @@ mod cmd_ctx_spnf_params;
 mod cmd_ctx_spsf;
 mod cmd_ctx_spsf_params;
 mod cmd_ctx_types;
+mod item_graph_edges_check;
 mod profile_filter_fn;
 mod profile_selection;
@@ -14,6 +14,11 @@ pub struct ItemStreamOutcome<T> {
     pub item_ids_processed: Vec<ItemId>,
     /// IDs of the items that were not processed.
     pub item_ids_not_processed: Vec<ItemId>,
+    /// IDs of the items that were interrupted while they were being applied.
+    ///
+    /// These items are also in `item_ids_processed`, and their stored state
+    /// is the state they reached before they stopped.
+    pub item_ids_interrupted: Vec<ItemId>,
 }
 
 impl<T> ItemStreamOutcome<T> {
@@ -24,6 +29,7 @@ impl<T> ItemStreamOutcome<T> {
             state: StreamOutcomeState::Finished,
             item_ids_processed,
             item_ids_not_processed: Vec::new(),
+            item_ids_interrupted: Vec::new(),
         }
     }
 
@@ -34,6 +40,7 @@ impl<T> ItemStreamOutcome<T> {
             state,
             item_ids_processed,
             item_ids_not_processed,
+            item_ids_interrupted,
         } = self;
 
         let value = f(value);
@@ -43,6 +50,7 @@ impl<T> ItemStreamOutcome<T> {
             state,
             item_ids_processed,
             item_ids_not_processed,
+            item_ids_interrupted,
         }
     }
 
@@ -53,6 +61,7 @@ impl<T> ItemStreamOutcome<T> {
             state,
             item_ids_processed,
             item_ids_not_processed,
+            item_ids_interrupted,
         } = self;
 
         (
@@ -61,6 +70,7 @@ impl<T> ItemStreamOutcome<T> {
                 state,
                 item_ids_processed,
                 item_ids_not_processed,
+                item_ids_interrupted,
             },
             value_existing,
         )
@@ -77,6 +87,7 @@ impl<T> ItemStreamOutcome<T> {
             state,
             item_ids_processed,
             item_ids_not_processed,
+            item_ids_interrupted,
         } = self;
 
         let (value, extracted) = f(value);
@@ -87,6 +98,7 @@ impl<T> ItemStreamOutcome<T> {
                 state,
                 item_ids_processed,
                 item_ids_not_processed,
+                item_ids_interrupted,
             },
             extracted,
         )
@@ -115,6 +127,10 @@ impl<T> ItemStreamOutcome<T> {
     pub fn item_ids_not_processed(&self) -> &[ItemId] {
         self.item_ids_not_processed.as_ref()
     }
+
+    pub fn item_ids_interrupted(&self) -> &[ItemId] {
+        self.item_ids_interrupted.as_ref()
+    }
 }
 
 impl<T> Default for ItemStreamOutcome<T>
@@ -127,6 +143,7 @@ where
             state: StreamOutcomeState::NotStarted,
             item_ids_processed: Vec::new(),
             item_ids_not_processed: Vec::new(),
+            item_ids_interrupted: Vec::new(),
         }
     }
 }
@@ -1,10 +1,17 @@
 use std::{collections::VecDeque, fmt::Debug};
 
+use chrono::Utc;
 use futures::{future, stream, Future, StreamExt, TryStreamExt};
 use interruptible::InterruptSignal;
+use peace_cfg::InterruptToken;
 use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
-use peace_cmd_model::{CmdBlockDesc, CmdOutcome};
-use peace_resource_rt::{resources::ts::SetUp, Resources};
+use peace_cmd_model::{CmdBlockDesc, CmdExecutionId, CmdOutcome};
+use peace_resource_rt::{
+    paths::{CmdCheckpointFile, FlowDir},
+    resources::ts::SetUp,
+    Resources,
+};
+use peace_rt_model::{CmdCheckpoint, CmdCheckpointSerializer, ItemIdsInterrupted, Storage};
 
 use crate::{CmdBlockError, CmdBlockRtBox, ItemStreamOutcomeMapper};
 
@@ -50,6 +57,9 @@ where
     /// Whether or not to render progress.
     #[cfg(feature = "output_progress")]
     progress_render_enabled: bool,
+    /// Name of the command to record in the `CmdCheckpoint`, if the progress
+    /// of this execution is persisted.
+    checkpoint_cmd_name: Option<String>,
 }
 
 impl<'types, ExecutionOutcome, CmdCtxTypesT> CmdExecution<'types, ExecutionOutcome, CmdCtxTypesT>
@@ -74,10 +84,22 @@ where
             execution_outcome_fetch,
             #[cfg(feature = "output_progress")]
             progress_render_enabled,
+            checkpoint_cmd_name,
         } = self;
         #[cfg(feature = "output_progress")]
         let progress_render_enabled = *progress_render_enabled;
 
+        if let Some(cmd_name) = checkpoint_cmd_name.as_ref() {
+            checkpoint_begin(&mut cmd_ctx.fields_mut().resources, cmd_name);
+        }
+
+        // Items observe interruptions through this token in their `FnCtx`.
+        let interrupt_token = InterruptToken::new();
+        cmd_ctx
+            .fields_mut()
+            .resources
+            .insert::<InterruptToken>(interrupt_token.clone());
+
         cfg_if::cfg_if! {
             if #[cfg(feature = "output_progress")] {
                 let CmdCtxSpsf {
@@ -93,6 +115,7 @@ where
 
                 cmd_ctx_spsf_fields.interruptibility_state
                     .set_fn_interrupt_activate(Some(move || {
+                        interrupt_token.interrupt();
                         if let Some(cmd_progress_tx) = cmd_progress_tx_for_interruptibility_state.upgrade() {
                             let _cmd_progress_send_result =
                             cmd_progress_tx.try_send(CmdProgressUpdate::Interrupt);
@@ -104,6 +127,9 @@ where
                     fields: ref mut cmd_ctx_spsf_fields,
                     ..
                 } = cmd_ctx;
+
+                cmd_ctx_spsf_fields.interruptibility_state
+                    .set_fn_interrupt_activate(Some(move || interrupt_token.interrupt()));
             }
         }
 
@@ -116,26 +142,69 @@ where
         );
 
         #[cfg(not(feature = "output_progress"))]
-        {
-            exec_internal(cmd_outcome_task).await
-        }
+        let cmd_outcome_result = exec_internal(cmd_outcome_task).await;
 
         #[cfg(feature = "output_progress")]
-        {
-            exec_internal(
-                cmd_outcome_task,
-                progress_render_enabled,
-                &mut **output,
-                cmd_progress_tracker,
-                cmd_progress_rx,
+        let cmd_outcome_result = exec_internal(
+            cmd_outcome_task,
+            progress_render_enabled,
+            &mut **output,
+            cmd_progress_tracker,
+            cmd_progress_rx,
+        )
+        .await;
+
+        if checkpoint_cmd_name.is_some() {
+            checkpoint_end(
+                &mut cmd_ctx.fields_mut().resources,
+                cmd_outcome_result.as_ref(),
             )
-            .await
+            .await?;
         }
+
+        cmd_outcome_result
     }
 
     // pub fn exec_bg -> CmdExecId
 }
 
+/// Inserts a new `CmdCheckpoint` into `Resources`, unless one is already
+/// inserted by a resumed command.
+fn checkpoint_begin(resources: &mut Resources<SetUp>, cmd_name: &str) {
+    if resources.contains::<CmdCheckpoint>() {
+        return;
+    }
+
+    let cmd_execution_id = CmdExecutionId::new(Utc::now().timestamp_millis().unsigned_abs());
+    resources.insert(CmdCheckpoint::new(cmd_execution_id, cmd_name.to_string()));
+}
+
+/// Removes the `CmdCheckpoint` from `Resources`, and deletes the checkpoint
+/// file if the execution completed.
+///
+/// The file is kept when the execution is interrupted or fails, so that the
+/// command may be resumed.
+async fn checkpoint_end<ExecutionOutcome, E>(
+    resources: &mut Resources<SetUp>,
+    cmd_outcome_result: Result<&CmdOutcome<ExecutionOutcome, E>, &E>,
+) -> Result<(), E>
+where
+    E: std::error::Error + From<peace_rt_model::Error> + Send,
+{
+    let _cmd_checkpoint = resources.try_remove::<CmdCheckpoint>();
+
+    let is_complete = cmd_outcome_result.is_ok_and(CmdOutcome::is_complete);
+    if is_complete {
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let cmd_checkpoint_file = CmdCheckpointFile::from(&*flow_dir);
+
+        CmdCheckpointSerializer::<E>::remove(&storage, &cmd_checkpoint_file).await?;
+    }
+
+    Ok(())
+}
+
 /// Executes and returns the `CmdOutcome`.
 ///
 /// This also runs the progress task if the `"output_progress"` feature is
@@ -354,6 +423,11 @@ where
     #[cfg(feature = "output_progress")]
     drop(cmd_progress_tx);
 
+    let item_ids_interrupted = resources
+        .try_remove::<ItemIdsInterrupted>()
+        .map(ItemIdsInterrupted::into_inner)
+        .unwrap_or_default();
+
     if let Some((cmd_block_index, cmd_block_error)) = cmd_block_index_and_error {
         match cmd_block_error {
             CmdBlockError::InputFetch(resource_fetch_error) => {
@@ -367,7 +441,8 @@ where
             }
             CmdBlockError::Exec(error) => Err(error),
             CmdBlockError::Interrupt { stream_outcome } => {
-                let item_stream_outcome = ItemStreamOutcomeMapper::map(flow, stream_outcome);
+                let mut item_stream_outcome = ItemStreamOutcomeMapper::map(flow, stream_outcome);
+                item_stream_outcome.item_ids_interrupted = item_ids_interrupted;
                 let cmd_blocks_processed = cmd_blocks
                     .range(0..cmd_block_index)
                     .map(|cmd_block_rt| cmd_block_rt.cmd_block_desc())
@@ -389,7 +464,8 @@ where
                 stream_outcome,
                 errors,
             } => {
-                let item_stream_outcome = ItemStreamOutcomeMapper::map(flow, stream_outcome);
+                let mut item_stream_outcome = ItemStreamOutcomeMapper::map(flow, stream_outcome);
+                item_stream_outcome.item_ids_interrupted = item_ids_interrupted;
                 let cmd_blocks_processed = cmd_blocks
                     .range(0..cmd_block_index)
                     .map(|cmd_block_rt| cmd_block_rt.cmd_block_desc())
@@ -28,6 +28,11 @@ where
     /// Defaults to `true`.
     #[cfg(feature = "output_progress")]
     progress_render_enabled: bool,
+    /// Name of the command to record in the `CmdCheckpoint`, if the progress
+    /// of this execution is persisted.
+    ///
+    /// Defaults to `None`.
+    checkpoint_cmd_name: Option<String>,
 }
 
 impl<'types, ExecutionOutcome, CmdCtxTypesT>
@@ -58,6 +63,7 @@ where
             execution_outcome_fetch,
             #[cfg(feature = "output_progress")]
             progress_render_enabled,
+            checkpoint_cmd_name,
         } = self;
 
         cmd_blocks.push_back(Box::pin(cmd_block));
@@ -67,6 +73,7 @@ where
             execution_outcome_fetch,
             #[cfg(feature = "output_progress")]
             progress_render_enabled,
+            checkpoint_cmd_name,
         }
     }
 
@@ -100,6 +107,23 @@ where
         self
     }
 
+    /// Persists a `CmdCheckpoint` as items are applied, so that an
+    /// interrupted execution can be resumed.
+    ///
+    /// The checkpoint is written to `cmd_checkpoint.yaml` in the flow
+    /// directory after each item is successfully applied, and is removed
+    /// when the execution completes. If a `CmdCheckpoint` is already in
+    /// `Resources` when the execution begins, that checkpoint is continued.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_name`: Name of the command to record in the checkpoint, e.g.
+    ///   `"EnsureCmd"`.
+    pub fn with_checkpoint(mut self, cmd_name: impl Into<String>) -> Self {
+        self.checkpoint_cmd_name = Some(cmd_name.into());
+        self
+    }
+
     /// Returns the `CmdExecution` to execute.
     pub fn build(self) -> CmdExecution<'types, ExecutionOutcome, CmdCtxTypesT>
     where
@@ -110,6 +134,7 @@ where
             execution_outcome_fetch,
             #[cfg(feature = "output_progress")]
             progress_render_enabled,
+            checkpoint_cmd_name,
         } = self;
 
         CmdExecution {
@@ -117,6 +142,7 @@ where
             execution_outcome_fetch,
             #[cfg(feature = "output_progress")]
             progress_render_enabled,
+            checkpoint_cmd_name,
         }
     }
 }
@@ -133,6 +159,7 @@ where
             execution_outcome_fetch,
             #[cfg(feature = "output_progress")]
             progress_render_enabled: true,
+            checkpoint_cmd_name: None,
         }
     }
 }
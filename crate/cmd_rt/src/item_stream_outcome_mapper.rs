@@ -55,6 +55,7 @@ impl ItemStreamOutcomeMapper {
             state,
             item_ids_processed,
             item_ids_not_processed,
+            item_ids_interrupted: Vec::new(),
         }
     }
 }
@@ -6,7 +6,7 @@ use fn_graph::{
 };
 use peace_item_model::ItemId;
 
-use crate::Data;
+use crate::{Data, DataBorrowInfo};
 
 /// A resource that may or may not exist.
 ///
@@ -54,6 +54,10 @@ where
             .ok()
             .into()
     }
+
+    fn borrow_infos() -> Vec<DataBorrowInfo> {
+        vec![DataBorrowInfo::new::<T>(false, false)]
+    }
 }
 
 impl<T> DataAccess for RMaybe<'_, T>
@@ -6,7 +6,7 @@ use fn_graph::{
 };
 use peace_item_model::ItemId;
 
-use crate::Data;
+use crate::{Data, DataBorrowInfo};
 
 /// A mutable resource that may or may not exist.
 ///
@@ -63,6 +63,10 @@ where
             .ok()
             .into()
     }
+
+    fn borrow_infos() -> Vec<DataBorrowInfo> {
+        vec![DataBorrowInfo::new::<T>(true, false)]
+    }
 }
 
 impl<T> DataAccess for WMaybe<'_, T>
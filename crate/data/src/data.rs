@@ -3,6 +3,8 @@ use std::fmt::Debug;
 use fn_graph::{DataAccess, DataAccessDyn, DataBorrow, Resources, R, W};
 use peace_item_model::ItemId;
 
+use crate::DataBorrowInfo;
+
 /// Defines the logic to instantiate and retrieve runtime data.
 ///
 /// # Note for API Consumers
@@ -22,6 +24,19 @@ pub trait Data<'borrow>: DataAccess + DataAccessDyn + Send {
     /// * `item_id`: ID of the item this borrow is used for.
     /// * `resources`: `Any` map to borrow the data from.
     fn borrow(item_id: &'borrow ItemId, resources: &'borrow Resources) -> Self;
+
+    /// Returns information about each type that `Self` borrows.
+    ///
+    /// This is used to check a flow before it is executed. The [`Data`
+    /// derive] collects the information from each field.
+    ///
+    /// [`Data` derive]: peace_data_derive::Data
+    fn borrow_infos() -> Vec<DataBorrowInfo>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
 }
 
 impl<'borrow> Data<'borrow> for () {
@@ -41,6 +56,10 @@ where
     fn borrow(_item_id: &'borrow ItemId, resources: &'borrow Resources) -> Self {
         <Self as DataBorrow>::borrow(resources)
     }
+
+    fn borrow_infos() -> Vec<DataBorrowInfo> {
+        vec![DataBorrowInfo::new::<T>(false, true)]
+    }
 }
 
 impl<'borrow, T> Data<'borrow> for W<'borrow, T>
@@ -50,4 +69,8 @@ where
     fn borrow(_item_id: &'borrow ItemId, resources: &'borrow Resources) -> Self {
         <Self as DataBorrow>::borrow(resources)
     }
+
+    fn borrow_infos() -> Vec<DataBorrowInfo> {
+        vec![DataBorrowInfo::new::<T>(true, true)]
+    }
 }
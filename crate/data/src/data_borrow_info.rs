@@ -0,0 +1,42 @@
+use std::any::TypeId;
+
+/// Describes a type that a [`Data`] type borrows from `Resources`.
+///
+/// This is used to check a flow before it is executed, e.g. that each type an
+/// item reads has been inserted into `Resources`, and that no two concurrent
+/// items write to the same type.
+///
+/// [`Data`]: crate::Data
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataBorrowInfo {
+    /// `TypeId` of the borrowed type.
+    pub type_id: TypeId,
+    /// Short name of the borrowed type, e.g. `Option<FileDownloadState>`.
+    pub type_name: String,
+    /// Whether the type is borrowed mutably.
+    pub mutable: bool,
+    /// Whether the type must exist in `Resources` when borrowed.
+    ///
+    /// This is `false` for accessors such as `RMaybe` and `WMaybe`.
+    pub required: bool,
+}
+
+impl DataBorrowInfo {
+    /// Returns a `DataBorrowInfo` for `T`.
+    ///
+    /// # Parameters
+    ///
+    /// * `mutable`: Whether the type is borrowed mutably.
+    /// * `required`: Whether the type must exist in `Resources` when borrowed.
+    pub fn new<T>(mutable: bool, required: bool) -> Self
+    where
+        T: 'static,
+    {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: tynm::type_name::<T>(),
+            mutable,
+            required,
+        }
+    }
+}
@@ -124,6 +124,17 @@ fn impl_data_access(ast: &DeriveInput) -> proc_macro2::TokenStream {
             fn borrow(item_id: & #impl_borrow_lt #peace_item_model_path::ItemId, resources: & #impl_borrow_lt #peace_data_path::Resources) -> Self {
                 #borrow_return
             }
+
+            fn borrow_infos() -> Vec<#peace_data_path::DataBorrowInfo> {
+                let mut r = Vec::new();
+
+                #( {
+                        let mut borrow_infos = <#tys as #peace_data_path::Data< #impl_borrow_lt >>::borrow_infos();
+                        r.append(&mut borrow_infos);
+                    } )*
+
+                r
+            }
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{Equality, MaybeEq, Tracked};
@@ -52,3 +54,12 @@ where
         <Tracked<T> as MaybeEq>::maybe_eq(&self.from, &self.to)
     }
 }
+
+impl<T> fmt::Display for Changeable<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.from, self.to)
+    }
+}
@@ -1,5 +1,7 @@
 //! Types to represent changed values.
 
+pub use peace_diff_derive::StateDiff;
+
 pub use crate::{changeable::Changeable, equality::Equality, maybe_eq::MaybeEq, tracked::Tracked};
 
 mod changeable;
@@ -1,11 +1,35 @@
-use std::hash::{Hash, Hasher};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, EnumAccess, MapAccess, VariantAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 use crate::{Equality, MaybeEq};
 
 /// Tracks the known state of a value.
-#[derive(Clone, Debug, Deserialize, Serialize, Eq)]
+///
+/// # Serialization
+///
+/// [`Tracked::Known`] is serialized as a map with a single `Known` key,
+/// instead of a tagged value, so that values which are themselves enums can
+/// be serialized, e.g. `Tracked::Known(Generated::Tbd)`:
+///
+/// ```yaml
+/// from: None
+/// to:
+///   Known: !Tbd null
+/// ```
+///
+/// `serde_yaml` cannot serialize a tagged value within another tagged value.
+/// Both the map and the tagged form are accepted when deserializing, so
+/// values serialized in the tagged form can still be read.
+#[derive(Clone, Debug, Eq)]
 pub enum Tracked<T> {
     /// Value does not exist.
     None,
@@ -37,6 +61,106 @@ where
     }
 }
 
+impl<T> Serialize for Tracked<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::None => serializer.serialize_unit_variant("Tracked", 0, "None"),
+            Self::Unknown => serializer.serialize_unit_variant("Tracked", 1, "Unknown"),
+            Self::Known(t) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("Known", t)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Tracked<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(TrackedVisitor(PhantomData))
+    }
+}
+
+/// Names of the [`Tracked`] variants.
+#[derive(Deserialize)]
+enum TrackedVariant {
+    None,
+    Unknown,
+    Known,
+}
+
+/// Deserializes a [`Tracked`] from a variant name, a map with a single
+/// `Known` key, or a tagged value.
+struct TrackedVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for TrackedVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Tracked<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("`None`, `Unknown`, or a map with a single `Known` key")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match v {
+            "None" => Ok(Tracked::None),
+            "Unknown" => Ok(Tracked::Unknown),
+            _ => Err(E::unknown_variant(v, &["None", "Unknown"])),
+        }
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let tracked = match map.next_key::<TrackedVariant>()? {
+            Some(TrackedVariant::Known) => Tracked::Known(map.next_value::<T>()?),
+            Some(TrackedVariant::None | TrackedVariant::Unknown) => {
+                return Err(de::Error::custom(
+                    "`None` and `Unknown` must not be used as map keys",
+                ));
+            }
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::custom(
+                "expected a map with a single `Known` key",
+            ));
+        }
+
+        Ok(tracked)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (tracked_variant, variant) = data.variant::<TrackedVariant>()?;
+        match tracked_variant {
+            TrackedVariant::None => variant.unit_variant().map(|()| Tracked::None),
+            TrackedVariant::Unknown => variant.unit_variant().map(|()| Tracked::Unknown),
+            TrackedVariant::Known => variant.newtype_variant::<T>().map(Tracked::Known),
+        }
+    }
+}
+
 impl<T> Hash for Tracked<T>
 where
     T: Hash,
@@ -70,3 +194,16 @@ where
         }
     }
 }
+
+impl<T> fmt::Display for Tracked<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Unknown => write!(f, "unknown"),
+            Self::Known(t) => t.fmt(f),
+        }
+    }
+}
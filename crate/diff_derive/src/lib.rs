@@ -0,0 +1,251 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+#![recursion_limit = "256"]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+use proc_macro::TokenStream;
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, WherePredicate};
+
+/// Used to `#[derive]` a field-wise diff type for a struct state.
+///
+/// For regular usage, use `#[derive(StateDiff)]`
+///
+/// For peace crates, also add the `#[peace_internal]` attribute, which
+/// references the `peace_diff` crate instead of the `peace::diff` re-export.
+///
+/// # Generated Types
+///
+/// For a state type named `MyState`, a `MyStateDiff` struct is generated, with
+/// each field wrapped in [`Changeable`]. `MyStateDiff` has:
+///
+/// * `MyStateDiff::new(state_from, state_to)`: Returns the diff between two
+///   states.
+/// * `MyStateDiff::equality()`: Returns the combined [`Equality`] of all
+///   fields.
+/// * `MyStateDiff::is_unchanged()`: Returns whether all fields are known to be
+///   equal.
+/// * `Clone`, `Debug`, `PartialEq`, `Display`, and `serde` implementations.
+///
+/// Each field type must implement `Clone`, `Display`, and [`MaybeEq`], and the
+/// crate using this derive must depend on `serde`.
+///
+/// Fields are compared using [`MaybeEq`], so a value that is not yet known,
+/// such as `External::Tbd`, is treated as possibly changed.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use peace::{cfg::state::Generated, diff::StateDiff};
+///
+/// #[derive(Clone, Debug, PartialEq, StateDiff, Serialize, Deserialize)]
+/// pub struct ServerState {
+///     pub name: String,
+///     pub ip: Generated<String>,
+/// }
+///
+/// // In the `Item` implementation:
+/// type StateDiff = ServerStateDiff;
+///
+/// async fn state_diff(
+///     _params_partial: &<Self::Params<'_> as Params>::Partial,
+///     _data: Self::Data<'_>,
+///     state_current: &Self::State,
+///     state_goal: &Self::State,
+/// ) -> Result<Self::StateDiff, MyError> {
+///     Ok(ServerStateDiff::new(state_current, state_goal))
+/// }
+/// ```
+///
+/// [`Changeable`]: peace_diff::Changeable
+/// [`Equality`]: peace_diff::Equality
+/// [`MaybeEq`]: peace_diff::MaybeEq
+#[proc_macro_derive(StateDiff, attributes(peace_internal))]
+pub fn state_diff(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).expect("StateDiff derive: Code failed to be parsed.");
+
+    let gen = impl_state_diff(&ast).unwrap_or_else(syn::Error::into_compile_error);
+
+    gen.into()
+}
+
+fn impl_state_diff(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let peace_diff_path = ast
+        .attrs
+        .iter()
+        .find(peace_internal)
+        .map(
+            #[cfg_attr(coverage_nightly, coverage(off))]
+            |_| quote!(peace_diff),
+        )
+        .unwrap_or_else(|| quote!(peace::diff));
+
+    let fields_named = match &ast.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => fields_named,
+            Fields::Unnamed(_) | Fields::Unit => {
+                return Err(syn::Error::new_spanned(
+                    &ast.ident,
+                    "`StateDiff` derive: Only structs with named fields are supported.",
+                ));
+            }
+        },
+        Data::Enum(_) | Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "`StateDiff` derive: Only structs with named fields are supported.",
+            ));
+        }
+    };
+
+    let vis = &ast.vis;
+    let state_name = &ast.ident;
+    let state_diff_name = format_ident!("{}Diff", state_name);
+    let state_diff_doc = format!("Field-wise diff between two [`{state_name}`]s.");
+
+    let field_idents = fields_named
+        .named
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .collect::<Vec<&Ident>>();
+    let field_tys = fields_named
+        .named
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+    let field_names = field_idents
+        .iter()
+        .map(|field_ident| {
+            let field_name = field_ident.to_string();
+            field_name
+                .strip_prefix("r#")
+                .map(str::to_string)
+                .unwrap_or(field_name)
+        })
+        .collect::<Vec<String>>();
+    let field_docs = field_names
+        .iter()
+        .map(|field_name| format!("Change in the `{field_name}` field."))
+        .collect::<Vec<String>>();
+    let field_count = field_idents.len();
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    // `new` and `equality` need each field type to be `MaybeEq`.
+    let mut generics_maybe_eq = ast.generics.clone();
+    {
+        let where_clause = generics_maybe_eq.make_where_clause();
+        field_tys.iter().for_each(|field_ty| {
+            let where_predicate: WherePredicate =
+                parse_quote!(#field_ty: Clone + #peace_diff_path::MaybeEq);
+            where_clause.predicates.push(where_predicate);
+        });
+    }
+    let where_clause_maybe_eq = &generics_maybe_eq.where_clause;
+
+    // `Display` additionally needs each field type to be `Display`.
+    let mut generics_display = generics_maybe_eq.clone();
+    {
+        let where_clause = generics_display.make_where_clause();
+        field_tys.iter().for_each(|field_ty| {
+            let where_predicate: WherePredicate = parse_quote!(#field_ty: std::fmt::Display);
+            where_clause.predicates.push(where_predicate);
+        });
+    }
+    let where_clause_display = &generics_display.where_clause;
+
+    Ok(quote! {
+        #[doc = #state_diff_doc]
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        #vis struct #state_diff_name #impl_generics #where_clause {
+            #(
+                #[doc = #field_docs]
+                pub #field_idents: #peace_diff_path::Changeable<#field_tys>,
+            )*
+        }
+
+        impl #impl_generics #state_diff_name #ty_generics #where_clause_maybe_eq {
+            /// Returns the field-wise diff between `state_from` and `state_to`.
+            pub fn new(
+                state_from: &#state_name #ty_generics,
+                state_to: &#state_name #ty_generics,
+            ) -> Self {
+                Self {
+                    #(
+                        #field_idents: #peace_diff_path::Changeable::known(
+                            state_from.#field_idents.clone(),
+                            state_to.#field_idents.clone(),
+                        ),
+                    )*
+                }
+            }
+
+            /// Returns the combined equality of all fields.
+            ///
+            /// This is `NotEqual` if any field is not equal, otherwise
+            /// `Unknown` if any field's equality is unknown.
+            pub fn equality(&self) -> #peace_diff_path::Equality {
+                let field_equalities: [#peace_diff_path::Equality; #field_count] = [
+                    #(self.#field_idents.equality(),)*
+                ];
+
+                field_equalities.into_iter().fold(
+                    #peace_diff_path::Equality::Equal,
+                    |equality, field_equality| match (equality, field_equality) {
+                        (#peace_diff_path::Equality::NotEqual, _)
+                        | (_, #peace_diff_path::Equality::NotEqual) => {
+                            #peace_diff_path::Equality::NotEqual
+                        }
+                        (#peace_diff_path::Equality::Unknown, _)
+                        | (_, #peace_diff_path::Equality::Unknown) => {
+                            #peace_diff_path::Equality::Unknown
+                        }
+                        (#peace_diff_path::Equality::Equal, #peace_diff_path::Equality::Equal) => {
+                            #peace_diff_path::Equality::Equal
+                        }
+                    },
+                )
+            }
+
+            /// Returns whether all fields are known to be unchanged.
+            pub fn is_unchanged(&self) -> bool {
+                self.equality() == #peace_diff_path::Equality::Equal
+            }
+        }
+
+        impl #impl_generics std::fmt::Display for #state_diff_name #ty_generics #where_clause_display {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let field_diffs: [(&str, #peace_diff_path::Equality, &dyn std::fmt::Display); #field_count] = [
+                    #(
+                        (
+                            #field_names,
+                            self.#field_idents.equality(),
+                            &self.#field_idents as &dyn std::fmt::Display,
+                        ),
+                    )*
+                ];
+                let mut field_diffs_changed = field_diffs
+                    .iter()
+                    .filter(|(_, equality, _)| *equality != #peace_diff_path::Equality::Equal);
+
+                match field_diffs_changed.next() {
+                    Some((field_name, _, field_diff)) => {
+                        write!(f, "`{field_name}`: {field_diff}")?;
+                        field_diffs_changed.try_for_each(|(field_name, _, field_diff)| {
+                            write!(f, ", `{field_name}`: {field_diff}")
+                        })
+                    }
+                    None => write!(f, "unchanged"),
+                }
+            }
+        }
+    })
+}
+
+fn peace_internal(attr: &&Attribute) -> bool {
+    attr.path().is_ident("peace_internal")
+}
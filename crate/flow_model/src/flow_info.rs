@@ -1,8 +1,9 @@
 use fn_graph::GraphInfo;
 
+use peace_item_model::ItemId;
 use serde::{Deserialize, Serialize};
 
-use crate::{FlowId, ItemInfo};
+use crate::{FlowId, FlowStateRef, ItemInfo};
 
 /// Serializable representation of values in a [`Flow`].
 ///
@@ -15,6 +16,13 @@ pub struct FlowInfo {
     pub flow_id: FlowId,
     /// Serialized representation of the flow graph.
     pub graph_info: GraphInfo<ItemInfo>,
+    /// Items in this flow whose params read the stored state of an item in
+    /// another flow.
+    ///
+    /// Each pair is the ID of the item in this flow, and the stored state it
+    /// reads.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flow_state_refs: Vec<(ItemId, FlowStateRef)>,
 }
 
 impl FlowInfo {
@@ -23,6 +31,32 @@ impl FlowInfo {
         Self {
             flow_id,
             graph_info,
+            flow_state_refs: Vec::new(),
         }
     }
+
+    /// Returns this `FlowInfo` with the given references to other flows'
+    /// stored states.
+    #[must_use]
+    pub fn with_flow_state_refs(mut self, flow_state_refs: Vec<(ItemId, FlowStateRef)>) -> Self {
+        self.flow_state_refs = flow_state_refs;
+        self
+    }
+
+    /// Returns the IDs of the other flows that this flow depends on, in order
+    /// of first reference.
+    ///
+    /// This flow depends on another flow when an item's params read that
+    /// flow's stored state.
+    pub fn flow_ids_dependencies(&self) -> Vec<&FlowId> {
+        self.flow_state_refs.iter().fold(
+            Vec::<&FlowId>::new(),
+            |mut flow_ids, (_item_id, flow_state_ref)| {
+                if !flow_ids.contains(&&flow_state_ref.flow_id) {
+                    flow_ids.push(&flow_state_ref.flow_id);
+                }
+                flow_ids
+            },
+        )
+    }
 }
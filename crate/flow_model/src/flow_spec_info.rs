@@ -3,9 +3,10 @@ use dot_ix::model::{
     info_graph::{GraphDir, GraphStyle, InfoGraph},
 };
 use fn_graph::{daggy::Walker, Edge, GraphInfo};
+use peace_item_model::ItemId;
 use serde::{Deserialize, Serialize};
 
-use crate::{FlowId, ItemSpecInfo};
+use crate::{FlowId, ItemSpecInfo, SubFlowSpecInfo};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -15,7 +16,6 @@ cfg_if::cfg_if! {
             common::AnyId,
             theme::{AnyIdOrDefaults, CssClassPartials, Theme, ThemeAttr},
         };
-        use peace_item_model::ItemId;
         use peace_progress_model::{ProgressComplete, ProgressStatus};
     }
 }
@@ -29,6 +29,16 @@ pub struct FlowSpecInfo {
     pub flow_id: FlowId,
     /// Serialized representation of the flow graph.
     pub graph_info: GraphInfo<ItemSpecInfo>,
+    /// Pairs of items where the second item's params spec reads the first
+    /// item's state.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params_refs: Vec<(ItemId, ItemId)>,
+    /// Flows embedded in this flow, whose items are rendered as a cluster.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_flows: Vec<SubFlowSpecInfo>,
+    /// Items that are disabled by their enable condition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item_ids_disabled: Vec<ItemId>,
 }
 
 impl FlowSpecInfo {
@@ -37,9 +47,42 @@ impl FlowSpecInfo {
         Self {
             flow_id,
             graph_info,
+            params_refs: Vec::new(),
+            sub_flows: Vec::new(),
+            item_ids_disabled: Vec::new(),
         }
     }
 
+    /// Returns this `FlowSpecInfo` with the given params spec references
+    /// between items.
+    ///
+    /// These are rendered as edges in the progress graph, in addition to the
+    /// edges from the flow graph.
+    #[must_use]
+    pub fn with_params_refs(mut self, params_refs: Vec<(ItemId, ItemId)>) -> Self {
+        self.params_refs = params_refs;
+        self
+    }
+
+    /// Returns this `FlowSpecInfo` with the given sub-flows.
+    ///
+    /// The items in each sub-flow are rendered within a node for the sub-flow
+    /// in the progress graph.
+    #[must_use]
+    pub fn with_sub_flows(mut self, sub_flows: Vec<SubFlowSpecInfo>) -> Self {
+        self.sub_flows = sub_flows;
+        self
+    }
+
+    /// Returns this `FlowSpecInfo` with the given disabled items.
+    ///
+    /// These are rendered with a "(disabled)" suffix in the progress graph.
+    #[must_use]
+    pub fn with_item_ids_disabled(mut self, item_ids_disabled: Vec<ItemId>) -> Self {
+        self.item_ids_disabled = item_ids_disabled;
+        self
+    }
+
     /// Returns an [`InfoGraph`] that represents the progress of the flow's
     /// execution.
     pub fn to_progress_info_graph(&self) -> InfoGraph {
@@ -73,14 +116,41 @@ impl FlowSpecInfo {
             NodeHierarchy::with_capacity(item_count),
             |mut hierarchy, (_node_index, item_spec_info)| {
                 let node_id = item_spec_info_to_node_id(item_spec_info);
-                // Progress nodes have no nested nodes.
-                hierarchy.insert(node_id, NodeHierarchy::new());
+                let sub_flow = self
+                    .sub_flows
+                    .iter()
+                    .find(|sub_flow| sub_flow.contains(&item_spec_info.item_id));
+
+                // Progress nodes have no nested nodes, but are nested within their
+                // sub-flow's node.
+                match sub_flow {
+                    Some(sub_flow) => {
+                        let sub_flow_node_id = sub_flow_id_to_node_id(&sub_flow.sub_flow_id);
+                        if let Some(sub_flow_hierarchy) = hierarchy.get_mut(&sub_flow_node_id) {
+                            sub_flow_hierarchy.insert(node_id, NodeHierarchy::new());
+                        } else {
+                            let mut sub_flow_hierarchy = NodeHierarchy::new();
+                            sub_flow_hierarchy.insert(node_id, NodeHierarchy::new());
+                            hierarchy.insert(sub_flow_node_id, sub_flow_hierarchy);
+                        }
+                    }
+                    None => {
+                        hierarchy.insert(node_id, NodeHierarchy::new());
+                    }
+                }
                 hierarchy
             },
         );
 
-        let edges = progress_node_edges(graph_info);
-        let node_names = node_names(graph_info);
+        let mut edges = progress_node_edges(graph_info);
+        params_refs_edges_insert(&mut edges, &self.params_refs);
+        let mut node_names = node_names(graph_info, &self.item_ids_disabled);
+        self.sub_flows.iter().for_each(|sub_flow| {
+            node_names.insert(
+                sub_flow_id_to_node_id(&sub_flow.sub_flow_id),
+                sub_flow.sub_flow_id.to_string(),
+            );
+        });
 
         let info_graph = InfoGraph::default()
             .with_graph_style(GraphStyle::Circle)
@@ -163,6 +233,9 @@ fn item_progress_css_class_partials(progress_status: &ProgressStatus) -> CssClas
         ProgressStatus::Complete(ProgressComplete::Fail) => {
             css_class_partials.insert(ThemeAttr::ShapeColor, "red".to_string());
         }
+        ProgressStatus::Complete(ProgressComplete::Interrupted) => {
+            css_class_partials.insert(ThemeAttr::ShapeColor, "yellow".to_string());
+        }
     }
     css_class_partials
 }
@@ -211,15 +284,33 @@ fn progress_node_edges(graph_info: &GraphInfo<ItemSpecInfo>) -> Edges {
     )
 }
 
+/// Inserts an edge for each params spec reference.
+///
+/// Edge IDs are derived from the item IDs, so references between items that
+/// already have an edge in the graph replace that edge with an identical one.
+fn params_refs_edges_insert(edges: &mut Edges, params_refs: &[(ItemId, ItemId)]) {
+    params_refs.iter().for_each(|(item_id_src, item_id_dest)| {
+        let node_id_src = item_id_to_node_id(item_id_src);
+        let node_id_dest = item_id_to_node_id(item_id_dest);
+        let edge_id = EdgeId::try_from(format!("{node_id_src}__{node_id_dest}"))
+            .expect("Expected concatenated `peace` `ItemId`s to be valid `dot_ix` `EdgeId`s.");
+        edges.insert(edge_id, [node_id_src, node_id_dest]);
+    });
+}
+
 /// Returns the list of edges between items in the graph.
-fn node_names(graph_info: &GraphInfo<ItemSpecInfo>) -> NodeNames {
+fn node_names(graph_info: &GraphInfo<ItemSpecInfo>, item_ids_disabled: &[ItemId]) -> NodeNames {
     graph_info.iter_insertion_with_indices().fold(
         NodeNames::with_capacity(graph_info.node_count()),
         |mut node_names, (_node_index, item_spec_info)| {
             let item_id = item_spec_info_to_node_id(item_spec_info);
 
             // Note: This does not have to be the ID, it can be a human readable name.
-            let node_name = item_id.to_string();
+            let node_name = if item_ids_disabled.contains(&item_spec_info.item_id) {
+                format!("{item_id} (disabled)")
+            } else {
+                item_id.to_string()
+            };
 
             node_names.insert(item_id, node_name);
 
@@ -229,6 +320,18 @@ fn node_names(graph_info: &GraphInfo<ItemSpecInfo>) -> NodeNames {
 }
 
 fn item_spec_info_to_node_id(item_spec_info: &ItemSpecInfo) -> NodeId {
-    NodeId::try_from(item_spec_info.item_id.to_string())
+    item_id_to_node_id(&item_spec_info.item_id)
+}
+
+/// Returns the node ID for a sub-flow's cluster.
+///
+/// This is suffixed, so that it does not clash with item node IDs.
+fn sub_flow_id_to_node_id(sub_flow_id: &FlowId) -> NodeId {
+    NodeId::try_from(format!("{sub_flow_id}__sub_flow"))
+        .expect("Expected `peace` `FlowId`s to be valid `dot_ix` `NodeId`s.`")
+}
+
+fn item_id_to_node_id(item_id: &ItemId) -> NodeId {
+    NodeId::try_from(item_id.to_string())
         .expect("Expected `peace` `ItemId`s to be valid `dot_ix` `NodeId`s.`")
 }
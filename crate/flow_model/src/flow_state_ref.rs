@@ -0,0 +1,24 @@
+use peace_item_model::ItemId;
+use serde::{Deserialize, Serialize};
+
+use crate::FlowId;
+
+/// Reference to the stored current state of an item in a flow.
+///
+/// This is used when an item's params are read from another flow's stored
+/// state, e.g. an app upload flow that uses the S3 bucket name from an
+/// environment deploy flow.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct FlowStateRef {
+    /// ID of the flow whose stored state is read.
+    pub flow_id: FlowId,
+    /// ID of the item in that flow whose stored state is read.
+    pub item_id: ItemId,
+}
+
+impl FlowStateRef {
+    /// Returns a new `FlowStateRef`.
+    pub fn new(flow_id: FlowId, item_id: ItemId) -> Self {
+        Self { flow_id, item_id }
+    }
+}
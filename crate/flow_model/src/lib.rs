@@ -12,12 +12,16 @@ pub use crate::{
     flow_id::{FlowId, FlowIdInvalidFmt},
     flow_info::FlowInfo,
     flow_spec_info::FlowSpecInfo,
+    flow_state_ref::FlowStateRef,
     item_info::ItemInfo,
     item_spec_info::ItemSpecInfo,
+    sub_flow_spec_info::SubFlowSpecInfo,
 };
 
 mod flow_id;
 mod flow_info;
 mod flow_spec_info;
+mod flow_state_ref;
 mod item_info;
 mod item_spec_info;
+mod sub_flow_spec_info;
@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use peace_item_model::ItemId;
+use serde::{Deserialize, Serialize};
+
+use crate::FlowId;
+
+/// Serializable representation of a flow that is embedded in another flow.
+///
+/// The IDs of items in a sub-flow are namespaced with the sub-flow ID, see
+/// [`SubFlowSpecInfo::item_id_namespaced`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SubFlowSpecInfo {
+    /// ID of the sub-flow, used as the prefix for its item IDs.
+    pub sub_flow_id: FlowId,
+    /// Namespaced IDs of the items in the sub-flow, in insertion order.
+    pub item_ids: Vec<ItemId>,
+}
+
+impl SubFlowSpecInfo {
+    /// Returns a new `SubFlowSpecInfo`.
+    pub fn new(sub_flow_id: FlowId, item_ids: Vec<ItemId>) -> Self {
+        Self {
+            sub_flow_id,
+            item_ids,
+        }
+    }
+
+    /// Returns the ID of an item within a sub-flow, e.g.
+    /// `app_upload__s3_bucket`.
+    ///
+    /// This is the sub-flow ID and item ID joined by a double underscore.
+    pub fn item_id_namespaced(sub_flow_id: &FlowId, item_id: &ItemId) -> ItemId {
+        ItemId::from_str(&format!("{sub_flow_id}__{item_id}"))
+            .expect("Expected concatenated `FlowId` and `ItemId` to be a valid `ItemId`.")
+    }
+
+    /// Returns whether the sub-flow contains the given item.
+    pub fn contains(&self, item_id: &ItemId) -> bool {
+        self.item_ids.contains(item_id)
+    }
+}
@@ -1,7 +1,16 @@
-use peace_data::fn_graph::GraphInfo;
-use peace_flow_model::{FlowId, FlowSpecInfo, ItemSpecInfo};
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+};
 
-use crate::ItemGraph;
+use peace_data::fn_graph::{daggy::Walker, Edge, FnId, GraphInfo, WouldCycle};
+use peace_flow_model::{FlowId, FlowInfo, FlowSpecInfo, FlowStateRef, ItemInfo, ItemSpecInfo};
+use peace_item_model::ItemId;
+use peace_params::{name_suggest, MappingFnId, MappingFnReg, ParamsSpecs};
+use peace_resource_rt::{resources::ts::SetUp, Resources};
+use peace_rt_model::{FlowIssue, FlowIssues};
+
+use crate::{ItemGraph, ItemGraphBuilder};
 
 cfg_if::cfg_if! {
     if #[cfg(all(feature = "item_interactions", feature = "item_state_example"))] {
@@ -15,19 +24,9 @@ cfg_if::cfg_if! {
             ItemLocationsAndInteractions,
             ItemLocationTree,
         };
-        use peace_item_model::ItemId;
-        use peace_params::{MappingFnReg, ParamsSpecs};
-        use peace_resource_rt::{resources::ts::SetUp, Resources};
     }
 }
 
-#[cfg(all(
-    feature = "item_interactions",
-    feature = "item_state_example",
-    feature = "output_progress",
-))]
-use std::collections::{HashMap, HashSet};
-
 /// A flow to manage items.
 ///
 /// A Flow ID is strictly associated with an [`ItemGraph`], as the graph
@@ -85,6 +84,65 @@ impl<E> Flow<E> {
         &self.graph
     }
 
+    /// Generates a `FlowInfo` from this `Flow`'s information.
+    pub fn flow_info(&self) -> FlowInfo
+    where
+        E: 'static,
+    {
+        let flow_id = self.flow_id.clone();
+        let graph_info = GraphInfo::from_graph(&self.graph, |item_boxed| {
+            ItemInfo::new(item_boxed.id().clone())
+        });
+
+        FlowInfo::new(flow_id, graph_info)
+    }
+
+    /// Generates a `FlowInfo` from this `Flow`'s information, including the
+    /// other flows' stored states that items' params specs read.
+    ///
+    /// See [`Flow::params_specs_flow_state_refs`].
+    pub fn flow_info_with_params_specs(&self, params_specs: &ParamsSpecs) -> FlowInfo
+    where
+        E: 'static,
+    {
+        self.flow_info()
+            .with_flow_state_refs(self.params_specs_flow_state_refs(params_specs))
+    }
+
+    /// Returns the items whose params specs read another flow's stored state,
+    /// through `ValueSpec::FlowState`, paired with the stored state they read.
+    pub fn params_specs_flow_state_refs(
+        &self,
+        params_specs: &ParamsSpecs,
+    ) -> Vec<(ItemId, FlowStateRef)>
+    where
+        E: 'static,
+    {
+        self.graph
+            .iter_insertion()
+            .filter_map(|item| {
+                params_specs
+                    .get_raw(item.id())
+                    .map(|params_spec| (item.id(), params_spec))
+            })
+            .fold(
+                Vec::<(ItemId, FlowStateRef)>::new(),
+                |mut flow_state_refs, (item_id, params_spec)| {
+                    params_spec
+                        .flow_state_refs()
+                        .into_iter()
+                        .for_each(|flow_state_ref| {
+                            let item_flow_state_ref = (item_id.clone(), flow_state_ref);
+                            if !flow_state_refs.contains(&item_flow_state_ref) {
+                                flow_state_refs.push(item_flow_state_ref);
+                            }
+                        });
+
+                    flow_state_refs
+                },
+            )
+    }
+
     /// Generates a `FlowSpecInfo` from this `Flow`'s information.
     pub fn flow_spec_info(&self) -> FlowSpecInfo
     where
@@ -96,7 +154,571 @@ impl<E> Flow<E> {
             ItemSpecInfo { item_id }
         });
 
-        FlowSpecInfo::new(flow_id, graph_info)
+        FlowSpecInfo::new(flow_id, graph_info).with_sub_flows(self.graph.sub_flows().to_vec())
+    }
+
+    /// Generates a `FlowSpecInfo` from this `Flow`'s information, including
+    /// references between items from their params specs.
+    ///
+    /// See [`Flow::params_specs_item_refs`].
+    pub fn flow_spec_info_with_params_specs(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+    ) -> FlowSpecInfo
+    where
+        E: 'static,
+    {
+        self.flow_spec_info()
+            .with_params_refs(self.params_specs_item_refs(params_specs, mapping_fn_reg))
+    }
+
+    /// Returns the pairs of items where the second item's params spec reads
+    /// the first item's state or data.
+    ///
+    /// An item's params spec reads another item's state or data when:
+    ///
+    /// * a mapping function it uses -- either in a `MappingFn` variant, or a
+    ///   placeholder in a `ValueSpec::Template` -- takes an argument whose type
+    ///   is that item's `State`, or is written by that item's `Data`.
+    /// * an `InMemory` variant reads a value whose type is that item's `State`,
+    ///   or is written by that item's `Data`.
+    /// * a `ValueSpec::ItemOutput` variant reads an output published by that
+    ///   item.
+    ///
+    /// Mapping functions that are not in `mapping_fn_reg` are ignored.
+    pub fn params_specs_item_refs(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+    ) -> Vec<(ItemId, ItemId)>
+    where
+        E: 'static,
+    {
+        let graph = self.graph();
+        graph
+            .iter_insertion()
+            .filter_map(|item| {
+                params_specs
+                    .get_raw(item.id())
+                    .map(|params_spec| (item.id(), params_spec))
+            })
+            .fold(
+                Vec::<(ItemId, ItemId)>::new(),
+                |mut item_refs, (item_id, params_spec)| {
+                    let mapping_fn_arg_type_ids = params_spec
+                        .mapping_fn_ids()
+                        .iter()
+                        .filter_map(|mapping_fn_id| mapping_fn_reg.get(mapping_fn_id))
+                        .flat_map(|mapping_fn| mapping_fn.arg_type_ids())
+                        .collect::<Vec<TypeId>>();
+                    let in_memory_type_ids = params_spec.in_memory_type_ids();
+
+                    mapping_fn_arg_type_ids
+                        .into_iter()
+                        .chain(in_memory_type_ids)
+                        .for_each(|type_id| {
+                            graph
+                                .iter_insertion()
+                                .filter(|item_src| {
+                                    item_src.id() != item_id
+                                        && (item_src.state_type_id() == type_id
+                                            || item_src.borrow_muts().contains(&type_id))
+                                })
+                                .for_each(|item_src| {
+                                    let item_ref = (item_src.id().clone(), item_id.clone());
+                                    if !item_refs.contains(&item_ref) {
+                                        item_refs.push(item_ref);
+                                    }
+                                });
+                        });
+
+                    params_spec
+                        .item_output_refs()
+                        .into_iter()
+                        .filter(|item_id_src| item_id_src != item_id)
+                        .filter_map(|item_id_src| {
+                            graph
+                                .iter_insertion()
+                                .find(|item_src| item_src.id() == &item_id_src)
+                        })
+                        .for_each(|item_src| {
+                            let item_ref = (item_src.id().clone(), item_id.clone());
+                            if !item_refs.contains(&item_ref) {
+                                item_refs.push(item_ref);
+                            }
+                        });
+
+                    item_refs
+                },
+            )
+    }
+
+    /// Returns the pairs of items where the second item's params spec reads
+    /// the first item's state or data, but the first item is not a
+    /// predecessor of the second item in the graph.
+    ///
+    /// When these items are run concurrently, the second item's params may
+    /// be resolved before the first item has written the value, so an edge
+    /// should be added between them.
+    ///
+    /// See [`Flow::params_specs_item_refs`].
+    pub fn params_specs_item_refs_missing(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+    ) -> Vec<(ItemId, ItemId)>
+    where
+        E: 'static,
+    {
+        let item_refs = self.params_specs_item_refs(params_specs, mapping_fn_reg);
+        if item_refs.is_empty() {
+            return item_refs;
+        }
+
+        let graph_info = GraphInfo::from_graph(&self.graph, |item_boxed| item_boxed.id().clone());
+        item_refs
+            .into_iter()
+            .filter(|(item_id_src, item_id_dest)| {
+                let node_index_dest =
+                    graph_info
+                        .iter_insertion_with_indices()
+                        .find_map(|(node_index, item_id)| {
+                            (item_id == item_id_dest).then_some(node_index)
+                        });
+
+                // Walk through the ancestors of the destination item.
+                let mut node_indices_visited = HashSet::new();
+                let mut node_indices_to_visit = node_index_dest.into_iter().collect::<Vec<_>>();
+                while let Some(node_index) = node_indices_to_visit.pop() {
+                    let parents = graph_info.parents(node_index);
+                    for (_edge_index, parent_node_index) in parents.iter(&graph_info) {
+                        if &graph_info[parent_node_index] == item_id_src {
+                            return false;
+                        }
+                        if node_indices_visited.insert(parent_node_index) {
+                            node_indices_to_visit.push(parent_node_index);
+                        }
+                    }
+                }
+
+                true
+            })
+            .collect()
+    }
+
+    /// Checks this flow for problems that would otherwise only surface when it
+    /// is executed.
+    ///
+    /// This checks that:
+    ///
+    /// * No two items that may run concurrently write to the same data type.
+    /// * Each item has a params spec.
+    /// * Each mapping function used by the params specs is registered.
+    /// * Each item whose params spec reads another item's state or data is a
+    ///   successor of that item.
+    ///
+    /// All problems are returned together, so this can be called in a unit
+    /// test to check the flow definition. See [`Flow::validate_with_resources`]
+    /// to also check that each item's data is inserted into `Resources`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FlowInvalid`] if the flow has any problems.
+    ///
+    /// [`Error::FlowInvalid`]: peace_rt_model::Error::FlowInvalid
+    pub fn validate(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+    ) -> Result<(), peace_rt_model::Error>
+    where
+        E: 'static,
+    {
+        self.flow_issues_into_result(self.flow_issues(params_specs, mapping_fn_reg))
+    }
+
+    /// Checks this flow for problems that would otherwise only surface when it
+    /// is executed, including data that is not inserted into `resources`.
+    ///
+    /// In addition to the checks in [`Flow::validate`], this checks that each
+    /// type an item's `Data` requires -- i.e. borrowed through `R<T>` or
+    /// `W<T>` -- exists in `resources`. `resources` should be the resources
+    /// after each item's `setup` has run, such as from a command context.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FlowInvalid`] if the flow has any problems.
+    ///
+    /// [`Error::FlowInvalid`]: peace_rt_model::Error::FlowInvalid
+    pub fn validate_with_resources(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Result<(), peace_rt_model::Error>
+    where
+        E: 'static,
+    {
+        self.flow_issues_into_result(self.flow_issues_with_resources(
+            params_specs,
+            mapping_fn_reg,
+            resources,
+        ))
+    }
+
+    /// Returns the problems with this flow that would otherwise only surface
+    /// when it is executed.
+    ///
+    /// See [`Flow::validate`] for the checks.
+    pub fn flow_issues(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+    ) -> FlowIssues
+    where
+        E: 'static,
+    {
+        let mut flow_issues = FlowIssues::new();
+        self.data_write_concurrent_issues(&mut flow_issues);
+        self.params_spec_not_found_issues(params_specs, &mut flow_issues);
+        self.mapping_fn_not_registered_issues(params_specs, mapping_fn_reg, &mut flow_issues);
+        flow_issues.extend(
+            self.params_specs_item_refs_missing(params_specs, mapping_fn_reg)
+                .into_iter()
+                .map(
+                    |(item_id_src, item_id_dest)| FlowIssue::ParamsRefEdgeMissing {
+                        item_id_src,
+                        item_id_dest,
+                    },
+                ),
+        );
+
+        flow_issues
+    }
+
+    /// Returns the problems with this flow that would otherwise only surface
+    /// when it is executed, including data that is not inserted into
+    /// `resources`.
+    ///
+    /// See [`Flow::validate_with_resources`] for the checks.
+    pub fn flow_issues_with_resources(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> FlowIssues
+    where
+        E: 'static,
+    {
+        let mut flow_issues = self.flow_issues(params_specs, mapping_fn_reg);
+        self.graph.iter_insertion().for_each(|item| {
+            let mut type_ids_not_found = Vec::<TypeId>::new();
+            item.data_borrow_infos()
+                .into_iter()
+                .filter(|data_borrow_info| {
+                    data_borrow_info.required && !resources.contains_key(&data_borrow_info.type_id)
+                })
+                .for_each(|data_borrow_info| {
+                    if !type_ids_not_found.contains(&data_borrow_info.type_id) {
+                        type_ids_not_found.push(data_borrow_info.type_id);
+                        flow_issues.push(FlowIssue::DataNotFound {
+                            item_id: item.id().clone(),
+                            type_name: data_borrow_info.type_name,
+                            mutable: data_borrow_info.mutable,
+                        });
+                    }
+                });
+        });
+
+        flow_issues
+    }
+
+    fn flow_issues_into_result(
+        &self,
+        flow_issues: FlowIssues,
+    ) -> Result<(), peace_rt_model::Error> {
+        if flow_issues.is_empty() {
+            Ok(())
+        } else {
+            Err(peace_rt_model::Error::FlowInvalid {
+                flow_id: self.flow_id.clone(),
+                flow_issues,
+            })
+        }
+    }
+
+    /// Adds an issue for each pair of items that write to the same data type,
+    /// where neither item is an ancestor of the other through logic edges.
+    fn data_write_concurrent_issues(&self, flow_issues: &mut FlowIssues)
+    where
+        E: 'static,
+    {
+        let graph_info = GraphInfo::from_graph(&self.graph, |item_boxed| item_boxed.id().clone());
+        // Data edges are added by the graph to order items that access the same
+        // data, so they do not count as an explicit order.
+        let order_edges = graph_info
+            .raw_edges()
+            .iter()
+            .filter(|edge| !matches!(edge.weight, Edge::Data))
+            .map(|edge| (edge.source(), edge.target()))
+            .collect::<Vec<_>>();
+        let is_ancestor = |node_index_src: FnId, node_index_dest: FnId| {
+            let mut node_indices_visited = HashSet::new();
+            let mut node_indices_to_visit = vec![node_index_dest];
+            while let Some(node_index) = node_indices_to_visit.pop() {
+                let parents = order_edges
+                    .iter()
+                    .filter(|(_parent, child)| *child == node_index)
+                    .map(|(parent, _child)| *parent);
+                for parent in parents {
+                    if parent == node_index_src {
+                        return true;
+                    }
+                    if node_indices_visited.insert(parent) {
+                        node_indices_to_visit.push(parent);
+                    }
+                }
+            }
+            false
+        };
+
+        let nodes = graph_info
+            .iter_insertion_with_indices()
+            .filter_map(|(node_index, item_id)| {
+                self.graph
+                    .iter_insertion()
+                    .find(|item| item.id() == item_id)
+                    .map(|item| (node_index, item))
+            })
+            .collect::<Vec<_>>();
+        nodes
+            .iter()
+            .enumerate()
+            .for_each(|(index, (node_index_a, item_a))| {
+                let borrow_muts_a = item_a.borrow_muts();
+                nodes[index + 1..]
+                    .iter()
+                    .filter(|(node_index_b, _item_b)| {
+                        !is_ancestor(*node_index_a, *node_index_b)
+                            && !is_ancestor(*node_index_b, *node_index_a)
+                    })
+                    .for_each(|(_node_index_b, item_b)| {
+                        let mut type_ids_written = Vec::<TypeId>::new();
+                        item_b
+                            .borrow_muts()
+                            .into_iter()
+                            .filter(|type_id| borrow_muts_a.contains(type_id))
+                            .for_each(|type_id| {
+                                if type_ids_written.contains(&type_id) {
+                                    return;
+                                }
+                                type_ids_written.push(type_id);
+
+                                let type_name = item_a
+                                    .data_borrow_infos()
+                                    .into_iter()
+                                    .find(|data_borrow_info| data_borrow_info.type_id == type_id)
+                                    .map(|data_borrow_info| data_borrow_info.type_name)
+                                    .unwrap_or_else(|| format!("{type_id:?}"));
+                                flow_issues.push(FlowIssue::DataWriteConcurrent {
+                                    item_id_a: item_a.id().clone(),
+                                    item_id_b: item_b.id().clone(),
+                                    type_name,
+                                });
+                            });
+                    });
+            });
+    }
+
+    /// Adds an issue for each item that has no params spec.
+    ///
+    /// Retired items are skipped, as their params specs are loaded from
+    /// storage.
+    fn params_spec_not_found_issues(&self, params_specs: &ParamsSpecs, flow_issues: &mut FlowIssues)
+    where
+        E: 'static,
+    {
+        let item_ids_unknown = params_specs
+            .keys()
+            .filter(|item_id| {
+                !self
+                    .graph
+                    .iter_insertion()
+                    .any(|item| item.id() == *item_id)
+            })
+            .collect::<Vec<&ItemId>>();
+
+        self.graph
+            .iter_insertion()
+            .filter(|item| {
+                !self.graph.item_ids_retired().contains(item.id())
+                    && params_specs.get_raw(item.id()).is_none()
+            })
+            .for_each(|item| {
+                let item_id_suggested = name_suggest(
+                    item.id().as_str(),
+                    item_ids_unknown.iter().map(|item_id| item_id.as_str()),
+                )
+                .and_then(|item_id_suggested| {
+                    item_ids_unknown
+                        .iter()
+                        .find(|item_id| item_id.as_str() == item_id_suggested)
+                        .map(|item_id| (*item_id).clone())
+                });
+                flow_issues.push(FlowIssue::ParamsSpecNotFound {
+                    item_id: item.id().clone(),
+                    item_id_suggested,
+                });
+            });
+    }
+
+    /// Adds an issue for each mapping function used by an item's params spec
+    /// that is not registered in `mapping_fn_reg`.
+    fn mapping_fn_not_registered_issues(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        flow_issues: &mut FlowIssues,
+    ) where
+        E: 'static,
+    {
+        self.graph
+            .iter_insertion()
+            .filter_map(|item| {
+                params_specs
+                    .get_raw(item.id())
+                    .map(|params_spec| (item.id(), params_spec))
+            })
+            .for_each(|(item_id, params_spec)| {
+                params_spec
+                    .mapping_fn_ids()
+                    .into_iter()
+                    .filter(|mapping_fn_id| !mapping_fn_reg.contains_key(mapping_fn_id))
+                    .for_each(|mapping_fn_id| {
+                        let mapping_fn_id_suggested = name_suggest(
+                            mapping_fn_id.as_str(),
+                            mapping_fn_reg.keys().map(MappingFnId::as_str),
+                        )
+                        .and_then(|mapping_fn_id_suggested| {
+                            mapping_fn_reg
+                                .keys()
+                                .find(|mapping_fn_id| {
+                                    mapping_fn_id.as_str() == mapping_fn_id_suggested
+                                })
+                                .cloned()
+                        });
+                        flow_issues.push(FlowIssue::MappingFnNotRegistered {
+                            item_id: item_id.clone(),
+                            mapping_fn_id,
+                            mapping_fn_id_suggested,
+                        });
+                    });
+            });
+    }
+
+    /// Returns a copy of this flow, with logic edges added between the given
+    /// pairs of items.
+    ///
+    /// Existing edges, sub-flows, and retired items are retained. Item IDs
+    /// that are not in this flow are ignored.
+    ///
+    /// This is used to add the edges returned by
+    /// [`Flow::params_specs_item_refs_missing`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding an edge would introduce a cycle in the
+    /// graph.
+    pub fn with_logic_edges(&self, item_refs: &[(ItemId, ItemId)]) -> Result<Self, WouldCycle<Edge>>
+    where
+        E: 'static,
+    {
+        let graph_info = GraphInfo::from_graph(&self.graph, |item_boxed| item_boxed.id().clone());
+
+        let mut graph_builder = ItemGraphBuilder::<E>::new();
+        let item_id_to_fn_id = self
+            .graph
+            .iter_insertion()
+            .map(|item| (item.id().clone(), graph_builder.add_fn(item.clone())))
+            .collect::<HashMap<ItemId, FnId>>();
+
+        graph_info
+            .iter_insertion_with_indices()
+            .try_for_each(|(node_index, item_id)| {
+                let fn_id = item_id_to_fn_id[item_id];
+                graph_info
+                    .children(node_index)
+                    .iter(&graph_info)
+                    .try_for_each(|(edge_index, child_node_index)| {
+                        let fn_id_child = item_id_to_fn_id[&graph_info[child_node_index]];
+                        match graph_info.edge_weight(edge_index).copied() {
+                            Some(Edge::Logic) => {
+                                graph_builder.add_logic_edge(fn_id, fn_id_child)?;
+                            }
+                            Some(Edge::Contains) => {
+                                graph_builder.add_contains_edge(fn_id, fn_id_child)?;
+                            }
+                            _ => {}
+                        }
+
+                        Ok::<_, WouldCycle<Edge>>(())
+                    })
+            })?;
+
+        item_refs
+            .iter()
+            .filter_map(|(item_id_src, item_id_dest)| {
+                item_id_to_fn_id
+                    .get(item_id_src)
+                    .copied()
+                    .zip(item_id_to_fn_id.get(item_id_dest).copied())
+            })
+            .try_for_each(|(fn_id_src, fn_id_dest)| {
+                graph_builder.add_logic_edge(fn_id_src, fn_id_dest)?;
+                Ok::<_, WouldCycle<Edge>>(())
+            })?;
+
+        let graph = graph_builder
+            .build()
+            .with_sub_flows(self.graph.sub_flows().to_vec())
+            .with_item_ids_retired(self.graph.item_ids_retired().to_vec());
+
+        Ok(Self::new(self.flow_id.clone(), graph))
+    }
+
+    /// Returns the JSON Schema for this flow's `params_specs.yaml`.
+    ///
+    /// The schema is an object keyed by item ID, where each value is the
+    /// schema of that item's params spec. See [`Params::params_spec_schema`]
+    /// for items whose params do not provide a schema.
+    ///
+    /// [`Params::params_spec_schema`]: peace_params::Params::params_spec_schema
+    #[cfg(feature = "params_schema")]
+    pub fn params_specs_schema(&self) -> schemars::Schema
+    where
+        E: 'static,
+    {
+        let mut generator = schemars::generate::SchemaSettings::draft2020_12().into_generator();
+        let properties = self
+            .graph()
+            .iter_insertion()
+            .map(|item| {
+                let params_spec_schema = item.params_spec_schema(&mut generator);
+                (item.id().to_string(), params_spec_schema.to_value())
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        let meta_schema = generator.settings().meta_schema.clone();
+        let definitions = generator.take_definitions(true);
+
+        schemars::json_schema!({
+            "$schema": meta_schema,
+            "title": self.flow_id.to_string(),
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+            "$defs": definitions,
+        })
     }
 
     // TODO: Refactor -- there is a lot of duplication between this method and
@@ -0,0 +1,250 @@
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use peace_item_model::ItemId;
+use peace_rt_model::ItemBoxed;
+
+use crate::{ItemFanOutFnIds, ItemGraphBuilder};
+
+/// Instantiates one item per element of a list, with item IDs derived from
+/// each element's key.
+///
+/// Each item's ID is `{item_id_prefix}__{element_key}`, e.g.
+/// `artifact_download__web_app`, `artifact_download__db_migrations`, so params
+/// specs and stored states for each element are tracked separately, and stay
+/// with that element when other elements are inserted or removed.
+///
+/// Element keys must be stable across runs, such as an artifact's name. They
+/// may only contain ASCII letters, digits, and single underscores, and must not
+/// begin or end with an underscore.
+///
+/// # Reconciliation
+///
+/// When elements are removed from the list, their items need to be cleaned
+/// up. Pass the item IDs from the previous run's stored states to
+/// [`ItemFanOut::add_to`], which adds an item for each removed element whose
+/// goal state is its clean state. When the flow is ensured:
+///
+/// * Items for new elements are ensured.
+/// * Items for removed elements are cleaned up.
+///
+/// Once all items are ensured successfully, the removed elements are removed
+/// from `StatesCurrentStored`, so they are not added again on subsequent runs.
+/// If ensuring is interrupted or fails, their stored states are retained, and
+/// they are cleaned up on the next run.
+///
+/// Params specs for removed elements do not need to be provided, as they are
+/// loaded from the params specs stored by the previous run.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let artifact_download = ItemFanOut::new(
+///     item_id!("artifact_download"),
+///     FileDownloadItem::<WebApp>::new,
+/// );
+///
+/// let item_ids_stored = StatesSerializer::<AppError>::deserialize_item_ids_opt(
+///     &storage,
+///     &states_current_file,
+/// )
+/// .await?
+/// .unwrap_or_default();
+///
+/// let artifact_names = artifacts
+///     .iter()
+///     .map(|artifact| artifact.name.as_str())
+///     .collect::<Vec<&str>>();
+///
+/// let mut graph_builder = ItemGraphBuilder::<AppError>::new();
+/// let fan_out_fn_ids =
+///     artifact_download.add_to(&mut graph_builder, &artifact_names, &item_ids_stored)?;
+/// ```
+///
+/// Params for each present element are provided with
+/// `.with_item_params::<FileDownloadItem<WebApp>>(artifact_download.item_id(artifact_name)?, ..)`.
+pub struct ItemFanOut<I, F> {
+    /// Prefix of each item's ID.
+    item_id_prefix: ItemId,
+    /// Returns a new item with the given item ID.
+    item_new: F,
+    /// Marker.
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I, F> fmt::Debug for ItemFanOut<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ItemFanOut")
+            .field("item_id_prefix", &self.item_id_prefix)
+            .field("item_new", &std::any::type_name::<F>())
+            .finish()
+    }
+}
+
+impl<I, F> ItemFanOut<I, F>
+where
+    F: Fn(ItemId) -> I,
+{
+    /// Returns a new `ItemFanOut`.
+    ///
+    /// # Parameters
+    ///
+    /// * `item_id_prefix`: Prefix of each item's ID.
+    /// * `item_new`: Returns a new item with the given item ID.
+    pub fn new(item_id_prefix: ItemId, item_new: F) -> Self {
+        Self {
+            item_id_prefix,
+            item_new,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the prefix of each item's ID.
+    pub fn item_id_prefix(&self) -> &ItemId {
+        &self.item_id_prefix
+    }
+
+    /// Returns the item ID for the element with the given key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ItemFanOutElementKeyInvalid`] if the key cannot be used
+    /// in an item ID.
+    ///
+    /// [`Error::ItemFanOutElementKeyInvalid`]: peace_rt_model::Error::ItemFanOutElementKeyInvalid
+    pub fn item_id(&self, element_key: &str) -> Result<ItemId, peace_rt_model::Error> {
+        let item_id_prefix = &self.item_id_prefix;
+        Some(element_key)
+            .filter(|element_key| Self::element_key_is_valid(element_key))
+            .and_then(|element_key| {
+                ItemId::from_str(&format!("{item_id_prefix}__{element_key}")).ok()
+            })
+            .ok_or_else(|| peace_rt_model::Error::ItemFanOutElementKeyInvalid {
+                item_id_prefix: item_id_prefix.clone(),
+                element_key: element_key.to_string(),
+            })
+    }
+
+    /// Returns the item IDs for the elements with the given keys, in element
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ItemFanOutElementKeyInvalid`] if a key cannot be used in an
+    ///   item ID.
+    /// * [`Error::ItemFanOutElementKeyDuplicate`] if a key is used by more than
+    ///   one element.
+    ///
+    /// [`Error::ItemFanOutElementKeyInvalid`]: peace_rt_model::Error::ItemFanOutElementKeyInvalid
+    /// [`Error::ItemFanOutElementKeyDuplicate`]: peace_rt_model::Error::ItemFanOutElementKeyDuplicate
+    pub fn item_ids<K>(&self, element_keys: &[K]) -> Result<Vec<ItemId>, peace_rt_model::Error>
+    where
+        K: AsRef<str>,
+    {
+        element_keys.iter().try_fold(
+            Vec::with_capacity(element_keys.len()),
+            |mut item_ids, element_key| {
+                let element_key = element_key.as_ref();
+                let item_id = self.item_id(element_key)?;
+                if item_ids.contains(&item_id) {
+                    return Err(peace_rt_model::Error::ItemFanOutElementKeyDuplicate {
+                        item_id_prefix: self.item_id_prefix.clone(),
+                        element_key: element_key.to_string(),
+                    });
+                }
+                item_ids.push(item_id);
+
+                Ok(item_ids)
+            },
+        )
+    }
+
+    /// Returns the element key of `item_id`, if it was derived by this
+    /// `ItemFanOut`.
+    pub fn element_key<'id>(&self, item_id: &'id ItemId) -> Option<&'id str> {
+        item_id
+            .as_str()
+            .strip_prefix(self.item_id_prefix.as_str())
+            .and_then(|suffix| suffix.strip_prefix("__"))
+            .filter(|element_key| Self::element_key_is_valid(element_key))
+    }
+
+    /// Adds an item for each element to the graph, and an item to clean up
+    /// each removed element.
+    ///
+    /// Removed elements are the item IDs in `item_ids_stored` that were
+    /// derived by this `ItemFanOut`, whose element key is not in
+    /// `element_keys`. These are added in element key order, after the present
+    /// elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph_builder`: The graph builder to add the items to.
+    /// * `element_keys`: Stable key of each element in the list.
+    /// * `item_ids_stored`: Item IDs from the previous run's stored states,
+    ///   e.g. from `StatesSerializer::deserialize_item_ids_opt`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an element key is invalid or used by more than one
+    /// element, in which case no items are added. See
+    /// [`ItemFanOut::item_ids`].
+    pub fn add_to<E, K>(
+        &self,
+        graph_builder: &mut ItemGraphBuilder<E>,
+        element_keys: &[K],
+        item_ids_stored: &[ItemId],
+    ) -> Result<ItemFanOutFnIds, peace_rt_model::Error>
+    where
+        E: 'static,
+        I: Into<ItemBoxed<E>>,
+        K: AsRef<str>,
+    {
+        let item_ids = self.item_ids(element_keys)?;
+
+        let mut item_ids_removed = item_ids_stored
+            .iter()
+            .filter(|item_id| self.element_key(item_id).is_some())
+            .filter(|item_id| !item_ids.contains(item_id))
+            .cloned()
+            .collect::<Vec<ItemId>>();
+        item_ids_removed
+            .sort_unstable_by(|item_id_a, item_id_b| item_id_a.as_str().cmp(item_id_b.as_str()));
+        item_ids_removed.dedup();
+
+        let fn_ids = item_ids
+            .into_iter()
+            .map(|item_id| {
+                let item_boxed: ItemBoxed<E> = (self.item_new)(item_id).into();
+                graph_builder.add_fn(item_boxed)
+            })
+            .collect::<Vec<_>>();
+
+        let fn_ids_removed = item_ids_removed
+            .into_iter()
+            .map(|item_id| {
+                let item_boxed: ItemBoxed<E> = (self.item_new)(item_id).into();
+                graph_builder.add_fn(item_boxed.with_state_goal_clean())
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ItemFanOutFnIds {
+            fn_ids,
+            fn_ids_removed,
+        })
+    }
+
+    /// Returns whether `element_key` only contains ASCII letters, digits, and
+    /// single underscores, and does not begin or end with an underscore.
+    ///
+    /// Disallowing `__` within keys means an item ID can only be derived from
+    /// one prefix and key, even when one prefix is the start of another.
+    fn element_key_is_valid(element_key: &str) -> bool {
+        !element_key.is_empty()
+            && !element_key.starts_with('_')
+            && !element_key.ends_with('_')
+            && !element_key.contains("__")
+            && element_key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
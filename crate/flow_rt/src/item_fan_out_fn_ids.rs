@@ -0,0 +1,18 @@
+use peace_data::fn_graph::FnId;
+
+/// IDs of the items added to an [`ItemGraphBuilder`] by an [`ItemFanOut`].
+///
+/// This is returned by [`ItemFanOut::add_to`], and is used to add edges to
+/// and from the fanned out items.
+///
+/// [`ItemFanOut`]: crate::ItemFanOut
+/// [`ItemFanOut::add_to`]: crate::ItemFanOut::add_to
+/// [`ItemGraphBuilder`]: crate::ItemGraphBuilder
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemFanOutFnIds {
+    /// IDs of the items for each element, in element order.
+    pub fn_ids: Vec<FnId>,
+    /// IDs of the items for elements that have been removed, in element key
+    /// order, which are cleaned up when the flow is ensured.
+    pub fn_ids_removed: Vec<FnId>,
+}
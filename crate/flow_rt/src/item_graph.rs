@@ -4,20 +4,30 @@ use std::{
 };
 
 use peace_data::fn_graph::FnGraph;
+use peace_flow_model::SubFlowSpecInfo;
+use peace_item_model::ItemId;
 use peace_resource_rt::states::{States, StatesSerde};
 use peace_rt_model::ItemBoxed;
 
 /// Graph of all [`Item`]s, `FnGraph<ItemBoxed<E>>` newtype.
 ///
+/// This also tracks which items belong to each sub-flow, when flows are
+/// embedded using [`ItemGraphBuilder::add_sub_flow`].
+///
+/// It also tracks which items are retired, when they are added using
+/// [`ItemGraphBuilder::add_retired`].
+///
 /// [`Item`]: peace_cfg::Item
+/// [`ItemGraphBuilder::add_sub_flow`]: crate::ItemGraphBuilder::add_sub_flow
+/// [`ItemGraphBuilder::add_retired`]: crate::ItemGraphBuilder::add_retired
 #[derive(Debug)]
-pub struct ItemGraph<E>(FnGraph<ItemBoxed<E>>);
+pub struct ItemGraph<E>(FnGraph<ItemBoxed<E>>, Vec<SubFlowSpecInfo>, Vec<ItemId>);
 
 // Manual implementation because derive requires `E` to be `Clone`,
 // which causes `graph.clone()` to call `FnGraph::clone`.
 impl<E> Clone for ItemGraph<E> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self(self.0.clone(), self.1.clone(), self.2.clone())
     }
 }
 
@@ -26,7 +36,7 @@ where
     E: 'static,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        self.0 == other.0 && self.1 == other.1 && self.2 == other.2
     }
 }
 
@@ -38,18 +48,51 @@ impl<E> ItemGraph<E> {
         self.0
     }
 
+    /// Returns the sub-flows embedded in this graph.
+    pub fn sub_flows(&self) -> &[SubFlowSpecInfo] {
+        &self.1
+    }
+
+    /// Returns this graph with the given sub-flows.
+    pub(crate) fn with_sub_flows(mut self, sub_flows: Vec<SubFlowSpecInfo>) -> Self {
+        self.1 = sub_flows;
+        self
+    }
+
+    /// Returns the IDs of retired items in this graph.
+    ///
+    /// Retired items are no longer part of the flow definition, and are only
+    /// in the graph so that their stored state can be cleaned up.
+    pub fn item_ids_retired(&self) -> &[ItemId] {
+        &self.2
+    }
+
+    /// Returns this graph with the given retired item IDs.
+    pub(crate) fn with_item_ids_retired(mut self, item_ids_retired: Vec<ItemId>) -> Self {
+        self.2 = item_ids_retired;
+        self
+    }
+
     /// Returns a user-friendly serializable states map.
     ///
     /// This will contain an entry for all items, in order of flow item
     /// insertion, whether or not a state exists in the provided `states` map.
+    ///
+    /// Items whose goal state is their clean state, such as retired items,
+    /// are only included if a state exists for them.
     pub fn states_serde<ValueT, TS>(&self, states: &States<TS>) -> StatesSerde<ValueT>
     where
         ValueT: Clone + Debug + PartialEq + Eq,
         E: 'static,
     {
-        StatesSerde::from_iter(self.0.iter_insertion().map(|item| {
+        StatesSerde::from_iter(self.0.iter_insertion().filter_map(|item| {
             let item_id = item.id();
-            (item_id.clone(), states.get_raw(item_id).cloned())
+            let state = states.get_raw(item_id).cloned();
+            if state.is_none() && item.state_goal_is_clean() {
+                None
+            } else {
+                Some((item_id.clone(), state))
+            }
         }))
     }
 }
@@ -70,7 +113,7 @@ impl<E> DerefMut for ItemGraph<E> {
 
 impl<E> From<FnGraph<ItemBoxed<E>>> for ItemGraph<E> {
     fn from(graph: FnGraph<ItemBoxed<E>>) -> Self {
-        Self(graph)
+        Self(graph, Vec::new(), Vec::new())
     }
 }
 
@@ -1,14 +1,23 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
 
-use peace_data::fn_graph::FnGraphBuilder;
+use peace_data::fn_graph::{daggy::Walker, Edge, FnGraphBuilder, FnId, GraphInfo, WouldCycle};
+use peace_flow_model::{FlowId, SubFlowSpecInfo};
+use peace_item_model::ItemId;
 use peace_rt_model::ItemBoxed;
 
-use crate::ItemGraph;
+use crate::{Flow, ItemGraph, SubFlowFnIds};
 
 /// Builder for an [`ItemGraph`], `FnGraphBuilder<ItemBoxed<E>>`
 /// newtype.
 #[derive(Debug)]
-pub struct ItemGraphBuilder<E>(FnGraphBuilder<ItemBoxed<E>>);
+pub struct ItemGraphBuilder<E>(
+    FnGraphBuilder<ItemBoxed<E>>,
+    Vec<SubFlowSpecInfo>,
+    Vec<ItemId>,
+);
 
 impl<E> ItemGraphBuilder<E> {
     /// Returns a new `ItemGraphBuilder`.
@@ -21,15 +30,203 @@ impl<E> ItemGraphBuilder<E> {
         self.0
     }
 
+    /// Adds a retired item, whose goal state is its clean state.
+    ///
+    /// Retired items are items that have been removed from the flow
+    /// definition, but may still have stored state. They are skipped unless
+    /// orphaned items are cleaned, e.g. with
+    /// `EnsureCmd::exec_with_orphans_clean`.
+    ///
+    /// See [`ItemsRetired`].
+    ///
+    /// [`ItemsRetired`]: crate::ItemsRetired
+    pub fn add_retired(&mut self, item_boxed: ItemBoxed<E>) -> FnId
+    where
+        E: 'static,
+    {
+        self.2.push(item_boxed.id().clone());
+        self.0.add_fn(item_boxed.with_state_goal_clean())
+    }
+
+    /// Adds the items in `flow` as a sub-flow, using the flow's ID as the
+    /// sub-flow ID.
+    ///
+    /// See [`ItemGraphBuilder::add_sub_flow`].
+    pub fn add_flow(&mut self, flow: &Flow<E>) -> SubFlowFnIds
+    where
+        E: 'static,
+    {
+        self.add_sub_flow(flow.flow_id().clone(), flow.graph())
+    }
+
+    /// Adds the items in `item_graph` as a sub-flow, along with the edges
+    /// between them.
+    ///
+    /// Each item's ID is namespaced with `sub_flow_id`, e.g. `s3_bucket`
+    /// becomes `app_upload__s3_bucket`, so params specs and stored states for
+    /// the sub-flow's items are grouped by the sub-flow ID. See
+    /// [`SubFlowSpecInfo::item_id_namespaced`].
+    ///
+    /// Use the returned [`SubFlowFnIds`] with
+    /// [`add_logic_edge_to_sub_flow`] and [`add_logic_edge_from_sub_flow`] to
+    /// wire the sub-flow's roots and leaves to other items.
+    ///
+    /// Sub-flows nested within `item_graph` are flattened into this sub-flow.
+    ///
+    /// [`add_logic_edge_to_sub_flow`]: Self::add_logic_edge_to_sub_flow
+    /// [`add_logic_edge_from_sub_flow`]: Self::add_logic_edge_from_sub_flow
+    pub fn add_sub_flow(&mut self, sub_flow_id: FlowId, item_graph: &ItemGraph<E>) -> SubFlowFnIds
+    where
+        E: 'static,
+    {
+        let graph_info = GraphInfo::from_graph(item_graph, |item_boxed| item_boxed.id().clone());
+
+        let (item_ids_namespaced, item_id_to_fn_id) = item_graph.iter_insertion().fold(
+            (
+                Vec::with_capacity(item_graph.node_count()),
+                HashMap::<ItemId, FnId>::with_capacity(item_graph.node_count()),
+            ),
+            |(mut item_ids_namespaced, mut item_id_to_fn_id), item| {
+                let item_id_namespaced =
+                    SubFlowSpecInfo::item_id_namespaced(&sub_flow_id, item.id());
+                let fn_id = self.0.add_fn(item.with_id(item_id_namespaced.clone()));
+
+                item_ids_namespaced.push(item_id_namespaced);
+                item_id_to_fn_id.insert(item.id().clone(), fn_id);
+
+                (item_ids_namespaced, item_id_to_fn_id)
+            },
+        );
+
+        let mut fn_ids = Vec::with_capacity(graph_info.node_count());
+        let mut fn_ids_root = Vec::new();
+        let mut fn_ids_leaf = Vec::new();
+        graph_info
+            .iter_insertion_with_indices()
+            .for_each(|(node_index, item_id)| {
+                let fn_id = item_id_to_fn_id[item_id];
+                fn_ids.push(fn_id);
+
+                let is_root =
+                    !graph_info
+                        .parents(node_index)
+                        .iter(&graph_info)
+                        .any(|(edge_index, _)| {
+                            matches!(
+                                graph_info.edge_weight(edge_index),
+                                Some(Edge::Logic | Edge::Contains)
+                            )
+                        });
+                if is_root {
+                    fn_ids_root.push(fn_id);
+                }
+
+                let mut is_leaf = true;
+                graph_info.children(node_index).iter(&graph_info).for_each(
+                    |(edge_index, child_node_index)| {
+                        let fn_id_child = item_id_to_fn_id[&graph_info[child_node_index]];
+                        let edge_added = match graph_info.edge_weight(edge_index).copied() {
+                            Some(Edge::Logic) => Some(self.0.add_logic_edge(fn_id, fn_id_child)),
+                            Some(Edge::Contains) => {
+                                Some(self.0.add_contains_edge(fn_id, fn_id_child))
+                            }
+                            _ => None,
+                        };
+                        if let Some(edge_added) = edge_added {
+                            is_leaf = false;
+                            edge_added.expect(
+                                "Expected edges copied from an acyclic sub-flow to not introduce \
+                                a cycle.",
+                            );
+                        }
+                    },
+                );
+                if is_leaf {
+                    fn_ids_leaf.push(fn_id);
+                }
+            });
+
+        self.1
+            .push(SubFlowSpecInfo::new(sub_flow_id, item_ids_namespaced));
+
+        SubFlowFnIds {
+            fn_ids,
+            fn_ids_root,
+            fn_ids_leaf,
+        }
+    }
+
+    /// Adds a logic edge from the given item to each root of the sub-flow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding an edge would introduce a cycle in the
+    /// graph.
+    pub fn add_logic_edge_to_sub_flow(
+        &mut self,
+        fn_id: FnId,
+        sub_flow_fn_ids: &SubFlowFnIds,
+    ) -> Result<(), WouldCycle<Edge>> {
+        sub_flow_fn_ids
+            .fn_ids_root
+            .iter()
+            .try_for_each(|fn_id_root| {
+                self.0.add_logic_edge(fn_id, *fn_id_root)?;
+                Ok(())
+            })
+    }
+
+    /// Adds a logic edge from each leaf of the sub-flow to the given item.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding an edge would introduce a cycle in the
+    /// graph.
+    pub fn add_logic_edge_from_sub_flow(
+        &mut self,
+        sub_flow_fn_ids: &SubFlowFnIds,
+        fn_id: FnId,
+    ) -> Result<(), WouldCycle<Edge>> {
+        sub_flow_fn_ids
+            .fn_ids_leaf
+            .iter()
+            .try_for_each(|fn_id_leaf| {
+                self.0.add_logic_edge(*fn_id_leaf, fn_id)?;
+                Ok(())
+            })
+    }
+
+    /// Adds a logic edge from each leaf of the `from` sub-flow to each root of
+    /// the `to` sub-flow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding an edge would introduce a cycle in the
+    /// graph.
+    pub fn add_logic_edge_between_sub_flows(
+        &mut self,
+        sub_flow_fn_ids_from: &SubFlowFnIds,
+        sub_flow_fn_ids_to: &SubFlowFnIds,
+    ) -> Result<(), WouldCycle<Edge>> {
+        sub_flow_fn_ids_from
+            .fn_ids_leaf
+            .iter()
+            .try_for_each(|fn_id_leaf| {
+                self.add_logic_edge_to_sub_flow(*fn_id_leaf, sub_flow_fn_ids_to)
+            })
+    }
+
     /// Builds and returns the [`ItemGraph`].
     pub fn build(self) -> ItemGraph<E> {
         ItemGraph::from(self.0.build())
+            .with_sub_flows(self.1)
+            .with_item_ids_retired(self.2)
     }
 }
 
 impl<E> Default for ItemGraphBuilder<E> {
     fn default() -> Self {
-        Self(FnGraphBuilder::default())
+        Self(FnGraphBuilder::default(), Vec::new(), Vec::new())
     }
 }
 
@@ -49,6 +246,6 @@ impl<E> DerefMut for ItemGraphBuilder<E> {
 
 impl<E> From<FnGraphBuilder<ItemBoxed<E>>> for ItemGraphBuilder<E> {
     fn from(graph: FnGraphBuilder<ItemBoxed<E>>) -> Self {
-        Self(graph)
+        Self(graph, Vec::new(), Vec::new())
     }
 }
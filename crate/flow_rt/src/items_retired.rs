@@ -0,0 +1,112 @@
+use peace_data::fn_graph::FnId;
+use peace_item_model::ItemId;
+use peace_rt_model::ItemBoxed;
+
+use crate::ItemGraphBuilder;
+
+/// Registry of items that have been removed from a flow, so that resources
+/// they created can still be cleaned up.
+///
+/// When an item is removed from a flow definition, its stored state remains
+/// in `states_current.yaml` as an orphaned entry, and the resource it manages
+/// is leaked. Registering the item's type here with its original item ID lets
+/// the framework clean it up:
+///
+/// 1. Pass the item IDs from the previous run's stored states to
+///    [`ItemsRetired::add_to`], which adds a retired item for each registered
+///    item that has a stored state.
+/// 2. Call `EnsureCmd::exec_with_orphans_clean`, which cleans up the retired
+///    items using their last stored state and stored params spec, and removes
+///    them from the stored states.
+///
+/// When orphans are not cleaned, retired items are skipped and their stored
+/// state is retained.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let items_retired = ItemsRetired::<AppError>::new()
+///     .with_item(FileDownloadItem::<WebApp>::new(item_id!("web_app_download")));
+///
+/// let item_ids_stored = StatesSerializer::<AppError>::deserialize_item_ids_opt(
+///     &storage,
+///     &states_current_file,
+/// )
+/// .await?
+/// .unwrap_or_default();
+///
+/// let mut graph_builder = ItemGraphBuilder::<AppError>::new();
+/// let fn_ids_retired = items_retired.add_to(&mut graph_builder, &item_ids_stored);
+/// ```
+#[derive(Debug)]
+pub struct ItemsRetired<E>(Vec<ItemBoxed<E>>);
+
+impl<E> ItemsRetired<E>
+where
+    E: 'static,
+{
+    /// Returns a new `ItemsRetired` registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a retired item.
+    ///
+    /// The item's ID must be the ID it had when it was part of the flow.
+    #[must_use]
+    pub fn with_item<I>(mut self, item: I) -> Self
+    where
+        I: Into<ItemBoxed<E>>,
+    {
+        self.0.push(item.into());
+        self
+    }
+
+    /// Returns the retired item with the given ID, if registered.
+    pub fn get(&self, item_id: &ItemId) -> Option<&ItemBoxed<E>> {
+        self.0.iter().find(|item| item.id() == item_id)
+    }
+
+    /// Returns the IDs of the registered retired items.
+    pub fn item_ids(&self) -> impl Iterator<Item = &ItemId> + '_ {
+        self.0.iter().map(|item| item.id())
+    }
+
+    /// Returns the number of registered retired items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether no retired items are registered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds each registered item whose ID is in `item_ids_stored` to the graph
+    /// as a retired item.
+    ///
+    /// Items are added in registration order.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph_builder`: The graph builder to add the items to.
+    /// * `item_ids_stored`: Item IDs from the previous run's stored states,
+    ///   e.g. from `StatesSerializer::deserialize_item_ids_opt`.
+    pub fn add_to(
+        &self,
+        graph_builder: &mut ItemGraphBuilder<E>,
+        item_ids_stored: &[ItemId],
+    ) -> Vec<FnId> {
+        self.0
+            .iter()
+            .filter(|item| item_ids_stored.contains(item.id()))
+            .map(|item| graph_builder.add_retired(item.clone()))
+            .collect::<Vec<FnId>>()
+    }
+}
+
+impl<E> Default for ItemsRetired<E> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
@@ -1,7 +1,15 @@
 //! Flow runtime types for the peace automation framework.
 
-pub use crate::{flow::Flow, item_graph::ItemGraph, item_graph_builder::ItemGraphBuilder};
+pub use crate::{
+    flow::Flow, item_fan_out::ItemFanOut, item_fan_out_fn_ids::ItemFanOutFnIds,
+    item_graph::ItemGraph, item_graph_builder::ItemGraphBuilder, items_retired::ItemsRetired,
+    sub_flow_fn_ids::SubFlowFnIds,
+};
 
 mod flow;
+mod item_fan_out;
+mod item_fan_out_fn_ids;
 mod item_graph;
 mod item_graph_builder;
+mod items_retired;
+mod sub_flow_fn_ids;
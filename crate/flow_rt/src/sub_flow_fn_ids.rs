@@ -0,0 +1,18 @@
+use peace_data::fn_graph::FnId;
+
+/// IDs of the items added to an [`ItemGraphBuilder`] from a sub-flow.
+///
+/// This is returned by [`ItemGraphBuilder::add_sub_flow`], and is used to add
+/// edges to and from the sub-flow.
+///
+/// [`ItemGraphBuilder`]: crate::ItemGraphBuilder
+/// [`ItemGraphBuilder::add_sub_flow`]: crate::ItemGraphBuilder::add_sub_flow
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubFlowFnIds {
+    /// IDs of all items in the sub-flow, in insertion order.
+    pub fn_ids: Vec<FnId>,
+    /// IDs of the items in the sub-flow that have no predecessors.
+    pub fn_ids_root: Vec<FnId>,
+    /// IDs of the items in the sub-flow that have no successors.
+    pub fn_ids_leaf: Vec<FnId>,
+}
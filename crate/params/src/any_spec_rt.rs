@@ -1,4 +1,8 @@
-use crate::AnySpecDataType;
+use std::any::TypeId;
+
+use crate::{
+    AnySpecDataType, FieldTemplate, FieldValueSetError, FlowStateRef, ItemId, MappingFnId,
+};
 
 /// Runtime logic of how to look up values for each field in this struct.
 ///
@@ -31,6 +35,71 @@ pub trait AnySpecRt {
     /// safe. Adding a `where: Self: Sized` bound prevents the method from being
     /// called from `cmd_ctx_builder`.
     fn merge(&mut self, other: &dyn AnySpecDataType);
+
+    /// Returns the IDs of the mapping functions this spec uses to resolve its
+    /// value.
+    ///
+    /// This includes `MappingFn` variants, as well as the placeholders within
+    /// `ValueSpec::Template`s. For `FieldWise` specs, the IDs are collected
+    /// from each field.
+    fn mapping_fn_ids(&self) -> Vec<MappingFnId> {
+        Vec::new()
+    }
+
+    /// Returns the `ValueSpec::Template`s this spec uses to resolve its value.
+    ///
+    /// For `FieldWise` specs, the templates are collected from each field.
+    fn field_templates(&self) -> Vec<FieldTemplate> {
+        Vec::new()
+    }
+
+    /// Returns the `TypeId`s of the values this spec reads from `Resources`
+    /// through `InMemory` variants.
+    ///
+    /// For `FieldWise` specs, the `TypeId`s are collected from each field.
+    fn in_memory_type_ids(&self) -> Vec<TypeId> {
+        Vec::new()
+    }
+
+    /// Returns the references to other flows' stored states that this spec
+    /// reads through `FlowState` variants.
+    ///
+    /// For `FieldWise` specs, the references are collected from each field.
+    fn flow_state_refs(&self) -> Vec<FlowStateRef> {
+        Vec::new()
+    }
+
+    /// Returns the IDs of the items whose outputs this spec reads through
+    /// `ItemOutput` variants.
+    ///
+    /// For `FieldWise` specs, the IDs are collected from each field.
+    fn item_output_refs(&self) -> Vec<ItemId> {
+        Vec::new()
+    }
+
+    /// Returns the names of the fields whose values can be set through
+    /// [`field_value_set`].
+    ///
+    /// Tuple fields are named by their index, e.g. `"0"`.
+    ///
+    /// [`field_value_set`]: Self::field_value_set
+    fn field_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Sets the value of the field with the given name, deserialized from
+    /// `value`.
+    ///
+    /// This is used to override individual fields of an item's params for a
+    /// command execution, e.g. `--set item_id.field=value`. Plain strings are
+    /// used as is, and other values are parsed as YAML.
+    fn field_value_set(&mut self, field_name: &str, value: &str) -> Result<(), FieldValueSetError> {
+        let _ = value;
+        Err(FieldValueSetError::FieldNotFound {
+            field_name: field_name.to_string(),
+            field_names: self.field_names(),
+        })
+    }
 }
 
 impl<T> AnySpecRt for Box<T>
@@ -47,4 +116,32 @@ where
     {
         self.as_mut().merge(other)
     }
+
+    fn mapping_fn_ids(&self) -> Vec<MappingFnId> {
+        self.as_ref().mapping_fn_ids()
+    }
+
+    fn field_templates(&self) -> Vec<FieldTemplate> {
+        self.as_ref().field_templates()
+    }
+
+    fn in_memory_type_ids(&self) -> Vec<TypeId> {
+        self.as_ref().in_memory_type_ids()
+    }
+
+    fn flow_state_refs(&self) -> Vec<FlowStateRef> {
+        self.as_ref().flow_state_refs()
+    }
+
+    fn item_output_refs(&self) -> Vec<ItemId> {
+        self.as_ref().item_output_refs()
+    }
+
+    fn field_names(&self) -> Vec<String> {
+        self.as_ref().field_names()
+    }
+
+    fn field_value_set(&mut self, field_name: &str, value: &str) -> Result<(), FieldValueSetError> {
+        self.as_mut().field_value_set(field_name, value)
+    }
 }
@@ -0,0 +1,17 @@
+use crate::{MappingFnId, TemplateValueKind};
+
+/// A `ValueSpec::Template` used to resolve a field's value.
+///
+/// This is used to check the mapping functions used by the template's
+/// placeholders against the field's type when the `CmdCtx` is built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldTemplate {
+    /// Name of the field's type.
+    pub field_type_name: String,
+    /// Kind of value the field's type is serialized as.
+    pub field_kind: TemplateValueKind,
+    /// The template string.
+    pub template: String,
+    /// IDs of the mapping functions used by the template's placeholders.
+    pub mapping_fn_ids: Vec<MappingFnId>,
+}
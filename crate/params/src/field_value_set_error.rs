@@ -0,0 +1,92 @@
+/// Failed to set a field's value in a params spec.
+#[derive(Debug, thiserror::Error)]
+#[cfg_attr(feature = "error_reporting", derive(miette::Diagnostic))]
+pub enum FieldValueSetError {
+    /// The params spec does not have a field with the given name.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::field_value_set_error::field_not_found),
+            help("{}", field_not_found_help(field_name, field_names))
+        )
+    )]
+    #[error("Params spec does not have a field named `{field_name}`.")]
+    FieldNotFound {
+        /// Name of the field that was attempted to be set.
+        field_name: String,
+        /// Names of the fields in the params spec.
+        field_names: Vec<String>,
+    },
+
+    /// Failed to deserialize the value as the field's type.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::field_value_set_error::value_deserialize),
+            help(
+                "Make sure the value is a valid `{field_type_name}`, in YAML format if it is not a \
+                plain string."
+            )
+        )
+    )]
+    #[error("Failed to parse `{value}` as `{field_type_name}` for field `{field_name}`.")]
+    ValueDeserialize {
+        /// Name of the field that was attempted to be set.
+        field_name: String,
+        /// Type name of the field.
+        field_type_name: String,
+        /// The value that failed to be deserialized.
+        value: String,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// The params spec's fields cannot be set individually.
+    ///
+    /// This is returned for `MappingFn` and `InMemory` params specs, and
+    /// params whose spec is fieldless.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::field_value_set_error::fields_not_settable),
+            help(
+                "Provide a field-wise params spec or a params value for this item, so that \
+                individual fields can be set."
+            )
+        )
+    )]
+    #[error(
+        "Fields cannot be set on a `{params_type_name}` params spec that is `{spec_variant}`."
+    )]
+    FieldsNotSettable {
+        /// Type name of the `Params`.
+        params_type_name: String,
+        /// Name of the params spec variant, e.g. `MappingFn`.
+        spec_variant: &'static str,
+    },
+}
+
+/// Returns the help text for a field that does not exist, suggesting the
+/// closest field name.
+#[cfg(feature = "error_reporting")]
+fn field_not_found_help(field_name: &str, field_names: &[String]) -> String {
+    let field_names_list = field_names
+        .iter()
+        .map(|field_name| format!("* {field_name}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    match crate::name_suggest(field_name, field_names.iter().map(String::as_str)) {
+        Some(field_name_suggested) => format!(
+            "Did you mean `{field_name_suggested}`? Available fields are:\n\
+            \n\
+            {field_names_list}"
+        ),
+        None => format!(
+            "Available fields are:\n\
+            \n\
+            {field_names_list}"
+        ),
+    }
+}
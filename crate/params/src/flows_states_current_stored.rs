@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use peace_flow_model::{FlowId, FlowStateRef};
+use peace_item_model::ItemId;
+
+/// Stored current states of other flows, read by `ValueSpec::FlowState`.
+///
+/// This is inserted into `Resources` when building a command context, with an
+/// entry for each flow referenced by the flow's params specs. A flow that has
+/// not been applied has no entry.
+///
+/// States are stored untyped, as the item types of another flow are not known
+/// to this flow.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlowsStatesCurrentStored(HashMap<FlowId, HashMap<ItemId, serde_yaml::Value>>);
+
+impl FlowsStatesCurrentStored {
+    /// Returns a new `FlowsStatesCurrentStored`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the stored state referenced by `flow_state_ref`.
+    ///
+    /// This is `None` if the flow has not been applied, or the item has no
+    /// stored state.
+    pub fn state_get(&self, flow_state_ref: &FlowStateRef) -> Option<&serde_yaml::Value> {
+        self.0
+            .get(&flow_state_ref.flow_id)
+            .and_then(|states| states.get(&flow_state_ref.item_id))
+    }
+}
+
+impl Deref for FlowsStatesCurrentStored {
+    type Target = HashMap<FlowId, HashMap<ItemId, serde_yaml::Value>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for FlowsStatesCurrentStored {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,125 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Values that an item publishes after it is applied, derived from its state.
+///
+/// `IndexMap<String, serde_yaml::Value>` newtype, from output name to value.
+///
+/// These are returned by `Item::outputs`, and can be read by other items'
+/// params specs through `ValueSpec::ItemOutput`.
+///
+/// # Examples
+///
+/// ```rust
+/// use peace_params::ItemOutputs;
+///
+/// let item_outputs = ItemOutputs::new()
+///     .with_output("bucket_arn", "arn:aws:s3:::my-bucket")
+///     .with_output("object_count", 3u32);
+///
+/// assert_eq!(2, item_outputs.len());
+/// ```
+///
+/// Outputs can be read as a typed struct whose fields are the output names:
+///
+/// ```rust
+/// use peace_params::ItemOutputs;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct BucketOutputs {
+///     bucket_arn: String,
+///     object_count: u32,
+/// }
+///
+/// let item_outputs = ItemOutputs::new()
+///     .with_output("bucket_arn", "arn:aws:s3:::my-bucket")
+///     .with_output("object_count", 3u32);
+///
+/// let bucket_outputs = item_outputs.to_typed::<BucketOutputs>()?;
+/// assert_eq!(
+///     BucketOutputs {
+///         bucket_arn: String::from("arn:aws:s3:::my-bucket"),
+///         object_count: 3,
+///     },
+///     bucket_outputs
+/// );
+/// # Ok::<(), serde_yaml::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ItemOutputs(IndexMap<String, serde_yaml::Value>);
+
+impl ItemOutputs {
+    /// Returns a new `ItemOutputs` map.
+    pub fn new() -> Self {
+        Self(IndexMap::new())
+    }
+
+    /// Returns this map with the given output added.
+    #[must_use]
+    pub fn with_output<V>(mut self, output_name: impl Into<String>, value: V) -> Self
+    where
+        V: Into<serde_yaml::Value>,
+    {
+        self.0.insert(output_name.into(), value.into());
+        self
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<String, serde_yaml::Value> {
+        self.0
+    }
+
+    /// Returns the outputs deserialized as `T`, whose fields are the output
+    /// names.
+    ///
+    /// Items that publish outputs may provide a type for this, e.g.
+    /// `FileDownloadOutputs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outputs cannot be deserialized as `T`, e.g.
+    /// when a required output is not published.
+    pub fn to_typed<T>(&self) -> Result<T, serde_yaml::Error>
+    where
+        T: DeserializeOwned,
+    {
+        serde_yaml::to_value(&self.0).and_then(serde_yaml::from_value)
+    }
+
+    /// Returns the value of an output deserialized as `T`, if published.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be deserialized as `T`.
+    pub fn output_get_typed<T>(&self, output_name: &str) -> Option<Result<T, serde_yaml::Error>>
+    where
+        T: DeserializeOwned,
+    {
+        self.0
+            .get(output_name)
+            .map(|value| serde_yaml::from_value(value.clone()))
+    }
+}
+
+impl Deref for ItemOutputs {
+    type Target = IndexMap<String, serde_yaml::Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ItemOutputs {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(String, serde_yaml::Value)> for ItemOutputs {
+    fn from_iter<I: IntoIterator<Item = (String, serde_yaml::Value)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
@@ -0,0 +1,158 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use peace_fmt::{Presentable, Presenter};
+use peace_item_model::ItemId;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::ItemOutputs;
+
+/// Outputs published by each item, read by `ValueSpec::ItemOutput`.
+///
+/// `IndexMap<ItemId, ItemOutputs>` newtype.
+///
+/// This is inserted into `Resources` when building a command context, with
+/// the outputs stored when each item was last applied. When an item is
+/// applied, its entry is replaced with the outputs from its new state, so
+/// successors read the latest values.
+///
+/// Items that publish no outputs have no entry.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ItemsOutputs(IndexMap<ItemId, ItemOutputs>);
+
+impl ItemsOutputs {
+    /// Returns a new `ItemsOutputs` map.
+    pub fn new() -> Self {
+        Self(IndexMap::new())
+    }
+
+    /// Returns a new `ItemsOutputs` map with the given preallocated capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<ItemId, ItemOutputs> {
+        self.0
+    }
+
+    /// Returns the value of an item's output, if published.
+    pub fn output_get(&self, item_id: &ItemId, output_name: &str) -> Option<&serde_yaml::Value> {
+        self.0
+            .get(item_id)
+            .and_then(|item_outputs| item_outputs.get(output_name))
+    }
+
+    /// Returns an item's outputs deserialized as `T`, if the item published
+    /// outputs.
+    ///
+    /// See [`ItemOutputs::to_typed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outputs cannot be deserialized as `T`.
+    pub fn item_outputs_typed<T>(&self, item_id: &ItemId) -> Option<Result<T, serde_yaml::Error>>
+    where
+        T: DeserializeOwned,
+    {
+        self.0.get(item_id).map(ItemOutputs::to_typed)
+    }
+
+    /// Sets the outputs for an item, removing its entry if `item_outputs` is
+    /// empty.
+    pub fn item_outputs_set(&mut self, item_id: ItemId, item_outputs: ItemOutputs) {
+        if item_outputs.is_empty() {
+            self.0.shift_remove(&item_id);
+        } else {
+            self.0.insert(item_id, item_outputs);
+        }
+    }
+
+    /// Returns the outputs as lines of `NAME="value"`, for use as an env file
+    /// or to `source` in a shell script.
+    ///
+    /// Each variable is named `{ITEM_ID}_{OUTPUT_NAME}` in upper case, with
+    /// characters that are not alphanumeric replaced with `_`. String values
+    /// are used as is, and other values are written as JSON.
+    pub fn to_env_string(&self) -> String {
+        self.0
+            .iter()
+            .flat_map(|(item_id, item_outputs)| {
+                item_outputs.iter().map(move |(output_name, value)| {
+                    let env_var_name = env_var_name(item_id, output_name);
+                    let env_var_value = match value {
+                        serde_yaml::Value::String(value) => value.clone(),
+                        value => serde_json::to_string(value).unwrap_or_default(),
+                    };
+                    let env_var_value = env_var_value
+                        .replace('\\', "\\\\")
+                        .replace('"', "\\\"")
+                        .replace('$', "\\$")
+                        .replace('`', "\\`")
+                        .replace('\n', "\\n");
+
+                    format!("{env_var_name}=\"{env_var_value}\"\n")
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the environment variable name for an item's output.
+fn env_var_name(item_id: &ItemId, output_name: &str) -> String {
+    format!("{}_{output_name}", item_id.as_str())
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+impl Deref for ItemsOutputs {
+    type Target = IndexMap<ItemId, ItemOutputs>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ItemsOutputs {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(ItemId, ItemOutputs)> for ItemsOutputs {
+    fn from_iter<I: IntoIterator<Item = (ItemId, ItemOutputs)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
+
+#[peace_fmt::async_trait(?Send)]
+impl Presentable for ItemsOutputs {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        presenter
+            .list_numbered_with(self.iter(), |(item_id, item_outputs)| {
+                let item_outputs = item_outputs
+                    .iter()
+                    .map(|(output_name, value)| match value {
+                        serde_yaml::Value::String(value) => format!("{output_name}: {value}"),
+                        value => format!(
+                            "{output_name}: {}",
+                            serde_json::to_string(value).unwrap_or_default()
+                        ),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                (item_id, format!(": {item_outputs}"))
+            })
+            .await
+    }
+}
@@ -62,58 +62,86 @@
 //! ```
 
 // Re-exports
+pub use peace_flow_model::FlowStateRef;
+pub use peace_item_model::ItemId;
 pub use peace_params_derive::{value_impl, Params, ParamsFieldless};
+pub use regex;
 pub use tynm;
 
+#[cfg(feature = "params_schema")]
+pub use schemars;
+
 pub use crate::{
     any_spec_data_type::AnySpecDataType,
     any_spec_rt::AnySpecRt,
     any_spec_rt_boxed::AnySpecRtBoxed,
     field_name_and_type::FieldNameAndType,
+    field_template::FieldTemplate,
+    field_value_set_error::FieldValueSetError,
     field_wise_spec_rt::FieldWiseSpecRt,
+    flows_states_current_stored::FlowsStatesCurrentStored,
     func::{FromFunc, Func},
+    item_outputs::ItemOutputs,
+    items_outputs::ItemsOutputs,
     mapping_fn::MappingFn,
     mapping_fn_id::MappingFnId,
     mapping_fn_impl::MappingFnImpl,
     mapping_fn_reg::MappingFnReg,
     mapping_fns::MappingFns,
+    name_suggest::name_suggest,
     params::Params,
     params_fieldless::ParamsFieldless,
     params_key::ParamsKey,
     params_merge_ext::ParamsMergeExt,
+    params_override::{ParamsOverride, ParamsOverrideParseError},
     params_resolve_error::ParamsResolveError,
     params_spec::ParamsSpec,
     params_spec_fieldless::ParamsSpecFieldless,
     params_specs::ParamsSpecs,
+    params_validators::ParamsValidators,
     params_value::ParamsValue,
+    template_value_kind::TemplateValueKind,
+    value_is_empty::ValueIsEmpty,
     value_resolution_ctx::ValueResolutionCtx,
     value_resolution_mode::ValueResolutionMode,
     value_spec::ValueSpec,
     value_spec_rt::ValueSpecRt,
+    value_template::{template_segments, TemplateSegment},
 };
 
 mod any_spec_data_type;
 mod any_spec_rt;
 mod any_spec_rt_boxed;
 mod field_name_and_type;
+mod field_template;
+mod field_value_set_error;
 mod field_wise_spec_rt;
+mod flows_states_current_stored;
 mod func;
+mod item_outputs;
+mod items_outputs;
 mod mapping_fn;
 mod mapping_fn_id;
 mod mapping_fn_impl;
 mod mapping_fn_reg;
 mod mapping_fns;
+mod name_suggest;
 mod params;
 mod params_fieldless;
 mod params_key;
 mod params_merge_ext;
+mod params_override;
 mod params_resolve_error;
 mod params_spec;
 mod params_spec_fieldless;
 mod params_specs;
+mod params_validators;
 mod params_value;
 mod std_impl;
+mod template_value_kind;
+mod value_is_empty;
 mod value_resolution_ctx;
 mod value_resolution_mode;
 mod value_spec;
 mod value_spec_rt;
+mod value_template;
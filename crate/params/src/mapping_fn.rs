@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{any::TypeId, fmt::Debug};
 
 use peace_resource_rt::{
     resources::ts::SetUp,
@@ -7,7 +7,7 @@ use peace_resource_rt::{
 };
 use serde::{Serialize, Serializer};
 
-use crate::{MappingFnImpl, ParamsResolveError, ValueResolutionCtx};
+use crate::{MappingFnImpl, ParamsResolveError, TemplateValueKind, ValueResolutionCtx};
 
 /// Type erased mapping function.
 ///
@@ -68,6 +68,29 @@ pub trait MappingFn: Debug + DataType {
     /// Deserialized mapping functions will not hold any function logic, and
     /// Peace uses this function to determine if this is an empty `MappingFn`.
     fn is_valued(&self) -> bool;
+
+    /// Returns the `TypeId`s of the data this mapping function reads from
+    /// `resources`.
+    ///
+    /// Peace uses this to determine which items' states a params spec depends
+    /// on.
+    fn arg_type_ids(&self) -> Vec<TypeId>;
+
+    /// Returns the names of the types of the data this mapping function reads
+    /// from `resources`, in the same order as [`arg_type_ids`].
+    ///
+    /// [`arg_type_ids`]: Self::arg_type_ids
+    fn arg_type_names(&self) -> Vec<String>;
+
+    /// Returns the name of the type this mapping function maps to.
+    fn output_type_name(&self) -> String;
+
+    /// Returns the kind of value this mapping function's output is serialized
+    /// as.
+    ///
+    /// Peace uses this to check the placeholders in a `ValueSpec::Template`
+    /// against the field's type.
+    fn output_kind(&self) -> TemplateValueKind;
 }
 
 impl Clone for Box<dyn MappingFn> {
@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// [`MappingFns`]: crate::MappingFns
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "params_schema", derive(schemars::JsonSchema))]
 pub struct MappingFnId(String);
 
 impl MappingFnId {
@@ -1,4 +1,5 @@
 use std::{
+    any::TypeId,
     fmt::{self, Debug},
     marker::PhantomData,
 };
@@ -8,7 +9,8 @@ use peace_resource_rt::{resources::ts::SetUp, type_reg::untagged::BoxDt, BorrowF
 use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
 
 use crate::{
-    FromFunc, Func, MappingFn, ParamsResolveError, ValueResolutionCtx, ValueResolutionMode,
+    FromFunc, Func, MappingFn, ParamsResolveError, TemplateValueKind, ValueResolutionCtx,
+    ValueResolutionMode,
 };
 
 #[cfg(feature = "item_state_example")]
@@ -59,6 +61,22 @@ impl MappingFn for MappingFnImpl<(), fn(), ()> {
     fn is_valued(&self) -> bool {
         true
     }
+
+    fn arg_type_ids(&self) -> Vec<TypeId> {
+        Vec::new()
+    }
+
+    fn arg_type_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn output_type_name(&self) -> String {
+        tynm::type_name::<()>()
+    }
+
+    fn output_kind(&self) -> TemplateValueKind {
+        TemplateValueKind::of::<()>()
+    }
 }
 
 impl<T, F, Args> Debug for MappingFnImpl<T, F, Args>
@@ -396,6 +414,22 @@ macro_rules! impl_mapping_fn_impl {
             fn is_valued(&self) -> bool {
                 self.fn_map.is_some()
             }
+
+            fn arg_type_ids(&self) -> Vec<TypeId> {
+                vec![$(TypeId::of::<$Arg>(),)+]
+            }
+
+            fn arg_type_names(&self) -> Vec<String> {
+                vec![$(tynm::type_name::<$Arg>(),)+]
+            }
+
+            fn output_type_name(&self) -> String {
+                tynm::type_name::<T>()
+            }
+
+            fn output_kind(&self) -> TemplateValueKind {
+                TemplateValueKind::of::<T>()
+            }
         }
     };
 }
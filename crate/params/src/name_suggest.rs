@@ -0,0 +1,15 @@
+/// Returns the candidate that is most similar to `name`, if any is similar
+/// enough to be a likely typo.
+///
+/// This is used to suggest item IDs and field names when overriding params.
+pub fn name_suggest<'s>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'s str>,
+) -> Option<&'s str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|(similarity, _candidate)| *similarity > 0.7)
+        .max_by(|(similarity_a, _), (similarity_b, _)| similarity_a.total_cmp(similarity_b))
+        .map(|(_similarity, candidate)| candidate)
+}
@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::FieldWiseSpecRt;
+use crate::{FieldWiseSpecRt, ParamsResolveError, ValueResolutionCtx};
 
 /// Input parameters to an item.
 ///
@@ -33,4 +33,63 @@ pub trait Params: Clone + Debug + Serialize + DeserializeOwned + Send + Sync + '
 
     /// Returns a builder to construct the `FieldWise` spec.
     fn field_wise_spec() -> Self::FieldWiseBuilder;
+
+    /// Validates the resolved params.
+    ///
+    /// This is run after the params have been resolved, before they are passed
+    /// to the item. `#[derive(Params)]` implements this using the
+    /// `#[validate(..)]` attributes on each field.
+    ///
+    /// # Parameters
+    ///
+    /// * `value_resolution_ctx`: Context of the resolution, used to name the
+    ///   item and field in the returned error.
+    fn validate(
+        &self,
+        _value_resolution_ctx: &mut ValueResolutionCtx,
+    ) -> Result<(), ParamsResolveError> {
+        Ok(())
+    }
+
+    /// Validates the fields of the partially resolved params that have values.
+    ///
+    /// This is run for every item before any item is applied, so that invalid
+    /// values are reported before any changes are made. Fields that cannot be
+    /// resolved yet are validated by [`Params::validate`] when the params are
+    /// resolved during the apply.
+    ///
+    /// # Parameters
+    ///
+    /// * `params_partial`: The partially resolved params.
+    /// * `value_resolution_ctx`: Context of the resolution, used to name the
+    ///   item and field in the returned error.
+    fn validate_partial(
+        _params_partial: &Self::Partial,
+        _value_resolution_ctx: &mut ValueResolutionCtx,
+    ) -> Result<(), ParamsResolveError> {
+        Ok(())
+    }
+
+    /// Returns the names of fields whose values are secret.
+    ///
+    /// Values of these fields are masked when params are displayed, such as in
+    /// params diffs. `#[derive(Params)]` implements this using the
+    /// `#[params(secret)]` attribute on each field.
+    ///
+    /// For tuple structs, the field name is the field index, e.g. `"0"`.
+    fn field_names_secret() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Returns the JSON Schema for this type's [`ParamsSpec`].
+    ///
+    /// `#[derive(Params)]` implements this when the type is annotated with
+    /// `#[params(schema)]`. Otherwise a schema that accepts any value is
+    /// returned.
+    ///
+    /// [`ParamsSpec`]: crate::ParamsSpec
+    #[cfg(feature = "params_schema")]
+    fn params_spec_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::Schema::from(true)
+    }
 }
@@ -0,0 +1,134 @@
+use std::{fmt, str::FromStr};
+
+use peace_item_model::{ItemId, ItemIdInvalidFmt};
+use serde::{Deserialize, Serialize};
+
+/// Value to set for one field of an item's params, e.g.
+/// `app_download.src=https://example.com/app.tar`.
+///
+/// The value is deserialized into the field's type when the `CmdCtx` is
+/// built. Plain strings are used as is, and other values are parsed as YAML,
+/// e.g. `true`, `123`, or `[1, 2, 3]`.
+///
+/// This implements `FromStr`, so it can be used as a command line argument
+/// type, e.g. `--set item_id.field=value`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ParamsOverride {
+    /// ID of the item whose params to override.
+    pub item_id: ItemId,
+    /// Name of the field to override.
+    ///
+    /// For tuple structs, this is the field index, e.g. `0`.
+    pub field_name: String,
+    /// String representation of the value.
+    pub value: String,
+}
+
+impl ParamsOverride {
+    /// Returns a new `ParamsOverride`.
+    pub fn new(item_id: ItemId, field_name: String, value: String) -> Self {
+        Self {
+            item_id,
+            field_name,
+            value,
+        }
+    }
+}
+
+impl fmt::Display for ParamsOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ParamsOverride {
+            item_id,
+            field_name,
+            value,
+        } = self;
+        write!(f, "{item_id}.{field_name}={value}")
+    }
+}
+
+impl FromStr for ParamsOverride {
+    type Err = ParamsOverrideParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, value) =
+            s.split_once('=')
+                .ok_or_else(|| ParamsOverrideParseError::ValueMissing {
+                    params_override: s.to_string(),
+                })?;
+        let (item_id, field_name) = path.trim().split_once('.').ok_or_else(|| {
+            ParamsOverrideParseError::FieldNameMissing {
+                params_override: s.to_string(),
+            }
+        })?;
+        let field_name = field_name.trim();
+        if field_name.is_empty() {
+            return Err(ParamsOverrideParseError::FieldNameMissing {
+                params_override: s.to_string(),
+            });
+        }
+        let item_id = ItemId::from_str(item_id.trim()).map_err(|error| {
+            ParamsOverrideParseError::ItemIdInvalid {
+                params_override: s.to_string(),
+                error,
+            }
+        })?;
+
+        Ok(Self {
+            item_id,
+            field_name: field_name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Failed to parse a `ParamsOverride` from a string.
+#[derive(Debug, thiserror::Error)]
+#[cfg_attr(feature = "error_reporting", derive(miette::Diagnostic))]
+pub enum ParamsOverrideParseError {
+    /// The params override does not contain `=`.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_override_parse_error::value_missing),
+            help("Params overrides must be in the form `item_id.field=value`.")
+        )
+    )]
+    #[error("Params override `{params_override}` does not contain a value.")]
+    ValueMissing {
+        /// The string that failed to be parsed.
+        params_override: String,
+    },
+
+    /// The params override does not contain a field name.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_override_parse_error::field_name_missing),
+            help("Params overrides must be in the form `item_id.field=value`.")
+        )
+    )]
+    #[error("Params override `{params_override}` does not contain a field name.")]
+    FieldNameMissing {
+        /// The string that failed to be parsed.
+        params_override: String,
+    },
+
+    /// The item ID in the params override is not a valid `ItemId`.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_override_parse_error::item_id_invalid),
+            help(
+                "Item IDs must begin with a letter or underscore, and contain only letters, \
+                numbers, and underscores."
+            )
+        )
+    )]
+    #[error("Params override `{params_override}` contains an invalid item ID.")]
+    ItemIdInvalid {
+        /// The string that failed to be parsed.
+        params_override: String,
+        /// Underlying error.
+        error: ItemIdInvalidFmt<'static>,
+    },
+}
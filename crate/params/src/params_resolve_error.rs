@@ -1,3 +1,8 @@
+use std::path::PathBuf;
+
+use peace_flow_model::FlowId;
+use peace_item_model::ItemId;
+
 use crate::{FieldNameAndType, MappingFnId, ValueResolutionCtx};
 
 /// Failed to resolve values for a `Params` object from `resources`.
@@ -163,6 +168,440 @@ pub enum ParamsResolveError {
         /// `MappingFns` variant.
         mapping_fn_id: MappingFnId,
     },
+
+    /// Environment variable for a field value is not set.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::env_var_not_present),
+            help(
+                "Set the `{env_var_name}` environment variable, or provide a `default` in the value spec."
+            )
+        )
+    )]
+    #[error(
+        "Environment variable `{env_var_name}` is not set to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    EnvVarNotPresent {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: ValueResolutionCtx,
+        /// Name of the environment variable.
+        env_var_name: String,
+    },
+
+    /// Environment variable for a field value is not valid unicode.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::env_var_not_unicode))
+    )]
+    #[error(
+        "Environment variable `{env_var_name}` is not valid unicode to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    EnvVarNotUnicode {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: ValueResolutionCtx,
+        /// Name of the environment variable.
+        env_var_name: String,
+    },
+
+    /// Failed to parse an environment variable's value for a field.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::env_var_parse))
+    )]
+    #[error(
+        "Failed to parse environment variable `{env_var_name}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    EnvVarParse {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: ValueResolutionCtx,
+        /// Name of the environment variable.
+        env_var_name: String,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Failed to read a file for a field value.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::file_read),
+            help("Make sure `{}` exists and is readable.", path.display())
+        )
+    )]
+    #[error(
+        "Failed to read `{}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```",
+        path.display()
+    )]
+    FileRead {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: ValueResolutionCtx,
+        /// Path to the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to parse a file for a field value.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::file_parse))
+    )]
+    #[error(
+        "Failed to parse `{}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```",
+        path.display()
+    )]
+    FileParse {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: ValueResolutionCtx,
+        /// Path to the file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Key path for a field value does not exist in a file.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::file_key_not_found),
+            help(
+                "Make sure `{key_path}` exists in `{}`, or update the key path in the value spec.",
+                path.display()
+            )
+        )
+    )]
+    #[error(
+        "Key path `{key_path}` not found in `{}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```",
+        path.display()
+    )]
+    FileKeyNotFound {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// Path to the file.
+        path: PathBuf,
+        /// `.` separated path to the value within the file.
+        key_path: String,
+    },
+
+    /// Failed to deserialize the value at a key path in a file.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::file_key_deserialize))
+    )]
+    #[error(
+        "Failed to deserialize `{key_path}` in `{}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```",
+        path.display()
+    )]
+    FileKeyDeserialize {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// Path to the file.
+        path: PathBuf,
+        /// `.` separated path to the value within the file.
+        key_path: String,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// A flow whose stored state is referenced has not been applied.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::flow_states_not_found),
+            help("Apply the `{flow_id}` flow before running this flow.")
+        )
+    )]
+    #[error(
+        "Flow `{flow_id}` has not been applied, \
+        so `{item_id}`'s state cannot be read to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    FlowStatesNotFound {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// ID of the flow whose stored state is referenced.
+        flow_id: FlowId,
+        /// ID of the item in that flow whose stored state is referenced.
+        item_id: ItemId,
+    },
+
+    /// An item whose stored state is referenced has no stored state in its
+    /// flow.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::flow_state_not_found),
+            help(
+                "Make sure `{item_id}` is an item in `{flow_id}`, and `{flow_id}` has been applied."
+            )
+        )
+    )]
+    #[error(
+        "`{item_id}` has no stored state in flow `{flow_id}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    FlowStateNotFound {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// ID of the flow whose stored state is referenced.
+        flow_id: FlowId,
+        /// ID of the item in that flow whose stored state is referenced.
+        item_id: ItemId,
+    },
+
+    /// Key path for a field value does not exist in another flow's stored
+    /// state.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::flow_state_key_not_found),
+            help(
+                "Make sure `{key_path}` exists in `{item_id}`'s state, \
+                or update the key path in the value spec."
+            )
+        )
+    )]
+    #[error(
+        "Key path `{key_path}` not found in `{item_id}`'s stored state \
+        in flow `{flow_id}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    FlowStateKeyNotFound {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// ID of the flow whose stored state is referenced.
+        flow_id: FlowId,
+        /// ID of the item in that flow whose stored state is referenced.
+        item_id: ItemId,
+        /// `.` separated path to the value within the state.
+        key_path: String,
+    },
+
+    /// Failed to deserialize the value at a key path in another flow's stored
+    /// state.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::flow_state_deserialize))
+    )]
+    #[error(
+        "Failed to deserialize `{key_path}` in `{item_id}`'s stored state \
+        in flow `{flow_id}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    FlowStateDeserialize {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// ID of the flow whose stored state is referenced.
+        flow_id: FlowId,
+        /// ID of the item in that flow whose stored state is referenced.
+        item_id: ItemId,
+        /// `.` separated path to the value within the state.
+        key_path: String,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// An item's output that a field value is read from has not been
+    /// published.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::item_output_not_found),
+            help(
+                "Make sure `{item_id}` publishes an output named `{output_name}` in \
+                `Item::outputs`, and has been applied."
+            )
+        )
+    )]
+    #[error(
+        "Output `{output_name}` of `{item_id}` not found to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    ItemOutputNotFound {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// ID of the item whose output is referenced.
+        item_id: ItemId,
+        /// Name of the output.
+        output_name: String,
+    },
+
+    /// Failed to deserialize an item's output that a field value is read
+    /// from.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::item_output_deserialize))
+    )]
+    #[error(
+        "Failed to deserialize output `{output_name}` of `{item_id}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    ItemOutputDeserialize {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// ID of the item whose output is referenced.
+        item_id: ItemId,
+        /// Name of the output.
+        output_name: String,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// A `ValueSpec::Template` could not be parsed.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::template_invalid),
+            help("Placeholders are written as `{{mapping_fn_id}}`, and literal braces as `{{{{` and `}}}}`.")
+        )
+    )]
+    #[error(
+        "Template `{template}` is invalid: {reason}\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    TemplateInvalid {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// The template string.
+        template: String,
+        /// Why the template is invalid.
+        reason: String,
+    },
+
+    /// Failed to serialize a mapping function's value to interpolate into a
+    /// template.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::template_value_serialize))
+    )]
+    #[error(
+        "Failed to serialize the value from `{mapping_fn_id:?}` for a template to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    TemplateValueSerialize {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// The mapping function whose value failed to serialize.
+        mapping_fn_id: MappingFnId,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Failed to deserialize a rendered template into the field's type.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_params::params_resolve_error::template_deserialize))
+    )]
+    #[error(
+        "Failed to deserialize rendered template `{rendered}` to populate:\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```"
+    )]
+    TemplateDeserialize {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: Box<ValueResolutionCtx>,
+        /// The rendered template.
+        rendered: String,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// A resolved field value failed validation.
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_params::params_resolve_error::validate),
+            help(
+                "Update the value of `{field_name}` for the `{item_id}` item.",
+                field_name = value_resolution_ctx
+                    .resolution_chain()
+                    .last()
+                    .map(FieldNameAndType::field_name)
+                    .unwrap_or(value_resolution_ctx.params_type_name()),
+                item_id = value_resolution_ctx.item_id(),
+            )
+        )
+    )]
+    #[error("Invalid value for `{field_name}` in `{item_id}`: {reason}\n\
+        \n\
+        ```rust\n\
+        {value_resolution_ctx}\n\
+        ```",
+        field_name = value_resolution_ctx
+            .resolution_chain()
+            .last()
+            .map(FieldNameAndType::field_name)
+            .unwrap_or(value_resolution_ctx.params_type_name()),
+        item_id = value_resolution_ctx.item_id())]
+    Validate {
+        /// Hierarchy of fields traversed to resolve the value.
+        value_resolution_ctx: ValueResolutionCtx,
+        /// Why the value is invalid.
+        reason: String,
+    },
 }
 
 impl ParamsResolveError {
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{any::TypeId, fmt::Debug};
 
 use peace_resource_rt::{
     resources::ts::SetUp, type_reg::untagged::BoxDataTypeDowncast, BorrowFail, Resources,
@@ -6,8 +6,9 @@ use peace_resource_rt::{
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    AnySpecDataType, AnySpecRt, FieldWiseSpecRt, MappingFnId, MappingFnReg, MappingFns, Params,
-    ParamsResolveError, ValueResolutionCtx, ValueResolutionMode, ValueSpecRt,
+    AnySpecDataType, AnySpecRt, FieldTemplate, FieldValueSetError, FieldWiseSpecRt, FlowStateRef,
+    ItemId, MappingFnId, MappingFnReg, MappingFns, Params, ParamsResolveError, ValueResolutionCtx,
+    ValueResolutionMode, ValueSpecRt,
 };
 
 /// How to populate a field's value in an item's params.
@@ -34,6 +35,11 @@ use crate::{
 ///
 /// * `T`: The `Item::Params` type.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "params_schema",
+    derive(schemars::JsonSchema),
+    schemars(bound = "T: Params + schemars::JsonSchema, T::FieldWiseSpec: schemars::JsonSchema")
+)]
 #[serde(bound = "T: Params")]
 pub enum ParamsSpec<T>
 where
@@ -328,6 +334,83 @@ where
         }
     }
 
+    fn mapping_fn_ids(&self) -> Vec<MappingFnId> {
+        match self {
+            Self::MappingFn { mapping_fn_id, .. } => vec![mapping_fn_id.clone()],
+            Self::FieldWise { field_wise_spec } => field_wise_spec.mapping_fn_ids(),
+            Self::Stored | Self::Value { .. } | Self::InMemory => Vec::new(),
+        }
+    }
+
+    fn field_templates(&self) -> Vec<FieldTemplate> {
+        match self {
+            Self::FieldWise { field_wise_spec } => field_wise_spec.field_templates(),
+            Self::Stored | Self::Value { .. } | Self::InMemory | Self::MappingFn { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    fn in_memory_type_ids(&self) -> Vec<TypeId> {
+        match self {
+            Self::InMemory => vec![TypeId::of::<T>()],
+            Self::FieldWise { field_wise_spec } => field_wise_spec.in_memory_type_ids(),
+            Self::Stored | Self::Value { .. } | Self::MappingFn { .. } => Vec::new(),
+        }
+    }
+
+    fn flow_state_refs(&self) -> Vec<FlowStateRef> {
+        match self {
+            Self::FieldWise { field_wise_spec } => field_wise_spec.flow_state_refs(),
+            Self::Stored | Self::Value { .. } | Self::InMemory | Self::MappingFn { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    fn item_output_refs(&self) -> Vec<ItemId> {
+        match self {
+            Self::FieldWise { field_wise_spec } => field_wise_spec.item_output_refs(),
+            Self::Stored | Self::Value { .. } | Self::InMemory | Self::MappingFn { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    fn field_names(&self) -> Vec<String> {
+        match self {
+            Self::Value { value } => match serde_yaml::to_value(value) {
+                Ok(serde_yaml::Value::Mapping(mapping)) => mapping
+                    .keys()
+                    .filter_map(serde_yaml::Value::as_str)
+                    .map(str::to_string)
+                    .collect(),
+                _ => Vec::new(),
+            },
+            Self::FieldWise { field_wise_spec } => field_wise_spec.field_names(),
+            Self::Stored | Self::InMemory | Self::MappingFn { .. } => Vec::new(),
+        }
+    }
+
+    fn field_value_set(&mut self, field_name: &str, value: &str) -> Result<(), FieldValueSetError> {
+        let spec_variant = match self {
+            Self::Value { value: params } => {
+                return params_field_value_set(params, field_name, value);
+            }
+            Self::FieldWise { field_wise_spec } => {
+                return field_wise_spec.field_value_set(field_name, value);
+            }
+            Self::Stored => "Stored",
+            Self::InMemory => "InMemory",
+            Self::MappingFn { .. } => "MappingFn",
+        };
+
+        Err(FieldValueSetError::FieldsNotSettable {
+            params_type_name: tynm::type_name::<T>(),
+            spec_variant,
+        })
+    }
+
     fn merge(&mut self, other_boxed: &dyn AnySpecDataType)
     where
         Self: Sized,
@@ -402,3 +485,59 @@ where
             .map(Result::ok)
     }
 }
+
+/// Sets the value of a field within `params`, deserialized from `value`.
+///
+/// `params` is serialized into a YAML mapping, the field is replaced, and the
+/// mapping is deserialized back into `T`. The value is first used as a plain
+/// string, then parsed as YAML if that fails to deserialize.
+fn params_field_value_set<T>(
+    params: &mut T,
+    field_name: &str,
+    value: &str,
+) -> Result<(), FieldValueSetError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let value_deserialize_error = |error| FieldValueSetError::ValueDeserialize {
+        field_name: field_name.to_string(),
+        field_type_name: tynm::type_name::<T>(),
+        value: value.to_string(),
+        error,
+    };
+
+    let mut mapping = match serde_yaml::to_value(&*params).map_err(value_deserialize_error)? {
+        serde_yaml::Value::Mapping(mapping) => mapping,
+        _ => {
+            return Err(FieldValueSetError::FieldNotFound {
+                field_name: field_name.to_string(),
+                field_names: Vec::new(),
+            });
+        }
+    };
+    let field_key = serde_yaml::Value::String(field_name.to_string());
+    if !mapping.contains_key(&field_key) {
+        return Err(FieldValueSetError::FieldNotFound {
+            field_name: field_name.to_string(),
+            field_names: mapping
+                .keys()
+                .filter_map(serde_yaml::Value::as_str)
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+
+    let field_value_string = serde_yaml::Value::String(value.to_string());
+    mapping.insert(field_key.clone(), field_value_string);
+    let params_deserialized =
+        serde_yaml::from_value::<T>(serde_yaml::Value::Mapping(mapping.clone())).or_else(|_| {
+            let field_value_yaml = serde_yaml::from_str::<serde_yaml::Value>(value)
+                .map_err(value_deserialize_error)?;
+            mapping.insert(field_key, field_value_yaml);
+            serde_yaml::from_value::<T>(serde_yaml::Value::Mapping(mapping))
+                .map_err(value_deserialize_error)
+        })?;
+    *params = params_deserialized;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{any::TypeId, fmt::Debug};
 
 use peace_resource_rt::{
     resources::ts::SetUp, type_reg::untagged::BoxDataTypeDowncast, BorrowFail, Resources,
@@ -6,8 +6,8 @@ use peace_resource_rt::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    AnySpecDataType, AnySpecRt, MappingFnId, MappingFnReg, MappingFns, ParamsFieldless,
-    ParamsResolveError, ValueResolutionCtx, ValueSpecRt,
+    AnySpecDataType, AnySpecRt, FieldValueSetError, MappingFnId, MappingFnReg, MappingFns,
+    ParamsFieldless, ParamsResolveError, ValueResolutionCtx, ValueSpecRt,
 };
 
 /// How to populate a field's value in an item's params.
@@ -31,6 +31,11 @@ use crate::{
 /// 4. These `AnySpecRtBoxed`s are downcasted back to `ParamsSpecFieldless<T,
 ///    MFns>` when resolving values for item params and params partials.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "params_schema",
+    derive(schemars::JsonSchema),
+    schemars(bound = "T: schemars::JsonSchema")
+)]
 #[serde(bound = "T: ParamsFieldless")]
 pub enum ParamsSpecFieldless<T>
 where
@@ -247,6 +252,35 @@ where
         }
     }
 
+    fn mapping_fn_ids(&self) -> Vec<MappingFnId> {
+        match self {
+            Self::MappingFn { mapping_fn_id, .. } => vec![mapping_fn_id.clone()],
+            Self::Stored | Self::Value { .. } | Self::InMemory => Vec::new(),
+        }
+    }
+
+    fn in_memory_type_ids(&self) -> Vec<TypeId> {
+        match self {
+            Self::InMemory => vec![TypeId::of::<T>()],
+            Self::Stored | Self::Value { .. } | Self::MappingFn { .. } => Vec::new(),
+        }
+    }
+
+    fn field_value_set(&mut self, field_name: &str, value: &str) -> Result<(), FieldValueSetError> {
+        let _ = (field_name, value);
+        let spec_variant = match self {
+            Self::Stored => "Stored",
+            Self::Value { .. } => "Value",
+            Self::InMemory => "InMemory",
+            Self::MappingFn { .. } => "MappingFn",
+        };
+
+        Err(FieldValueSetError::FieldsNotSettable {
+            params_type_name: tynm::type_name::<T>(),
+            spec_variant,
+        })
+    }
+
     fn merge(&mut self, other_boxed: &dyn AnySpecDataType)
     where
         Self: Sized,
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use regex::Regex;
+
+use crate::ValueIsEmpty;
+
+/// Checks used by the `#[validate(..)]` field attributes of
+/// `#[derive(Params)]`.
+///
+/// Each check returns the reason the value is invalid as the error.
+#[derive(Debug)]
+pub struct ParamsValidators;
+
+impl ParamsValidators {
+    /// `#[validate(non_empty)]`: Returns an error if the value is empty.
+    pub fn non_empty<T>(value: &T) -> Result<(), String>
+    where
+        T: ValueIsEmpty + ?Sized,
+    {
+        if value.value_is_empty() {
+            Err(String::from("value must not be empty"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// `#[validate(range(min = .., max = ..))]`: Returns an error if the value
+    /// is outside the inclusive range.
+    pub fn range<T>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String>
+    where
+        T: PartialOrd + Display,
+    {
+        match (min, max) {
+            (Some(min), _) if *value < min => Err(format!("`{value}` is less than `{min}`")),
+            (_, Some(max)) if *value > max => Err(format!("`{value}` is greater than `{max}`")),
+            _ => Ok(()),
+        }
+    }
+
+    /// `#[validate(regex = "..")]`: Returns an error if the value does not
+    /// match the pattern.
+    ///
+    /// `#[derive(Params)]` checks the pattern when the macro is expanded, and
+    /// compiles it once per field.
+    pub fn regex<T>(value: &T, regex: &Regex) -> Result<(), String>
+    where
+        T: AsRef<str> + ?Sized,
+    {
+        let value = value.as_ref();
+        if regex.is_match(value) {
+            Ok(())
+        } else {
+            Err(format!("`{value}` does not match `{regex}`"))
+        }
+    }
+}
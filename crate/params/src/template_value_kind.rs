@@ -0,0 +1,190 @@
+use std::fmt;
+
+use serde::{de::Visitor, Deserialize, Deserializer};
+
+/// Kind of value a type is serialized as, used to check the values
+/// interpolated into a `ValueSpec::Template` against the field's type.
+///
+/// The kind is determined from the type's `Deserialize` implementation, so
+/// types that deserialize from any value, e.g. `serde_yaml::Value` or
+/// `#[serde(untagged)]` enums, are [`TemplateValueKind::Unknown`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemplateValueKind {
+    /// Strings, characters, and bytes.
+    Text,
+    /// Integers and floating point numbers.
+    Number,
+    /// `true` or `false`.
+    Bool,
+    /// Sequences, maps, and structs.
+    Structured,
+    /// Kind could not be determined, e.g. enums and unit types.
+    Unknown,
+}
+
+impl TemplateValueKind {
+    /// Returns the kind of value that `T` is serialized as.
+    pub fn of<'de, T>() -> Self
+    where
+        T: Deserialize<'de>,
+    {
+        match T::deserialize(KindProbe) {
+            Err(KindFound(Some(template_value_kind))) => template_value_kind,
+            Ok(_) | Err(KindFound(None)) => Self::Unknown,
+        }
+    }
+
+    /// Returns whether a value of this kind may be interpolated into a
+    /// template for a field of the `field_kind`.
+    ///
+    /// Unknown kinds are always compatible, and structured fields are parsed
+    /// from the rendered template as YAML, so any value may be interpolated
+    /// into them.
+    pub fn is_interpolatable_into(self, field_kind: TemplateValueKind) -> bool {
+        match (self, field_kind) {
+            (Self::Unknown, _) | (_, Self::Unknown | Self::Structured) => true,
+            (Self::Structured, _) => false,
+            (_, Self::Text) => true,
+            (value_kind, field_kind) => value_kind == field_kind,
+        }
+    }
+}
+
+impl fmt::Display for TemplateValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => f.write_str("text"),
+            Self::Number => f.write_str("number"),
+            Self::Bool => f.write_str("bool"),
+            Self::Structured => f.write_str("structured"),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// `Deserializer` that records which kind of value was requested, without
+/// producing a value.
+struct KindProbe;
+
+/// Error returned by [`KindProbe`] carrying the requested kind.
+#[derive(Debug)]
+struct KindFound(Option<TemplateValueKind>);
+
+impl fmt::Display for KindFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Deserialize requested a `{:?}` value.", self.0)
+    }
+}
+
+impl std::error::Error for KindFound {}
+
+impl serde::de::Error for KindFound {
+    fn custom<T>(_msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self(None)
+    }
+}
+
+macro_rules! kind_probe_fns {
+    ($template_value_kind:ident => $($fn_name:ident),+ $(,)?) => {
+        $(
+            fn $fn_name<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                Err(KindFound(Some(TemplateValueKind::$template_value_kind)))
+            }
+        )+
+    };
+}
+
+impl<'de> Deserializer<'de> for KindProbe {
+    type Error = KindFound;
+
+    kind_probe_fns!(Bool => deserialize_bool);
+    kind_probe_fns!(Number =>
+        deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64, deserialize_i128,
+        deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64, deserialize_u128,
+        deserialize_f32, deserialize_f64,
+    );
+    kind_probe_fns!(Text =>
+        deserialize_char, deserialize_str, deserialize_string, deserialize_bytes,
+        deserialize_byte_buf, deserialize_identifier,
+    );
+    kind_probe_fns!(Structured => deserialize_seq, deserialize_map);
+    kind_probe_fns!(Unknown => deserialize_any, deserialize_unit, deserialize_ignored_any);
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(KindFound(Some(TemplateValueKind::Unknown)))
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(KindFound(Some(TemplateValueKind::Structured)))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(KindFound(Some(TemplateValueKind::Structured)))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(KindFound(Some(TemplateValueKind::Structured)))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(KindFound(Some(TemplateValueKind::Unknown)))
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+/// Types that can be checked by `#[validate(non_empty)]`.
+pub trait ValueIsEmpty {
+    /// Returns whether the value is empty.
+    fn value_is_empty(&self) -> bool;
+}
+
+impl ValueIsEmpty for str {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl ValueIsEmpty for String {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl ValueIsEmpty for Path {
+    fn value_is_empty(&self) -> bool {
+        self.as_os_str().is_empty()
+    }
+}
+
+impl ValueIsEmpty for PathBuf {
+    fn value_is_empty(&self) -> bool {
+        self.as_os_str().is_empty()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ValueIsEmpty for std::ffi::OsString {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> ValueIsEmpty for [T] {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> ValueIsEmpty for Vec<T> {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> ValueIsEmpty for Option<T>
+where
+    T: ValueIsEmpty,
+{
+    fn value_is_empty(&self) -> bool {
+        self.as_ref().is_none_or(ValueIsEmpty::value_is_empty)
+    }
+}
+
+impl<K, V, S> ValueIsEmpty for HashMap<K, V, S> {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T, S> ValueIsEmpty for HashSet<T, S> {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<K, V> ValueIsEmpty for BTreeMap<K, V> {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> ValueIsEmpty for BTreeSet<T> {
+    fn value_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+}
@@ -1,13 +1,24 @@
-use std::fmt::Debug;
+use std::{
+    any::TypeId,
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
 
+use peace_flow_model::{FlowId, FlowStateRef};
+use peace_item_model::ItemId;
 use peace_resource_rt::{
-    resources::ts::SetUp, type_reg::untagged::BoxDataTypeDowncast, BorrowFail, Resources,
+    paths::WorkspaceDir, resources::ts::SetUp, type_reg::untagged::BoxDataTypeDowncast, BorrowFail,
+    Resources,
+};
+use serde::{
+    de::{value::StrDeserializer, DeserializeOwned},
+    Deserialize, Serialize,
 };
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    AnySpecDataType, AnySpecRt, MappingFnId, MappingFnReg, MappingFns, ParamsResolveError,
-    ValueResolutionCtx, ValueSpecRt,
+    value_template, AnySpecDataType, AnySpecRt, FieldTemplate, FieldValueSetError,
+    FlowsStatesCurrentStored, ItemsOutputs, MappingFnId, MappingFnReg, MappingFns,
+    ParamsResolveError, TemplateValueKind, ValueResolutionCtx, ValueSpecRt,
 };
 
 /// How to populate a field's value in an item's params.
@@ -30,6 +41,11 @@ use crate::{
 /// 4. These `AnySpecRtBoxed`s are downcasted back to `ValueSpec<T>` when
 ///    resolving values for item params and params partials.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "params_schema",
+    derive(schemars::JsonSchema),
+    schemars(bound = "T: schemars::JsonSchema")
+)]
 #[serde(bound = "T: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static")]
 pub enum ValueSpec<T>
 where
@@ -79,6 +95,103 @@ where
         /// The name of the mapping function.
         mapping_fn_id: MappingFnId,
     },
+    /// Uses a value read from an environment variable.
+    ///
+    /// The variable's value is used as is if `T` deserializes from a string,
+    /// otherwise it is parsed as YAML, e.g. `"8080"` for a `u16`.
+    ///
+    /// If the variable is not set, `default` is used if present.
+    Env {
+        /// Name of the environment variable.
+        name: String,
+        /// Value to use if the environment variable is not set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<T>,
+    },
+    /// Uses the contents of a file.
+    ///
+    /// A single trailing newline (`\n` or `\r\n`) is removed from the
+    /// contents, then they are used as is if `T` deserializes from a string,
+    /// otherwise they are parsed as YAML.
+    ///
+    /// A relative path is resolved against the workspace directory.
+    File {
+        /// Path to the file.
+        path: PathBuf,
+    },
+    /// Uses a value at a key path within a YAML or JSON file.
+    ///
+    /// The key path is a `.` separated list of keys, where numeric segments
+    /// index into sequences, e.g. `servers.0.port`. An empty key path uses
+    /// the whole document.
+    ///
+    /// A relative path is resolved against the workspace directory.
+    FileKey {
+        /// Path to the YAML or JSON file.
+        path: PathBuf,
+        /// `.` separated path to the value within the file.
+        key_path: String,
+    },
+    /// Uses a value at a key path within another flow's stored current
+    /// state.
+    ///
+    /// The state is read from that flow's `states_current.yaml` in the same
+    /// profile when the command context is built, so that flow must have been
+    /// applied first. The key path is a `.` separated list of keys, where
+    /// numeric segments index into sequences. An empty key path uses the whole
+    /// state.
+    ///
+    /// The referenced flow is shown as a dependency in this flow's
+    /// `FlowInfo`.
+    FlowState {
+        /// ID of the flow whose stored state is read.
+        #[cfg_attr(feature = "params_schema", schemars(with = "String"))]
+        flow_id: FlowId,
+        /// ID of the item in that flow whose stored state is read.
+        #[cfg_attr(feature = "params_schema", schemars(with = "String"))]
+        item_id: ItemId,
+        /// `.` separated path to the value within the state.
+        #[serde(default)]
+        key_path: String,
+    },
+    /// Uses an output published by another item in this flow.
+    ///
+    /// Outputs are published by `Item::outputs` when an item is applied, and
+    /// are stored alongside the current states, so this resolves even when
+    /// the producing item is not applied in this command.
+    ///
+    /// The producing item is treated as a predecessor of the item whose
+    /// params reference its output.
+    ItemOutput {
+        /// ID of the item that publishes the output.
+        #[cfg_attr(feature = "params_schema", schemars(with = "String"))]
+        item_id: ItemId,
+        /// Name of the output.
+        output_name: String,
+    },
+    /// Uses a string built from a template.
+    ///
+    /// Each `{placeholder}` in the template is the ID of a mapping function,
+    /// whose value is interpolated into the string. Literal braces are written
+    /// as `{{` and `}}`, e.g.
+    /// `s3://{BucketNameFromS3BucketState_v0_1_0}/{AppVersion_v0_1_0}/app.tar`.
+    ///
+    /// Placeholders do not name resources or params fields directly; to
+    /// interpolate those, register a mapping function that reads them.
+    ///
+    /// The rendered string is used as is if `T` deserializes from a string,
+    /// otherwise it is parsed as YAML.
+    ///
+    /// When the `CmdCtx` is built, each placeholder is checked to be a
+    /// registered mapping function whose arguments are available, and whose
+    /// value can be interpolated into `T`, e.g. a text value is not used for
+    /// a number field. Values are only rendered when the params are resolved,
+    /// so a rendered string that fails to deserialize as `T` is returned as an
+    /// error then.
+    Template {
+        /// The template string.
+        template: String,
+    },
 }
 
 impl<T> From<T> for ValueSpec<T>
@@ -109,6 +222,32 @@ where
         }
     }
 
+    /// Sets this spec to `ValueSpec::Value`, with the value deserialized from
+    /// the given string.
+    ///
+    /// Plain strings are used as is, and other values are parsed as YAML.
+    ///
+    /// # Parameters
+    ///
+    /// * `field_name`: Name of the field this spec is for, used in errors.
+    /// * `value`: String representation of the value.
+    pub fn value_set_from_str(
+        &mut self,
+        field_name: &str,
+        value: &str,
+    ) -> Result<(), FieldValueSetError> {
+        let value_t =
+            t_from_str::<T>(value).map_err(|error| FieldValueSetError::ValueDeserialize {
+                field_name: field_name.to_string(),
+                field_type_name: tynm::type_name::<T>(),
+                value: value.to_string(),
+                error,
+            })?;
+        *self = Self::Value { value: value_t };
+
+        Ok(())
+    }
+
     /// Returns the value of `T` by applying this spec to the passed in
     /// `resources`.
     pub fn resolve(
@@ -142,6 +281,39 @@ where
                 field_name.as_deref(),
                 mapping_fn_id,
             ),
+            ValueSpec::Env { name, default } => {
+                resolve_t_from_env(value_resolution_ctx, name, default.as_ref())
+            }
+            ValueSpec::File { path } => resolve_t_from_file(resources, value_resolution_ctx, path),
+            ValueSpec::FileKey { path, key_path } => {
+                resolve_t_from_file_key(resources, value_resolution_ctx, path, key_path)
+            }
+            ValueSpec::FlowState {
+                flow_id,
+                item_id,
+                key_path,
+            } => resolve_t_from_flow_state(
+                resources,
+                value_resolution_ctx,
+                flow_id,
+                item_id,
+                key_path,
+            ),
+            ValueSpec::ItemOutput {
+                item_id,
+                output_name,
+            } => resolve_t_from_item_output(resources, value_resolution_ctx, item_id, output_name),
+            ValueSpec::Template { template } => {
+                let rendered = value_template::render(
+                    template,
+                    mapping_fn_reg,
+                    resources,
+                    value_resolution_ctx,
+                    false,
+                )?
+                .unwrap_or_default();
+                t_from_template_rendered(value_resolution_ctx, rendered)
+            }
         }
     }
 
@@ -187,10 +359,314 @@ where
 
                 Ok(t)
             }
+            ValueSpec::Env { name, default } => {
+                match resolve_t_from_env(value_resolution_ctx, name, default.as_ref()) {
+                    Ok(t) => Ok(Some(t)),
+                    Err(ParamsResolveError::EnvVarNotPresent { .. }) => Ok(None),
+                    Err(error) => Err(error),
+                }
+            }
+            ValueSpec::File { path } => resolve_partial_from_source(resolve_t_from_file(
+                resources,
+                value_resolution_ctx,
+                path,
+            )),
+            ValueSpec::FileKey { path, key_path } => resolve_partial_from_source(
+                resolve_t_from_file_key(resources, value_resolution_ctx, path, key_path),
+            ),
+            ValueSpec::FlowState {
+                flow_id,
+                item_id,
+                key_path,
+            } => resolve_partial_from_source(resolve_t_from_flow_state(
+                resources,
+                value_resolution_ctx,
+                flow_id,
+                item_id,
+                key_path,
+            )),
+            ValueSpec::ItemOutput {
+                item_id,
+                output_name,
+            } => resolve_partial_from_source(resolve_t_from_item_output(
+                resources,
+                value_resolution_ctx,
+                item_id,
+                output_name,
+            )),
+            ValueSpec::Template { template } => value_template::render(
+                template,
+                mapping_fn_reg,
+                resources,
+                value_resolution_ctx,
+                true,
+            )?
+            .map(|rendered| t_from_template_rendered(value_resolution_ctx, rendered))
+            .transpose(),
         }
     }
 }
 
+/// Maps errors for a file, flow state, item output, or key that is not present
+/// to `Ok(None)`.
+fn resolve_partial_from_source<T>(
+    t_result: Result<T, ParamsResolveError>,
+) -> Result<Option<T>, ParamsResolveError> {
+    match t_result {
+        Ok(t) => Ok(Some(t)),
+        Err(ParamsResolveError::FileRead { error, .. })
+            if error.kind() == std::io::ErrorKind::NotFound =>
+        {
+            Ok(None)
+        }
+        Err(ParamsResolveError::FileKeyNotFound { .. })
+        | Err(ParamsResolveError::FlowStatesNotFound { .. })
+        | Err(ParamsResolveError::FlowStateNotFound { .. })
+        | Err(ParamsResolveError::FlowStateKeyNotFound { .. })
+        | Err(ParamsResolveError::ItemOutputNotFound { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Returns a `T` from an environment variable, falling back to `default`.
+fn resolve_t_from_env<T>(
+    value_resolution_ctx: &ValueResolutionCtx,
+    name: &str,
+    default: Option<&T>,
+) -> Result<T, ParamsResolveError>
+where
+    T: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    match std::env::var(name) {
+        Ok(value) => t_from_str(&value).map_err(|error| ParamsResolveError::EnvVarParse {
+            value_resolution_ctx: value_resolution_ctx.clone(),
+            env_var_name: name.to_string(),
+            error,
+        }),
+        Err(std::env::VarError::NotPresent) => {
+            default
+                .cloned()
+                .ok_or_else(|| ParamsResolveError::EnvVarNotPresent {
+                    value_resolution_ctx: value_resolution_ctx.clone(),
+                    env_var_name: name.to_string(),
+                })
+        }
+        Err(std::env::VarError::NotUnicode(_)) => Err(ParamsResolveError::EnvVarNotUnicode {
+            value_resolution_ctx: value_resolution_ctx.clone(),
+            env_var_name: name.to_string(),
+        }),
+    }
+}
+
+/// Returns a `T` from the contents of a file, without its trailing newline.
+fn resolve_t_from_file<T>(
+    resources: &Resources<SetUp>,
+    value_resolution_ctx: &ValueResolutionCtx,
+    path: &Path,
+) -> Result<T, ParamsResolveError>
+where
+    T: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    let path = file_path_resolve(resources, path);
+    let contents = file_read(value_resolution_ctx, &path)?;
+    let contents = contents
+        .strip_suffix('\n')
+        .map(|contents| contents.strip_suffix('\r').unwrap_or(contents))
+        .unwrap_or(&contents);
+    t_from_str(contents).map_err(|error| ParamsResolveError::FileParse {
+        value_resolution_ctx: value_resolution_ctx.clone(),
+        path,
+        error,
+    })
+}
+
+/// Returns a `T` from the value at `key_path` within a YAML or JSON file.
+fn resolve_t_from_file_key<T>(
+    resources: &Resources<SetUp>,
+    value_resolution_ctx: &ValueResolutionCtx,
+    path: &Path,
+    key_path: &str,
+) -> Result<T, ParamsResolveError>
+where
+    T: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    let path = file_path_resolve(resources, path);
+    let path = path.as_path();
+    let contents = file_read(value_resolution_ctx, path)?;
+    let document = serde_yaml::from_str::<serde_yaml::Value>(&contents).map_err(|error| {
+        ParamsResolveError::FileParse {
+            value_resolution_ctx: value_resolution_ctx.clone(),
+            path: path.to_path_buf(),
+            error,
+        }
+    })?;
+
+    let value = value_at_key_path(&document, key_path).ok_or_else(|| {
+        ParamsResolveError::FileKeyNotFound {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            path: path.to_path_buf(),
+            key_path: key_path.to_string(),
+        }
+    })?;
+
+    serde_yaml::from_value::<T>(value.clone()).map_err(|error| {
+        ParamsResolveError::FileKeyDeserialize {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            path: path.to_path_buf(),
+            key_path: key_path.to_string(),
+            error,
+        }
+    })
+}
+
+/// Returns a `T` from the value at `key_path` within another flow's stored
+/// current state.
+fn resolve_t_from_flow_state<T>(
+    resources: &Resources<SetUp>,
+    value_resolution_ctx: &ValueResolutionCtx,
+    flow_id: &FlowId,
+    item_id: &ItemId,
+    key_path: &str,
+) -> Result<T, ParamsResolveError>
+where
+    T: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    let flows_states_current_stored = resources.try_borrow::<FlowsStatesCurrentStored>();
+    let states = flows_states_current_stored
+        .as_ref()
+        .ok()
+        .and_then(|flows_states_current_stored| flows_states_current_stored.get(flow_id))
+        .ok_or_else(|| ParamsResolveError::FlowStatesNotFound {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            flow_id: flow_id.clone(),
+            item_id: item_id.clone(),
+        })?;
+    let state = states
+        .get(item_id)
+        .ok_or_else(|| ParamsResolveError::FlowStateNotFound {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            flow_id: flow_id.clone(),
+            item_id: item_id.clone(),
+        })?;
+
+    let value = value_at_key_path(state, key_path).ok_or_else(|| {
+        ParamsResolveError::FlowStateKeyNotFound {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            flow_id: flow_id.clone(),
+            item_id: item_id.clone(),
+            key_path: key_path.to_string(),
+        }
+    })?;
+
+    serde_yaml::from_value::<T>(value.clone()).map_err(|error| {
+        ParamsResolveError::FlowStateDeserialize {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            flow_id: flow_id.clone(),
+            item_id: item_id.clone(),
+            key_path: key_path.to_string(),
+            error,
+        }
+    })
+}
+
+/// Returns a `T` from an output published by another item.
+fn resolve_t_from_item_output<T>(
+    resources: &Resources<SetUp>,
+    value_resolution_ctx: &ValueResolutionCtx,
+    item_id: &ItemId,
+    output_name: &str,
+) -> Result<T, ParamsResolveError>
+where
+    T: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    let items_outputs = resources.try_borrow::<ItemsOutputs>();
+    let value = items_outputs
+        .as_ref()
+        .ok()
+        .and_then(|items_outputs| items_outputs.output_get(item_id, output_name))
+        .ok_or_else(|| ParamsResolveError::ItemOutputNotFound {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            item_id: item_id.clone(),
+            output_name: output_name.to_string(),
+        })?;
+
+    serde_yaml::from_value::<T>(value.clone()).map_err(|error| {
+        ParamsResolveError::ItemOutputDeserialize {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            item_id: item_id.clone(),
+            output_name: output_name.to_string(),
+            error,
+        }
+    })
+}
+
+/// Returns the value at a `.` separated `key_path` within `document`, where
+/// numeric segments index into sequences.
+fn value_at_key_path<'doc>(
+    document: &'doc serde_yaml::Value,
+    key_path: &str,
+) -> Option<&'doc serde_yaml::Value> {
+    key_path
+        .split('.')
+        .filter(|key| !key.is_empty())
+        .try_fold(document, |value, key| match value {
+            serde_yaml::Value::Sequence(sequence) => key
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| sequence.get(index)),
+            _ => value.get(key),
+        })
+}
+
+/// Returns the path joined onto the workspace directory if it is relative.
+///
+/// The path is returned as is if it is absolute, or if the workspace directory
+/// is not in `resources`.
+fn file_path_resolve(resources: &Resources<SetUp>, path: &Path) -> PathBuf {
+    if path.is_relative() {
+        if let Ok(workspace_dir) = resources.try_borrow::<WorkspaceDir>() {
+            return workspace_dir.join(path);
+        }
+    }
+    path.to_path_buf()
+}
+
+fn file_read(
+    value_resolution_ctx: &ValueResolutionCtx,
+    path: &Path,
+) -> Result<String, ParamsResolveError> {
+    std::fs::read_to_string(path).map_err(|error| ParamsResolveError::FileRead {
+        value_resolution_ctx: value_resolution_ctx.clone(),
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Returns a `T` from a rendered template.
+fn t_from_template_rendered<T>(
+    value_resolution_ctx: &ValueResolutionCtx,
+    rendered: String,
+) -> Result<T, ParamsResolveError>
+where
+    T: DeserializeOwned,
+{
+    t_from_str(&rendered).map_err(|error| ParamsResolveError::TemplateDeserialize {
+        value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+        rendered,
+        error,
+    })
+}
+
+/// Returns a `T` from a string, used as is if `T` deserializes from a string,
+/// otherwise parsed as YAML.
+fn t_from_str<T>(s: &str) -> Result<T, serde_yaml::Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(StrDeserializer::<serde::de::value::Error>::new(s))
+        .or_else(|_| serde_yaml::from_str::<T>(s))
+}
+
 /// Returns a `T` by downcasting it from a `BoxDt` resolved by a mapping
 /// function.
 ///
@@ -228,7 +704,97 @@ where
     fn is_usable(&self) -> bool {
         match self {
             Self::Stored => false,
-            Self::Value { .. } | Self::InMemory | Self::MappingFn { .. } => true,
+            Self::Value { .. }
+            | Self::InMemory
+            | Self::MappingFn { .. }
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::FlowState { .. }
+            | Self::ItemOutput { .. }
+            | Self::Template { .. } => true,
+        }
+    }
+
+    fn mapping_fn_ids(&self) -> Vec<MappingFnId> {
+        match self {
+            Self::MappingFn { mapping_fn_id, .. } => vec![mapping_fn_id.clone()],
+            Self::Template { template } => value_template::mapping_fn_ids(template),
+            Self::Stored
+            | Self::Value { .. }
+            | Self::InMemory
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::FlowState { .. }
+            | Self::ItemOutput { .. } => Vec::new(),
+        }
+    }
+
+    fn field_templates(&self) -> Vec<FieldTemplate> {
+        match self {
+            Self::Template { template } => vec![FieldTemplate {
+                field_type_name: tynm::type_name::<T>(),
+                field_kind: TemplateValueKind::of::<T>(),
+                template: template.clone(),
+                mapping_fn_ids: value_template::mapping_fn_ids(template),
+            }],
+            Self::Stored
+            | Self::Value { .. }
+            | Self::InMemory
+            | Self::MappingFn { .. }
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::FlowState { .. }
+            | Self::ItemOutput { .. } => Vec::new(),
+        }
+    }
+
+    fn in_memory_type_ids(&self) -> Vec<TypeId> {
+        match self {
+            Self::InMemory => vec![TypeId::of::<T>()],
+            Self::Stored
+            | Self::Value { .. }
+            | Self::MappingFn { .. }
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::FlowState { .. }
+            | Self::ItemOutput { .. }
+            | Self::Template { .. } => Vec::new(),
+        }
+    }
+
+    fn flow_state_refs(&self) -> Vec<FlowStateRef> {
+        match self {
+            Self::FlowState {
+                flow_id, item_id, ..
+            } => vec![FlowStateRef::new(flow_id.clone(), item_id.clone())],
+            Self::Stored
+            | Self::Value { .. }
+            | Self::InMemory
+            | Self::MappingFn { .. }
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::ItemOutput { .. }
+            | Self::Template { .. } => Vec::new(),
+        }
+    }
+
+    fn item_output_refs(&self) -> Vec<ItemId> {
+        match self {
+            Self::ItemOutput { item_id, .. } => vec![item_id.clone()],
+            Self::Stored
+            | Self::Value { .. }
+            | Self::InMemory
+            | Self::MappingFn { .. }
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::FlowState { .. }
+            | Self::Template { .. } => Vec::new(),
         }
     }
 
@@ -252,7 +818,15 @@ where
             Self::Stored => *self = other.clone(),
 
             // Use set value / no change on these variants
-            Self::Value { .. } | Self::InMemory | Self::MappingFn { .. } => {}
+            Self::Value { .. }
+            | Self::InMemory
+            | Self::MappingFn { .. }
+            | Self::Env { .. }
+            | Self::File { .. }
+            | Self::FileKey { .. }
+            | Self::FlowState { .. }
+            | Self::ItemOutput { .. }
+            | Self::Template { .. } => {}
         }
     }
 }
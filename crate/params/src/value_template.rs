@@ -0,0 +1,161 @@
+use peace_resource_rt::{resources::ts::SetUp, Resources};
+
+use crate::{MappingFnId, MappingFnReg, ParamsResolveError, ValueResolutionCtx};
+
+/// Part of a parsed template.
+///
+/// See [`ValueSpec::Template`] for the template syntax.
+///
+/// [`ValueSpec::Template`]: crate::ValueSpec::Template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateSegment<'s> {
+    /// Literal text, with escaped braces already unescaped.
+    Literal(String),
+    /// Name of the value to interpolate, with surrounding whitespace trimmed.
+    ///
+    /// For `ValueSpec::Template`, this is the ID of a mapping function.
+    Placeholder(&'s str),
+}
+
+/// Returns the segments of the given template.
+///
+/// Templates contain `{placeholder}`s, and literal braces are written as `{{`
+/// and `}}`. For `ValueSpec::Template`, each placeholder is the ID of a
+/// mapping function registered in the `MappingFnReg`.
+///
+/// Returns an error message if a brace is not matched or escaped, or if a
+/// placeholder is empty.
+pub fn template_segments(template: &str) -> Result<Vec<TemplateSegment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '{' if chars.next_if(|(_, c)| *c == '{').is_some() => literal.push('{'),
+            '}' if chars.next_if(|(_, c)| *c == '}').is_some() => literal.push('}'),
+            '{' => {
+                let placeholder_start = index + 1;
+                let placeholder_end = loop {
+                    match chars.next() {
+                        Some((index_end, '}')) => break index_end,
+                        Some((_, '{')) | None => {
+                            return Err(format!(
+                                "`{{` at position {index} is not closed by a matching `}}`."
+                            ));
+                        }
+                        Some(_) => {}
+                    }
+                };
+                let placeholder = template[placeholder_start..placeholder_end].trim();
+                if placeholder.is_empty() {
+                    return Err(format!("Placeholder at position {index} is empty."));
+                }
+
+                if !literal.is_empty() {
+                    segments.push(TemplateSegment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(TemplateSegment::Placeholder(placeholder));
+            }
+            '}' => {
+                return Err(format!(
+                    "`}}` at position {index} has no matching `{{`. Use `}}}}` for a literal `}}`."
+                ));
+            }
+            _ => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(TemplateSegment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// Returns the mapping function IDs referenced by the given template.
+///
+/// Invalid templates return the IDs up to the first error.
+pub(crate) fn mapping_fn_ids(template: &str) -> Vec<MappingFnId> {
+    let mut mapping_fn_ids = Vec::new();
+    if let Ok(segments) = template_segments(template) {
+        segments.into_iter().for_each(|segment| {
+            if let TemplateSegment::Placeholder(placeholder) = segment {
+                let mapping_fn_id = MappingFnId::new(placeholder.to_string());
+                if !mapping_fn_ids.contains(&mapping_fn_id) {
+                    mapping_fn_ids.push(mapping_fn_id);
+                }
+            }
+        });
+    }
+    mapping_fn_ids
+}
+
+/// Returns the template with each placeholder replaced by its mapping
+/// function's value.
+///
+/// Returns `Ok(None)` if `is_partial` is `true` and any mapping function's
+/// value is not available.
+pub(crate) fn render(
+    template: &str,
+    mapping_fn_reg: &MappingFnReg,
+    resources: &Resources<SetUp>,
+    value_resolution_ctx: &mut ValueResolutionCtx,
+    is_partial: bool,
+) -> Result<Option<String>, ParamsResolveError> {
+    let segments =
+        template_segments(template).map_err(|reason| ParamsResolveError::TemplateInvalid {
+            value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+            template: template.to_string(),
+            reason,
+        })?;
+
+    let mut rendered = String::with_capacity(template.len());
+    for segment in segments {
+        match segment {
+            TemplateSegment::Literal(literal) => rendered.push_str(&literal),
+            TemplateSegment::Placeholder(placeholder) => {
+                let mapping_fn_id = MappingFnId::new(placeholder.to_string());
+                let mapping_fn = mapping_fn_reg.get(&mapping_fn_id).ok_or_else(|| {
+                    ParamsResolveError::mapping_fn_resolve(value_resolution_ctx, &mapping_fn_id)
+                })?;
+                let box_dt = if is_partial {
+                    match mapping_fn.try_map(resources, value_resolution_ctx, None)? {
+                        Some(box_dt) => box_dt,
+                        None => return Ok(None),
+                    }
+                } else {
+                    mapping_fn.map(resources, value_resolution_ctx, None)?
+                };
+
+                let value = serde_yaml::to_value(&box_dt).map_err(|error| {
+                    ParamsResolveError::TemplateValueSerialize {
+                        value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+                        mapping_fn_id: mapping_fn_id.clone(),
+                        error,
+                    }
+                })?;
+                match value {
+                    serde_yaml::Value::Null => {}
+                    serde_yaml::Value::Bool(value) => rendered.push_str(&value.to_string()),
+                    serde_yaml::Value::Number(value) => rendered.push_str(&value.to_string()),
+                    serde_yaml::Value::String(value) => rendered.push_str(&value),
+                    value @ (serde_yaml::Value::Sequence(_)
+                    | serde_yaml::Value::Mapping(_)
+                    | serde_yaml::Value::Tagged(_)) => {
+                        let value_yaml = serde_yaml::to_string(&value).map_err(|error| {
+                            ParamsResolveError::TemplateValueSerialize {
+                                value_resolution_ctx: Box::new(value_resolution_ctx.clone()),
+                                mapping_fn_id: mapping_fn_id.clone(),
+                                error,
+                            }
+                        })?;
+                        rendered.push_str(value_yaml.trim_end());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(Some(rendered))
+}
@@ -1,6 +1,11 @@
 use syn::{DeriveInput, Ident, ImplGenerics, Path, TypeGenerics, WhereClause};
 
-use crate::{spec_is_usable::is_usable_body, spec_merge::spec_merge};
+use crate::{
+    spec_field_value_set::{field_names_body, field_value_set_body},
+    spec_fields_collect::fields_collect_body,
+    spec_is_usable::is_usable_body,
+    spec_merge::spec_merge,
+};
 
 /// `impl AnySpecRt for ValueSpec`, so that Peace can tell if a spec is usable,
 /// and merge provided and stored params together.
@@ -9,11 +14,62 @@ pub fn impl_any_spec_rt_for_field_wise(
     generics_split: &(ImplGenerics, TypeGenerics, Option<&WhereClause>),
     peace_params_path: &Path,
     params_field_wise_name: &Ident,
+    is_external: bool,
 ) -> proc_macro2::TokenStream {
     let (impl_generics, ty_generics, where_clause) = generics_split;
 
     let is_usable_body = is_usable_body(ast, params_field_wise_name, peace_params_path);
     let spec_merge = spec_merge(ast, params_field_wise_name, peace_params_path);
+    let mapping_fn_ids_body = fields_collect_body(
+        ast,
+        params_field_wise_name,
+        peace_params_path,
+        &format_ident!("mapping_fn_ids"),
+    );
+    let field_templates_body = fields_collect_body(
+        ast,
+        params_field_wise_name,
+        peace_params_path,
+        &format_ident!("field_templates"),
+    );
+    let in_memory_type_ids_body = fields_collect_body(
+        ast,
+        params_field_wise_name,
+        peace_params_path,
+        &format_ident!("in_memory_type_ids"),
+    );
+    let flow_state_refs_body = fields_collect_body(
+        ast,
+        params_field_wise_name,
+        peace_params_path,
+        &format_ident!("flow_state_refs"),
+    );
+    let item_output_refs_body = fields_collect_body(
+        ast,
+        params_field_wise_name,
+        peace_params_path,
+        &format_ident!("item_output_refs"),
+    );
+    // External types are wrapped as a whole, so their fields cannot be set
+    // individually.
+    let (field_names_body, field_value_set_body) = if is_external {
+        let params_type_name = ast.ident.to_string();
+        (
+            quote!(Vec::new()),
+            quote! {
+                let _ = (field_name, value);
+                Err(#peace_params_path::FieldValueSetError::FieldsNotSettable {
+                    params_type_name: String::from(#params_type_name),
+                    spec_variant: "FieldWise",
+                })
+            },
+        )
+    } else {
+        (
+            field_names_body(ast, params_field_wise_name),
+            field_value_set_body(ast, params_field_wise_name, peace_params_path),
+        )
+    };
 
     quote! {
         impl #impl_generics #peace_params_path::AnySpecRt
@@ -25,6 +81,38 @@ pub fn impl_any_spec_rt_for_field_wise(
             }
 
             #spec_merge
+
+            fn mapping_fn_ids(&self) -> Vec<#peace_params_path::MappingFnId> {
+                #mapping_fn_ids_body
+            }
+
+            fn field_templates(&self) -> Vec<#peace_params_path::FieldTemplate> {
+                #field_templates_body
+            }
+
+            fn in_memory_type_ids(&self) -> Vec<::std::any::TypeId> {
+                #in_memory_type_ids_body
+            }
+
+            fn flow_state_refs(&self) -> Vec<#peace_params_path::FlowStateRef> {
+                #flow_state_refs_body
+            }
+
+            fn item_output_refs(&self) -> Vec<#peace_params_path::ItemId> {
+                #item_output_refs_body
+            }
+
+            fn field_names(&self) -> Vec<String> {
+                #field_names_body
+            }
+
+            fn field_value_set(
+                &mut self,
+                field_name: &str,
+                value: &str,
+            ) -> Result<(), #peace_params_path::FieldValueSetError> {
+                #field_value_set_body
+            }
         }
     }
 }
@@ -11,8 +11,8 @@ use proc_macro::TokenStream;
 use quote::ToTokens;
 
 use syn::{
-    Data, DeriveInput, GenericParam, Ident, ImplGenerics, Path, Type, TypeGenerics, WhereClause,
-    WherePredicate,
+    Attribute, Data, DeriveInput, GenericParam, Ident, ImplGenerics, Path, Type, TypeGenerics,
+    WhereClause, WherePredicate,
 };
 
 use crate::{
@@ -28,9 +28,14 @@ use crate::{
     impl_params_merge_ext_for_params::impl_params_merge_ext_for_params,
     impl_try_from_params_partial_for_params::impl_try_from_params_partial_for_params,
     impl_value_spec_rt_for_field_wise::impl_value_spec_rt_for_field_wise,
+    params_field_names_secret::params_field_names_secret_fn,
+    params_validate::params_validate_fn,
     type_gen::TypeGen,
     type_gen_external::type_gen_external,
-    util::{is_fieldless_type, serde_bounds_for_type_params, ImplMode},
+    util::{
+        is_fieldless_type, is_tagged_schema, schema_derive_attrs, serde_bounds_for_type_params,
+        ImplMode,
+    },
 };
 
 mod field_wise_enum_builder_ctx;
@@ -45,6 +50,10 @@ mod impl_from_params_for_params_partial;
 mod impl_params_merge_ext_for_params;
 mod impl_try_from_params_partial_for_params;
 mod impl_value_spec_rt_for_field_wise;
+mod params_field_names_secret;
+mod params_validate;
+mod spec_field_value_set;
+mod spec_fields_collect;
 mod spec_is_usable;
 mod spec_merge;
 mod type_gen;
@@ -92,9 +101,36 @@ mod util;
 ///
 /// * `default`: Enum variant attribute to indicate which variant to instantiate
 ///   for `ParamsPartial::default()`.
+///
+/// * `validate(..)`: Field level attribute to check the resolved value in
+///   `Params::validate`. Multiple checks may be specified:
+///
+///     - `non_empty`: The value must not be empty.
+///     - `range(min = 1, max = 10)`: The value must be within the inclusive
+///       range. Either bound may be omitted.
+///     - `regex = "^[a-z]+$"`: The value must match the pattern.
+///     - `custom = path::to::fn`: Calls `fn(&T) -> Result<(), String>`, where
+///       the error is the reason the value is invalid.
+///
+/// * `params(secret)`: Field level attribute indicating the field's value is
+///   secret, and is masked when params are displayed. This is returned from
+///   `Params::field_names_secret`.
+///
+/// * `params(schema)`: Type level attribute to implement
+///   `Params::params_spec_schema`, which returns the JSON Schema of the
+///   type's `ParamsSpec`. This requires the `params_schema` feature, and the
+///   `Params` type and its field types to implement `schemars::JsonSchema`.
 #[proc_macro_derive(
     Params,
-    attributes(peace_internal, crate_internal, value_spec, default, serde)
+    attributes(
+        peace_internal,
+        crate_internal,
+        value_spec,
+        default,
+        serde,
+        validate,
+        params
+    )
 )]
 pub fn value_spec(input: TokenStream) -> TokenStream {
     let mut ast = syn::parse(input)
@@ -170,6 +206,7 @@ fn impl_value(ast: &mut DeriveInput, impl_mode: ImplMode) -> proc_macro2::TokenS
         });
 
     type_parameters_constrain(ast);
+    let is_schema = impl_mode == ImplMode::Fieldwise && is_tagged_schema(&ast.attrs);
     let value_name = &ast.ident;
     let generics = &ast.generics;
     let generics_split = generics.split_for_impl();
@@ -256,6 +293,7 @@ fn impl_value(ast: &mut DeriveInput, impl_mode: ImplMode) -> proc_macro2::TokenS
                 value_name,
                 &t_field_wise_name,
                 &t_partial_name,
+                is_schema,
             );
 
             (t_partial, t_field_wise, None, None)
@@ -269,6 +307,7 @@ fn impl_value(ast: &mut DeriveInput, impl_mode: ImplMode) -> proc_macro2::TokenS
                 value_name,
                 &t_field_wise_name,
                 &t_partial_name,
+                is_schema,
             );
 
             let t_field_wise_builder = impl_field_wise_builder(
@@ -299,21 +338,40 @@ fn impl_value(ast: &mut DeriveInput, impl_mode: ImplMode) -> proc_macro2::TokenS
 
     let mut impl_value_tokens = proc_macro2::TokenStream::new();
     match impl_mode {
-        ImplMode::Fieldwise => impl_value_tokens.extend(quote! {
-            impl #impl_generics #peace_params_path::Params
-            for #value_name #ty_generics
-            #where_clause
-            {
-                type Spec = #peace_params_path::ParamsSpec<#value_name #ty_generics>;
-                type Partial = #t_partial_name #ty_generics;
-                type FieldWiseSpec = #t_field_wise_name #ty_generics;
-                type FieldWiseBuilder = #t_field_wise_builder_name #builder_generics;
-
-                fn field_wise_spec() -> Self::FieldWiseBuilder {
-                    Self::FieldWiseBuilder::default()
+        ImplMode::Fieldwise => {
+            let params_validate_fn = params_validate_fn(ast, &peace_params_path, &t_partial_name);
+            let params_field_names_secret_fn = params_field_names_secret_fn(ast);
+            let params_spec_schema_fn = is_schema.then(|| {
+                quote! {
+                    fn params_spec_schema(
+                        generator: &mut #peace_params_path::schemars::SchemaGenerator,
+                    ) -> #peace_params_path::schemars::Schema {
+                        generator.subschema_for::<#peace_params_path::ParamsSpec<Self>>()
+                    }
                 }
-            }
-        }),
+            });
+            impl_value_tokens.extend(quote! {
+                impl #impl_generics #peace_params_path::Params
+                for #value_name #ty_generics
+                #where_clause
+                {
+                    type Spec = #peace_params_path::ParamsSpec<#value_name #ty_generics>;
+                    type Partial = #t_partial_name #ty_generics;
+                    type FieldWiseSpec = #t_field_wise_name #ty_generics;
+                    type FieldWiseBuilder = #t_field_wise_builder_name #builder_generics;
+
+                    fn field_wise_spec() -> Self::FieldWiseBuilder {
+                        Self::FieldWiseBuilder::default()
+                    }
+
+                    #params_validate_fn
+
+                    #params_field_names_secret_fn
+
+                    #params_spec_schema_fn
+                }
+            })
+        }
         ImplMode::Fieldless => {}
     }
 
@@ -464,6 +522,8 @@ fn t_partial_external(
 ///     dest_path: peace_params::ParamsSpecFieldless<PathBuf>,
 /// }
 /// ```
+// TODO: Refactor this crate to not pass redundant information, or use a context object.
+#[allow(clippy::too_many_arguments)]
 fn t_field_wise(
     ast: &DeriveInput,
     generics_split: &(ImplGenerics, TypeGenerics, Option<&WhereClause>),
@@ -472,20 +532,25 @@ fn t_field_wise(
     params_name: &Ident,
     t_field_wise_name: &Ident,
     t_partial_name: &Ident,
+    is_schema: bool,
 ) -> proc_macro2::TokenStream {
+    let mut attrs: Vec<Attribute> = vec![
+        parse_quote! {
+            #[doc="Specification of how to look up values for an item's parameters."]
+        },
+        // `Clone` and `Debug` are implemented manually, so that type parameters do not receive
+        // the `Clone` and `Debug` bounds.
+        parse_quote!(#[derive(serde::Serialize, serde::Deserialize)]),
+    ];
+    if is_schema {
+        attrs.extend(schema_derive_attrs(peace_params_path));
+    }
     let mut t_field_wise = TypeGen::gen_from_value_type(
         ast,
         generics_split,
         t_field_wise_name,
         |fields| fields_to_value_spec(fields, peace_params_path),
-        &[
-            parse_quote! {
-                #[doc="Specification of how to look up values for an item's parameters."]
-            },
-            // `Clone` and `Debug` are implemented manually, so that type parameters do not receive
-            // the `Clone` and `Debug` bounds.
-            parse_quote!(#[derive(serde::Serialize, serde::Deserialize)]),
-        ],
+        &attrs,
         true,
     );
 
@@ -521,6 +586,7 @@ fn t_field_wise(
         generics_split,
         peace_params_path,
         t_field_wise_name,
+        false,
     ));
 
     t_field_wise
@@ -542,19 +608,19 @@ fn t_field_wise_external(
     params_name: &Ident,
     t_field_wise_name: &Ident,
     t_partial_name: &Ident,
+    is_schema: bool,
 ) -> proc_macro2::TokenStream {
-    let mut t_field_wise = type_gen_external(
-        ast,
-        generics_split,
-        params_ty,
-        t_field_wise_name,
-        &[
-            parse_quote! {
-                #[doc="Specification of how to look up values for an item's parameters."]
-            },
-            parse_quote!(#[derive(serde::Serialize, serde::Deserialize)]),
-        ],
-    );
+    let mut attrs: Vec<Attribute> = vec![
+        parse_quote! {
+            #[doc="Specification of how to look up values for an item's parameters."]
+        },
+        parse_quote!(#[derive(serde::Serialize, serde::Deserialize)]),
+    ];
+    if is_schema {
+        attrs.extend(schema_derive_attrs(peace_params_path));
+    }
+    let mut t_field_wise =
+        type_gen_external(ast, generics_split, params_ty, t_field_wise_name, &attrs);
 
     t_field_wise.extend(impl_field_wise_spec_rt_for_field_wise_external(
         generics_split,
@@ -570,6 +636,7 @@ fn t_field_wise_external(
         generics_split,
         peace_params_path,
         t_field_wise_name,
+        true,
     ));
 
     t_field_wise
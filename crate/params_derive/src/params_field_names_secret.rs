@@ -0,0 +1,79 @@
+use std::collections::BTreeSet;
+
+use syn::{Attribute, DeriveInput, Fields};
+
+/// Generates `fn field_names_secret` for the `Params` impl, if any field has a
+/// `#[params(secret)]` attribute.
+///
+/// For enums, the field names of all variants are returned.
+///
+/// Generates something like the following:
+///
+/// ```rust,ignore
+/// fn field_names_secret() -> Vec<&'static str> {
+///     vec!["password", "token"]
+/// }
+/// ```
+pub fn params_field_names_secret_fn(ast: &DeriveInput) -> Option<proc_macro2::TokenStream> {
+    let field_names_secret = match &ast.data {
+        syn::Data::Struct(data_struct) => field_names_secret(&data_struct.fields),
+        syn::Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| field_names_secret(&variant.fields))
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect(),
+        syn::Data::Union(data_union) => {
+            field_names_secret(&Fields::from(data_union.fields.clone()))
+        }
+    };
+
+    if field_names_secret.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        fn field_names_secret() -> Vec<&'static str> {
+            vec![#(#field_names_secret),*]
+        }
+    })
+}
+
+/// Returns the names of fields with the `#[params(secret)]` attribute.
+///
+/// Tuple fields are named by their index.
+fn field_names_secret(fields: &Fields) -> Vec<String> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_field_index, field)| is_tagged_secret(&field.attrs))
+        .map(|(field_index, field)| match field.ident.as_ref() {
+            Some(field_ident) => {
+                let field_ident = field_ident.to_string();
+                field_ident
+                    .strip_prefix("r#")
+                    .map(str::to_string)
+                    .unwrap_or(field_ident)
+            }
+            None => field_index.to_string(),
+        })
+        .collect()
+}
+
+/// Returns whether any of the attributes contains `#[params(secret)]`.
+fn is_tagged_secret(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if attr.path().is_ident("params") {
+            let mut is_secret = false;
+            let _ = attr.parse_nested_meta(|parse_nested_meta| {
+                is_secret |= parse_nested_meta.path.is_ident("secret");
+                Ok(())
+            });
+
+            is_secret
+        } else {
+            false
+        }
+    })
+}
@@ -0,0 +1,384 @@
+use syn::{
+    punctuated::Punctuated, Attribute, DeriveInput, Expr, Field, Fields, Ident, LitStr, Path,
+    Variant,
+};
+
+use crate::util::{is_phantom_data, tuple_ident_from_field_index, tuple_index_from_field_index};
+
+/// A check declared through a `#[validate(..)]` field attribute.
+enum FieldValidation {
+    /// `#[validate(non_empty)]`
+    NonEmpty,
+    /// `#[validate(range(min = 1, max = 10))]`
+    Range {
+        min: Option<Box<Expr>>,
+        max: Option<Box<Expr>>,
+    },
+    /// `#[validate(regex = "^[a-z]+$")]`
+    Regex(LitStr),
+    /// `#[validate(custom = path::to::fn)]`
+    Custom(Path),
+}
+
+/// Whether the validation is generated for the params or the params partial.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ValidateTarget {
+    /// `fn validate(&self, ..)`, where fields are the values.
+    Params,
+    /// `fn validate_partial(params_partial, ..)`, where fields are `Option`s.
+    ParamsPartial,
+}
+
+/// Generates `fn validate` and `fn validate_partial` for the `Params` impl, if
+/// any field has a `#[validate(..)]` attribute.
+///
+/// Generates something like the following:
+///
+/// ```rust,ignore
+/// fn validate(
+///     &self,
+///     value_resolution_ctx: &mut peace_params::ValueResolutionCtx,
+/// ) -> Result<(), peace_params::ParamsResolveError> {
+///     let Self { name, port, .. } = self;
+///
+///     value_resolution_ctx.push(peace_params::FieldNameAndType::new(
+///         String::from(stringify!(name)),
+///         String::from(peace_params::tynm::type_name::<String>()),
+///     ));
+///     peace_params::ParamsValidators::non_empty(name).map_err(|reason| {
+///         peace_params::ParamsResolveError::Validate {
+///             value_resolution_ctx: value_resolution_ctx.clone(),
+///             reason,
+///         }
+///     })?;
+///     value_resolution_ctx.pop();
+///
+///     // ..
+///
+///     Ok(())
+/// }
+///
+/// fn validate_partial(
+///     params_partial: &Self::Partial,
+///     value_resolution_ctx: &mut peace_params::ValueResolutionCtx,
+/// ) -> Result<(), peace_params::ParamsResolveError> {
+///     let MyParamsPartial { name, port, .. } = params_partial;
+///
+///     if let Some(name) = name {
+///         // same as above
+///     }
+///
+///     // ..
+///
+///     Ok(())
+/// }
+/// ```
+pub fn params_validate_fn(
+    ast: &DeriveInput,
+    peace_params_path: &Path,
+    t_partial_name: &Ident,
+) -> Option<proc_macro2::TokenStream> {
+    let validate_body = fields_validate_body(
+        ast,
+        peace_params_path,
+        &quote!(Self),
+        ValidateTarget::Params,
+    )?;
+    let validate_partial_body = fields_validate_body(
+        ast,
+        peace_params_path,
+        &quote!(#t_partial_name),
+        ValidateTarget::ParamsPartial,
+    )?;
+
+    Some(quote! {
+        fn validate(
+            &self,
+            value_resolution_ctx: &mut #peace_params_path::ValueResolutionCtx,
+        ) -> Result<(), #peace_params_path::ParamsResolveError> {
+            #validate_body
+
+            Ok(())
+        }
+
+        fn validate_partial(
+            params_partial: &Self::Partial,
+            value_resolution_ctx: &mut #peace_params_path::ValueResolutionCtx,
+        ) -> Result<(), #peace_params_path::ParamsResolveError> {
+            #validate_partial_body
+
+            Ok(())
+        }
+    })
+}
+
+/// Returns the statements to validate the fields, or `None` if no field has a
+/// `#[validate(..)]` attribute.
+fn fields_validate_body(
+    ast: &DeriveInput,
+    peace_params_path: &Path,
+    type_path: &proc_macro2::TokenStream,
+    validate_target: ValidateTarget,
+) -> Option<proc_macro2::TokenStream> {
+    let validate_body = match &ast.data {
+        syn::Data::Struct(data_struct) => struct_fields_validate(
+            peace_params_path,
+            type_path,
+            validate_target,
+            &data_struct.fields,
+        ),
+        syn::Data::Enum(data_enum) => variants_validate(
+            peace_params_path,
+            type_path,
+            validate_target,
+            &data_enum.variants,
+        ),
+        syn::Data::Union(_) => Ok(None),
+    };
+
+    match validate_body {
+        Ok(validate_body) => validate_body,
+        Err(error) => Some(error.to_compile_error()),
+    }
+}
+
+fn struct_fields_validate(
+    peace_params_path: &Path,
+    type_path: &proc_macro2::TokenStream,
+    validate_target: ValidateTarget,
+    fields: &Fields,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let Some((fields_deconstructed, fields_validate)) =
+        fields_validate(peace_params_path, validate_target, fields)?
+    else {
+        return Ok(None);
+    };
+
+    let subject = subject(validate_target);
+    let tokens = match fields {
+        Fields::Named(_) => quote! {
+            let #type_path { #(#fields_deconstructed,)* .. } = #subject;
+            #fields_validate
+        },
+        Fields::Unnamed(_) => quote! {
+            let #type_path(#(#fields_deconstructed),*) = #subject;
+            #fields_validate
+        },
+        Fields::Unit => return Ok(None),
+    };
+
+    Ok(Some(tokens))
+}
+
+fn variants_validate(
+    peace_params_path: &Path,
+    type_path: &proc_macro2::TokenStream,
+    validate_target: ValidateTarget,
+    variants: &Punctuated<Variant, Token![,]>,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let mut any_validations = false;
+    let mut variant_arms = proc_macro2::TokenStream::new();
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let fields_validated =
+            fields_validate(peace_params_path, validate_target, &variant.fields)?;
+        let arm = match (&variant.fields, fields_validated) {
+            (Fields::Named(_), Some((fields_deconstructed, fields_validate))) => {
+                any_validations = true;
+                quote! {
+                    #type_path::#variant_name { #(#fields_deconstructed,)* .. } => {
+                        #fields_validate
+                    }
+                }
+            }
+            (Fields::Unnamed(_), Some((fields_deconstructed, fields_validate))) => {
+                any_validations = true;
+                quote! {
+                    #type_path::#variant_name(#(#fields_deconstructed),*) => {
+                        #fields_validate
+                    }
+                }
+            }
+            (Fields::Named(_), None) => quote!(#type_path::#variant_name { .. } => {}),
+            (Fields::Unnamed(_), None) => quote!(#type_path::#variant_name(..) => {}),
+            (Fields::Unit, _) => quote!(#type_path::#variant_name => {}),
+        };
+        variant_arms.extend(arm);
+    }
+
+    if any_validations {
+        let subject = subject(validate_target);
+        Ok(Some(quote! {
+            match #subject {
+                #variant_arms
+            }
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+fn subject(validate_target: ValidateTarget) -> proc_macro2::TokenStream {
+    match validate_target {
+        ValidateTarget::Params => quote!(self),
+        ValidateTarget::ParamsPartial => quote!(params_partial),
+    }
+}
+
+/// Returns the deconstructed fields and the validation statements for the
+/// fields, or `None` if no field has a `#[validate(..)]` attribute.
+///
+/// Named fields without validations are omitted from the deconstruction, and
+/// tuple fields without validations are deconstructed as `_`.
+#[allow(clippy::type_complexity)]
+fn fields_validate(
+    peace_params_path: &Path,
+    validate_target: ValidateTarget,
+    fields: &Fields,
+) -> syn::Result<Option<(Vec<proc_macro2::TokenStream>, proc_macro2::TokenStream)>> {
+    let mut fields_deconstructed = Vec::new();
+    let mut fields_validate = proc_macro2::TokenStream::new();
+
+    for (field_index, field) in fields.iter().enumerate() {
+        let field_validations = field_validations(field)?;
+        if field_validations.is_empty() || is_phantom_data(&field.ty) {
+            if field.ident.is_none() {
+                fields_deconstructed.push(quote!(_));
+            }
+            continue;
+        }
+
+        let (field_var, field_name) = match field.ident.as_ref() {
+            Some(field_ident) => (field_ident.clone(), quote!(#field_ident)),
+            None => {
+                let field_index_lit = tuple_index_from_field_index(field_index);
+                (
+                    tuple_ident_from_field_index(field_index),
+                    quote!(#field_index_lit),
+                )
+            }
+        };
+        fields_deconstructed.push(quote!(#field_var));
+
+        let field_ty = &field.ty;
+        let checks = field_validations.iter().map(|field_validation| {
+            let check = match field_validation {
+                FieldValidation::NonEmpty => {
+                    quote!(#peace_params_path::ParamsValidators::non_empty(#field_var))
+                }
+                FieldValidation::Range { min, max } => {
+                    let min = bound_tokens(min.as_deref());
+                    let max = bound_tokens(max.as_deref());
+                    quote!(#peace_params_path::ParamsValidators::range(#field_var, #min, #max))
+                }
+                FieldValidation::Regex(pattern) => quote! {
+                    {
+                        // The pattern is checked when the macro is expanded, and compiled
+                        // once on first use.
+                        static REGEX: ::std::sync::OnceLock<#peace_params_path::regex::Regex> =
+                            ::std::sync::OnceLock::new();
+                        let regex = REGEX.get_or_init(|| {
+                            #peace_params_path::regex::Regex::new(#pattern)
+                                .expect("Expected `regex` pattern to be checked by the derive.")
+                        });
+                        #peace_params_path::ParamsValidators::regex(#field_var, regex)
+                    }
+                },
+                FieldValidation::Custom(custom_fn) => quote!(#custom_fn(#field_var)),
+            };
+
+            quote! {
+                #check.map_err(|reason| #peace_params_path::ParamsResolveError::Validate {
+                    value_resolution_ctx: value_resolution_ctx.clone(),
+                    reason,
+                })?;
+            }
+        });
+
+        let field_validate = quote! {
+            value_resolution_ctx.push(#peace_params_path::FieldNameAndType::new(
+                String::from(stringify!(#field_name)),
+                String::from(#peace_params_path::tynm::type_name::<#field_ty>())),
+            );
+            #(#checks)*
+            value_resolution_ctx.pop();
+        };
+        match validate_target {
+            ValidateTarget::Params => fields_validate.extend(field_validate),
+            // Fields that are not yet resolved are validated when they are resolved.
+            ValidateTarget::ParamsPartial => fields_validate.extend(quote! {
+                if let Some(#field_var) = #field_var {
+                    #field_validate
+                }
+            }),
+        }
+    }
+
+    if fields_validate.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some((fields_deconstructed, fields_validate)))
+    }
+}
+
+fn bound_tokens(bound: Option<&Expr>) -> proc_macro2::TokenStream {
+    match bound {
+        Some(bound) => quote!(Some(#bound)),
+        None => quote!(None),
+    }
+}
+
+/// Parses the `#[validate(..)]` attributes on a field.
+fn field_validations(field: &Field) -> syn::Result<Vec<FieldValidation>> {
+    let mut field_validations = Vec::new();
+    field
+        .attrs
+        .iter()
+        .filter(|attr| is_validate_attr(attr))
+        .try_for_each(|attr| {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("non_empty") {
+                    field_validations.push(FieldValidation::NonEmpty);
+                    Ok(())
+                } else if meta.path.is_ident("range") {
+                    let mut min = None;
+                    let mut max = None;
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("min") {
+                            min = Some(Box::new(meta.value()?.parse::<Expr>()?));
+                            Ok(())
+                        } else if meta.path.is_ident("max") {
+                            max = Some(Box::new(meta.value()?.parse::<Expr>()?));
+                            Ok(())
+                        } else {
+                            Err(meta.error("expected `min` or `max`"))
+                        }
+                    })?;
+                    field_validations.push(FieldValidation::Range { min, max });
+                    Ok(())
+                } else if meta.path.is_ident("regex") {
+                    let pattern = meta.value()?.parse::<LitStr>()?;
+                    if let Err(error) = regex::Regex::new(&pattern.value()) {
+                        return Err(syn::Error::new(
+                            pattern.span(),
+                            format!("`regex` pattern is invalid: {error}"),
+                        ));
+                    }
+                    field_validations.push(FieldValidation::Regex(pattern));
+                    Ok(())
+                } else if meta.path.is_ident("custom") {
+                    let custom_fn = meta.value()?.parse::<Path>()?;
+                    field_validations.push(FieldValidation::Custom(custom_fn));
+                    Ok(())
+                } else {
+                    Err(meta.error("expected one of `non_empty`, `range`, `regex`, or `custom`"))
+                }
+            })
+        })?;
+
+    Ok(field_validations)
+}
+
+fn is_validate_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("validate")
+}
@@ -0,0 +1,273 @@
+use syn::{punctuated::Punctuated, DeriveInput, Fields, Ident, LitInt, LitStr, Path, Variant};
+
+use crate::util::{
+    fields_deconstruct, fields_stmt_map, is_phantom_data, tuple_index_from_field_index,
+    variant_match_arm,
+};
+
+/// Returns the `fn field_names` body, which returns the names of the fields
+/// whose values may be set.
+pub fn field_names_body(
+    ast: &DeriveInput,
+    params_field_wise_name: &Ident,
+) -> proc_macro2::TokenStream {
+    match &ast.data {
+        syn::Data::Struct(data_struct) => {
+            let field_names = field_names(&data_struct.fields);
+
+            quote!(vec![#(String::from(#field_names)),*])
+        }
+        syn::Data::Enum(data_enum) => {
+            // Generates:
+            //
+            // ```rust
+            // match self {
+            //     ValueSpec::Variant1 => vec![],
+            //     ValueSpec::Variant2(..) => vec![String::from("0"), String::from("1")],
+            //     ValueSpec::Variant3 { .. } => vec![String::from("field_1")],
+            // }
+            // ```
+            let variant_field_names_arms = data_enum.variants.iter().fold(
+                proc_macro2::TokenStream::new(),
+                |mut tokens, variant| {
+                    let variant_name = &variant.ident;
+                    let field_names = field_names(&variant.fields);
+                    let variant_pattern = match &variant.fields {
+                        Fields::Named(_) => quote!(#params_field_wise_name::#variant_name { .. }),
+                        Fields::Unnamed(_) => quote!(#params_field_wise_name::#variant_name(..)),
+                        Fields::Unit => quote!(#params_field_wise_name::#variant_name),
+                    };
+
+                    tokens.extend(quote! {
+                        #variant_pattern => vec![#(String::from(#field_names)),*],
+                    });
+
+                    tokens
+                },
+            );
+
+            quote! {
+                match self {
+                    #variant_field_names_arms
+                }
+            }
+        }
+        syn::Data::Union(data_union) => {
+            let fields = Fields::from(data_union.fields.clone());
+            let field_names = field_names(&fields);
+
+            quote!(vec![#(String::from(#field_names)),*])
+        }
+    }
+}
+
+/// Returns the `fn field_value_set` body, which sets the value of the field
+/// with the given name.
+pub fn field_value_set_body(
+    ast: &DeriveInput,
+    params_field_wise_name: &Ident,
+    peace_params_path: &Path,
+) -> proc_macro2::TokenStream {
+    match &ast.data {
+        syn::Data::Struct(data_struct) => {
+            let fields = &data_struct.fields;
+
+            struct_fields_value_set(params_field_wise_name, fields, peace_params_path)
+        }
+        syn::Data::Enum(data_enum) => {
+            let variants = &data_enum.variants;
+
+            variants_field_value_set(params_field_wise_name, variants, peace_params_path)
+        }
+        syn::Data::Union(data_union) => {
+            let fields = Fields::from(data_union.fields.clone());
+
+            struct_fields_value_set(params_field_wise_name, &fields, peace_params_path)
+        }
+    }
+}
+
+/// Returns the statements to set the value of a field within this struct.
+pub fn struct_fields_value_set(
+    params_field_wise_name: &Ident,
+    fields: &Fields,
+    peace_params_path: &Path,
+) -> proc_macro2::TokenStream {
+    let fields_value_set_match = fields_value_set_match(fields, peace_params_path);
+    let fields_deconstructed = fields_deconstruct(fields);
+
+    match fields {
+        Fields::Named(_fields_named) => {
+            // Generates:
+            //
+            // ```rust
+            // let #params_field_wise_name {
+            //     field_1,
+            //     field_2,
+            //     marker: PhantomData,
+            // } = self;
+            //
+            // match field_name {
+            //     "field_1" => ValueSpec::value_set_from_str(field_1, field_name, value),
+            //     "field_2" => ValueSpec::value_set_from_str(field_2, field_name, value),
+            //     _ => Err(FieldValueSetError::FieldNotFound { .. }),
+            // }
+            // ```
+
+            quote! {
+                let #params_field_wise_name {
+                    #(#fields_deconstructed),*
+                } = self;
+
+                #fields_value_set_match
+            }
+        }
+        Fields::Unnamed(_fields_unnamed) => {
+            // Generates:
+            //
+            // ```rust
+            // let #params_name(_0, _1, PhantomData,) = self;
+            //
+            // match field_name {
+            //     "0" => ValueSpec::value_set_from_str(_0, field_name, value),
+            //     "1" => ValueSpec::value_set_from_str(_1, field_name, value),
+            //     _ => Err(FieldValueSetError::FieldNotFound { .. }),
+            // }
+            // ```
+
+            quote! {
+                let #params_field_wise_name(#(#fields_deconstructed),*) = self;
+
+                #fields_value_set_match
+            }
+        }
+        Fields::Unit => fields_value_set_match,
+    }
+}
+
+/// Returns the statements to set the value of a field within the current
+/// enum variant.
+pub fn variants_field_value_set(
+    params_field_wise_name: &Ident,
+    variants: &Punctuated<Variant, Token![,]>,
+    peace_params_path: &Path,
+) -> proc_macro2::TokenStream {
+    // Generates:
+    //
+    // ```rust
+    // match self {
+    //     ValueSpec::Variant1 => Err(FieldValueSetError::FieldNotFound { .. }),
+    //     ValueSpec::Variant2(_0, _1, PhantomData) => match field_name {
+    //         "0" => ValueSpec::value_set_from_str(_0, field_name, value),
+    //         "1" => ValueSpec::value_set_from_str(_1, field_name, value),
+    //         _ => Err(FieldValueSetError::FieldNotFound { .. }),
+    //     }
+    //     ValueSpec::Variant3 {
+    //         field_1,
+    //         marker: PhantomData,
+    //     } => match field_name {
+    //         "field_1" => ValueSpec::value_set_from_str(field_1, field_name, value),
+    //         _ => Err(FieldValueSetError::FieldNotFound { .. }),
+    //     }
+    // }
+    // ```
+
+    let variant_field_value_set_arms =
+        variants
+            .iter()
+            .fold(proc_macro2::TokenStream::new(), |mut tokens, variant| {
+                let fields = &variant.fields;
+                let fields_deconstructed = fields_deconstruct(fields);
+                let fields_value_set_match = fields_value_set_match(fields, peace_params_path);
+
+                tokens.extend(variant_match_arm(
+                    params_field_wise_name,
+                    variant,
+                    &fields_deconstructed,
+                    fields_value_set_match,
+                ));
+
+                tokens
+            });
+
+    quote! {
+        match self {
+            #variant_field_value_set_arms
+        }
+    }
+}
+
+/// Returns the `match field_name { .. }` expression that sets the value of
+/// the matching field.
+///
+/// When there are no fields, `value` is explicitly ignored so that the
+/// generated code does not trigger an `unused_variables` warning.
+fn fields_value_set_match(fields: &Fields, peace_params_path: &Path) -> proc_macro2::TokenStream {
+    let field_names = field_names(fields);
+    let field_value_set_arms = fields_stmt_map(fields, move |field, field_var, field_index| {
+        let field_name = field_name_lit(field.ident.as_ref(), &field_index);
+        quote! {
+            #field_name => #peace_params_path::ValueSpec::value_set_from_str(
+                #field_var,
+                field_name,
+                value,
+            ),
+        }
+    })
+    .fold(
+        proc_macro2::TokenStream::new(),
+        |mut tokens, next_tokens| {
+            tokens.extend(next_tokens);
+            tokens
+        },
+    );
+    let value_ignore = if field_names.is_empty() {
+        quote!(let _ = value;)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    quote! {
+        #value_ignore
+        match field_name {
+            #field_value_set_arms
+            _ => Err(#peace_params_path::FieldValueSetError::FieldNotFound {
+                field_name: field_name.to_string(),
+                field_names: vec![#(String::from(#field_names)),*],
+            }),
+        }
+    }
+}
+
+/// Returns the names of the non-marker fields, as used in params overrides.
+///
+/// Named fields use the field name, and tuple fields use the field index.
+fn field_names(fields: &Fields) -> Vec<LitStr> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_field_index, field)| !is_phantom_data(&field.ty))
+        .map(|(field_index, field)| {
+            field_name_lit(
+                field.ident.as_ref(),
+                &tuple_index_from_field_index(field_index),
+            )
+        })
+        .collect()
+}
+
+/// Returns the field name string literal for a field.
+fn field_name_lit(field_ident: Option<&Ident>, field_index: &LitInt) -> LitStr {
+    let field_name = match field_ident {
+        Some(field_ident) => {
+            let field_ident = field_ident.to_string();
+            field_ident
+                .strip_prefix("r#")
+                .map(str::to_string)
+                .unwrap_or(field_ident)
+        }
+        None => field_index.base10_digits().to_string(),
+    };
+
+    LitStr::new(&field_name, proc_macro2::Span::call_site())
+}
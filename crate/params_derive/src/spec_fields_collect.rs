@@ -0,0 +1,182 @@
+use syn::{punctuated::Punctuated, DeriveInput, Fields, Ident, Path, Variant};
+
+use crate::util::{fields_deconstruct, fields_stmt_map, variant_match_arm};
+
+/// Returns the body of an `AnySpecRt` method that returns a `Vec`, which
+/// collects the values returned by the same method on each field's spec.
+///
+/// This is used for `fn mapping_fn_ids`, `fn field_templates`,
+/// `fn in_memory_type_ids`, `fn flow_state_refs`, and `fn item_output_refs`.
+pub fn fields_collect_body(
+    ast: &DeriveInput,
+    params_field_wise_name: &Ident,
+    peace_params_path: &Path,
+    fn_name: &Ident,
+) -> proc_macro2::TokenStream {
+    match &ast.data {
+        syn::Data::Struct(data_struct) => {
+            let fields = &data_struct.fields;
+
+            struct_fields_collect(params_field_wise_name, fields, peace_params_path, fn_name)
+        }
+        syn::Data::Enum(data_enum) => {
+            let variants = &data_enum.variants;
+
+            variants_collect(params_field_wise_name, variants, peace_params_path, fn_name)
+        }
+        syn::Data::Union(data_union) => {
+            let fields = Fields::from(data_union.fields.clone());
+
+            struct_fields_collect(params_field_wise_name, &fields, peace_params_path, fn_name)
+        }
+    }
+}
+
+/// Returns the values collected from the fields within this struct.
+pub fn struct_fields_collect(
+    params_field_wise_name: &Ident,
+    fields: &Fields,
+    peace_params_path: &Path,
+    fn_name: &Ident,
+) -> proc_macro2::TokenStream {
+    let fields_collect = fields_collect(fields, peace_params_path, fn_name);
+    let fields_deconstructed = fields_deconstruct(fields);
+
+    match fields {
+        Fields::Named(_fields_named) => {
+            // Generates:
+            //
+            // ```rust
+            // let #params_field_wise_name {
+            //     field_1,
+            //     field_2,
+            //     marker: PhantomData,
+            // } = self;
+            //
+            // let mut collected = Vec::new();
+            // collected.extend(AnySpecRt::#fn_name(field_1));
+            // collected.extend(AnySpecRt::#fn_name(field_2));
+            //
+            // collected
+            // ```
+
+            quote! {
+                let #params_field_wise_name {
+                    #(#fields_deconstructed),*
+                } = self;
+
+                let mut collected = Vec::new();
+                #fields_collect
+
+                collected
+            }
+        }
+        Fields::Unnamed(_fields_unnamed) => {
+            // Generates:
+            //
+            // ```rust
+            // let #params_name(_0, _1, PhantomData,) = self;
+            //
+            // let mut collected = Vec::new();
+            // collected.extend(AnySpecRt::#fn_name(_0));
+            // collected.extend(AnySpecRt::#fn_name(_1));
+            //
+            // collected
+            // ```
+
+            quote! {
+                let #params_field_wise_name(#(#fields_deconstructed),*) = self;
+
+                let mut collected = Vec::new();
+                #fields_collect
+
+                collected
+            }
+        }
+        Fields::Unit => quote!(Vec::new()),
+    }
+}
+
+/// Returns the values collected from the fields within each enum variant.
+pub fn variants_collect(
+    params_field_wise_name: &Ident,
+    variants: &Punctuated<Variant, Token![,]>,
+    peace_params_path: &Path,
+    fn_name: &Ident,
+) -> proc_macro2::TokenStream {
+    // Generates:
+    //
+    // ```rust
+    // match self {
+    //     ValueSpec::Variant1 => Vec::new(),
+    //     ValueSpec::Variant2(_0, _1, PhantomData) => {
+    //         let mut collected = Vec::new();
+    //         collected.extend(AnySpecRt::#fn_name(_0));
+    //         collected.extend(AnySpecRt::#fn_name(_1));
+    //         collected
+    //     }
+    //     ValueSpec::Variant3 {
+    //         field_1,
+    //         field_2,
+    //         marker: PhantomData,
+    //     } => {
+    //         let mut collected = Vec::new();
+    //         collected.extend(AnySpecRt::#fn_name(field_1));
+    //         collected.extend(AnySpecRt::#fn_name(field_2));
+    //         collected
+    //     }
+    // }
+    // ```
+
+    let variant_collect_arms =
+        variants
+            .iter()
+            .fold(proc_macro2::TokenStream::new(), |mut tokens, variant| {
+                let fields = &variant.fields;
+                let fields_deconstructed = fields_deconstruct(fields);
+
+                let variant_fields_collect = {
+                    let fields_collect = fields_collect(fields, peace_params_path, fn_name);
+
+                    quote! {
+                        let mut collected = Vec::new();
+                        #fields_collect
+
+                        collected
+                    }
+                };
+                tokens.extend(variant_match_arm(
+                    params_field_wise_name,
+                    variant,
+                    &fields_deconstructed,
+                    variant_fields_collect,
+                ));
+
+                tokens
+            });
+
+    quote! {
+        match self {
+            #variant_collect_arms
+        }
+    }
+}
+
+fn fields_collect(
+    fields: &Fields,
+    peace_params_path: &Path,
+    fn_name: &Ident,
+) -> proc_macro2::TokenStream {
+    fields_stmt_map(fields, move |_field, field_name, _field_index| {
+        quote! {
+            collected.extend(#peace_params_path::AnySpecRt::#fn_name(#field_name));
+        }
+    })
+    .fold(
+        proc_macro2::TokenStream::new(),
+        |mut tokens, next_tokens| {
+            tokens.extend(next_tokens);
+            tokens
+        },
+    )
+}
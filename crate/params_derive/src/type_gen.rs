@@ -201,6 +201,7 @@ impl TypeGen {
 
             Some(quote! {
                 #[doc = #constructor_doc]
+                #[allow(clippy::too_many_arguments)] // Code gen, one parameter per field.
                 pub fn new(#(#fields_as_params),*) -> Self {
                     #constructor
                 }
@@ -74,6 +74,38 @@ pub fn is_tagged_fieldless(attrs: &[Attribute]) -> bool {
     })
 }
 
+/// Returns whether the type is annotated with `#[params(schema)]`, which means
+/// a JSON Schema should be generated for its `ParamsSpec`.
+pub fn is_tagged_schema(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if attr.path().is_ident("params") {
+            let mut is_schema = false;
+            let _ = attr.parse_nested_meta(|parse_nested_meta| {
+                is_schema |= parse_nested_meta.path.is_ident("schema");
+                Ok(())
+            });
+
+            is_schema
+        } else {
+            false
+        }
+    })
+}
+
+/// Returns the attributes to derive `JsonSchema` for a generated type.
+///
+/// The `schemars` crate is referenced through the `peace_params` re-export, so
+/// that users do not need to depend on `schemars` directly.
+pub fn schema_derive_attrs(peace_params_path: &Path) -> Vec<Attribute> {
+    let schemars_crate = quote!(#peace_params_path::schemars)
+        .to_string()
+        .replace(' ', "");
+    vec![
+        parse_quote!(#[derive(#peace_params_path::schemars::JsonSchema)]),
+        parse_quote!(#[schemars(crate = #schemars_crate)]),
+    ]
+}
+
 /// Returns whether the attribute is a `#[serde(bound = "..")]` attribute.
 pub fn is_serde_bound_attr(attr: &Attribute) -> bool {
     if attr.path().is_ident("serde") {
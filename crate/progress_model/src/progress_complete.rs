@@ -21,6 +21,10 @@ pub enum ProgressComplete {
     Success,
     /// Execution did not complete.
     Fail,
+    /// Execution stopped early because the command was interrupted.
+    ///
+    /// The item may have made partial progress towards its target state.
+    Interrupted,
 }
 
 impl ProgressComplete {
@@ -28,14 +32,14 @@ impl ProgressComplete {
     pub fn is_successful(&self) -> bool {
         match self {
             Self::Success => true,
-            Self::Fail => false,
+            Self::Fail | Self::Interrupted => false,
         }
     }
 
     /// Returns whether this is a failure outcome.
     pub fn is_failure(&self) -> bool {
         match self {
-            Self::Success => false,
+            Self::Success | Self::Interrupted => false,
             Self::Fail => true,
         }
     }
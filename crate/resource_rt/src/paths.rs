@@ -70,13 +70,17 @@
 //! ```
 
 pub use self::{
-    flow_dir::FlowDir, params_specs_file::ParamsSpecsFile, peace_app_dir::PeaceAppDir,
-    peace_dir::PeaceDir, profile_dir::ProfileDir, profile_history_dir::ProfileHistoryDir,
-    states_current_file::StatesCurrentFile, states_goal_file::StatesGoalFile,
-    workspace_dir::WorkspaceDir,
+    cmd_checkpoint_file::CmdCheckpointFile, flow_dir::FlowDir, outputs_file::OutputsFile,
+    params_applied_file::ParamsAppliedFile, params_specs_file::ParamsSpecsFile,
+    peace_app_dir::PeaceAppDir, peace_dir::PeaceDir, profile_dir::ProfileDir,
+    profile_history_dir::ProfileHistoryDir, states_current_file::StatesCurrentFile,
+    states_goal_file::StatesGoalFile, workspace_dir::WorkspaceDir,
 };
 
+mod cmd_checkpoint_file;
 mod flow_dir;
+mod outputs_file;
+mod params_applied_file;
 mod params_specs_file;
 mod peace_app_dir;
 mod peace_dir;
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use crate::paths::FlowDir;
+
+/// Path to the file that stores the progress of an interrupted command
+/// execution.
+///
+/// Typically `$workspace_dir/.peace/$profile/$flow_id/cmd_checkpoint.yaml`.
+///
+/// See `CmdCheckpointFile::from<&FlowDir>` if you want to construct a
+/// `CmdCheckpointFile` with the conventional `$flow_dir/cmd_checkpoint.yaml`
+/// path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CmdCheckpointFile(PathBuf);
+
+crate::paths::pathbuf_newtype!(CmdCheckpointFile);
+
+impl CmdCheckpointFile {
+    /// File name of the command checkpoint file.
+    pub const NAME: &'static str = "cmd_checkpoint.yaml";
+}
+
+impl From<&FlowDir> for CmdCheckpointFile {
+    fn from(flow_dir: &FlowDir) -> Self {
+        let path = flow_dir.join(Self::NAME);
+
+        Self(path)
+    }
+}
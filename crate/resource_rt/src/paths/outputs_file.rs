@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use crate::paths::FlowDir;
+
+/// Path to the file that stores the outputs each item published when it was
+/// last applied.
+///
+/// Typically `$workspace_dir/.peace/$profile/$flow_id/outputs.yaml`.
+///
+/// See `OutputsFile::from<&FlowDir>` if you want to construct an
+/// `OutputsFile` with the conventional `$flow_dir/outputs.yaml` path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputsFile(PathBuf);
+
+crate::paths::pathbuf_newtype!(OutputsFile);
+
+impl OutputsFile {
+    /// File name of the outputs file.
+    pub const NAME: &'static str = "outputs.yaml";
+}
+
+impl From<&FlowDir> for OutputsFile {
+    fn from(flow_dir: &FlowDir) -> Self {
+        let path = flow_dir.join(Self::NAME);
+
+        Self(path)
+    }
+}
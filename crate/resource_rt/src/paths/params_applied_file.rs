@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use crate::paths::FlowDir;
+
+/// Path to the file that stores the params each item was last applied with.
+///
+/// Typically `$workspace_dir/.peace/$profile/$flow_id/params_applied.yaml`.
+///
+/// See `ParamsAppliedFile::from<&FlowDir>` if you want to construct a
+/// `ParamsAppliedFile` with the conventional `$flow_dir/params_applied.yaml`
+/// path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamsAppliedFile(PathBuf);
+
+crate::paths::pathbuf_newtype!(ParamsAppliedFile);
+
+impl ParamsAppliedFile {
+    /// File name of the params applied file.
+    pub const NAME: &'static str = "params_applied.yaml";
+}
+
+impl From<&FlowDir> for ParamsAppliedFile {
+    fn from(flow_dir: &FlowDir) -> Self {
+        let path = flow_dir.join(Self::NAME);
+
+        Self(path)
+    }
+}
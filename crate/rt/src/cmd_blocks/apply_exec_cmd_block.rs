@@ -2,14 +2,15 @@ use std::{fmt::Debug, marker::PhantomData};
 
 use fn_graph::{StreamOpts, StreamOutcome};
 use futures::join;
-use peace_cfg::{ApplyCheck, FnCtx};
+use peace_cfg::{ApplyCheck, FnCtx, InterruptToken};
 use peace_cmd_ctx::{CmdCtxSpsfFields, CmdCtxTypes};
 use peace_cmd_model::CmdBlockOutcome;
 use peace_cmd_rt::{async_trait, CmdBlock};
 use peace_item_model::ItemId;
-use peace_params::{MappingFnReg, ParamsSpecs};
+use peace_params::{MappingFnReg, ParamsSpecs, ValueResolutionMode};
 use peace_resource_rt::{
     internal::StatesMut,
+    paths::{CmdCheckpointFile, FlowDir},
     resources::ts::SetUp,
     states::{
         ts::{Clean, Cleaned, CleanedDry, Ensured, EnsuredDry, Goal},
@@ -19,7 +20,8 @@ use peace_resource_rt::{
 };
 use peace_rt_model::{
     outcomes::{ItemApplyBoxed, ItemApplyPartialBoxed},
-    ItemBoxed, ItemRt,
+    CmdCheckpoint, CmdCheckpointSerializer, ItemBoxed, ItemIdsInterrupted, ItemIdsResumed, ItemRt,
+    Storage,
 };
 use tokio::sync::mpsc::{self, Receiver};
 
@@ -41,6 +43,7 @@ cfg_if::cfg_if! {
             ProgressUpdateAndId,
             ProgressSender,
         };
+        use peace_rt_model::{ItemIdsDisabled, ItemIdsOrphaned};
     }
 }
 
@@ -167,11 +170,19 @@ where
             ItemRt::apply_exec
         };
 
+        let interrupt_token = resources
+            .try_borrow::<InterruptToken>()
+            .map(|interrupt_token| InterruptToken::clone(&interrupt_token))
+            .ok();
         let fn_ctx = FnCtx::new(
             item_id,
             #[cfg(feature = "output_progress")]
             ProgressSender::new(item_id, progress_tx),
         );
+        let fn_ctx = match interrupt_token.as_ref() {
+            Some(interrupt_token) => fn_ctx.with_interrupt_token(interrupt_token),
+            None => fn_ctx,
+        };
         let item_apply = match apply_for_internal {
             ApplyForInternal::Ensure => {
                 ItemRt::ensure_prepare(&**item, params_specs, mapping_fn_reg, resources, fn_ctx)
@@ -208,16 +219,41 @@ where
                     }
                     ApplyCheck::ExecNotRequired => {
                         #[cfg(feature = "output_progress")]
-                        let _progress_send_unused = progress_tx.try_send(
-                            ProgressUpdateAndId {
-                                item_id: item_id.clone(),
-                                progress_update: ProgressUpdate::Complete(
-                                    ProgressComplete::Success,
-                                ),
-                                msg_update: ProgressMsgUpdate::Set(String::from("nothing to do!")),
-                            }
-                            .into(),
-                        );
+                        {
+                            // Disabled, retired, and resumed items are skipped, so they
+                            // never need execution.
+                            let is_disabled = resources
+                                .try_borrow::<ItemIdsDisabled>()
+                                .map(|item_ids_disabled| item_ids_disabled.is_skipped(item_id))
+                                .unwrap_or(false);
+                            let is_retired = resources
+                                .try_borrow::<ItemIdsOrphaned>()
+                                .map(|item_ids_orphaned| item_ids_orphaned.is_skipped(item_id))
+                                .unwrap_or(false);
+                            let is_resumed = resources
+                                .try_borrow::<ItemIdsResumed>()
+                                .map(|item_ids_resumed| item_ids_resumed.contains(item_id))
+                                .unwrap_or(false);
+                            let msg = if is_disabled {
+                                "disabled"
+                            } else if is_retired {
+                                "retired"
+                            } else if is_resumed {
+                                "resumed"
+                            } else {
+                                "nothing to do!"
+                            };
+                            let _progress_send_unused = progress_tx.try_send(
+                                ProgressUpdateAndId {
+                                    item_id: item_id.clone(),
+                                    progress_update: ProgressUpdate::Complete(
+                                        ProgressComplete::Success,
+                                    ),
+                                    msg_update: ProgressMsgUpdate::Set(String::from(msg)),
+                                }
+                                .into(),
+                            );
+                        }
 
                         // TODO: write test for this case
                         // In case of an interrupt or power failure, we may not have written states
@@ -245,27 +281,56 @@ where
                 .await
                 {
                     Ok(()) => {
-                        // apply succeeded
-
-                        #[cfg(feature = "output_progress")]
-                        let _progress_send_unused = progress_tx.try_send(
-                            ProgressUpdateAndId {
-                                item_id: item_id.clone(),
-                                progress_update: ProgressUpdate::Complete(
-                                    ProgressComplete::Success,
-                                ),
-                                msg_update: ProgressMsgUpdate::Set(String::from("done!")),
-                            }
-                            .into(),
-                        );
-
-                        outcomes_tx
-                            .send(ItemApplyOutcome::Success {
-                                item_id: item.id().clone(),
-                                item_apply,
-                            })
-                            .await
-                            .expect("unreachable: `outcomes_rx` is in a sibling task.");
+                        // apply succeeded, but the item may have stopped early if the execution
+                        // was interrupted.
+                        let interrupted = fn_ctx.is_interrupted()
+                            && item_apply.state_applied().is_some_and(|state_applied| {
+                                !item
+                                    .state_eq(&state_applied, &item_apply.state_target())
+                                    .unwrap_or(true)
+                            });
+
+                        if interrupted {
+                            #[cfg(feature = "output_progress")]
+                            let _progress_send_unused = progress_tx.try_send(
+                                ProgressUpdateAndId {
+                                    item_id: item_id.clone(),
+                                    progress_update: ProgressUpdate::Complete(
+                                        ProgressComplete::Interrupted,
+                                    ),
+                                    msg_update: ProgressMsgUpdate::Set(String::from("interrupted")),
+                                }
+                                .into(),
+                            );
+
+                            outcomes_tx
+                                .send(ItemApplyOutcome::Interrupted {
+                                    item_id: item.id().clone(),
+                                    item_apply,
+                                })
+                                .await
+                                .expect("unreachable: `outcomes_rx` is in a sibling task.");
+                        } else {
+                            #[cfg(feature = "output_progress")]
+                            let _progress_send_unused = progress_tx.try_send(
+                                ProgressUpdateAndId {
+                                    item_id: item_id.clone(),
+                                    progress_update: ProgressUpdate::Complete(
+                                        ProgressComplete::Success,
+                                    ),
+                                    msg_update: ProgressMsgUpdate::Set(String::from("done!")),
+                                }
+                                .into(),
+                            );
+
+                            outcomes_tx
+                                .send(ItemApplyOutcome::Success {
+                                    item_id: item.id().clone(),
+                                    item_apply,
+                                })
+                                .await
+                                .expect("unreachable: `outcomes_rx` is in a sibling task.");
+                        }
 
                         Ok(())
                     }
@@ -332,6 +397,7 @@ where
     }
 
     async fn outcome_collate_task(
+        resources: &Resources<SetUp>,
         mut outcomes_rx: Receiver<ItemApplyOutcome<<CmdCtxTypesT as CmdCtxTypes>::AppError>>,
         mut states_applied_mut: StatesMut<StatesTs>,
         mut states_target_mut: StatesMut<StatesTs::TsTarget>,
@@ -340,11 +406,17 @@ where
             States<StatesTs>,
             States<StatesTs::TsTarget>,
             IndexMap<ItemId, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
+            ItemIdsInterrupted,
         ),
         <CmdCtxTypesT as CmdCtxTypes>::AppError,
     > {
         let mut errors = IndexMap::new();
+        let mut item_ids_interrupted = Vec::new();
         while let Some(item_outcome) = outcomes_rx.recv().await {
+            Self::checkpoint_write(resources, &item_outcome).await?;
+            if let ItemApplyOutcome::Interrupted { item_id, .. } = &item_outcome {
+                item_ids_interrupted.push(item_id.clone());
+            }
             Self::outcome_collate(
                 &mut states_applied_mut,
                 &mut states_target_mut,
@@ -355,8 +427,87 @@ where
 
         let states_applied = States::<StatesTs>::from(states_applied_mut);
         let states_target = States::<StatesTs::TsTarget>::from(states_target_mut);
+        let item_ids_interrupted = ItemIdsInterrupted::new(item_ids_interrupted);
+
+        Ok((states_applied, states_target, errors, item_ids_interrupted))
+    }
+
+    /// Records a successfully applied item in the `CmdCheckpoint`, and writes
+    /// the checkpoint to storage.
+    ///
+    /// Items that were interrupted while being applied are not recorded, so
+    /// that they are applied again when the command is resumed.
+    ///
+    /// Items that were resumed are not recorded again, as the checkpoint
+    /// already holds their stored state from before the interrupted
+    /// execution, which is what remains in storage until states are written.
+    ///
+    /// This does nothing if the `CmdExecution` does not persist a checkpoint.
+    async fn checkpoint_write(
+        resources: &Resources<SetUp>,
+        item_outcome: &ItemApplyOutcome<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
+    ) -> Result<(), <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let ItemApplyOutcome::Success {
+            item_id,
+            item_apply,
+        } = item_outcome
+        else {
+            return Ok(());
+        };
+        let item_resumed = resources
+            .try_borrow::<ItemIdsResumed>()
+            .map(|item_ids_resumed| item_ids_resumed.contains(item_id))
+            .unwrap_or(false);
+        if item_resumed {
+            return Ok(());
+        }
+        let cmd_checkpoint = {
+            let Ok(mut cmd_checkpoint) = resources.try_borrow_mut::<CmdCheckpoint>() else {
+                return Ok(());
+            };
+
+            let state_serialize_error = |error| {
+                <CmdCtxTypesT as CmdCtxTypes>::AppError::from(
+                    peace_rt_model::Error::CmdCheckpointStateSerialize {
+                        item_id: item_id.clone(),
+                        error,
+                    },
+                )
+            };
+            let state_current_stored = item_apply
+                .state_current_stored()
+                .map(|state_current_stored| serde_yaml::to_value(&state_current_stored))
+                .transpose()
+                .map_err(state_serialize_error)?;
+            let state_completed = item_apply
+                .state_applied()
+                .unwrap_or_else(|| item_apply.state_current());
+            let state_completed =
+                serde_yaml::to_value(&state_completed).map_err(state_serialize_error)?;
+
+            cmd_checkpoint.item_completed_insert(
+                item_id.clone(),
+                state_current_stored,
+                state_completed,
+            );
+            cmd_checkpoint.clone()
+        };
 
-        Ok((states_applied, states_target, errors))
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let cmd_checkpoint_file = CmdCheckpointFile::from(&*flow_dir);
+
+        CmdCheckpointSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::serialize(
+            &storage,
+            &cmd_checkpoint,
+            &cmd_checkpoint_file,
+        )
+        .await?;
+
+        drop(flow_dir);
+        drop(storage);
+
+        Ok(())
     }
 
     fn outcome_collate(
@@ -389,6 +540,10 @@ where
             ItemApplyOutcome::Success {
                 item_id,
                 item_apply,
+            }
+            | ItemApplyOutcome::Interrupted {
+                item_id,
+                item_apply,
             } => {
                 if let Some(state_applied) = item_apply.state_applied() {
                     states_applied_mut.insert_raw(item_id.clone(), state_applied);
@@ -515,6 +670,20 @@ where
         let item_graph = flow.graph();
         let resources_ref = &*resources;
         let apply_for = StatesTs::apply_for();
+
+        // Validate every item's params before any item is applied.
+        let value_resolution_mode = match apply_for {
+            ApplyFor::Ensure => ValueResolutionMode::Goal,
+            ApplyFor::Clean => ValueResolutionMode::Current,
+        };
+        item_graph.iter_insertion().try_for_each(|item| {
+            item.params_validate(
+                params_specs,
+                mapping_fn_reg,
+                resources_ref,
+                value_resolution_mode,
+            )
+        })?;
         let apply_for_internal = match apply_for {
             ApplyFor::Ensure => ApplyForInternal::Ensure,
             ApplyFor::Clean => ApplyForInternal::Clean { states_current },
@@ -555,12 +724,19 @@ where
 
                 stream_outcome
             };
-            let outcome_collate_task =
-                Self::outcome_collate_task(outcomes_rx, states_applied_mut, states_target_mut);
+            let outcome_collate_task = Self::outcome_collate_task(
+                resources_ref,
+                outcomes_rx,
+                states_applied_mut,
+                states_target_mut,
+            );
 
             join!(item_apply_exec_task, outcome_collate_task)
         };
-        let (states_applied, states_target, errors) = outcome_collate?;
+        let (states_applied, states_target, errors, item_ids_interrupted) = outcome_collate?;
+        if !item_ids_interrupted.is_empty() {
+            resources.insert(item_ids_interrupted);
+        }
 
         let stream_outcome = {
             let (Ok(stream_outcome) | Err((stream_outcome, ()))) = stream_outcome_result.map_err(
@@ -622,6 +798,14 @@ pub enum ItemApplyOutcome<E> {
         item_id: ItemId,
         item_apply: ItemApplyBoxed,
     },
+    /// Ensure execution stopped early because the `CmdExecution` was
+    /// interrupted.
+    ///
+    /// `item_apply` holds the state that the item reached.
+    Interrupted {
+        item_id: ItemId,
+        item_apply: ItemApplyBoxed,
+    },
     /// Ensure execution failed.
     Fail {
         item_id: ItemId,
@@ -121,16 +121,28 @@ impl<CmdCtxTypesT, ApplyStoreStateSync>
 where
     CmdCtxTypesT: CmdCtxTypes,
 {
+    /// Returns the items whose stored state differs from their discovered
+    /// state.
+    ///
+    /// When `states_are_goal` is `true`, items that are cleaned up on ensure
+    /// are not compared, as their discovered goal state is their clean state,
+    /// whereas their stored goal state is from before they were removed or
+    /// disabled.
     fn items_state_stored_stale<StatesTsStored, StatesTs>(
         cmd_ctx_spsf_fields: &CmdCtxSpsfFields<'_, CmdCtxTypesT>,
         states_stored: &States<StatesTsStored>,
         states_discovered: &States<StatesTs>,
+        states_are_goal: bool,
         #[cfg(feature = "output_progress")] progress_tx: &Sender<CmdProgressUpdate>,
     ) -> Result<ItemsStateStoredStale, <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let resources = &cmd_ctx_spsf_fields.resources;
         let items_state_stored_stale = cmd_ctx_spsf_fields.flow.graph().iter_insertion().try_fold(
             ItemsStateStoredStale::new(),
             |mut items_state_stored_stale, item_rt| {
                 let item_id = item_rt.id();
+                if states_are_goal && item_rt.is_cleaned_on_ensure(resources) {
+                    return Ok(items_state_stored_stale);
+                }
                 let state_stored = states_stored.get_raw(item_id);
                 let state_discovered = states_discovered.get_raw(item_id);
 
@@ -316,6 +328,7 @@ where
             cmd_ctx_spsf_fields,
             states_current_stored,
             states_current,
+            false,
             #[cfg(feature = "output_progress")]
             progress_tx,
         );
@@ -395,6 +408,7 @@ where
             cmd_ctx_spsf_fields,
             states_goal_stored,
             states_goal,
+            true,
             #[cfg(feature = "output_progress")]
             progress_tx,
         );
@@ -494,6 +508,7 @@ where
             cmd_ctx_spsf_fields,
             states_current_stored,
             states_current,
+            false,
             #[cfg(feature = "output_progress")]
             progress_tx,
         );
@@ -517,6 +532,7 @@ where
             cmd_ctx_spsf_fields,
             states_goal_stored,
             states_goal,
+            true,
             #[cfg(feature = "output_progress")]
             progress_tx,
         );
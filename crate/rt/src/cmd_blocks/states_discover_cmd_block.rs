@@ -1,7 +1,7 @@
 use std::{fmt::Debug, marker::PhantomData};
 
 use futures::join;
-use peace_cfg::FnCtx;
+use peace_cfg::{FnCtx, InterruptToken};
 use peace_cmd_ctx::{CmdCtxSpsfFields, CmdCtxTypes};
 use peace_cmd_model::CmdBlockOutcome;
 use peace_cmd_rt::{async_trait, CmdBlock};
@@ -142,11 +142,19 @@ where
         item: &ItemBoxed<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
     ) {
         let item_id = item.id();
+        let interrupt_token = resources
+            .try_borrow::<InterruptToken>()
+            .map(|interrupt_token| InterruptToken::clone(&interrupt_token))
+            .ok();
         let fn_ctx = FnCtx::new(
             item_id,
             #[cfg(feature = "output_progress")]
             ProgressSender::new(item_id, progress_tx),
         );
+        let fn_ctx = match interrupt_token.as_ref() {
+            Some(interrupt_token) => fn_ctx.with_interrupt_token(interrupt_token),
+            None => fn_ctx,
+        };
 
         let (states_current_result, states_goal_result) =
             DiscoverFor::discover(item, params_specs, mapping_fn_reg, resources, fn_ctx).await;
@@ -12,7 +12,7 @@ use peace_resource_rt::{
     type_reg::untagged::{BoxDtDisplay, TypeReg},
     ResourceFetchError, Resources,
 };
-use peace_rt_model::Storage;
+use peace_rt_model::{StatesResumed, Storage};
 use peace_state_rt::StatesSerializer;
 
 cfg_if::cfg_if! {
@@ -50,14 +50,24 @@ where
         let storage = resources.borrow::<Storage>();
         let states_goal_file = StatesGoalFile::from(&*flow_dir);
 
-        let states_goal_stored =
-            StatesSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_goal(
-                &flow_id,
-                &storage,
-                states_type_reg,
-                &states_goal_file,
-            )
-            .await?;
+        // When resuming from a checkpoint, the completed items' states are
+        // only persisted after the resumed command is applied.
+        let states_resumed = resources
+            .try_borrow::<StatesResumed>()
+            .ok()
+            .map(|states_resumed| states_resumed.states_goal_stored().clone());
+        let states_goal_stored = match states_resumed {
+            Some(states_goal_stored) => states_goal_stored,
+            None => {
+                StatesSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_goal(
+                    &flow_id,
+                    &storage,
+                    states_type_reg,
+                    &states_goal_file,
+                )
+                .await?
+            }
+        };
 
         drop(storage);
         drop(flow_dir);
@@ -13,6 +13,17 @@ pub use self::{
     clean_cmd::CleanCmd,
     diff_cmd::{DiffCmd, DiffInfoSpec, DiffStateSpec},
     ensure_cmd::EnsureCmd,
+    flow_check_cmd::FlowCheckCmd,
+    outputs_show_cmd::OutputsShowCmd,
+    params_diff_cmd::ParamsDiffCmd,
+    profile_copy_cmd::ProfileCopyCmd,
+    profile_create_cmd::ProfileCreateCmd,
+    profile_delete_cmd::ProfileDeleteCmd,
+    profile_export_cmd::ProfileExportCmd,
+    profile_import_cmd::ProfileImportCmd,
+    profile_list_cmd::ProfileListCmd,
+    profile_rename_cmd::ProfileRenameCmd,
+    profile_switch_cmd::ProfileSwitchCmd,
     states_current_read_cmd::StatesCurrentReadCmd,
     states_current_stored_display_cmd::StatesCurrentStoredDisplayCmd,
     states_discover_cmd::StatesDiscoverCmd,
@@ -22,8 +33,21 @@ pub use self::{
 
 mod apply_stored_state_sync;
 mod clean_cmd;
+mod cmd_checkpoint_support;
 mod diff_cmd;
 mod ensure_cmd;
+mod flow_check_cmd;
+mod outputs_show_cmd;
+mod params_diff_cmd;
+mod profile_cmd_support;
+mod profile_copy_cmd;
+mod profile_create_cmd;
+mod profile_delete_cmd;
+mod profile_export_cmd;
+mod profile_import_cmd;
+mod profile_list_cmd;
+mod profile_rename_cmd;
+mod profile_switch_cmd;
 mod states_current_read_cmd;
 mod states_current_stored_display_cmd;
 mod states_discover_cmd;
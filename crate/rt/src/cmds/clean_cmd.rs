@@ -4,13 +4,14 @@ use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
 use peace_cmd_model::CmdOutcome;
 use peace_cmd_rt::{CmdBlockWrapper, CmdExecution};
 use peace_flow_rt::ItemGraph;
+use peace_params::ItemsOutputs;
 use peace_resource_rt::{
-    paths::{FlowDir, StatesCurrentFile},
+    paths::{FlowDir, OutputsFile, StatesCurrentFile},
     resources::ts::SetUp,
     states::{States, StatesCleaned, StatesCleanedDry, StatesPrevious},
     Resources,
 };
-use peace_rt_model::Storage;
+use peace_rt_model::{ItemsOutputsSerializer, Storage};
 
 use crate::{
     cmd_blocks::{
@@ -181,6 +182,7 @@ where
                     CleanExecChange::Some(states_previous_and_cleaned) => {
                         let (states_previous, states_cleaned) = *states_previous_and_cleaned;
                         Self::serialize_current(item_graph, resources, &states_cleaned).await?;
+                        Self::serialize_outputs(resources).await?;
 
                         resources.insert::<StatesPrevious>(states_previous);
 
@@ -298,6 +300,28 @@ where
 
         Ok(())
     }
+
+    async fn serialize_outputs(
+        resources: &Resources<SetUp>,
+    ) -> Result<(), <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let items_outputs = resources.borrow::<ItemsOutputs>();
+        let outputs_file = OutputsFile::from(&*flow_dir);
+
+        ItemsOutputsSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::serialize(
+            &storage,
+            &items_outputs,
+            &outputs_file,
+        )
+        .await?;
+
+        drop(flow_dir);
+        drop(storage);
+        drop(items_outputs);
+
+        Ok(())
+    }
 }
 
 impl<CmdCtxTypesT> Default for CleanCmd<CmdCtxTypesT> {
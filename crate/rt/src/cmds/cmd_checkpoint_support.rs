@@ -0,0 +1,159 @@
+use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
+use peace_item_model::ItemId;
+use peace_resource_rt::paths::{CmdCheckpointFile, FlowDir, StatesCurrentFile, StatesGoalFile};
+use peace_rt_model::{
+    CmdCheckpoint, CmdCheckpointSerializer, ItemIdsResumed, StatesResumed, Storage,
+};
+use peace_state_rt::StatesSerializer;
+
+/// Common code used by commands that resume an interrupted execution from its
+/// [`CmdCheckpoint`].
+pub(crate) struct CmdCheckpointSupport;
+
+impl CmdCheckpointSupport {
+    /// Reads the [`CmdCheckpoint`] of an interrupted execution, and prepares
+    /// `Resources` to resume it.
+    ///
+    /// Each completed item's stored state must be either its stored state
+    /// before it was applied, or its state after it was applied -- the latter
+    /// is the case when the interrupted command stored states before it
+    /// exited. The completed states are then used in place of the stored
+    /// current and goal states as [`StatesResumed`], and the completed items
+    /// are inserted into `Resources` as [`ItemIdsResumed`] so that they are
+    /// skipped.
+    ///
+    /// Nothing is written to storage -- the completed states are persisted
+    /// when states are stored after the resumed command is applied.
+    ///
+    /// This does nothing if there is no checkpoint.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_ctx`: The command context.
+    /// * `cmd_name`: Name of the command that is resuming the checkpoint.
+    pub(crate) async fn resume_prepare<CmdCtxTypesT>(
+        cmd_ctx: &mut CmdCtxSpsf<'_, CmdCtxTypesT>,
+        cmd_name: &str,
+    ) -> Result<(), <CmdCtxTypesT as CmdCtxTypes>::AppError>
+    where
+        CmdCtxTypesT: CmdCtxTypes,
+    {
+        let CmdCtxSpsfFields {
+            flow,
+            ref mut resources,
+            ref states_type_reg,
+            ..
+        } = cmd_ctx.fields_mut();
+        let item_graph = flow.graph();
+
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let cmd_checkpoint_file = CmdCheckpointFile::from(&*flow_dir);
+
+        let Some(cmd_checkpoint) =
+            CmdCheckpointSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_opt(
+                &storage,
+                &cmd_checkpoint_file,
+            )
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if cmd_checkpoint.cmd_name() != cmd_name {
+            return Err(peace_rt_model::Error::CmdCheckpointCmdMismatch {
+                cmd_name_checkpoint: cmd_checkpoint.cmd_name().to_string(),
+                cmd_name: cmd_name.to_string(),
+            }
+            .into());
+        }
+
+        let states_current_file = StatesCurrentFile::from(&*flow_dir);
+        let states_goal_file = StatesGoalFile::from(&*flow_dir);
+        let mut states_current_untyped =
+            StatesSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_untyped_opt(
+                &storage,
+                &states_current_file,
+            )
+            .await?
+            .unwrap_or_default();
+
+        let item_ids_stale = cmd_checkpoint
+            .states_completed()
+            .iter()
+            .filter_map(|(item_id, state_completed)| {
+                let item_in_flow = item_graph.iter_insertion().any(|item| item.id() == item_id);
+                let state_stored = states_current_untyped
+                    .get(item_id)
+                    .filter(|state_stored| !state_stored.is_null());
+                let state_stored_valid = state_stored
+                    == cmd_checkpoint.states_current_stored().get(item_id)
+                    || state_stored == Some(state_completed);
+
+                if item_in_flow && state_stored_valid {
+                    None
+                } else {
+                    Some(item_id.clone())
+                }
+            })
+            .collect::<Vec<ItemId>>();
+        if !item_ids_stale.is_empty() {
+            return Err(peace_rt_model::Error::CmdCheckpointStale {
+                item_ids: item_ids_stale,
+            }
+            .into());
+        }
+
+        let mut states_goal_untyped =
+            StatesSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_untyped_opt(
+                &storage,
+                &states_goal_file,
+            )
+            .await?
+            .unwrap_or_default();
+        cmd_checkpoint
+            .states_completed()
+            .iter()
+            .for_each(|(item_id, state_completed)| {
+                states_current_untyped.insert(item_id.clone(), state_completed.clone());
+                states_goal_untyped.insert(item_id.clone(), state_completed.clone());
+            });
+        let states_current_stored =
+            StatesSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_from_untyped(
+                states_type_reg,
+                &states_current_untyped,
+            )?;
+        let states_goal_stored =
+            StatesSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_from_untyped(
+                states_type_reg,
+                &states_goal_untyped,
+            )?;
+
+        drop(flow_dir);
+        drop(storage);
+
+        let item_ids_resumed = cmd_checkpoint
+            .item_ids_completed()
+            .cloned()
+            .collect::<ItemIdsResumed>();
+        resources.insert(item_ids_resumed);
+        resources.insert(StatesResumed::new(
+            states_current_stored,
+            states_goal_stored,
+        ));
+        resources.insert::<CmdCheckpoint>(cmd_checkpoint);
+
+        Ok(())
+    }
+
+    /// Removes the [`ItemIdsResumed`] and [`StatesResumed`] inserted by
+    /// [`Self::resume_prepare`].
+    pub(crate) fn resume_end<CmdCtxTypesT>(cmd_ctx: &mut CmdCtxSpsf<'_, CmdCtxTypesT>)
+    where
+        CmdCtxTypesT: CmdCtxTypes,
+    {
+        let resources = cmd_ctx.fields_mut().resources_mut();
+        let _item_ids_resumed = resources.try_remove::<ItemIdsResumed>();
+        let _states_resumed = resources.try_remove::<StatesResumed>();
+    }
+}
@@ -3,21 +3,23 @@ use std::{fmt::Debug, marker::PhantomData};
 use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
 use peace_cmd_model::CmdOutcome;
 use peace_cmd_rt::{CmdBlockWrapper, CmdExecution};
-use peace_flow_rt::ItemGraph;
+use peace_flow_rt::{Flow, ItemGraph};
+use peace_item_model::ItemId;
+use peace_params::{ItemsOutputs, MappingFnReg, ParamsSpecs};
 use peace_resource_rt::{
-    paths::{FlowDir, StatesCurrentFile, StatesGoalFile},
+    paths::{FlowDir, OutputsFile, ParamsAppliedFile, StatesCurrentFile, StatesGoalFile},
     resources::ts::SetUp,
     states::{States, StatesEnsured, StatesEnsuredDry, StatesGoal, StatesPrevious},
     Resources,
 };
-use peace_rt_model::Storage;
+use peace_rt_model::{ItemIdsOrphaned, ItemsOutputsSerializer, ParamsAppliedSerializer, Storage};
 
 use crate::{
     cmd_blocks::{
         apply_exec_cmd_block::StatesTsApplyExt, ApplyExecCmdBlock, ApplyStateSyncCheckCmdBlock,
         StatesCurrentReadCmdBlock, StatesDiscoverCmdBlock, StatesGoalReadCmdBlock,
     },
-    cmds::ApplyStoredStateSync,
+    cmds::{cmd_checkpoint_support::CmdCheckpointSupport, ApplyStoredStateSync, ParamsDiffCmd},
 };
 
 #[derive(Debug)]
@@ -27,6 +29,9 @@ impl<CmdCtxTypesT> EnsureCmd<CmdCtxTypesT>
 where
     CmdCtxTypesT: CmdCtxTypes,
 {
+    /// Name of this command, recorded in its `CmdCheckpoint`.
+    const CMD_NAME: &'static str = "EnsureCmd";
+
     /// Conditionally runs [`Item::apply_exec_dry`] for each [`Item`].
     ///
     /// In practice this runs [`Item::apply_check`], and only runs
@@ -46,10 +51,14 @@ where
     /// 2. For `Item`s that return `ApplyCheck::ExecRequired`, run
     ///    `Item::apply_exec_dry`.
     ///
+    /// The params fields that changed since each item was last applied are
+    /// inserted into `resources` as [`ParamsDiffs`].
+    ///
     /// [`apply_exec_dry`]: peace_cfg::Item::apply_exec_dry
     /// [`Item::apply_check`]: peace_cfg::Item::apply_check
     /// [`Item::apply_exec_dry`]: peace_cfg::ItemRt::apply_exec_dry
     /// [`Item`]: peace_cfg::Item
+    /// [`ParamsDiffs`]: peace_rt_model::ParamsDiffs
     pub async fn exec_dry<'ctx>(
         cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
     ) -> Result<
@@ -78,7 +87,60 @@ where
     where
         CmdCtxTypesT: 'ctx,
     {
-        let cmd_outcome = Self::exec_internal(cmd_ctx, apply_stored_state_sync).await?;
+        Self::exec_dry_with_orphans(cmd_ctx, apply_stored_state_sync, false).await
+    }
+
+    /// Conditionally runs [`Item::apply_exec_dry`] for each [`Item`], and
+    /// cleans up retired items.
+    ///
+    /// See [`Self::exec_with_orphans_clean`] for full documentation.
+    pub async fn exec_dry_with_orphans_clean<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+        apply_stored_state_sync: ApplyStoredStateSync,
+    ) -> Result<
+        CmdOutcome<StatesEnsuredDry, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        <CmdCtxTypesT as CmdCtxTypes>::AppError,
+    >
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        Self::exec_dry_with_orphans(cmd_ctx, apply_stored_state_sync, true).await
+    }
+
+    async fn exec_dry_with_orphans<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+        apply_stored_state_sync: ApplyStoredStateSync,
+        orphans_clean: bool,
+    ) -> Result<
+        CmdOutcome<StatesEnsuredDry, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        <CmdCtxTypesT as CmdCtxTypes>::AppError,
+    >
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        let cmd_outcome =
+            Self::exec_internal(cmd_ctx, apply_stored_state_sync, orphans_clean).await?;
+
+        let params_diffs = {
+            let CmdCtxSpsfFields {
+                flow,
+                params_specs,
+                mapping_fn_reg,
+                resources,
+                ..
+            } = cmd_ctx.fields();
+            let items_params_applied =
+                ParamsDiffCmd::<CmdCtxTypesT>::items_params_applied_stored(resources).await?;
+
+            ParamsDiffCmd::<CmdCtxTypesT>::params_diffs(
+                flow,
+                params_specs,
+                mapping_fn_reg,
+                resources,
+                &items_params_applied,
+            )?
+        };
+        cmd_ctx.fields_mut().resources_mut().insert(params_diffs);
 
         let cmd_outcome = cmd_outcome.map(|ensure_exec_change| match ensure_exec_change {
             EnsureExecChange::None => Default::default(),
@@ -115,10 +177,18 @@ where
     /// 2. For `Item`s that return `ApplyCheck::ExecRequired`, run
     ///    `Item::apply_exec`.
     ///
+    /// When all items are applied successfully, the params that each item was
+    /// applied with are stored, so that [`ParamsDiffCmd`] can show what
+    /// changed since. Items whose goal state is their clean state, such as
+    /// items for elements removed from an [`ItemFanOut`], are removed from the
+    /// stored states.
+    ///
     /// [`apply_exec`]: peace_cfg::Item::apply_exec
     /// [`Item::apply_check`]: peace_cfg::Item::apply_check
     /// [`Item::apply_exec`]: peace_cfg::ItemRt::apply_exec
     /// [`Item`]: peace_cfg::Item
+    /// [`ItemFanOut`]: peace_flow_rt::ItemFanOut
+    /// [`ParamsDiffCmd`]: crate::cmds::ParamsDiffCmd
     pub async fn exec<'ctx>(
         cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
     ) -> Result<
@@ -147,13 +217,106 @@ where
     where
         CmdCtxTypesT: 'ctx,
     {
-        let cmd_outcome = Self::exec_internal(cmd_ctx, apply_stored_state_sync).await?;
+        Self::exec_with_orphans(cmd_ctx, apply_stored_state_sync, false).await
+    }
+
+    /// Conditionally runs [`Item::apply_exec`] for each [`Item`], and cleans
+    /// up retired items.
+    ///
+    /// Retired items are items that are no longer part of the flow
+    /// definition, but still have stored state, and are added to the graph
+    /// using [`ItemsRetired`]. By default these are skipped; this cleans them
+    /// up using their last stored state and stored params spec.
+    ///
+    /// When all items are applied successfully, the retired items are removed
+    /// from the stored states.
+    ///
+    /// Orphaned items that are not retired are not cleaned up, and are
+    /// listed in the [`ItemIdsOrphaned`] resource.
+    ///
+    /// See [`Self::exec`] for full documentation.
+    ///
+    /// [`Item::apply_exec`]: peace_cfg::ItemRt::apply_exec
+    /// [`Item`]: peace_cfg::Item
+    /// [`ItemIdsOrphaned`]: peace_rt_model::ItemIdsOrphaned
+    /// [`ItemsRetired`]: peace_flow_rt::ItemsRetired
+    pub async fn exec_with_orphans_clean<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+        apply_stored_state_sync: ApplyStoredStateSync,
+    ) -> Result<
+        CmdOutcome<StatesEnsured, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        <CmdCtxTypesT as CmdCtxTypes>::AppError,
+    >
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        Self::exec_with_orphans(cmd_ctx, apply_stored_state_sync, true).await
+    }
+
+    /// Resumes an [`exec`] that was interrupted, skipping the items that it
+    /// completed.
+    ///
+    /// While items are applied, `exec` writes a checkpoint to
+    /// `cmd_checkpoint.yaml` in the flow directory, which is removed when the
+    /// execution completes. If the execution is interrupted, e.g. by Ctrl-C,
+    /// this reads the checkpoint, validates it against the stored current
+    /// states, and applies the remaining items. The items completed by the
+    /// interrupted execution are skipped, and their applied state is stored.
+    ///
+    /// If there is no checkpoint, this is the same as [`exec`].
+    ///
+    /// # Errors
+    ///
+    /// * [`CmdCheckpointCmdMismatch`]: The checkpoint was written by a
+    ///   different command.
+    /// * [`CmdCheckpointStale`]: The stored state of a completed item was
+    ///   changed since the checkpoint was written, or the item is no longer in
+    ///   the flow.
+    ///
+    /// [`exec`]: Self::exec
+    /// [`CmdCheckpointCmdMismatch`]: peace_rt_model::Error::CmdCheckpointCmdMismatch
+    /// [`CmdCheckpointStale`]: peace_rt_model::Error::CmdCheckpointStale
+    pub async fn exec_resume<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+    ) -> Result<
+        CmdOutcome<StatesEnsured, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        <CmdCtxTypesT as CmdCtxTypes>::AppError,
+    >
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        CmdCheckpointSupport::resume_prepare(cmd_ctx, Self::CMD_NAME).await?;
+        let cmd_outcome = Self::exec(cmd_ctx).await;
+        CmdCheckpointSupport::resume_end(cmd_ctx);
+
+        cmd_outcome
+    }
+
+    async fn exec_with_orphans<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+        apply_stored_state_sync: ApplyStoredStateSync,
+        orphans_clean: bool,
+    ) -> Result<
+        CmdOutcome<StatesEnsured, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        <CmdCtxTypesT as CmdCtxTypes>::AppError,
+    >
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        let cmd_outcome =
+            Self::exec_internal(cmd_ctx, apply_stored_state_sync, orphans_clean).await?;
+        let is_complete = cmd_outcome.is_complete();
 
         let CmdCtxSpsfFields {
             flow,
+            params_specs,
+            mapping_fn_reg,
             ref mut resources,
             ..
         } = cmd_ctx.fields_mut();
+        let flow: &Flow<_> = flow;
+        let params_specs: &ParamsSpecs = params_specs;
+        let mapping_fn_reg: &MappingFnReg = mapping_fn_reg;
         let item_graph = flow.graph();
 
         // We shouldn't serialize current or goal if we returned from an interruption /
@@ -164,8 +327,33 @@ where
                     EnsureExecChange::None => Ok(Default::default()),
                     EnsureExecChange::Some(stateses_boxed) => {
                         let (states_previous, states_applied, states_goal) = *stateses_boxed;
-                        Self::serialize_current(item_graph, resources, &states_applied).await?;
-                        Self::serialize_goal(item_graph, resources, &states_goal).await?;
+                        if is_complete {
+                            // Items whose goal state is their clean state have been cleaned
+                            // up, so they are no longer stored.
+                            let item_ids_cleaned =
+                                Self::item_ids_cleaned(item_graph, orphans_clean);
+                            let states_applied_stored =
+                                Self::states_cleaned_remove(&states_applied, &item_ids_cleaned);
+                            let states_goal_stored =
+                                Self::states_cleaned_remove(&states_goal, &item_ids_cleaned);
+                            Self::serialize_current(item_graph, resources, &states_applied_stored)
+                                .await?;
+                            Self::serialize_goal(item_graph, resources, &states_goal_stored)
+                                .await?;
+                        } else {
+                            Self::serialize_current(item_graph, resources, &states_applied).await?;
+                            Self::serialize_goal(item_graph, resources, &states_goal).await?;
+                        }
+                        Self::serialize_outputs(resources).await?;
+                        if is_complete {
+                            Self::serialize_params_applied(
+                                flow,
+                                params_specs,
+                                mapping_fn_reg,
+                                resources,
+                            )
+                            .await?;
+                        }
 
                         resources.insert::<StatesPrevious>(states_previous);
 
@@ -189,6 +377,7 @@ where
     async fn exec_internal<'ctx, StatesTs>(
         cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
         apply_stored_state_sync: ApplyStoredStateSync,
+        orphans_clean: bool,
     ) -> Result<
         CmdOutcome<EnsureExecChange<StatesTs>, <CmdCtxTypesT as CmdCtxTypes>::AppError>,
         <CmdCtxTypesT as CmdCtxTypes>::AppError,
@@ -237,6 +426,11 @@ where
                 ),
             };
 
+            // Dry runs do not apply anything, so there is nothing to resume.
+            if !StatesTs::dry_run() {
+                cmd_execution_builder = cmd_execution_builder.with_checkpoint(Self::CMD_NAME);
+            }
+
             cmd_execution_builder
                 .with_cmd_block(CmdBlockWrapper::new(
                     ApplyExecCmdBlock::<CmdCtxTypesT, StatesTs>::new(),
@@ -274,7 +468,10 @@ where
                 .build()
         };
 
-        let ensure_exec_change = cmd_execution.exec(cmd_ctx).await?;
+        Self::orphans_clean_set(cmd_ctx, orphans_clean);
+        let ensure_exec_change = cmd_execution.exec(cmd_ctx).await;
+        Self::orphans_clean_set(cmd_ctx, false);
+        let ensure_exec_change = ensure_exec_change?;
 
         // TODO: Should we run `StatesCurrentFn` again?
         //
@@ -290,6 +487,47 @@ where
         Ok(ensure_exec_change)
     }
 
+    /// Sets whether retired items are cleaned up instead of skipped.
+    fn orphans_clean_set(cmd_ctx: &mut CmdCtxSpsf<'_, CmdCtxTypesT>, orphans_clean: bool) {
+        if let Ok(mut item_ids_orphaned) = cmd_ctx
+            .fields_mut()
+            .resources_mut()
+            .try_borrow_mut::<ItemIdsOrphaned>()
+        {
+            item_ids_orphaned.set_clean(orphans_clean);
+        }
+    }
+
+    /// Returns the IDs of items that are cleaned up when applied, and so are
+    /// removed from the stored states once applied successfully.
+    ///
+    /// These are items whose goal state is their clean state, such as items
+    /// for elements removed from an `ItemFanOut`. Retired items are only
+    /// included when `orphans_clean` is `true`, as they are otherwise skipped.
+    fn item_ids_cleaned(
+        item_graph: &ItemGraph<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        orphans_clean: bool,
+    ) -> Vec<ItemId> {
+        let item_ids_retired = item_graph.item_ids_retired();
+        item_graph
+            .iter_insertion()
+            .filter(|item| item.state_goal_is_clean())
+            .map(|item| item.id())
+            .filter(|item_id| orphans_clean || !item_ids_retired.contains(item_id))
+            .cloned()
+            .collect::<Vec<ItemId>>()
+    }
+
+    /// Returns a copy of `states` without the states of cleaned up items.
+    fn states_cleaned_remove<TS>(states: &States<TS>, item_ids_cleaned: &[ItemId]) -> States<TS> {
+        let mut states = states.clone().into_inner();
+        item_ids_cleaned.iter().for_each(|item_id| {
+            states.shift_remove(item_id);
+        });
+
+        States::from(states)
+    }
+
     // TODO: This duplicates a bit of code with `StatesDiscoverCmd`,
     async fn serialize_current(
         item_graph: &ItemGraph<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
@@ -329,6 +567,58 @@ where
 
         Ok(())
     }
+
+    async fn serialize_params_applied(
+        flow: &Flow<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Result<(), <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let items_params_applied = ParamsDiffCmd::<CmdCtxTypesT>::items_params_applied(
+            flow,
+            params_specs,
+            mapping_fn_reg,
+            resources,
+        )?;
+
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let params_applied_file = ParamsAppliedFile::from(&*flow_dir);
+
+        ParamsAppliedSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::serialize(
+            &storage,
+            &items_params_applied,
+            &params_applied_file,
+        )
+        .await?;
+
+        drop(flow_dir);
+        drop(storage);
+
+        Ok(())
+    }
+
+    async fn serialize_outputs(
+        resources: &Resources<SetUp>,
+    ) -> Result<(), <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let items_outputs = resources.borrow::<ItemsOutputs>();
+        let outputs_file = OutputsFile::from(&*flow_dir);
+
+        ItemsOutputsSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::serialize(
+            &storage,
+            &items_outputs,
+            &outputs_file,
+        )
+        .await?;
+
+        drop(flow_dir);
+        drop(storage);
+        drop(items_outputs);
+
+        Ok(())
+    }
 }
 
 impl<CmdCtxTypesT> Default for EnsureCmd<CmdCtxTypesT> {
@@ -0,0 +1,55 @@
+use std::marker::PhantomData;
+
+use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
+use peace_rt_model::FlowIssues;
+use peace_rt_model_core::output::OutputWrite;
+
+/// Checks a flow for problems that would otherwise only surface when it is
+/// executed.
+///
+/// See [`Flow::validate_with_resources`] for the checks.
+///
+/// [`Flow::validate_with_resources`]: peace_flow_rt::Flow::validate_with_resources
+#[derive(Debug)]
+pub struct FlowCheckCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> FlowCheckCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Returns the problems with the command context's flow, and presents them
+    /// to the output if there are any.
+    ///
+    /// The flow is checked against the command context's params specs,
+    /// mapping functions, and resources, after each item's `setup` has run.
+    pub async fn exec<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+    ) -> Result<FlowIssues, <CmdCtxTypesT as CmdCtxTypes>::AppError>
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        let flow_issues = {
+            let CmdCtxSpsfFields {
+                flow,
+                params_specs,
+                mapping_fn_reg,
+                resources,
+                ..
+            } = cmd_ctx.fields();
+
+            flow.flow_issues_with_resources(params_specs, mapping_fn_reg, resources)
+        };
+
+        if !flow_issues.is_empty() {
+            cmd_ctx.output_mut().present(&flow_issues).await?;
+        }
+
+        Ok(flow_issues)
+    }
+}
+
+impl<CmdCtxTypesT> Default for FlowCheckCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
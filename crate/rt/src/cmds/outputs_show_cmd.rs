@@ -0,0 +1,47 @@
+use std::marker::PhantomData;
+
+use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxTypes};
+use peace_params::ItemsOutputs;
+use peace_rt_model_core::output::OutputWrite;
+
+/// Shows the outputs that each item published when it was last applied.
+///
+/// Outputs are returned by `Item::outputs`, and are stored in the flow's
+/// `outputs.yaml` after each `EnsureCmd` or `CleanCmd`.
+///
+/// When the output format is JSON, the outputs are presented as a JSON
+/// object. To write them as an env file, use
+/// [`ItemsOutputs::to_env_string`] on the returned value.
+#[derive(Debug)]
+pub struct OutputsShowCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> OutputsShowCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Returns the stored outputs of each item, and presents them to the
+    /// output.
+    pub async fn exec<'ctx>(
+        cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
+    ) -> Result<ItemsOutputs, <CmdCtxTypesT as CmdCtxTypes>::AppError>
+    where
+        CmdCtxTypesT: 'ctx,
+    {
+        let items_outputs = cmd_ctx
+            .fields()
+            .resources()
+            .try_borrow::<ItemsOutputs>()
+            .map(|items_outputs| (*items_outputs).clone())
+            .unwrap_or_default();
+
+        cmd_ctx.output_mut().present(&items_outputs).await?;
+
+        Ok(items_outputs)
+    }
+}
+
+impl<CmdCtxTypesT> Default for OutputsShowCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,129 @@
+use std::marker::PhantomData;
+
+use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
+use peace_flow_rt::Flow;
+use peace_params::{MappingFnReg, ParamsSpecs, ValueResolutionMode};
+use peace_resource_rt::{
+    paths::{FlowDir, ParamsAppliedFile},
+    resources::ts::SetUp,
+    Resources,
+};
+use peace_rt_model::{ItemsParamsApplied, ParamsAppliedSerializer, ParamsDiffs, Storage};
+
+/// Shows the params fields that changed since each item was last applied.
+///
+/// The params that each item is applied with are stored in
+/// `params_applied.yaml` after each successful [`EnsureCmd::exec`].
+///
+/// [`EnsureCmd::exec`]: crate::cmds::EnsureCmd::exec
+#[derive(Debug)]
+pub struct ParamsDiffCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ParamsDiffCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Returns the params fields that changed for each item since it was last
+    /// applied.
+    ///
+    /// Items that have not been applied return all of their fields as added,
+    /// and values of fields marked `#[params(secret)]` are masked.
+    pub async fn exec(
+        cmd_ctx: &CmdCtxSpsf<'_, CmdCtxTypesT>,
+    ) -> Result<ParamsDiffs, <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let CmdCtxSpsfFields {
+            flow,
+            params_specs,
+            mapping_fn_reg,
+            resources,
+            ..
+        } = cmd_ctx.fields();
+
+        let items_params_applied = Self::items_params_applied_stored(resources).await?;
+
+        Self::params_diffs(
+            flow,
+            params_specs,
+            mapping_fn_reg,
+            resources,
+            &items_params_applied,
+        )
+    }
+
+    /// Returns the params fields that changed for each item, compared with
+    /// the given `items_params_applied`.
+    pub(crate) fn params_diffs(
+        flow: &Flow<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+        items_params_applied: &ItemsParamsApplied,
+    ) -> Result<ParamsDiffs, <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        flow.graph()
+            .iter_insertion()
+            .try_fold(ParamsDiffs::new(), |mut params_diffs, item| {
+                let item_id = item.id();
+                let params_applied = item.params_applied(
+                    params_specs,
+                    mapping_fn_reg,
+                    resources,
+                    ValueResolutionMode::Goal,
+                )?;
+                let params_field_diffs = params_applied.field_diffs(
+                    items_params_applied.get(item_id),
+                    &item.params_field_names_secret(),
+                );
+                if !params_field_diffs.is_empty() {
+                    params_diffs.insert(item_id.clone(), params_field_diffs);
+                }
+
+                Ok(params_diffs)
+            })
+    }
+
+    /// Returns the params that each item in the flow is applied with.
+    pub(crate) fn items_params_applied(
+        flow: &Flow<<CmdCtxTypesT as CmdCtxTypes>::AppError>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Result<ItemsParamsApplied, <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        flow.graph()
+            .iter_insertion()
+            .map(|item| {
+                item.params_applied(
+                    params_specs,
+                    mapping_fn_reg,
+                    resources,
+                    ValueResolutionMode::Goal,
+                )
+                .map(|params_applied| (item.id().clone(), params_applied))
+            })
+            .collect()
+    }
+
+    /// Reads the stored params that each item was last applied with.
+    pub(crate) async fn items_params_applied_stored(
+        resources: &Resources<SetUp>,
+    ) -> Result<ItemsParamsApplied, <CmdCtxTypesT as CmdCtxTypes>::AppError> {
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let params_applied_file = ParamsAppliedFile::from(&*flow_dir);
+
+        let items_params_applied =
+            ParamsAppliedSerializer::<<CmdCtxTypesT as CmdCtxTypes>::AppError>::deserialize_opt(
+                &storage,
+                &params_applied_file,
+            )
+            .await?
+            .unwrap_or_default();
+
+        Ok(items_params_applied)
+    }
+}
+
+impl<CmdCtxTypesT> Default for ParamsDiffCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, path::Path};
+
+use peace_params::ParamsKey;
+use peace_profile_model::Profile;
+use peace_resource_rt::{
+    internal::{ProfileParamsFile, WorkspaceParamsFile},
+    paths::{PeaceAppDir, ProfileDir, ProfileHistoryDir},
+};
+use peace_rt_model::{
+    params::{ProfileParams, WorkspaceParams},
+    Workspace, WorkspaceInitializer,
+};
+
+/// Common code used by the profile management commands.
+pub(crate) struct ProfileCmdSupport;
+
+impl ProfileCmdSupport {
+    /// Returns an error if the profile does not exist.
+    pub(crate) fn profile_exists_check(
+        profiles: &[Profile],
+        profile: &Profile,
+    ) -> Result<(), peace_rt_model::Error> {
+        if profiles.contains(profile) {
+            Ok(())
+        } else {
+            Err(peace_rt_model::Error::ProfileNotInScope {
+                profile: profile.clone(),
+                profiles_in_scope: profiles.to_vec(),
+            })
+        }
+    }
+
+    /// Returns an error if the profile already exists.
+    pub(crate) fn profile_not_exists_check(
+        profiles: &[Profile],
+        profile: &Profile,
+    ) -> Result<(), peace_rt_model::Error> {
+        if profiles.contains(profile) {
+            Err(peace_rt_model::Error::ProfileExists {
+                profile: profile.clone(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns whether the given profile is the one selected by the workspace
+    /// param.
+    pub(crate) fn profile_is_active<WorkspaceParamsK>(
+        workspace_params: &WorkspaceParams<WorkspaceParamsK>,
+        profile_key: &WorkspaceParamsK,
+        profile: &Profile,
+    ) -> bool
+    where
+        WorkspaceParamsK: ParamsKey,
+    {
+        workspace_params.get::<Profile, _>(profile_key) == Some(profile)
+    }
+
+    /// Returns the profile directory and profile history directory for the
+    /// given profile.
+    pub(crate) fn profile_and_history_dir(
+        peace_app_dir: &PeaceAppDir,
+        profile: &Profile,
+    ) -> (ProfileDir, ProfileHistoryDir) {
+        let profile_dir = ProfileDir::from((peace_app_dir, profile));
+        let profile_history_dir = ProfileHistoryDir::from(&profile_dir);
+        (profile_dir, profile_history_dir)
+    }
+
+    /// Creates the profile directory and profile history directory.
+    pub(crate) async fn profile_and_history_dir_create(
+        workspace: &Workspace,
+        profile_dir: &ProfileDir,
+        profile_history_dir: &ProfileHistoryDir,
+    ) -> Result<(), peace_rt_model::Error> {
+        Self::dirs_create(
+            workspace,
+            [
+                AsRef::<Path>::as_ref(profile_dir),
+                AsRef::<Path>::as_ref(profile_history_dir),
+            ],
+        )
+        .await
+    }
+
+    /// Creates the given directories.
+    pub(crate) async fn dirs_create<'f, I>(
+        #[cfg_attr(not(target_arch = "wasm32"), allow(unused_variables))] workspace: &Workspace,
+        dirs: I,
+    ) -> Result<(), peace_rt_model::Error>
+    where
+        I: IntoIterator<Item = &'f Path>,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            WorkspaceInitializer::dirs_create(workspace.storage(), dirs).await
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            WorkspaceInitializer::dirs_create(dirs).await
+        }
+    }
+
+    /// Serializes workspace params to storage.
+    pub(crate) async fn workspace_params_serialize<WorkspaceParamsK>(
+        workspace: &Workspace,
+        workspace_params: &WorkspaceParams<WorkspaceParamsK>,
+    ) -> Result<(), peace_rt_model::Error>
+    where
+        WorkspaceParamsK: ParamsKey,
+    {
+        let workspace_params_file = WorkspaceParamsFile::from(workspace.dirs().peace_app_dir());
+        WorkspaceInitializer::workspace_params_serialize(
+            workspace.storage(),
+            workspace_params,
+            &workspace_params_file,
+        )
+        .await
+    }
+
+    /// Serializes profile params to storage.
+    pub(crate) async fn profile_params_serialize<ProfileParamsK>(
+        workspace: &Workspace,
+        profile_params: &ProfileParams<ProfileParamsK>,
+        profile_dir: &ProfileDir,
+    ) -> Result<(), peace_rt_model::Error>
+    where
+        ProfileParamsK: ParamsKey,
+    {
+        let profile_params_file = ProfileParamsFile::from(profile_dir);
+        WorkspaceInitializer::profile_params_serialize(
+            workspace.storage(),
+            profile_params,
+            &profile_params_file,
+        )
+        .await
+    }
+
+    /// Inserts the profile into the list of profiles, maintaining sort order.
+    pub(crate) fn profiles_insert(profiles: &mut Vec<Profile>, profile: Profile) {
+        if let Err(index) = profiles.binary_search(&profile) {
+            profiles.insert(index, profile);
+        }
+    }
+
+    /// Moves the value stored against `profile_from` to `profile_to`.
+    pub(crate) fn profile_entry_rename<V>(
+        map: &mut BTreeMap<Profile, V>,
+        profile_from: &Profile,
+        profile_to: &Profile,
+    ) {
+        if let Some(value) = map.remove(profile_from) {
+            map.insert(profile_to.clone(), value);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields, CmdCtxTypes};
+use peace_profile_model::Profile;
+use peace_resource_rt::{
+    internal::ProfileParamsFile,
+    paths::{StatesCurrentFile, StatesGoalFile},
+};
+use peace_rt_model::WorkspaceInitializer;
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Copies a profile in the workspace.
+#[derive(Debug)]
+pub struct ProfileCopyCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileCopyCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Copies the profile params and each flow's params from `profile_src` to
+    /// a new profile named `profile_dest`.
+    ///
+    /// Stored states are not copied, as the items managed by `profile_src` do
+    /// not belong to `profile_dest`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ProfileNotInScope`] if `profile_src` does not exist.
+    /// * [`Error::ProfileExists`] if `profile_dest` already exists.
+    ///
+    /// [`Error::ProfileNotInScope`]: peace_rt_model::Error::ProfileNotInScope
+    /// [`Error::ProfileExists`]: peace_rt_model::Error::ProfileExists
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtxMpnf<'_, CmdCtxTypesT>,
+        profile_src: &Profile,
+        profile_dest: Profile,
+    ) -> Result<(), CmdCtxTypesT::AppError> {
+        let CmdCtxMpnfFields {
+            workspace,
+            profiles,
+            profile_dirs,
+            profile_history_dirs,
+            profile_params_type_reg,
+            profile_to_profile_params,
+            ..
+        } = cmd_ctx.fields_mut();
+
+        ProfileCmdSupport::profile_exists_check(profiles, profile_src)?;
+        ProfileCmdSupport::profile_not_exists_check(profiles, &profile_dest)?;
+
+        let peace_app_dir = workspace.dirs().peace_app_dir();
+        let (profile_dir_src, _) =
+            ProfileCmdSupport::profile_and_history_dir(peace_app_dir, profile_src);
+        let (profile_dir_dest, profile_history_dir_dest) =
+            ProfileCmdSupport::profile_and_history_dir(peace_app_dir, &profile_dest);
+
+        let storage = workspace.storage();
+        storage
+            .dir_copy(
+                &profile_dir_src,
+                &profile_dir_dest,
+                &[StatesCurrentFile::NAME, StatesGoalFile::NAME],
+            )
+            .await?;
+        ProfileCmdSupport::profile_and_history_dir_create(
+            workspace,
+            &profile_dir_dest,
+            &profile_history_dir_dest,
+        )
+        .await?;
+
+        let profile_params_file = ProfileParamsFile::from(&profile_dir_dest);
+        let profile_params = WorkspaceInitializer::profile_params_deserialize(
+            storage,
+            profile_params_type_reg,
+            &profile_params_file,
+        )
+        .await?
+        .unwrap_or_default();
+
+        ProfileCmdSupport::profiles_insert(profiles, profile_dest.clone());
+        profile_dirs.insert(profile_dest.clone(), profile_dir_dest);
+        profile_history_dirs.insert(profile_dest.clone(), profile_history_dir_dest);
+        profile_to_profile_params.insert(profile_dest, profile_params);
+
+        Ok(())
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileCopyCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
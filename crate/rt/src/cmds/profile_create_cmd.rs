@@ -0,0 +1,68 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields, CmdCtxTypes};
+use peace_profile_model::Profile;
+use peace_rt_model::params::ProfileParams;
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Creates a profile in the workspace.
+#[derive(Debug)]
+pub struct ProfileCreateCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileCreateCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Creates the profile directories and writes the profile params.
+    ///
+    /// The created profile is added to the `CmdCtxMpnf`'s profiles. This does
+    /// not switch to the new profile -- use [`ProfileSwitchCmd`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProfileExists`] if the profile already exists.
+    ///
+    /// [`Error::ProfileExists`]: peace_rt_model::Error::ProfileExists
+    /// [`ProfileSwitchCmd`]: crate::cmds::ProfileSwitchCmd
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtxMpnf<'_, CmdCtxTypesT>,
+        profile: Profile,
+        profile_params: ProfileParams<CmdCtxTypesT::ProfileParamsKey>,
+    ) -> Result<(), CmdCtxTypesT::AppError> {
+        let CmdCtxMpnfFields {
+            workspace,
+            profiles,
+            profile_dirs,
+            profile_history_dirs,
+            profile_to_profile_params,
+            ..
+        } = cmd_ctx.fields_mut();
+
+        ProfileCmdSupport::profile_not_exists_check(profiles, &profile)?;
+
+        let (profile_dir, profile_history_dir) =
+            ProfileCmdSupport::profile_and_history_dir(workspace.dirs().peace_app_dir(), &profile);
+        ProfileCmdSupport::profile_and_history_dir_create(
+            workspace,
+            &profile_dir,
+            &profile_history_dir,
+        )
+        .await?;
+        ProfileCmdSupport::profile_params_serialize(workspace, &profile_params, &profile_dir)
+            .await?;
+
+        ProfileCmdSupport::profiles_insert(profiles, profile.clone());
+        profile_dirs.insert(profile.clone(), profile_dir);
+        profile_history_dirs.insert(profile.clone(), profile_history_dir);
+        profile_to_profile_params.insert(profile, profile_params);
+
+        Ok(())
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileCreateCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,245 @@
+use std::{fmt::Debug, marker::PhantomData, path::Path, str::FromStr};
+
+use peace_cmd_ctx::{CmdCtxMpsf, CmdCtxMpsfFields, CmdCtxTypes};
+use peace_flow_model::FlowId;
+use peace_item_model::ItemId;
+use peace_profile_model::Profile;
+use peace_resource_rt::paths::StatesCurrentFile;
+use peace_rt_model::Storage;
+use peace_state_rt::StatesSerializer;
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Deletes a profile from the workspace.
+#[derive(Debug)]
+pub struct ProfileDeleteCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileDeleteCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Deletes the profile's directory, including its params and stored
+    /// states.
+    ///
+    /// The profile is only deleted if the stored current state of every item
+    /// in every flow within the profile is the item's clean state, so that the
+    /// automation does not lose track of items that still exist. Stored states
+    /// of items that are no longer in the flow are treated as not clean, as
+    /// their clean state is not known.
+    ///
+    /// Every flow that has stored states within the profile must be checked,
+    /// so a command context must be passed in for each flow other than the
+    /// `cmd_ctx`'s flow.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_ctx`: The command context.
+    /// * `cmd_ctxs_other_flows`: Command contexts for the other flows that have
+    ///   stored states within the profile.
+    /// * `profile_key`: Workspace params key that the active profile is stored
+    ///   against.
+    /// * `profile`: The profile to delete.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ProfileNotInScope`] if the profile does not exist.
+    /// * [`Error::ProfileDeleteActive`] if the profile is the active profile.
+    /// * [`Error::ProfileDeleteFlowsNotChecked`] if a flow with stored states
+    ///   within the profile does not have a command context passed in.
+    /// * [`Error::ProfileDeleteStatesNotClean`] if any item's stored state is
+    ///   not clean.
+    ///
+    /// [`Error::ProfileNotInScope`]: peace_rt_model::Error::ProfileNotInScope
+    /// [`Error::ProfileDeleteActive`]: peace_rt_model::Error::ProfileDeleteActive
+    /// [`Error::ProfileDeleteFlowsNotChecked`]: peace_rt_model::Error::ProfileDeleteFlowsNotChecked
+    /// [`Error::ProfileDeleteStatesNotClean`]: peace_rt_model::Error::ProfileDeleteStatesNotClean
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtxMpsf<'_, CmdCtxTypesT>,
+        cmd_ctxs_other_flows: &[&CmdCtxMpsf<'_, CmdCtxTypesT>],
+        profile_key: &CmdCtxTypesT::WorkspaceParamsKey,
+        profile: &Profile,
+    ) -> Result<(), CmdCtxTypesT::AppError> {
+        let mut item_ids_not_clean = Self::item_ids_not_clean(cmd_ctx.fields(), profile).await?;
+        for cmd_ctx_other_flow in cmd_ctxs_other_flows {
+            Self::item_ids_not_clean(cmd_ctx_other_flow.fields(), profile)
+                .await?
+                .into_iter()
+                .for_each(|item_id| {
+                    if !item_ids_not_clean.contains(&item_id) {
+                        item_ids_not_clean.push(item_id);
+                    }
+                });
+        }
+
+        let CmdCtxMpsfFields {
+            workspace,
+            flow,
+            profiles,
+            profile_dirs,
+            profile_history_dirs,
+            flow_dirs,
+            workspace_params,
+            profile_to_profile_params,
+            profile_to_flow_params,
+            profile_to_states_current_stored,
+            profile_to_params_specs,
+            ..
+        } = cmd_ctx.fields_mut();
+
+        ProfileCmdSupport::profile_exists_check(profiles, profile)?;
+        if ProfileCmdSupport::profile_is_active(workspace_params, profile_key, profile) {
+            Err(peace_rt_model::Error::ProfileDeleteActive {
+                profile: profile.clone(),
+            })?;
+        }
+
+        let (profile_dir, _) =
+            ProfileCmdSupport::profile_and_history_dir(workspace.dirs().peace_app_dir(), profile);
+        let flow_ids_not_checked = Self::flow_ids_states_stored(workspace.storage(), &profile_dir)
+            .await?
+            .into_iter()
+            .filter(|flow_id| {
+                flow_id != flow.flow_id()
+                    && !cmd_ctxs_other_flows.iter().any(|cmd_ctx_other_flow| {
+                        flow_id == cmd_ctx_other_flow.fields().flow().flow_id()
+                    })
+            })
+            .collect::<Vec<FlowId>>();
+        if !flow_ids_not_checked.is_empty() {
+            Err(peace_rt_model::Error::ProfileDeleteFlowsNotChecked {
+                profile: profile.clone(),
+                flow_ids: flow_ids_not_checked,
+            })?;
+        }
+        if !item_ids_not_clean.is_empty() {
+            Err(peace_rt_model::Error::ProfileDeleteStatesNotClean {
+                profile: profile.clone(),
+                item_ids: item_ids_not_clean,
+            })?;
+        }
+
+        workspace.storage().dir_remove(&profile_dir).await?;
+
+        profiles.retain(|profile_existing| profile_existing != profile);
+        profile_dirs.remove(profile);
+        profile_history_dirs.remove(profile);
+        flow_dirs.remove(profile);
+        profile_to_profile_params.remove(profile);
+        profile_to_flow_params.remove(profile);
+        profile_to_states_current_stored.remove(profile);
+        profile_to_params_specs.remove(profile);
+
+        Ok(())
+    }
+
+    /// Returns the IDs of flows that have a stored current states file within
+    /// the profile directory, sorted by ID.
+    async fn flow_ids_states_stored(
+        storage: &Storage,
+        profile_dir: &Path,
+    ) -> Result<Vec<FlowId>, CmdCtxTypesT::AppError> {
+        let file_paths = storage.dir_file_paths(profile_dir).await?;
+        let mut flow_dir_names = file_paths
+            .iter()
+            .filter(|file_path| {
+                file_path
+                    .file_name()
+                    .is_some_and(|file_name| file_name == StatesCurrentFile::NAME)
+            })
+            .filter_map(|states_current_file| states_current_file.parent())
+            .filter(|flow_dir| flow_dir.parent() == Some(profile_dir))
+            .filter_map(|flow_dir| flow_dir.file_name()?.to_str())
+            .collect::<Vec<&str>>();
+        flow_dir_names.sort_unstable();
+
+        // Directories that are not valid flow IDs are not flow directories.
+        let flow_ids = flow_dir_names
+            .into_iter()
+            .filter_map(|flow_dir_name| FlowId::from_str(flow_dir_name).ok())
+            .collect::<Vec<FlowId>>();
+
+        Ok(flow_ids)
+    }
+
+    /// Returns the IDs of items whose stored current state is not their clean
+    /// state.
+    ///
+    /// Items with a stored state that are not in the flow are included, as
+    /// their clean state cannot be determined.
+    async fn item_ids_not_clean(
+        cmd_ctx_mpsf_fields: &CmdCtxMpsfFields<'_, CmdCtxTypesT>,
+        profile: &Profile,
+    ) -> Result<Vec<ItemId>, CmdCtxTypesT::AppError> {
+        let CmdCtxMpsfFields {
+            workspace,
+            flow,
+            flow_dirs,
+            profile_to_states_current_stored,
+            profile_to_params_specs,
+            mapping_fn_reg,
+            resources,
+            ..
+        } = cmd_ctx_mpsf_fields;
+
+        let Some(Some(states_current_stored)) = profile_to_states_current_stored.get(profile)
+        else {
+            return Ok(Vec::new());
+        };
+        let params_specs = profile_to_params_specs.get(profile);
+
+        // Stored states of items that are not in the flow are not deserialized
+        // into `states_current_stored`, so their IDs are read from storage.
+        let states_current_untyped = match flow_dirs.get(profile) {
+            Some(flow_dir) => {
+                let states_current_file = StatesCurrentFile::from(flow_dir);
+                StatesSerializer::<CmdCtxTypesT::AppError>::deserialize_untyped_opt(
+                    workspace.storage(),
+                    &states_current_file,
+                )
+                .await?
+                .unwrap_or_default()
+            }
+            None => Default::default(),
+        };
+        let mut item_ids_not_clean = states_current_untyped
+            .iter()
+            .filter(|(item_id, state_stored)| {
+                !state_stored.is_null()
+                    && !flow
+                        .graph()
+                        .iter_insertion()
+                        .any(|item_rt| item_rt.id() == *item_id)
+            })
+            .map(|(item_id, _state_stored)| item_id.clone())
+            .collect::<Vec<ItemId>>();
+        for item_rt in flow.graph().iter_insertion() {
+            let item_id = item_rt.id();
+            let Some(state_stored) = states_current_stored.get_raw(item_id) else {
+                continue;
+            };
+
+            // Without params specs we cannot tell what the clean state is, so we
+            // treat the item as not clean.
+            let state_is_clean = match params_specs {
+                Some(params_specs) => {
+                    let state_clean = item_rt
+                        .state_clean(params_specs, mapping_fn_reg, resources)
+                        .await?;
+                    item_rt.state_eq(state_stored, &state_clean)?
+                }
+                None => false,
+            };
+            if !state_is_clean {
+                item_ids_not_clean.push(item_id.clone());
+            }
+        }
+
+        Ok(item_ids_not_clean)
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileDeleteCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
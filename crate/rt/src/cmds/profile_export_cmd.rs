@@ -0,0 +1,142 @@
+use std::{fmt::Debug, marker::PhantomData, path::Path};
+
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields, CmdCtxTypes};
+use peace_flow_rt::Flow;
+use peace_profile_model::Profile;
+use peace_resource_rt::{
+    internal::ProfileParamsFile,
+    paths::{FlowDir, ParamsSpecsFile, StatesCurrentFile, StatesGoalFile},
+};
+use peace_rt_model::{
+    ProfileBundle, ProfileBundleFlow, ProfileBundleFlowManifest, ProfileBundleManifest, Storage,
+};
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Exports a profile to a single bundle file.
+#[derive(Debug)]
+pub struct ProfileExportCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileExportCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Writes the profile's params, and each flow's params specs and stored
+    /// states to `bundle_path`.
+    ///
+    /// Only flows that have at least one stored file within the profile are
+    /// included in the bundle. The bundle's manifest is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_ctx`: The command context.
+    /// * `flows`: Flows whose stored files to include in the bundle.
+    /// * `profile`: The profile to export.
+    /// * `bundle_path`: Path to write the bundle to.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ProfileNotInScope`] if the profile does not exist.
+    ///
+    /// [`Error::ProfileNotInScope`]: peace_rt_model::Error::ProfileNotInScope
+    pub async fn exec(
+        cmd_ctx: &CmdCtxMpnf<'_, CmdCtxTypesT>,
+        flows: &[&Flow<CmdCtxTypesT::AppError>],
+        profile: &Profile,
+        bundle_path: &Path,
+    ) -> Result<ProfileBundleManifest, CmdCtxTypesT::AppError> {
+        let CmdCtxMpnfFields {
+            workspace,
+            profiles,
+            ..
+        } = cmd_ctx.fields();
+
+        ProfileCmdSupport::profile_exists_check(profiles, profile)?;
+
+        let storage = workspace.storage();
+        let (profile_dir, _) =
+            ProfileCmdSupport::profile_and_history_dir(workspace.dirs().peace_app_dir(), profile);
+
+        let profile_params_file = ProfileParamsFile::from(&profile_dir);
+        let profile_params = Self::file_read_opt(storage, &profile_params_file).await?;
+
+        let mut flow_manifests = Vec::with_capacity(flows.len());
+        let mut bundle_flows = Vec::with_capacity(flows.len());
+        for flow in flows {
+            let flow_dir = FlowDir::from((&profile_dir, flow.flow_id()));
+            let params_specs =
+                Self::file_read_opt(storage, &ParamsSpecsFile::from(&flow_dir)).await?;
+            let states_current =
+                Self::file_read_opt(storage, &StatesCurrentFile::from(&flow_dir)).await?;
+            let states_goal =
+                Self::file_read_opt(storage, &StatesGoalFile::from(&flow_dir)).await?;
+
+            if params_specs.is_none() && states_current.is_none() && states_goal.is_none() {
+                continue;
+            }
+
+            flow_manifests.push(ProfileBundleFlowManifest {
+                flow_id: flow.flow_id().clone(),
+                item_ids: flow
+                    .graph()
+                    .iter_insertion()
+                    .map(|item| item.id().clone())
+                    .collect(),
+            });
+            bundle_flows.push(ProfileBundleFlow {
+                flow_id: flow.flow_id().clone(),
+                params_specs,
+                states_current,
+                states_goal,
+            });
+        }
+
+        let manifest = ProfileBundleManifest {
+            version: ProfileBundle::VERSION,
+            app_name: workspace.app_name().clone(),
+            profile: profile.clone(),
+            flows: flow_manifests,
+        };
+        let profile_bundle = ProfileBundle {
+            manifest,
+            profile_params,
+            flows: bundle_flows,
+        };
+
+        storage
+            .serialized_write(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ProfileExportCmd::exec".to_string(),
+                bundle_path,
+                &profile_bundle,
+                peace_rt_model::Error::ProfileBundleSerialize,
+            )
+            .await?;
+
+        Ok(profile_bundle.manifest)
+    }
+
+    /// Reads a stored file as a YAML value, if it exists.
+    async fn file_read_opt(
+        storage: &Storage,
+        path: &Path,
+    ) -> Result<Option<serde_yaml::Value>, peace_rt_model::Error> {
+        storage
+            .serialized_read_opt(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ProfileExportCmd::file_read_opt".to_string(),
+                path,
+                |error| peace_rt_model::Error::ProfileBundleFileDeserialize {
+                    path: path.to_path_buf(),
+                    error,
+                },
+            )
+            .await
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileExportCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
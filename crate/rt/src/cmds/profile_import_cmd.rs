@@ -0,0 +1,343 @@
+use std::{fmt::Debug, marker::PhantomData, path::Path, str::FromStr};
+
+use peace_cfg::AppName;
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields, CmdCtxTypes};
+use peace_flow_rt::Flow;
+use peace_item_model::ItemId;
+use peace_profile_model::Profile;
+use peace_resource_rt::{
+    internal::ProfileParamsFile,
+    paths::{FlowDir, ParamsSpecsFile, StatesCurrentFile, StatesGoalFile},
+};
+use peace_rt_model::{ProfileBundle, ProfileBundleFlow, WorkspaceInitializer};
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Imports a profile from a bundle file written by [`ProfileExportCmd`].
+///
+/// [`ProfileExportCmd`]: crate::cmds::ProfileExportCmd
+#[derive(Debug)]
+pub struct ProfileImportCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileImportCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Reads the bundle at `bundle_path`, and writes its contents to a new
+    /// profile in the workspace.
+    ///
+    /// The bundle is validated against the given flows before anything is
+    /// written. The name of the imported profile is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_ctx`: The command context.
+    /// * `flows`: Flows registered in the application.
+    /// * `bundle_path`: Path to the bundle to import.
+    /// * `profile_rename`: Name to import the profile as. If `None`, the
+    ///   profile name in the bundle is used.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ProfileBundleFileNotExists`] if the bundle does not exist.
+    /// * [`Error::ProfileBundleVersionUnsupported`] if the bundle was written
+    ///   in a different format version.
+    /// * [`Error::ProfileBundleAppNameMismatch`] if the bundle was exported by
+    ///   a different application.
+    /// * [`Error::ProfileBundleFlowNotRegistered`] if the bundle contains a
+    ///   flow that is not in `flows`.
+    /// * [`Error::ProfileBundleItemsNotInFlow`] if the bundle's manifest,
+    ///   params specs, or stored states contain items that are not in the
+    ///   flow.
+    /// * [`Error::ProfileBundleFlowFileInvalid`] if the bundle's params specs
+    ///   or stored states are not keyed by item ID.
+    /// * [`Error::ProfileBundleProfileInvalid`] if the profile name to import
+    ///   as is not a valid profile name.
+    /// * [`Error::ProfileExists`] if the profile already exists.
+    ///
+    /// [`Error::ProfileBundleFileNotExists`]: peace_rt_model::Error::ProfileBundleFileNotExists
+    /// [`Error::ProfileBundleVersionUnsupported`]: peace_rt_model::Error::ProfileBundleVersionUnsupported
+    /// [`Error::ProfileBundleAppNameMismatch`]: peace_rt_model::Error::ProfileBundleAppNameMismatch
+    /// [`Error::ProfileBundleFlowNotRegistered`]: peace_rt_model::Error::ProfileBundleFlowNotRegistered
+    /// [`Error::ProfileBundleItemsNotInFlow`]: peace_rt_model::Error::ProfileBundleItemsNotInFlow
+    /// [`Error::ProfileBundleFlowFileInvalid`]: peace_rt_model::Error::ProfileBundleFlowFileInvalid
+    /// [`Error::ProfileBundleProfileInvalid`]: peace_rt_model::Error::ProfileBundleProfileInvalid
+    /// [`Error::ProfileExists`]: peace_rt_model::Error::ProfileExists
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtxMpnf<'_, CmdCtxTypesT>,
+        flows: &[&Flow<CmdCtxTypesT::AppError>],
+        bundle_path: &Path,
+        profile_rename: Option<Profile>,
+    ) -> Result<Profile, CmdCtxTypesT::AppError> {
+        let CmdCtxMpnfFields {
+            workspace,
+            profiles,
+            profile_dirs,
+            profile_history_dirs,
+            profile_params_type_reg,
+            profile_to_profile_params,
+            ..
+        } = cmd_ctx.fields_mut();
+
+        let storage = workspace.storage();
+        let profile_bundle = storage
+            .serialized_read_opt::<ProfileBundle, _>(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ProfileImportCmd::exec".to_string(),
+                bundle_path,
+                |error| peace_rt_model::Error::ProfileBundleDeserialize {
+                    bundle_path: bundle_path.to_path_buf(),
+                    error,
+                },
+            )
+            .await?
+            .ok_or_else(|| peace_rt_model::Error::ProfileBundleFileNotExists {
+                bundle_path: bundle_path.to_path_buf(),
+            })?;
+
+        Self::bundle_validate(&profile_bundle, workspace.app_name(), flows)?;
+
+        let ProfileBundle {
+            manifest,
+            profile_params,
+            flows: bundle_flows,
+        } = profile_bundle;
+        // `Profile`s deserialized from the bundle are not validated, and the
+        // profile name is used as a directory name.
+        let profile = profile_rename.unwrap_or(manifest.profile);
+        let profile = Profile::from_str(profile.as_str()).map_err(|error| {
+            peace_rt_model::Error::ProfileBundleProfileInvalid {
+                profile: profile.into_inner().into_owned(),
+                error,
+            }
+        })?;
+        ProfileCmdSupport::profile_not_exists_check(profiles, &profile)?;
+
+        let (profile_dir, profile_history_dir) =
+            ProfileCmdSupport::profile_and_history_dir(workspace.dirs().peace_app_dir(), &profile);
+        ProfileCmdSupport::profile_and_history_dir_create(
+            workspace,
+            &profile_dir,
+            &profile_history_dir,
+        )
+        .await?;
+
+        let profile_params_file = ProfileParamsFile::from(&profile_dir);
+        if let Some(profile_params) = profile_params.as_ref() {
+            storage
+                .serialized_write(
+                    #[cfg(not(target_arch = "wasm32"))]
+                    "ProfileImportCmd::exec".to_string(),
+                    &profile_params_file,
+                    profile_params,
+                    peace_rt_model::Error::ProfileParamsSerialize,
+                )
+                .await?;
+        }
+
+        for bundle_flow in bundle_flows.iter() {
+            let flow_dir = FlowDir::from((&profile_dir, &bundle_flow.flow_id));
+            ProfileCmdSupport::dirs_create(workspace, [AsRef::<Path>::as_ref(&flow_dir)]).await?;
+
+            if let Some(params_specs) = bundle_flow.params_specs.as_ref() {
+                storage
+                    .serialized_write(
+                        #[cfg(not(target_arch = "wasm32"))]
+                        "ProfileImportCmd::exec".to_string(),
+                        &ParamsSpecsFile::from(&flow_dir),
+                        params_specs,
+                        peace_rt_model::Error::ParamsSpecsSerialize,
+                    )
+                    .await?;
+            }
+            if let Some(states_current) = bundle_flow.states_current.as_ref() {
+                storage
+                    .serialized_write(
+                        #[cfg(not(target_arch = "wasm32"))]
+                        "ProfileImportCmd::exec".to_string(),
+                        &StatesCurrentFile::from(&flow_dir),
+                        states_current,
+                        peace_rt_model::Error::StatesSerialize,
+                    )
+                    .await?;
+            }
+            if let Some(states_goal) = bundle_flow.states_goal.as_ref() {
+                storage
+                    .serialized_write(
+                        #[cfg(not(target_arch = "wasm32"))]
+                        "ProfileImportCmd::exec".to_string(),
+                        &StatesGoalFile::from(&flow_dir),
+                        states_goal,
+                        peace_rt_model::Error::StatesSerialize,
+                    )
+                    .await?;
+            }
+        }
+
+        let profile_params = WorkspaceInitializer::profile_params_deserialize(
+            storage,
+            profile_params_type_reg,
+            &profile_params_file,
+        )
+        .await?
+        .unwrap_or_default();
+
+        ProfileCmdSupport::profiles_insert(profiles, profile.clone());
+        profile_dirs.insert(profile.clone(), profile_dir);
+        profile_history_dirs.insert(profile.clone(), profile_history_dir);
+        profile_to_profile_params.insert(profile.clone(), profile_params);
+
+        Ok(profile)
+    }
+
+    /// Returns an error if the bundle cannot be imported into this
+    /// application.
+    fn bundle_validate(
+        profile_bundle: &ProfileBundle,
+        app_name: &AppName,
+        flows: &[&Flow<CmdCtxTypesT::AppError>],
+    ) -> Result<(), peace_rt_model::Error> {
+        let manifest = &profile_bundle.manifest;
+        if manifest.version != ProfileBundle::VERSION {
+            return Err(peace_rt_model::Error::ProfileBundleVersionUnsupported {
+                version: manifest.version,
+                version_supported: ProfileBundle::VERSION,
+            });
+        }
+        if &manifest.app_name != app_name {
+            return Err(peace_rt_model::Error::ProfileBundleAppNameMismatch {
+                app_name_bundle: manifest.app_name.clone(),
+                app_name: app_name.clone(),
+            });
+        }
+
+        manifest.flows.iter().try_for_each(|flow_manifest| {
+            let flow = flows
+                .iter()
+                .find(|flow| flow.flow_id() == &flow_manifest.flow_id)
+                .ok_or_else(|| peace_rt_model::Error::ProfileBundleFlowNotRegistered {
+                    flow_id: flow_manifest.flow_id.clone(),
+                    flow_ids_registered: flows.iter().map(|flow| flow.flow_id().clone()).collect(),
+                })?;
+
+            let item_ids_not_in_flow = flow_manifest
+                .item_ids
+                .iter()
+                .filter(|item_id| {
+                    !flow
+                        .graph()
+                        .iter_insertion()
+                        .any(|item| item.id() == *item_id)
+                })
+                .cloned()
+                .collect::<Vec<ItemId>>();
+            if item_ids_not_in_flow.is_empty() {
+                Ok(())
+            } else {
+                Err(peace_rt_model::Error::ProfileBundleItemsNotInFlow {
+                    flow_id: flow_manifest.flow_id.clone(),
+                    item_ids: item_ids_not_in_flow,
+                })
+            }
+        })?;
+
+        // Flows whose files are in the bundle must also be registered, even if
+        // the manifest does not mention them, and the items in those files
+        // must be in the flow.
+        profile_bundle.flows.iter().try_for_each(|bundle_flow| {
+            let flow = flows
+                .iter()
+                .find(|flow| flow.flow_id() == &bundle_flow.flow_id)
+                .ok_or_else(|| peace_rt_model::Error::ProfileBundleFlowNotRegistered {
+                    flow_id: bundle_flow.flow_id.clone(),
+                    flow_ids_registered: flows.iter().map(|flow| flow.flow_id().clone()).collect(),
+                })?;
+
+            let item_ids_not_in_flow = Self::bundle_flow_item_ids(bundle_flow)?
+                .into_iter()
+                .filter(|item_id| {
+                    !flow
+                        .graph()
+                        .iter_insertion()
+                        .any(|item| item.id() == item_id)
+                })
+                .collect::<Vec<ItemId>>();
+            if item_ids_not_in_flow.is_empty() {
+                Ok(())
+            } else {
+                Err(peace_rt_model::Error::ProfileBundleItemsNotInFlow {
+                    flow_id: bundle_flow.flow_id.clone(),
+                    item_ids: item_ids_not_in_flow,
+                })
+            }
+        })
+    }
+
+    /// Returns the item IDs that the bundle flow's params specs and stored
+    /// states are keyed by, without duplicates.
+    fn bundle_flow_item_ids(
+        bundle_flow: &ProfileBundleFlow,
+    ) -> Result<Vec<ItemId>, peace_rt_model::Error> {
+        let ProfileBundleFlow {
+            flow_id,
+            params_specs,
+            states_current,
+            states_goal,
+        } = bundle_flow;
+
+        let file_invalid =
+            |file_name: &str, reason: String| peace_rt_model::Error::ProfileBundleFlowFileInvalid {
+                flow_id: flow_id.clone(),
+                file_name: file_name.to_string(),
+                reason,
+            };
+
+        let files = [
+            (ParamsSpecsFile::NAME, params_specs),
+            (StatesCurrentFile::NAME, states_current),
+            (StatesGoalFile::NAME, states_goal),
+        ];
+        files
+            .into_iter()
+            .filter_map(|(file_name, value)| value.as_ref().map(|value| (file_name, value)))
+            .try_fold(Vec::new(), |mut item_ids, (file_name, value)| {
+                let mapping = match value {
+                    serde_yaml::Value::Null => return Ok(item_ids),
+                    serde_yaml::Value::Mapping(mapping) => mapping,
+                    _ => {
+                        return Err(file_invalid(
+                            file_name,
+                            String::from("Expected a map of item IDs to values."),
+                        ))
+                    }
+                };
+                mapping.keys().try_for_each(|key| {
+                    let item_id = key
+                        .as_str()
+                        .ok_or_else(|| {
+                            file_invalid(file_name, format!("Expected `{key:?}` to be a string."))
+                        })
+                        .and_then(|key| {
+                            ItemId::from_str(key).map_err(|error| {
+                                file_invalid(
+                                    file_name,
+                                    format!("`{key}` is not an item ID: {error}"),
+                                )
+                            })
+                        })?;
+                    if !item_ids.contains(&item_id) {
+                        item_ids.push(item_id);
+                    }
+                    Ok::<_, peace_rt_model::Error>(())
+                })?;
+
+                Ok(item_ids)
+            })
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileImportCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,27 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxTypes};
+use peace_profile_model::Profile;
+
+/// Lists the profiles in the workspace.
+#[derive(Debug)]
+pub struct ProfileListCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileListCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Returns the profiles in the workspace, sorted by name.
+    ///
+    /// Profiles excluded by the `CmdCtxMpnf`'s profile filter function are not
+    /// returned.
+    pub fn exec(cmd_ctx: &CmdCtxMpnf<'_, CmdCtxTypesT>) -> Vec<Profile> {
+        cmd_ctx.fields().profiles().to_vec()
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileListCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,84 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields, CmdCtxTypes};
+use peace_profile_model::Profile;
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Renames a profile in the workspace.
+#[derive(Debug)]
+pub struct ProfileRenameCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileRenameCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Renames `profile_from` to `profile_to`, including its stored params and
+    /// states.
+    ///
+    /// If `profile_from` is the active profile, the workspace param at
+    /// `profile_key` is updated to `profile_to`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ProfileNotInScope`] if `profile_from` does not exist.
+    /// * [`Error::ProfileExists`] if `profile_to` already exists.
+    ///
+    /// [`Error::ProfileNotInScope`]: peace_rt_model::Error::ProfileNotInScope
+    /// [`Error::ProfileExists`]: peace_rt_model::Error::ProfileExists
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtxMpnf<'_, CmdCtxTypesT>,
+        profile_key: CmdCtxTypesT::WorkspaceParamsKey,
+        profile_from: &Profile,
+        profile_to: Profile,
+    ) -> Result<(), CmdCtxTypesT::AppError> {
+        let CmdCtxMpnfFields {
+            workspace,
+            profiles,
+            profile_dirs,
+            profile_history_dirs,
+            workspace_params,
+            profile_to_profile_params,
+            ..
+        } = cmd_ctx.fields_mut();
+
+        ProfileCmdSupport::profile_exists_check(profiles, profile_from)?;
+        ProfileCmdSupport::profile_not_exists_check(profiles, &profile_to)?;
+
+        let peace_app_dir = workspace.dirs().peace_app_dir();
+        let (profile_dir_from, _) =
+            ProfileCmdSupport::profile_and_history_dir(peace_app_dir, profile_from);
+        let (profile_dir_to, profile_history_dir_to) =
+            ProfileCmdSupport::profile_and_history_dir(peace_app_dir, &profile_to);
+
+        workspace
+            .storage()
+            .dir_rename(&profile_dir_from, &profile_dir_to)
+            .await?;
+
+        if ProfileCmdSupport::profile_is_active(workspace_params, &profile_key, profile_from) {
+            workspace_params.insert(profile_key, profile_to.clone());
+            ProfileCmdSupport::workspace_params_serialize(workspace, workspace_params).await?;
+        }
+
+        profiles.retain(|profile| profile != profile_from);
+        ProfileCmdSupport::profiles_insert(profiles, profile_to.clone());
+        profile_dirs.remove(profile_from);
+        profile_dirs.insert(profile_to.clone(), profile_dir_to);
+        profile_history_dirs.remove(profile_from);
+        profile_history_dirs.insert(profile_to.clone(), profile_history_dir_to);
+        ProfileCmdSupport::profile_entry_rename(
+            profile_to_profile_params,
+            profile_from,
+            &profile_to,
+        );
+
+        Ok(())
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileRenameCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
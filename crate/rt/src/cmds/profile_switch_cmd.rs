@@ -0,0 +1,56 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields, CmdCtxTypes};
+use peace_profile_model::Profile;
+
+use crate::cmds::profile_cmd_support::ProfileCmdSupport;
+
+/// Switches the active profile in the workspace.
+#[derive(Debug)]
+pub struct ProfileSwitchCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> ProfileSwitchCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Sets the workspace param that selects the active profile, and writes
+    /// it to storage.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_ctx`: The command context.
+    /// * `profile_key`: Workspace params key that the active profile is stored
+    ///   against.
+    /// * `profile`: The profile to switch to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProfileNotInScope`] if the profile does not exist.
+    ///
+    /// [`Error::ProfileNotInScope`]: peace_rt_model::Error::ProfileNotInScope
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtxMpnf<'_, CmdCtxTypesT>,
+        profile_key: CmdCtxTypesT::WorkspaceParamsKey,
+        profile: Profile,
+    ) -> Result<(), CmdCtxTypesT::AppError> {
+        let CmdCtxMpnfFields {
+            workspace,
+            profiles,
+            workspace_params,
+            ..
+        } = cmd_ctx.fields_mut();
+
+        ProfileCmdSupport::profile_exists_check(profiles, &profile)?;
+
+        workspace_params.insert(profile_key, profile);
+        ProfileCmdSupport::workspace_params_serialize(workspace, workspace_params).await?;
+
+        Ok(())
+    }
+}
+
+impl<CmdCtxTypesT> Default for ProfileSwitchCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
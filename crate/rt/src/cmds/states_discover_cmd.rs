@@ -40,9 +40,14 @@ where
     ///
     /// This function will always serialize states to storage.
     ///
+    /// Items with a stored state that are no longer part of the flow are
+    /// listed in the [`ItemIdsOrphaned`] resource, and their stored states are
+    /// retained.
+    ///
     /// [`Current<T>`]: https://docs.rs/peace_data/latest/peace_data/marker/struct.Current.html
     /// [`Data`]: peace_cfg::TryFnSpec::Data
     /// [`Item`]: peace_cfg::Item
+    /// [`ItemIdsOrphaned`]: peace_rt_model::ItemIdsOrphaned
     /// [`try_state_current`]: peace_cfg::Item::try_state_current
     pub async fn current<'ctx>(
         cmd_ctx: &mut CmdCtxSpsf<'ctx, CmdCtxTypesT>,
@@ -211,10 +216,15 @@ where
     ///
     /// This function will always serialize states to storage.
     ///
+    /// Items with a stored state that are no longer part of the flow are
+    /// listed in the [`ItemIdsOrphaned`] resource, and their stored states are
+    /// retained.
+    ///
     /// [`Current<T>`]: https://docs.rs/peace_data/latest/peace_data/marker/struct.Current.html
     /// [`Data`]: peace_cfg::TryFnSpec::Data
     /// [`Goal<T>`]: https://docs.rs/peace_data/latest/peace_data/marker/struct.Goal.html
     /// [`Item`]: peace_cfg::Item
+    /// [`ItemIdsOrphaned`]: peace_rt_model::ItemIdsOrphaned
     /// [`try_state_current`]: peace_cfg::Item::try_state_current
     /// [`try_state_goal`]: peace_cfg::Item::try_state_goal
     pub async fn current_and_goal<'ctx>(
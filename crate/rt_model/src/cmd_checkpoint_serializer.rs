@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+
+use peace_resource_rt::paths::CmdCheckpointFile;
+use peace_rt_model_core::CmdCheckpoint;
+
+use crate::{Error, Storage};
+
+/// Reads, writes, and removes the [`CmdCheckpoint`] in storage.
+pub struct CmdCheckpointSerializer<E>(PhantomData<E>);
+
+impl<E> CmdCheckpointSerializer<E>
+where
+    E: std::error::Error + From<Error> + Send,
+{
+    /// Serializes the [`CmdCheckpoint`] of a command execution to disk.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to write to.
+    /// * `cmd_checkpoint`: `CmdCheckpoint` to serialize.
+    /// * `cmd_checkpoint_file`: Path to save the serialized checkpoint to.
+    pub async fn serialize(
+        storage: &Storage,
+        cmd_checkpoint: &CmdCheckpoint,
+        cmd_checkpoint_file: &CmdCheckpointFile,
+    ) -> Result<(), E> {
+        storage
+            .serialized_write(
+                #[cfg(not(target_arch = "wasm32"))]
+                "CmdCheckpointSerializer::serialize".to_string(),
+                cmd_checkpoint_file,
+                cmd_checkpoint,
+                Error::CmdCheckpointSerialize,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the [`CmdCheckpoint`] of an interrupted command execution if
+    /// it exists on disk.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `cmd_checkpoint_file`: `CmdCheckpointFile` to deserialize.
+    pub async fn deserialize_opt(
+        storage: &Storage,
+        cmd_checkpoint_file: &CmdCheckpointFile,
+    ) -> Result<Option<CmdCheckpoint>, E> {
+        let cmd_checkpoint = storage
+            .serialized_read_opt(
+                #[cfg(not(target_arch = "wasm32"))]
+                "CmdCheckpointSerializer::deserialize_opt".to_string(),
+                cmd_checkpoint_file,
+                |error| Error::CmdCheckpointDeserialize {
+                    path: cmd_checkpoint_file.to_path_buf(),
+                    error,
+                },
+            )
+            .await?;
+
+        Ok(cmd_checkpoint)
+    }
+
+    /// Removes the [`CmdCheckpoint`] from disk, if it exists.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to remove the checkpoint from.
+    /// * `cmd_checkpoint_file`: `CmdCheckpointFile` to remove.
+    pub async fn remove(
+        storage: &Storage,
+        cmd_checkpoint_file: &CmdCheckpointFile,
+    ) -> Result<(), E> {
+        storage.file_remove(cmd_checkpoint_file).await?;
+
+        Ok(())
+    }
+}
@@ -17,9 +17,10 @@ use std::{
 
 use peace_cfg::Item;
 use peace_data::fn_graph::{DataAccessDyn, TypeIds};
+use peace_item_model::ItemId;
 use peace_params::{Params, ParamsMergeExt};
 
-use crate::{ItemRt, ItemWrapper};
+use crate::{ItemEnableCondition, ItemRt, ItemWrapper};
 
 /// Holds a type-erased `ItemWrapper` in a `Box`.
 ///
@@ -33,6 +34,33 @@ use crate::{ItemRt, ItemWrapper};
 #[derive(Debug)]
 pub struct ItemBoxed<E>(Box<dyn ItemRt<E>>);
 
+impl<E: 'static> ItemBoxed<E> {
+    /// Returns a copy of this item with the given ID.
+    ///
+    /// This is used to namespace items when a flow is embedded in another
+    /// flow.
+    pub fn with_id(&self, item_id: ItemId) -> Self {
+        Self(self.0.with_id(item_id))
+    }
+
+    /// Returns a copy of this item whose goal state is its clean state.
+    ///
+    /// This is used for items that are being removed from a flow, so that
+    /// `EnsureCmd` cleans them up.
+    pub fn with_state_goal_clean(&self) -> Self {
+        Self(self.0.with_state_goal_clean())
+    }
+
+    /// Returns this item, which is only enabled when the given condition is
+    /// `true`.
+    ///
+    /// See [`ItemEnableCondition`].
+    #[must_use]
+    pub fn with_enable_condition(self, enable_condition: ItemEnableCondition) -> Self {
+        Self(self.0.with_enable_condition(enable_condition))
+    }
+}
+
 impl<E> Clone for ItemBoxed<E> {
     fn clone(&self) -> Self {
         Self(dyn_clone::clone_box(self.0.as_ref()))
@@ -0,0 +1,63 @@
+use std::{fmt, sync::Arc};
+
+use peace_resource_rt::{resources::ts::Empty, Resource, Resources};
+
+/// Function that evaluates whether an item is enabled.
+type EnableConditionFn = dyn Fn(&Resources<Empty>) -> bool + Send + Sync;
+
+/// Condition that determines whether an item is enabled.
+///
+/// This is evaluated when building a `CmdCtxSpsf`, after workspace, profile,
+/// and flow params are inserted into [`Resources`]. Each param value is
+/// inserted by its type, so conditions usually borrow a param's value from
+/// `resources`.
+///
+/// Items are enabled unless they have a condition that evaluates to `false`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let monitoring_item = ItemBoxed::from(MonitoringItem::new(item_id!("monitoring")))
+///     .with_enable_condition(ItemEnableCondition::param_eq(EnvType::Production));
+/// ```
+#[derive(Clone)]
+pub struct ItemEnableCondition(Arc<EnableConditionFn>);
+
+impl ItemEnableCondition {
+    /// Returns a new `ItemEnableCondition` that evaluates the given function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&Resources<Empty>) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Returns an `ItemEnableCondition` that is `true` when the param value of
+    /// type `T` equals `value`.
+    ///
+    /// The condition is `false` when there is no param value of type `T`.
+    pub fn param_eq<T>(value: T) -> Self
+    where
+        T: PartialEq + Resource,
+    {
+        Self::new(move |resources| {
+            resources
+                .try_borrow::<T>()
+                .map(|param_value| *param_value == value)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Returns whether the item is enabled.
+    pub fn is_enabled(&self, resources: &Resources<Empty>) -> bool {
+        (self.0)(resources)
+    }
+}
+
+impl fmt::Debug for ItemEnableCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ItemEnableCondition")
+            .field(&"Fn(&Resources<Empty>) -> bool")
+            .finish()
+    }
+}
@@ -0,0 +1,86 @@
+use peace_item_model::ItemId;
+
+/// IDs of items that are disabled by their [`ItemEnableCondition`].
+///
+/// This is inserted into `Resources` when building a `CmdCtxSpsf`.
+///
+/// Disabled items are skipped during discovery and apply -- their stored
+/// state is used as their current and goal state, so it is retained in
+/// `StatesCurrentStored`.
+///
+/// Disabled items whose stored state is not clean are flagged in
+/// [`item_ids_stored_not_clean`]. If the command context is built with
+/// `with_items_disabled_clean(true)`, these items are cleaned up instead of
+/// being skipped.
+///
+/// [`ItemEnableCondition`]: crate::ItemEnableCondition
+/// [`item_ids_stored_not_clean`]: Self::item_ids_stored_not_clean
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ItemIdsDisabled {
+    /// IDs of items that are disabled.
+    item_ids: Vec<ItemId>,
+    /// IDs of disabled items whose stored state is not clean.
+    item_ids_stored_not_clean: Vec<ItemId>,
+    /// Whether disabled items whose stored state is not clean are cleaned up.
+    stored_not_clean_clean: bool,
+}
+
+impl ItemIdsDisabled {
+    /// Returns a new `ItemIdsDisabled`.
+    ///
+    /// # Parameters
+    ///
+    /// * `item_ids`: IDs of items that are disabled.
+    /// * `item_ids_stored_not_clean`: IDs of disabled items whose stored state
+    ///   is not clean.
+    /// * `stored_not_clean_clean`: Whether disabled items whose stored state is
+    ///   not clean are cleaned up.
+    pub fn new(
+        item_ids: Vec<ItemId>,
+        item_ids_stored_not_clean: Vec<ItemId>,
+        stored_not_clean_clean: bool,
+    ) -> Self {
+        Self {
+            item_ids,
+            item_ids_stored_not_clean,
+            stored_not_clean_clean,
+        }
+    }
+
+    /// Returns the IDs of items that are disabled.
+    pub fn item_ids(&self) -> &[ItemId] {
+        &self.item_ids
+    }
+
+    /// Returns the IDs of disabled items whose stored state is not clean.
+    pub fn item_ids_stored_not_clean(&self) -> &[ItemId] {
+        &self.item_ids_stored_not_clean
+    }
+
+    /// Returns whether disabled items whose stored state is not clean are
+    /// cleaned up.
+    pub fn stored_not_clean_clean(&self) -> bool {
+        self.stored_not_clean_clean
+    }
+
+    /// Returns whether the item is disabled.
+    pub fn contains(&self, item_id: &ItemId) -> bool {
+        self.item_ids.contains(item_id)
+    }
+
+    /// Returns whether the item is disabled and skipped during discovery and
+    /// apply.
+    pub fn is_skipped(&self, item_id: &ItemId) -> bool {
+        self.contains(item_id) && !self.is_cleaned(item_id)
+    }
+
+    /// Returns whether the item is disabled and is cleaned up when applied.
+    pub fn is_cleaned(&self, item_id: &ItemId) -> bool {
+        self.stored_not_clean_clean && self.item_ids_stored_not_clean.contains(item_id)
+    }
+
+    /// Returns whether no items are disabled.
+    pub fn is_empty(&self) -> bool {
+        self.item_ids.is_empty()
+    }
+}
@@ -0,0 +1,46 @@
+use peace_item_model::ItemId;
+
+/// IDs of items that were interrupted while they were being applied.
+///
+/// This is inserted into `Resources` by `ApplyExecCmdBlock` when the command
+/// execution is interrupted while items are being applied, and those items
+/// stop before reaching their target state.
+///
+/// The state that each interrupted item reached is stored as its current
+/// state, but is not recorded in the `CmdCheckpoint`, so the item is applied
+/// again when the command is resumed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ItemIdsInterrupted(Vec<ItemId>);
+
+impl ItemIdsInterrupted {
+    /// Returns a new `ItemIdsInterrupted`.
+    pub fn new(item_ids: Vec<ItemId>) -> Self {
+        Self(item_ids)
+    }
+
+    /// Returns the IDs of items that were interrupted while being applied.
+    pub fn item_ids(&self) -> &[ItemId] {
+        &self.0
+    }
+
+    /// Returns the underlying IDs.
+    pub fn into_inner(self) -> Vec<ItemId> {
+        self.0
+    }
+
+    /// Returns whether the item was interrupted while being applied.
+    pub fn contains(&self, item_id: &ItemId) -> bool {
+        self.0.contains(item_id)
+    }
+
+    /// Returns whether no items were interrupted.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<ItemId> for ItemIdsInterrupted {
+    fn from_iter<I: IntoIterator<Item = ItemId>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
@@ -0,0 +1,93 @@
+use peace_item_model::ItemId;
+
+/// IDs of items in the stored states that are no longer part of the flow
+/// definition.
+///
+/// This is inserted into `Resources` when building a `CmdCtxSpsf`.
+///
+/// Orphaned items whose item type is registered as retired are added to the
+/// item graph, and are listed in [`item_ids_retired`]. These are skipped
+/// during discovery and apply, so their stored state is retained, unless
+/// [`clean`] is `true`, in which case they are cleaned up.
+///
+/// Orphaned items that are not retired cannot be cleaned up by the framework,
+/// and are listed in [`item_ids_unretired`].
+///
+/// [`clean`]: Self::clean
+/// [`item_ids_retired`]: Self::item_ids_retired
+/// [`item_ids_unretired`]: Self::item_ids_unretired
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ItemIdsOrphaned {
+    /// IDs of orphaned items, including retired items.
+    item_ids: Vec<ItemId>,
+    /// IDs of orphaned items that are retired items in the graph.
+    item_ids_retired: Vec<ItemId>,
+    /// Whether retired items are cleaned up.
+    clean: bool,
+}
+
+impl ItemIdsOrphaned {
+    /// Returns a new `ItemIdsOrphaned`.
+    ///
+    /// # Parameters
+    ///
+    /// * `item_ids`: IDs of orphaned items, including retired items.
+    /// * `item_ids_retired`: IDs of orphaned items that are retired items in
+    ///   the graph.
+    pub fn new(item_ids: Vec<ItemId>, item_ids_retired: Vec<ItemId>) -> Self {
+        Self {
+            item_ids,
+            item_ids_retired,
+            clean: false,
+        }
+    }
+
+    /// Returns the IDs of orphaned items, including retired items.
+    pub fn item_ids(&self) -> &[ItemId] {
+        &self.item_ids
+    }
+
+    /// Returns the IDs of orphaned items that are retired items in the graph.
+    pub fn item_ids_retired(&self) -> &[ItemId] {
+        &self.item_ids_retired
+    }
+
+    /// Returns the IDs of orphaned items that are not retired, and so cannot
+    /// be cleaned up.
+    pub fn item_ids_unretired(&self) -> impl Iterator<Item = &ItemId> + '_ {
+        self.item_ids
+            .iter()
+            .filter(|item_id| !self.item_ids_retired.contains(item_id))
+    }
+
+    /// Returns whether retired items are cleaned up.
+    pub fn clean(&self) -> bool {
+        self.clean
+    }
+
+    /// Sets whether retired items are cleaned up.
+    pub fn set_clean(&mut self, clean: bool) {
+        self.clean = clean;
+    }
+
+    /// Returns whether the item is orphaned.
+    pub fn contains(&self, item_id: &ItemId) -> bool {
+        self.item_ids.contains(item_id)
+    }
+
+    /// Returns whether the item is retired and skipped during discovery and
+    /// apply.
+    pub fn is_skipped(&self, item_id: &ItemId) -> bool {
+        !self.clean && self.item_ids_retired.contains(item_id)
+    }
+
+    /// Returns whether the item is retired and is cleaned up when applied.
+    pub fn is_cleaned(&self, item_id: &ItemId) -> bool {
+        self.clean && self.item_ids_retired.contains(item_id)
+    }
+
+    /// Returns whether no items are orphaned.
+    pub fn is_empty(&self) -> bool {
+        self.item_ids.is_empty()
+    }
+}
@@ -0,0 +1,46 @@
+use peace_item_model::ItemId;
+
+/// IDs of items that were completed by an interrupted command execution.
+///
+/// This is inserted into `Resources` when resuming a command from its
+/// `CmdCheckpoint`.
+///
+/// Resumed items are skipped during discovery and apply -- their state from
+/// the checkpoint is used as their current and goal state, so it is retained
+/// in `StatesCurrentStored`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ItemIdsResumed(Vec<ItemId>);
+
+impl ItemIdsResumed {
+    /// Returns a new `ItemIdsResumed`.
+    pub fn new(item_ids: Vec<ItemId>) -> Self {
+        Self(item_ids)
+    }
+
+    /// Returns the IDs of items that were completed by the interrupted
+    /// execution.
+    pub fn item_ids(&self) -> &[ItemId] {
+        &self.0
+    }
+
+    /// Returns the underlying IDs.
+    pub fn into_inner(self) -> Vec<ItemId> {
+        self.0
+    }
+
+    /// Returns whether the item was completed by the interrupted execution.
+    pub fn contains(&self, item_id: &ItemId) -> bool {
+        self.0.contains(item_id)
+    }
+
+    /// Returns whether no items were completed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<ItemId> for ItemIdsResumed {
+    fn from_iter<I: IntoIterator<Item = ItemId>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
@@ -1,10 +1,16 @@
-use std::{any::Any, fmt::Debug};
+use std::{
+    any::{Any, TypeId},
+    fmt::Debug,
+};
 
 use dyn_clone::DynClone;
 use peace_cfg::{async_trait, FnCtx};
-use peace_data::fn_graph::{DataAccess, DataAccessDyn};
+use peace_data::{
+    fn_graph::{DataAccess, DataAccessDyn},
+    DataBorrowInfo,
+};
 use peace_item_model::ItemId;
-use peace_params::{MappingFnReg, ParamsSpecs};
+use peace_params::{MappingFnReg, ParamsSpecs, ValueResolutionMode};
 use peace_resource_rt::{
     resources::ts::{Empty, SetUp},
     states::StatesCurrent,
@@ -14,7 +20,7 @@ use peace_resource_rt::{
 
 use crate::{
     outcomes::{ItemApplyBoxed, ItemApplyPartialBoxed},
-    ParamsSpecsTypeReg, StatesTypeReg,
+    ItemEnableCondition, ParamsApplied, ParamsSpecsTypeReg, StatesTypeReg,
 };
 
 /// Internal trait that erases the types from [`Item`]
@@ -34,6 +40,38 @@ pub trait ItemRt<E>:
     /// [`Item::id`]: peace_cfg::Item::id
     fn id(&self) -> &ItemId;
 
+    /// Returns a copy of this item with the given ID.
+    ///
+    /// This is used to namespace items when a flow is embedded in another
+    /// flow.
+    fn with_id(&self, item_id: ItemId) -> Box<dyn ItemRt<E>>;
+
+    /// Returns a copy of this item whose goal state is its clean state.
+    ///
+    /// This is used for items that are being removed from a flow, so that
+    /// `EnsureCmd` cleans them up.
+    fn with_state_goal_clean(&self) -> Box<dyn ItemRt<E>>;
+
+    /// Returns whether this item's goal state is its clean state.
+    fn state_goal_is_clean(&self) -> bool;
+
+    /// Returns whether this item is cleaned up when the flow is ensured.
+    ///
+    /// This is `true` when the item's goal state is its clean state and it is
+    /// not skipped, or when the item is disabled and disabled items are
+    /// cleaned up.
+    fn is_cleaned_on_ensure(&self, resources: &Resources<SetUp>) -> bool;
+
+    /// Returns a copy of this item that is only enabled when the given
+    /// condition is `true`.
+    fn with_enable_condition(&self, enable_condition: ItemEnableCondition) -> Box<dyn ItemRt<E>>;
+
+    /// Returns whether this item is enabled.
+    ///
+    /// This is `true` unless the item has an [`ItemEnableCondition`] that
+    /// evaluates to `false`.
+    fn is_enabled(&self, resources: &Resources<Empty>) -> bool;
+
     /// Returns whether this item is equal to the other.
     fn eq(&self, other: &dyn ItemRt<E>) -> bool;
 
@@ -43,6 +81,23 @@ pub trait ItemRt<E>:
     /// requirement.
     fn as_any(&self) -> &dyn Any;
 
+    /// Returns the `TypeId` of this item's [`Item::State`].
+    ///
+    /// This is used to determine which item produces the data that a mapping
+    /// function reads.
+    ///
+    /// [`Item::State`]: peace_cfg::Item::State
+    fn state_type_id(&self) -> TypeId;
+
+    /// Returns information about each type that this item's [`Item::Data`]
+    /// borrows.
+    ///
+    /// This does not include the item's params, which are inserted by the
+    /// framework before each function is run.
+    ///
+    /// [`Item::Data`]: peace_cfg::Item::Data
+    fn data_borrow_infos(&self) -> Vec<DataBorrowInfo>;
+
     /// Initializes data for the item's functions.
     async fn setup(&self, resources: &mut Resources<Empty>) -> Result<(), E>
     where
@@ -77,6 +132,58 @@ pub trait ItemRt<E>:
     where
         E: Debug + std::error::Error;
 
+    /// Resolves this item's params partially and runs
+    /// [`Params::validate_partial`] on them.
+    ///
+    /// This is run for every item before any item is applied, so that invalid
+    /// values are reported before any changes are made.
+    ///
+    /// Fields that cannot be resolved yet -- such as values that are only
+    /// available after a predecessor is applied -- are not validated here, and
+    /// are instead validated when they are resolved during the apply.
+    ///
+    /// [`Params::validate_partial`]: peace_params::Params::validate_partial
+    fn params_validate(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<(), E>
+    where
+        E: Debug + std::error::Error;
+
+    /// Returns this item's serialized params spec and params, to compare with
+    /// or store as the params that the item is applied with.
+    ///
+    /// Params that cannot be resolved yet -- such as values that are only
+    /// available after a predecessor is applied -- are returned as `None`.
+    fn params_applied(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<ParamsApplied, E>
+    where
+        E: Debug + std::error::Error;
+
+    /// Returns the names of this item's params fields whose values are
+    /// secret.
+    ///
+    /// See [`Params::field_names_secret`].
+    ///
+    /// [`Params::field_names_secret`]: peace_params::Params::field_names_secret
+    fn params_field_names_secret(&self) -> Vec<&'static str>;
+
+    /// Returns the JSON Schema of this item's params spec.
+    ///
+    /// See [`Params::params_spec_schema`].
+    ///
+    /// [`Params::params_spec_schema`]: peace_params::Params::params_spec_schema
+    #[cfg(feature = "params_schema")]
+    fn params_spec_schema(&self, generator: &mut schemars::SchemaGenerator) -> schemars::Schema;
+
     /// Returns an example fully deployed state of the managed item.
     ///
     /// # Design
@@ -1,5 +1,5 @@
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     fmt::{self, Debug},
     marker::PhantomData,
     ops::{Deref, DerefMut},
@@ -9,16 +9,16 @@ use peace_cfg::{async_trait, ApplyCheck, FnCtx, Item};
 use peace_data::{
     fn_graph::{DataAccess, DataAccessDyn, TypeIds},
     marker::{ApplyDry, Clean, Current, Goal},
-    Data,
+    Data, DataBorrowInfo,
 };
 use peace_item_model::ItemId;
 use peace_params::{
-    MappingFnReg, Params, ParamsMergeExt, ParamsSpec, ParamsSpecs, ValueResolutionCtx,
-    ValueResolutionMode,
+    ItemsOutputs, MappingFnReg, Params, ParamsMergeExt, ParamsSpec, ParamsSpecs,
+    ValueResolutionCtx, ValueResolutionMode,
 };
 use peace_resource_rt::{
     resources::ts::{Empty, SetUp},
-    states::StatesCurrent,
+    states::{StatesCurrent, StatesCurrentStored},
     type_reg::untagged::{BoxDtDisplay, TypeMap},
     Resources,
 };
@@ -26,7 +26,8 @@ use type_reg::untagged::BoxDataTypeDowncast;
 
 use crate::{
     outcomes::{ItemApply, ItemApplyBoxed, ItemApplyPartial, ItemApplyPartialBoxed},
-    ItemRt, ParamsSpecsTypeReg, StateDowncastError, StatesTypeReg,
+    ItemEnableCondition, ItemIdsDisabled, ItemIdsOrphaned, ItemIdsResumed, ItemRt, ParamsApplied,
+    ParamsSpecsTypeReg, StateDowncastError, StatesTypeReg,
 };
 
 #[cfg(feature = "output_progress")]
@@ -46,15 +47,38 @@ use peace_item_interaction_model::ItemLocationState;
 ///     Notably, `E` here should be the application's error type, which is not
 ///     necessarily the item's error type (unless you have only one item
 ///     spec in the application).
+///
+/// The item ID is stored separately from the item, so that the item can be
+/// namespaced when it is embedded in another flow as a sub-flow.
 #[allow(clippy::type_complexity)]
-pub struct ItemWrapper<I, E>(I, PhantomData<E>);
+pub struct ItemWrapper<I, E> {
+    /// The wrapped item.
+    item: I,
+    /// ID of the item, which may be namespaced.
+    item_id: ItemId,
+    /// Whether the item's goal state is its clean state.
+    ///
+    /// This is used for items that are being removed from a flow, so that
+    /// `EnsureCmd` cleans them up.
+    state_goal_is_clean: bool,
+    /// Condition that determines whether this item is enabled.
+    enable_condition: Option<ItemEnableCondition>,
+    /// Marker.
+    marker: PhantomData<E>,
+}
 
 impl<I, E> Clone for ItemWrapper<I, E>
 where
     I: Clone,
 {
     fn clone(&self) -> Self {
-        Self(self.0.clone(), PhantomData)
+        Self {
+            item: self.item.clone(),
+            item_id: self.item_id.clone(),
+            state_goal_is_clean: self.state_goal_is_clean,
+            enable_condition: self.enable_condition.clone(),
+            marker: PhantomData,
+        }
     }
 }
 
@@ -66,6 +90,53 @@ impl<I, E> PartialEq for ItemWrapper<I, E> {
 
 impl<I, E> Eq for ItemWrapper<I, E> {}
 
+impl<I, E> ItemWrapper<I, E> {
+    /// Returns the ID of this item.
+    ///
+    /// This differs from the wrapped item's [`Item::id`] when the item is
+    /// within a sub-flow.
+    pub fn id(&self) -> &ItemId {
+        &self.item_id
+    }
+
+    /// Returns whether this item's goal state is its clean state.
+    pub fn state_goal_is_clean(&self) -> bool {
+        self.state_goal_is_clean
+    }
+
+    /// Returns the condition that determines whether this item is enabled.
+    pub fn enable_condition(&self) -> Option<&ItemEnableCondition> {
+        self.enable_condition.as_ref()
+    }
+
+    /// Returns whether this item is disabled, retired, or was completed by a
+    /// resumed execution, and skipped during discovery and apply.
+    fn is_skipped(&self, resources: &Resources<SetUp>) -> bool {
+        let is_disabled_skipped = resources
+            .try_borrow::<ItemIdsDisabled>()
+            .map(|item_ids_disabled| item_ids_disabled.is_skipped(self.id()))
+            .unwrap_or(false);
+        let is_retired_skipped = resources
+            .try_borrow::<ItemIdsOrphaned>()
+            .map(|item_ids_orphaned| item_ids_orphaned.is_skipped(self.id()))
+            .unwrap_or(false);
+        let is_resumed_skipped = resources
+            .try_borrow::<ItemIdsResumed>()
+            .map(|item_ids_resumed| item_ids_resumed.contains(self.id()))
+            .unwrap_or(false);
+
+        is_disabled_skipped || is_retired_skipped || is_resumed_skipped
+    }
+
+    /// Returns whether this item is disabled and is cleaned up when applied.
+    fn is_cleaned(&self, resources: &Resources<SetUp>) -> bool {
+        resources
+            .try_borrow::<ItemIdsDisabled>()
+            .map(|item_ids_disabled| item_ids_disabled.is_cleaned(self.id()))
+            .unwrap_or(false)
+    }
+}
+
 impl<I, E> ItemWrapper<I, E>
 where
     I: Debug + Item + Send + Sync,
@@ -130,6 +201,15 @@ where
         resources: &Resources<SetUp>,
         fn_ctx: FnCtx<'_>,
     ) -> Result<Option<I::State>, E> {
+        if self.is_skipped(resources) {
+            let state_current = self.state_current_stored(resources);
+            if let Some(state_current) = state_current.as_ref() {
+                resources.borrow_mut::<Current<I::State>>().0 = Some(state_current.clone());
+            }
+
+            return Ok(state_current);
+        }
+
         let state_current = {
             let params_partial = self.params_partial(
                 params_specs,
@@ -159,6 +239,15 @@ where
         resources: &Resources<SetUp>,
         fn_ctx: FnCtx<'_>,
     ) -> Result<I::State, E> {
+        if self.is_skipped(resources) {
+            let state_current = self
+                .state_skipped(params_specs, mapping_fn_reg, resources)
+                .await?;
+            resources.borrow_mut::<Current<I::State>>().0 = Some(state_current.clone());
+
+            return Ok(state_current);
+        }
+
         let state_current = {
             let params = self.params(
                 params_specs,
@@ -186,6 +275,21 @@ where
         resources: &Resources<SetUp>,
         fn_ctx: FnCtx<'_>,
     ) -> Result<Option<I::State>, E> {
+        if self.is_skipped(resources) {
+            let state_goal = self.state_current_stored(resources);
+            if let Some(state_goal) = state_goal.as_ref() {
+                resources.borrow_mut::<Goal<I::State>>().0 = Some(state_goal.clone());
+            }
+
+            return Ok(state_goal);
+        }
+        if self.state_goal_is_clean || self.is_cleaned(resources) {
+            return self
+                .state_goal_clean(params_specs, mapping_fn_reg, resources)
+                .await
+                .map(Some);
+        }
+
         let params_partial = self.params_partial(
             params_specs,
             mapping_fn_reg,
@@ -231,6 +335,20 @@ where
         resources: &Resources<SetUp>,
         fn_ctx: FnCtx<'_>,
     ) -> Result<I::State, E> {
+        if self.is_skipped(resources) {
+            let state_goal = self
+                .state_skipped(params_specs, mapping_fn_reg, resources)
+                .await?;
+            resources.borrow_mut::<Goal<I::State>>().0 = Some(state_goal.clone());
+
+            return Ok(state_goal);
+        }
+        if self.state_goal_is_clean || self.is_cleaned(resources) {
+            return self
+                .state_goal_clean(params_specs, mapping_fn_reg, resources)
+                .await;
+        }
+
         let params = self.params(
             params_specs,
             mapping_fn_reg,
@@ -244,6 +362,51 @@ where
         Ok(state_goal)
     }
 
+    /// Returns the stored current state of this item, if any.
+    fn state_current_stored(&self, resources: &Resources<SetUp>) -> Option<I::State> {
+        resources
+            .try_borrow::<StatesCurrentStored>()
+            .ok()
+            .and_then(|states_current_stored| {
+                states_current_stored.get::<I::State, _>(self.id()).cloned()
+            })
+    }
+
+    /// Returns the state to use for this item when it is disabled and skipped.
+    ///
+    /// This is the stored current state, or the clean state if there is none,
+    /// so that skipped items are not discovered or applied.
+    async fn state_skipped(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Result<I::State, E> {
+        match self.state_current_stored(resources) {
+            Some(state_current_stored) => Ok(state_current_stored),
+            None => {
+                self.state_clean(params_specs, mapping_fn_reg, resources)
+                    .await
+            }
+        }
+    }
+
+    /// Returns the clean state as the goal state, for items that are being
+    /// removed from a flow.
+    async fn state_goal_clean(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Result<I::State, E> {
+        let state_clean = self
+            .state_clean(params_specs, mapping_fn_reg, resources)
+            .await?;
+        resources.borrow_mut::<Goal<I::State>>().0 = Some(state_clean.clone());
+
+        Ok(state_clean)
+    }
+
     async fn state_diff_exec(
         &self,
         params_specs: &ParamsSpecs,
@@ -252,7 +415,7 @@ where
         states_a: &TypeMap<ItemId, BoxDtDisplay>,
         states_b: &TypeMap<ItemId, BoxDtDisplay>,
     ) -> Result<Option<I::StateDiff>, E> {
-        let item_id = <I as Item>::id(self);
+        let item_id = self.id();
         let state_base = states_a.get::<I::State, _>(item_id);
         let state_goal = states_b.get::<I::State, _>(item_id);
 
@@ -327,6 +490,10 @@ where
         state_diff: &I::StateDiff,
         value_resolution_mode: ValueResolutionMode,
     ) -> Result<ApplyCheck, E> {
+        if self.is_skipped(resources) {
+            return Ok(ApplyCheck::ExecNotRequired);
+        }
+
         // Normally an `apply_check` only compares the states / state diff.
         //
         // We use `ValueResolutionMode::Goal` because an apply is between the current
@@ -406,6 +573,12 @@ where
 
         resources.borrow_mut::<Current<I::State>>().0 = Some(state_ensured.clone());
 
+        // Successors that reference this item's outputs read the newly published
+        // values.
+        if let Ok(mut items_outputs) = resources.try_borrow_mut::<ItemsOutputs>() {
+            items_outputs.item_outputs_set(self.id().clone(), I::outputs(&state_ensured));
+        }
+
         #[cfg(feature = "output_progress")]
         fn_ctx
             .progress_sender()
@@ -455,9 +628,14 @@ where
             item_id.clone(),
             tynm::type_name::<I::Params<'_>>(),
         );
-        Ok(params_spec
+        let params = params_spec
             .resolve(&mapping_fn_reg, resources, &mut value_resolution_ctx)
-            .map_err(crate::Error::ParamsResolveError)?)
+            .map_err(crate::Error::ParamsResolveError)?;
+        params
+            .validate(&mut value_resolution_ctx)
+            .map_err(crate::Error::ParamsResolveError)?;
+
+        Ok(params)
     }
 }
 
@@ -466,7 +644,7 @@ where
     I: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
+        self.item.fmt(f)
     }
 }
 
@@ -474,13 +652,13 @@ impl<I, E> Deref for ItemWrapper<I, E> {
     type Target = I;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.item
     }
 }
 
 impl<I, E> DerefMut for ItemWrapper<I, E> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.item
     }
 }
 
@@ -490,7 +668,14 @@ where
     E: Debug + Send + Sync + std::error::Error + From<<I as Item>::Error> + 'static,
 {
     fn from(item: I) -> Self {
-        Self(item, PhantomData)
+        let item_id = <I as Item>::id(&item).clone();
+        Self {
+            item,
+            item_id,
+            state_goal_is_clean: false,
+            enable_condition: None,
+            marker: PhantomData,
+        }
     }
 }
 
@@ -545,7 +730,43 @@ where
         + From<<I::Params<'params> as TryFrom<<I::Params<'params> as Params>::Partial>>::Error>,
 {
     fn id(&self) -> &ItemId {
-        <I as Item>::id(self)
+        ItemWrapper::id(self)
+    }
+
+    fn with_id(&self, item_id: ItemId) -> Box<dyn ItemRt<E>> {
+        Box::new(Self {
+            item_id,
+            ..self.clone()
+        })
+    }
+
+    fn with_state_goal_clean(&self) -> Box<dyn ItemRt<E>> {
+        Box::new(Self {
+            state_goal_is_clean: true,
+            ..self.clone()
+        })
+    }
+
+    fn state_goal_is_clean(&self) -> bool {
+        ItemWrapper::state_goal_is_clean(self)
+    }
+
+    fn is_cleaned_on_ensure(&self, resources: &Resources<SetUp>) -> bool {
+        !self.is_skipped(resources) && (self.state_goal_is_clean || self.is_cleaned(resources))
+    }
+
+    fn with_enable_condition(&self, enable_condition: ItemEnableCondition) -> Box<dyn ItemRt<E>> {
+        Box::new(Self {
+            enable_condition: Some(enable_condition),
+            ..self.clone()
+        })
+    }
+
+    fn is_enabled(&self, resources: &Resources<Empty>) -> bool {
+        self.enable_condition
+            .as_ref()
+            .map(|enable_condition| enable_condition.is_enabled(resources))
+            .unwrap_or(true)
     }
 
     fn eq(&self, other: &dyn ItemRt<E>) -> bool {
@@ -568,6 +789,14 @@ where
         self
     }
 
+    fn state_type_id(&self) -> TypeId {
+        TypeId::of::<I::State>()
+    }
+
+    fn data_borrow_infos(&self) -> Vec<DataBorrowInfo> {
+        <I::Data<'_> as Data<'_>>::borrow_infos()
+    }
+
     async fn setup(&self, resources: &mut Resources<Empty>) -> Result<(), E> {
         // Insert `XMarker<I::State>` to create entries in `Resources`.
         // This is used for referential param values (#94)
@@ -589,8 +818,8 @@ where
         params_specs_type_reg: &mut ParamsSpecsTypeReg,
         states_type_reg: &mut StatesTypeReg,
     ) {
-        params_specs_type_reg.register::<ParamsSpec<I::Params<'_>>>(I::id(self).clone());
-        states_type_reg.register::<I::State>(I::id(self).clone());
+        params_specs_type_reg.register::<ParamsSpec<I::Params<'_>>>(self.id().clone());
+        states_type_reg.register::<I::State>(self.id().clone());
     }
 
     fn state_eq(&self, state_a: &BoxDtDisplay, state_b: &BoxDtDisplay) -> Result<bool, E> {
@@ -620,6 +849,80 @@ where
         }
     }
 
+    fn params_validate(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<(), E> {
+        let item_id = self.id();
+        let params_spec = params_specs
+            .get::<ParamsSpec<I::Params<'_>>, _>(item_id)
+            .ok_or_else(|| crate::Error::ParamsSpecNotFound {
+                item_id: item_id.clone(),
+            })?;
+        let mut value_resolution_ctx = ValueResolutionCtx::new(
+            value_resolution_mode,
+            item_id.clone(),
+            tynm::type_name::<I::Params<'_>>(),
+        );
+
+        // Values from predecessors may not be available until they are applied, so
+        // the fields that are resolved are validated here, and the remaining fields
+        // are validated when they are resolved during the apply.
+        let params_partial = params_spec
+            .resolve_partial(mapping_fn_reg, resources, &mut value_resolution_ctx)
+            .map_err(crate::Error::ParamsResolveError)?;
+        <I::Params<'_> as Params>::validate_partial(&params_partial, &mut value_resolution_ctx)
+            .map_err(crate::Error::ParamsResolveError)?;
+
+        Ok(())
+    }
+
+    fn params_applied(
+        &self,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<ParamsApplied, E> {
+        let item_id = self.id();
+        let params_spec = params_specs
+            .get::<ParamsSpec<I::Params<'_>>, _>(item_id)
+            .ok_or_else(|| crate::Error::ParamsSpecNotFound {
+                item_id: item_id.clone(),
+            })?;
+        let value_serialize_error = |error| crate::Error::ParamsAppliedValueSerialize {
+            item_id: item_id.clone(),
+            error,
+        };
+        let mut value_resolution_ctx = ValueResolutionCtx::new(
+            value_resolution_mode,
+            item_id.clone(),
+            tynm::type_name::<I::Params<'_>>(),
+        );
+
+        let params_spec_value = serde_yaml::to_value(params_spec).map_err(value_serialize_error)?;
+        let params_value = params_spec
+            .resolve(mapping_fn_reg, resources, &mut value_resolution_ctx)
+            .ok()
+            .map(|params| serde_yaml::to_value(&params))
+            .transpose()
+            .map_err(value_serialize_error)?;
+
+        Ok(ParamsApplied::new(params_spec_value, params_value))
+    }
+
+    fn params_field_names_secret(&self) -> Vec<&'static str> {
+        <I::Params<'_> as Params>::field_names_secret()
+    }
+
+    #[cfg(feature = "params_schema")]
+    fn params_spec_schema(&self, generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        <I::Params<'_> as Params>::params_spec_schema(generator)
+    }
+
     #[cfg(feature = "item_state_example")]
     fn state_example(
         &self,
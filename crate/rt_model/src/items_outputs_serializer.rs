@@ -0,0 +1,68 @@
+use std::marker::PhantomData;
+
+use peace_params::ItemsOutputs;
+use peace_resource_rt::paths::OutputsFile;
+
+use crate::{Error, Storage};
+
+/// Reads and writes [`ItemsOutputs`] to and from storage.
+pub struct ItemsOutputsSerializer<E>(PhantomData<E>);
+
+impl<E> ItemsOutputsSerializer<E>
+where
+    E: std::error::Error + From<Error> + Send,
+{
+    /// Serializes the [`ItemsOutputs`] of all [`Item`]s to disk.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to write to.
+    /// * `items_outputs`: `ItemsOutputs` to serialize.
+    /// * `outputs_file`: Path to save the serialized outputs to.
+    ///
+    /// [`Item`]: peace_cfg::Item
+    pub async fn serialize(
+        storage: &Storage,
+        items_outputs: &ItemsOutputs,
+        outputs_file: &OutputsFile,
+    ) -> Result<(), E> {
+        storage
+            .serialized_write(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ItemsOutputsSerializer::serialize".to_string(),
+                outputs_file,
+                items_outputs,
+                Error::ItemsOutputsSerialize,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the [`ItemsOutputs`] of all [`Item`]s if it exists on disk.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `outputs_file`: `OutputsFile` to deserialize.
+    ///
+    /// [`Item`]: peace_cfg::Item
+    pub async fn deserialize_opt(
+        storage: &Storage,
+        outputs_file: &OutputsFile,
+    ) -> Result<Option<ItemsOutputs>, E> {
+        let items_outputs = storage
+            .serialized_read_opt(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ItemsOutputsSerializer::deserialize_opt".to_string(),
+                outputs_file,
+                |error| Error::ItemsOutputsDeserialize {
+                    path: outputs_file.to_path_buf(),
+                    error,
+                },
+            )
+            .await?;
+
+        Ok(items_outputs)
+    }
+}
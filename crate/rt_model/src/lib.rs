@@ -16,17 +16,31 @@ pub use peace_rt_model_native::*;
 pub use peace_rt_model_web::*;
 
 pub use crate::{
-    in_memory_text_output::InMemoryTextOutput, item_boxed::ItemBoxed, item_rt::ItemRt,
-    item_wrapper::ItemWrapper, params_specs_serializer::ParamsSpecsSerializer,
-    params_specs_type_reg::ParamsSpecsTypeReg, states_type_reg::StatesTypeReg,
+    cmd_checkpoint_serializer::CmdCheckpointSerializer, in_memory_text_output::InMemoryTextOutput,
+    item_boxed::ItemBoxed, item_enable_condition::ItemEnableCondition,
+    item_ids_disabled::ItemIdsDisabled, item_ids_interrupted::ItemIdsInterrupted,
+    item_ids_orphaned::ItemIdsOrphaned, item_ids_resumed::ItemIdsResumed, item_rt::ItemRt,
+    item_wrapper::ItemWrapper, items_outputs_serializer::ItemsOutputsSerializer,
+    params_applied_serializer::ParamsAppliedSerializer,
+    params_specs_serializer::ParamsSpecsSerializer, params_specs_type_reg::ParamsSpecsTypeReg,
+    states_resumed::StatesResumed, states_type_reg::StatesTypeReg,
 };
 
 pub mod outcomes;
 
+mod cmd_checkpoint_serializer;
 mod in_memory_text_output;
 mod item_boxed;
+mod item_enable_condition;
+mod item_ids_disabled;
+mod item_ids_interrupted;
+mod item_ids_orphaned;
+mod item_ids_resumed;
 mod item_rt;
 mod item_wrapper;
+mod items_outputs_serializer;
+mod params_applied_serializer;
 mod params_specs_serializer;
 mod params_specs_type_reg;
+mod states_resumed;
 mod states_type_reg;
@@ -0,0 +1,68 @@
+use std::marker::PhantomData;
+
+use peace_resource_rt::paths::ParamsAppliedFile;
+
+use crate::{Error, ItemsParamsApplied, Storage};
+
+/// Reads and writes [`ItemsParamsApplied`] to and from storage.
+pub struct ParamsAppliedSerializer<E>(PhantomData<E>);
+
+impl<E> ParamsAppliedSerializer<E>
+where
+    E: std::error::Error + From<Error> + Send,
+{
+    /// Serializes the [`ItemsParamsApplied`] of all [`Item`]s to disk.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to write to.
+    /// * `items_params_applied`: `ItemsParamsApplied` to serialize.
+    /// * `params_applied_file`: Path to save the serialized params applied to.
+    ///
+    /// [`Item`]: peace_cfg::Item
+    pub async fn serialize(
+        storage: &Storage,
+        items_params_applied: &ItemsParamsApplied,
+        params_applied_file: &ParamsAppliedFile,
+    ) -> Result<(), E> {
+        storage
+            .serialized_write(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ParamsAppliedSerializer::serialize".to_string(),
+                params_applied_file,
+                items_params_applied,
+                Error::ParamsAppliedSerialize,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the [`ItemsParamsApplied`] of all [`Item`]s if it exists on
+    /// disk.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `params_applied_file`: `ParamsAppliedFile` to deserialize.
+    ///
+    /// [`Item`]: peace_cfg::Item
+    pub async fn deserialize_opt(
+        storage: &Storage,
+        params_applied_file: &ParamsAppliedFile,
+    ) -> Result<Option<ItemsParamsApplied>, E> {
+        let items_params_applied = storage
+            .serialized_read_opt(
+                #[cfg(not(target_arch = "wasm32"))]
+                "ParamsAppliedSerializer::deserialize_opt".to_string(),
+                params_applied_file,
+                |error| Error::ParamsAppliedDeserialize {
+                    path: params_applied_file.to_path_buf(),
+                    error,
+                },
+            )
+            .await?;
+
+        Ok(items_params_applied)
+    }
+}
@@ -0,0 +1,37 @@
+use peace_resource_rt::states::{StatesCurrentStored, StatesGoalStored};
+
+/// Stored states to use when resuming a command from its `CmdCheckpoint`.
+///
+/// These are the stored states with each completed item's state replaced by
+/// its state from the checkpoint. They are read in place of the states files,
+/// which are only written after the resumed command is applied.
+#[derive(Clone, Debug)]
+pub struct StatesResumed {
+    /// Stored current states with the completed items' states.
+    states_current_stored: StatesCurrentStored,
+    /// Stored goal states with the completed items' states.
+    states_goal_stored: StatesGoalStored,
+}
+
+impl StatesResumed {
+    /// Returns a new `StatesResumed`.
+    pub fn new(
+        states_current_stored: StatesCurrentStored,
+        states_goal_stored: StatesGoalStored,
+    ) -> Self {
+        Self {
+            states_current_stored,
+            states_goal_stored,
+        }
+    }
+
+    /// Returns the stored current states with the completed items' states.
+    pub fn states_current_stored(&self) -> &StatesCurrentStored {
+        &self.states_current_stored
+    }
+
+    /// Returns the stored goal states with the completed items' states.
+    pub fn states_goal_stored(&self) -> &StatesGoalStored {
+        &self.states_goal_stored
+    }
+}
@@ -0,0 +1,102 @@
+use indexmap::IndexMap;
+use peace_cmd_model::CmdExecutionId;
+use peace_item_model::ItemId;
+use serde::{Deserialize, Serialize};
+
+/// Progress of a command execution, persisted as items finish applying.
+///
+/// This is written to `cmd_checkpoint.yaml` in the flow directory after each
+/// item is successfully applied, and removed when the execution completes.
+/// If the execution is interrupted, the checkpoint remains, and the command
+/// may be resumed, skipping the items that were already completed.
+///
+/// States are stored in their serialized form, so that the checkpoint can be
+/// validated against the stored states without needing the states type
+/// registry.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct CmdCheckpoint {
+    /// ID of the execution that wrote this checkpoint.
+    cmd_execution_id: CmdExecutionId,
+    /// Name of the command that was executed, e.g. `"EnsureCmd"`.
+    cmd_name: String,
+    /// Stored current state of each completed item before it was applied.
+    ///
+    /// Items that had no stored state are not present in this map.
+    states_current_stored: IndexMap<ItemId, serde_yaml::Value>,
+    /// State of each completed item after it was applied.
+    states_completed: IndexMap<ItemId, serde_yaml::Value>,
+}
+
+impl CmdCheckpoint {
+    /// Returns a new `CmdCheckpoint` with no completed items.
+    ///
+    /// # Parameters
+    ///
+    /// * `cmd_execution_id`: ID of the execution that writes this checkpoint.
+    /// * `cmd_name`: Name of the command being executed, e.g. `"EnsureCmd"`.
+    pub fn new(cmd_execution_id: CmdExecutionId, cmd_name: String) -> Self {
+        Self {
+            cmd_execution_id,
+            cmd_name,
+            states_current_stored: IndexMap::new(),
+            states_completed: IndexMap::new(),
+        }
+    }
+
+    /// Returns the ID of the execution that wrote this checkpoint.
+    pub fn cmd_execution_id(&self) -> CmdExecutionId {
+        self.cmd_execution_id
+    }
+
+    /// Returns the name of the command that was executed.
+    pub fn cmd_name(&self) -> &str {
+        &self.cmd_name
+    }
+
+    /// Returns the stored current state of each completed item before it was
+    /// applied.
+    pub fn states_current_stored(&self) -> &IndexMap<ItemId, serde_yaml::Value> {
+        &self.states_current_stored
+    }
+
+    /// Returns the state of each completed item after it was applied.
+    pub fn states_completed(&self) -> &IndexMap<ItemId, serde_yaml::Value> {
+        &self.states_completed
+    }
+
+    /// Returns the IDs of completed items, in the order they completed.
+    pub fn item_ids_completed(&self) -> impl Iterator<Item = &ItemId> + '_ {
+        self.states_completed.keys()
+    }
+
+    /// Records that an item has been successfully applied.
+    ///
+    /// # Parameters
+    ///
+    /// * `item_id`: ID of the completed item.
+    /// * `state_current_stored`: Stored current state of the item before it
+    ///   was applied, if any.
+    /// * `state_completed`: State of the item after it was applied.
+    pub fn item_completed_insert(
+        &mut self,
+        item_id: ItemId,
+        state_current_stored: Option<serde_yaml::Value>,
+        state_completed: serde_yaml::Value,
+    ) {
+        match state_current_stored {
+            Some(state_current_stored) => {
+                self.states_current_stored
+                    .insert(item_id.clone(), state_current_stored);
+            }
+            None => {
+                self.states_current_stored.shift_remove(&item_id);
+            }
+        }
+        self.states_completed.insert(item_id, state_completed);
+    }
+
+    /// Returns whether no items have been completed.
+    pub fn is_empty(&self) -> bool {
+        self.states_completed.is_empty()
+    }
+}
@@ -4,10 +4,14 @@ use peace_cmd_model::CmdExecutionError;
 use peace_core::AppName;
 use peace_flow_model::FlowId;
 use peace_item_model::ItemId;
-use peace_params::{ParamsResolveError, ParamsSpecs};
-use peace_profile_model::Profile;
+use peace_params::{
+    FieldValueSetError, MappingFnId, ParamsOverride, ParamsResolveError, ParamsSpecs,
+};
+use peace_profile_model::{Profile, ProfileInvalidFmt};
 use peace_resource_rt::{internal::WorkspaceParamsFile, paths::ParamsSpecsFile};
 
+use crate::{FlowIssues, TemplateTypeError};
+
 pub use self::{
     apply_cmd_error::ApplyCmdError, params_specs_deserialize_error::ParamsSpecsDeserializeError,
     state_downcast_error::StateDowncastError, states_deserialize_error::StatesDeserializeError,
@@ -198,6 +202,209 @@ pub enum Error {
         params_specs_not_usable: Vec<ItemId>,
     },
 
+    /// Item params specs use mapping functions that are not registered.
+    ///
+    /// Mapping functions are used by `MappingFn` variants, as well as
+    /// placeholders in `ValueSpec::Template`s.
+    #[error("Item params specs use mapping functions that are not registered.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_specs_mapping_fns_not_registered),
+            help(
+                "Add the following mapping functions to `CmdCtxTypes::MappingFns`, \
+                or update the items' params specs:\n\
+                \n\
+                {}",
+                item_mapping_fn_ids
+                    .iter()
+                    .map(|(item_id, mapping_fn_id)| format!("* {item_id}: {}", mapping_fn_id.as_str()))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        )
+    )]
+    ParamsSpecsMappingFnsNotRegistered {
+        /// Item IDs and the unregistered mapping function IDs they use.
+        item_mapping_fn_ids: Vec<(ItemId, MappingFnId)>,
+    },
+
+    /// Placeholders in item params specs' `ValueSpec::Template`s use mapping
+    /// functions whose types do not match the available data or the field.
+    #[error("Item params specs' templates use mapping functions whose types do not match.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_specs_template_types_mismatch),
+            help(
+                "Update the following templates or mapping functions:\n\
+                \n\
+                {}",
+                item_template_type_errors
+                    .iter()
+                    .map(|(item_id, template_type_error)| format!("* {item_id}: {template_type_error}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        )
+    )]
+    ParamsSpecsTemplateTypesMismatch {
+        /// Item IDs and the template type errors in their params specs.
+        item_template_type_errors: Vec<(ItemId, TemplateTypeError)>,
+    },
+
+    /// Item params specs read data from items that are not their
+    /// predecessors in the item graph.
+    ///
+    /// The items may run concurrently, so the data may not be available when
+    /// the params are resolved.
+    #[error("Item params specs read data from items that are not their predecessors.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::item_graph_edges_missing),
+            help(
+                "Add the following edges to the `ItemGraph`, or use \
+                `ItemGraphEdgesCheck::Infer` to add them automatically:\n\
+                \n\
+                {}",
+                item_refs
+                    .iter()
+                    .map(|(item_id_src, item_id_dest)| format!("* {item_id_src} -> {item_id_dest}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        )
+    )]
+    ItemGraphEdgesMissing {
+        /// Pairs of the item that writes the data, and the item whose params
+        /// spec reads it.
+        item_refs: Vec<(ItemId, ItemId)>,
+    },
+
+    /// A flow has problems that would surface when it is executed.
+    ///
+    /// This is returned by `Flow::validate`.
+    #[error("Flow `{flow_id}` has {} issue(s).", flow_issues.len())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::flow_invalid),
+            help(
+                "Fix the following issues:\n\
+                \n\
+                {}",
+                flow_issues
+                    .iter()
+                    .map(|flow_issue| format!("* {flow_issue}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        )
+    )]
+    FlowInvalid {
+        /// ID of the flow.
+        flow_id: FlowId,
+        /// Problems with the flow.
+        flow_issues: FlowIssues,
+    },
+
+    /// Adding the inferred item graph edges would introduce a cycle.
+    ///
+    /// This happens when an item's params spec reads data from an item that
+    /// runs after it.
+    #[error("Adding the inferred item graph edges would introduce a cycle.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::item_graph_edges_infer_cycle),
+            help(
+                "Check the params specs of the following items, and the order of \
+                items in the `ItemGraph`:\n\
+                \n\
+                {}",
+                item_refs
+                    .iter()
+                    .map(|(item_id_src, item_id_dest)| format!("* {item_id_src} -> {item_id_dest}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        )
+    )]
+    ItemGraphEdgesInferCycle {
+        /// Pairs of the item that writes the data, and the item whose params
+        /// spec reads it.
+        item_refs: Vec<(ItemId, ItemId)>,
+    },
+
+    /// An `ItemFanOut` element key cannot be used in an item ID.
+    #[error("Element key `{element_key}` for `{item_id_prefix}` cannot be used in an item ID.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::item_fan_out_element_key_invalid),
+            help(
+                "Element keys must only contain ASCII letters, digits, and single underscores, \
+                and must not begin or end with an underscore."
+            )
+        )
+    )]
+    ItemFanOutElementKeyInvalid {
+        /// Prefix of the fanned out items' IDs.
+        item_id_prefix: ItemId,
+        /// The invalid element key.
+        element_key: String,
+    },
+
+    /// An `ItemFanOut` element key is used by more than one element.
+    #[error(
+        "Element key `{element_key}` for `{item_id_prefix}` is used by more than one element."
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::item_fan_out_element_key_duplicate),
+            help("Each element must have a unique key, as it is used to derive the item ID.")
+        )
+    )]
+    ItemFanOutElementKeyDuplicate {
+        /// Prefix of the fanned out items' IDs.
+        item_id_prefix: ItemId,
+        /// The duplicated element key.
+        element_key: String,
+    },
+
+    /// A params override was provided for an item that is not in the flow.
+    #[error("Params override `{params_override}` is for an item that is not in the flow.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_override_item_not_found),
+            help("{}", params_override_item_not_found_help(params_override, item_ids))
+        )
+    )]
+    ParamsOverrideItemNotFound {
+        /// The params override.
+        params_override: ParamsOverride,
+        /// IDs of the items in the flow.
+        item_ids: Vec<ItemId>,
+    },
+
+    /// Failed to apply a params override to an item's params spec.
+    #[error("Failed to apply params override `{params_override}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::params_override_apply))
+    )]
+    ParamsOverrideApply {
+        /// The params override.
+        params_override: Box<ParamsOverride>,
+        /// Underlying error.
+        #[cfg_attr(feature = "error_reporting", diagnostic_source)]
+        #[source]
+        error: FieldValueSetError,
+    },
+
     /// In a `MultiProfileSingleFlow` diff, neither profile had `Params::Specs`
     /// defined.
     #[error("Params specifications not defined for `{profile_a}` or `{profile_b}`.")]
@@ -250,6 +457,23 @@ pub enum Error {
         Box<StatesDeserializeError>,
     ),
 
+    /// Failed to deserialize item IDs from a states file.
+    #[error("Failed to deserialize item IDs from `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::states_item_ids_deserialize),
+            help("The states file should be a map of item IDs to their states.")
+        )
+    )]
+    StatesItemIdsDeserialize {
+        /// Path to the states file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
     /// Failed to serialize states.
     #[error("Failed to serialize states.")]
     #[cfg_attr(
@@ -258,6 +482,17 @@ pub enum Error {
     )]
     StatesSerialize(#[source] serde_yaml::Error),
 
+    /// Failed to deserialize resumed states into their item state types.
+    #[error("Failed to deserialize resumed states.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::states_untyped_deserialize),
+            help("The command checkpoint may have been written by a different version of the flow's items.")
+        )
+    )]
+    StatesUntypedDeserialize(#[source] serde_yaml::Error),
+
     /// Failed to deserialize params specs.
     #[error("Failed to deserialize params specs.")]
     ParamsSpecsDeserialize(
@@ -275,6 +510,163 @@ pub enum Error {
     )]
     ParamsSpecsSerialize(#[source] serde_yaml::Error),
 
+    /// Failed to serialize an item's params spec or params to store as the
+    /// params it was applied with.
+    #[error("Failed to serialize params applied for item: `{item_id}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::params_applied_value_serialize))
+    )]
+    ParamsAppliedValueSerialize {
+        /// ID of the item whose params failed to be serialized.
+        item_id: ItemId,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Failed to serialize params applied.
+    #[error("Failed to serialize params applied.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::params_applied_serialize))
+    )]
+    ParamsAppliedSerialize(#[source] serde_yaml::Error),
+
+    /// Failed to deserialize params applied.
+    #[error("Failed to deserialize params applied from `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_applied_deserialize),
+            help(
+                "The file is written after each successful apply. \
+                If it has been modified, it may be deleted, and params changes will be shown \
+                relative to the next apply."
+            )
+        )
+    )]
+    ParamsAppliedDeserialize {
+        /// Path to the params applied file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Failed to serialize item outputs.
+    #[error("Failed to serialize item outputs.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::items_outputs_serialize))
+    )]
+    ItemsOutputsSerialize(#[source] serde_yaml::Error),
+
+    /// Failed to deserialize item outputs.
+    #[error("Failed to deserialize item outputs from `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::items_outputs_deserialize),
+            help(
+                "The file is written after each successful apply. \
+                If it has been modified, it may be deleted, and outputs will be published \
+                again the next time each item is applied."
+            )
+        )
+    )]
+    ItemsOutputsDeserialize {
+        /// Path to the outputs file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Failed to serialize command checkpoint.
+    #[error("Failed to serialize command checkpoint.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::cmd_checkpoint_serialize))
+    )]
+    CmdCheckpointSerialize(#[source] serde_yaml::Error),
+
+    /// Failed to deserialize command checkpoint.
+    #[error("Failed to deserialize command checkpoint from `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::cmd_checkpoint_deserialize),
+            help(
+                "The file is written while a command is executing, and removed when it completes. \
+                If it has been modified, it may be deleted, \
+                and the command run again from the start."
+            )
+        )
+    )]
+    CmdCheckpointDeserialize {
+        /// Path to the command checkpoint file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Failed to serialize an applied item's state into the command
+    /// checkpoint.
+    #[error("Failed to serialize state of `{item_id}` into command checkpoint.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::cmd_checkpoint_state_serialize))
+    )]
+    CmdCheckpointStateSerialize {
+        /// ID of the item whose state failed to serialize.
+        item_id: ItemId,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Command checkpoint was written by a different command.
+    #[error("Command checkpoint was written by `{cmd_name_checkpoint}`, not `{cmd_name}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::cmd_checkpoint_cmd_mismatch),
+            help("Resume with `{cmd_name_checkpoint}`, or delete the checkpoint file.")
+        )
+    )]
+    CmdCheckpointCmdMismatch {
+        /// Name of the command that wrote the checkpoint.
+        cmd_name_checkpoint: String,
+        /// Name of the command that is resuming the checkpoint.
+        cmd_name: String,
+    },
+
+    /// Command checkpoint does not match the stored states.
+    ///
+    /// This happens when the stored state of a completed item was changed
+    /// after the checkpoint was written, or the item is no longer in the flow.
+    #[error(
+        "Command checkpoint does not match the stored states for: {}.",
+        item_ids.iter().map(|item_id| format!("`{item_id}`")).collect::<Vec<_>>().join(", ")
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::cmd_checkpoint_stale),
+            help(
+                "The stored state of these items changed since the checkpoint was written, \
+                or the items are no longer in the flow. \
+                Delete the checkpoint file and run the command again from the start."
+            )
+        )
+    )]
+    CmdCheckpointStale {
+        /// IDs of the items whose stored state does not match the checkpoint.
+        item_ids: Vec<ItemId>,
+    },
+
     /// Params specs file does not exist.
     ///
     /// This is returned when `ParamsSpecs` is attempted to be
@@ -462,6 +854,248 @@ pub enum Error {
         profile: Profile,
     },
 
+    /// Profile to create already exists.
+    #[error("Profile `{profile}` already exists.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_exists),
+            help("Choose a different profile name, or delete the existing profile first.")
+        )
+    )]
+    ProfileExists {
+        /// The profile that already exists.
+        profile: Profile,
+    },
+
+    /// Profile to delete is the active profile.
+    #[error("Profile `{profile}` cannot be deleted as it is the active profile.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_delete_active),
+            help("Switch to a different profile before deleting this one.")
+        )
+    )]
+    ProfileDeleteActive {
+        /// The profile that was requested to be deleted.
+        profile: Profile,
+    },
+
+    /// Profile to delete still has items whose stored state is not clean.
+    #[error("Profile `{profile}` cannot be deleted as it still has items that are not cleaned.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_delete_states_not_clean),
+            help(
+                "Run the clean command for the profile before deleting it.\n\
+                Items that are not cleaned: [{item_ids}]",
+                item_ids = item_ids
+                    .iter()
+                    .map(|item_id| format!("{item_id}"))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        )
+    )]
+    ProfileDeleteStatesNotClean {
+        /// The profile that was requested to be deleted.
+        profile: Profile,
+        /// IDs of items whose stored current state is not their clean state.
+        item_ids: Vec<ItemId>,
+    },
+
+    /// Profile to delete has stored states for flows that were not checked.
+    #[error(
+        "Profile `{profile}` cannot be deleted as it has stored states for flows that were not checked."
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_delete_flows_not_checked),
+            help(
+                "Pass a command context for each of these flows to `ProfileDeleteCmd::exec`.\n\
+                Flows that were not checked: [{flow_ids}]",
+                flow_ids = flow_ids
+                    .iter()
+                    .map(|flow_id| format!("{flow_id}"))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        )
+    )]
+    ProfileDeleteFlowsNotChecked {
+        /// The profile that was requested to be deleted.
+        profile: Profile,
+        /// IDs of flows with stored states that were not checked.
+        flow_ids: Vec<FlowId>,
+    },
+
+    /// Failed to serialize profile bundle.
+    #[error("Failed to serialize profile bundle.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::profile_bundle_serialize))
+    )]
+    ProfileBundleSerialize(#[source] serde_yaml::Error),
+
+    /// Failed to deserialize profile bundle.
+    #[error("Failed to deserialize profile bundle: `{}`.", bundle_path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::profile_bundle_deserialize))
+    )]
+    ProfileBundleDeserialize {
+        /// Path to the bundle file.
+        bundle_path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Profile bundle file does not exist.
+    #[error("Profile bundle file does not exist: `{}`.", bundle_path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::profile_bundle_file_not_exists))
+    )]
+    ProfileBundleFileNotExists {
+        /// Path to the bundle file.
+        bundle_path: PathBuf,
+    },
+
+    /// Failed to deserialize a profile file when exporting it to a bundle.
+    #[error("Failed to read `{}` into profile bundle.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::profile_bundle_file_deserialize))
+    )]
+    ProfileBundleFileDeserialize {
+        /// Path to the file being read.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
+    /// Profile bundle version is not supported.
+    #[error(
+        "Profile bundle version `{version}` is not supported, supported version is `{version_supported}`."
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_bundle_version_unsupported),
+            help("Export the bundle using the same version of the application.")
+        )
+    )]
+    ProfileBundleVersionUnsupported {
+        /// Version of the bundle.
+        version: u32,
+        /// Version supported by this application.
+        version_supported: u32,
+    },
+
+    /// Profile bundle was exported by a different application.
+    #[error(
+        "Profile bundle was exported by `{app_name_bundle}`, but is being imported into `{app_name}`."
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::profile_bundle_app_name_mismatch))
+    )]
+    ProfileBundleAppNameMismatch {
+        /// Name of the application that exported the bundle.
+        app_name_bundle: AppName,
+        /// Name of the application importing the bundle.
+        app_name: AppName,
+    },
+
+    /// Profile bundle contains a flow that is not registered.
+    #[error("Profile bundle contains flow `{flow_id}` which is not registered.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_bundle_flow_not_registered),
+            help(
+                "Registered flows are: {}",
+                flow_ids_registered
+                    .iter()
+                    .map(|flow_id| format!("`{flow_id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        )
+    )]
+    ProfileBundleFlowNotRegistered {
+        /// ID of the flow in the bundle.
+        flow_id: FlowId,
+        /// IDs of the flows registered in the application.
+        flow_ids_registered: Vec<FlowId>,
+    },
+
+    /// Profile bundle contains items that are not in the registered flow.
+    #[error("Profile bundle contains items that are not in flow `{flow_id}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_bundle_items_not_in_flow),
+            help(
+                "The following items are not in the flow: {}",
+                item_ids
+                    .iter()
+                    .map(|item_id| format!("`{item_id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        )
+    )]
+    ProfileBundleItemsNotInFlow {
+        /// ID of the flow.
+        flow_id: FlowId,
+        /// IDs of the items in the bundle that are not in the flow.
+        item_ids: Vec<ItemId>,
+    },
+
+    /// Profile bundle contains a flow file whose entries are not keyed by item
+    /// ID.
+    #[error("Profile bundle contains an invalid `{file_name}` for flow `{flow_id}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_bundle_flow_file_invalid),
+            help("{reason}")
+        )
+    )]
+    ProfileBundleFlowFileInvalid {
+        /// ID of the flow.
+        flow_id: FlowId,
+        /// Name of the file within the flow directory.
+        file_name: String,
+        /// Why the file contents are invalid.
+        reason: String,
+    },
+
+    /// Profile name to import a bundle as is not a valid profile name.
+    #[error("Profile name `{profile}` to import the bundle as is invalid.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_bundle_profile_invalid),
+            help(
+                "Profile names must begin with a letter or underscore, and contain only letters, \
+                numbers, and underscores."
+            )
+        )
+    )]
+    ProfileBundleProfileInvalid {
+        /// The profile name that failed to be validated.
+        profile: String,
+        /// Underlying error.
+        error: ProfileInvalidFmt<'static>,
+    },
+
     /// Failed to serialize profile init params.
     #[error("Failed to serialize profile init params.")]
     #[cfg_attr(
@@ -604,6 +1238,34 @@ fn params_specs_mismatch_display(
     items.join("\n")
 }
 
+#[cfg(feature = "error_reporting")]
+fn params_override_item_not_found_help(
+    params_override: &ParamsOverride,
+    item_ids: &[ItemId],
+) -> String {
+    let item_ids_list = item_ids
+        .iter()
+        .map(|item_id| format!("* {item_id}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    match peace_params::name_suggest(
+        params_override.item_id.as_str(),
+        item_ids.iter().map(|item_id| item_id.as_str()),
+    ) {
+        Some(item_id_suggested) => format!(
+            "Did you mean `{item_id_suggested}`? Items in the flow are:\n\
+            \n\
+            {item_ids_list}"
+        ),
+        None => format!(
+            "Items in the flow are:\n\
+            \n\
+            {item_ids_list}"
+        ),
+    }
+}
+
 #[cfg(feature = "error_reporting")]
 impl<'b> std::borrow::Borrow<dyn miette::Diagnostic + 'b> for Box<Error> {
     fn borrow<'s>(&'s self) -> &'s (dyn miette::Diagnostic + 'b) {
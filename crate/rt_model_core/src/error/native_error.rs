@@ -129,6 +129,80 @@ pub enum NativeError {
         error: ProfileInvalidFmt<'static>,
     },
 
+    /// Failed to copy a directory.
+    #[error("Failed to copy `{}` to `{}`.", src.display(), dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::dir_copy))
+    )]
+    DirCopy {
+        /// Path being copied from.
+        src: PathBuf,
+        /// Path being copied to.
+        dest: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to rename a directory.
+    #[error("Failed to rename `{}` to `{}`.", src.display(), dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::dir_rename))
+    )]
+    DirRename {
+        /// Path being renamed.
+        src: PathBuf,
+        /// Path to rename to.
+        dest: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to read the entries of a directory.
+    #[error("Failed to read directory: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::dir_read))
+    )]
+    DirRead {
+        /// Path to the directory.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to remove a directory.
+    #[error("Failed to remove directory: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::dir_remove))
+    )]
+    DirRemove {
+        /// Path to the directory.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to remove a file.
+    #[error("Failed to remove file: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::file_remove))
+    )]
+    FileRemove {
+        /// Path to the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
     /// Failed to write to stdout.
     #[error("Failed to write to stdout.")]
     #[cfg_attr(
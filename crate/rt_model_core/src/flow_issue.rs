@@ -0,0 +1,154 @@
+use std::fmt;
+
+use peace_item_model::ItemId;
+use peace_params::MappingFnId;
+use serde::{Deserialize, Serialize};
+
+/// A problem with a flow that would surface when the flow is executed.
+///
+/// These are returned by `Flow::validate` and `FlowCheckCmd`, so that all
+/// problems are reported at once, before any item is applied.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FlowIssue {
+    /// Two items that may run concurrently both write to the same data type.
+    ///
+    /// The items are run in insertion order, so whichever item writes last is
+    /// determined by the order the items were added to the graph, instead of
+    /// an explicit edge.
+    DataWriteConcurrent {
+        /// ID of the first item that writes the data.
+        item_id_a: ItemId,
+        /// ID of the second item that writes the data.
+        item_id_b: ItemId,
+        /// Short name of the data type.
+        type_name: String,
+    },
+    /// An item's `Data` requires a type that has not been inserted into
+    /// `Resources`.
+    DataNotFound {
+        /// ID of the item that borrows the data.
+        item_id: ItemId,
+        /// Short name of the data type.
+        type_name: String,
+        /// Whether the data is borrowed mutably.
+        mutable: bool,
+    },
+    /// An item has no params spec.
+    ParamsSpecNotFound {
+        /// ID of the item.
+        item_id: ItemId,
+        /// ID of an item that is not in the flow, whose params spec was likely
+        /// meant for this item.
+        item_id_suggested: Option<ItemId>,
+    },
+    /// An item's params spec uses a mapping function that is not registered
+    /// in the `MappingFnReg`.
+    MappingFnNotRegistered {
+        /// ID of the item.
+        item_id: ItemId,
+        /// ID of the mapping function.
+        mapping_fn_id: MappingFnId,
+        /// ID of a registered mapping function with a similar name.
+        mapping_fn_id_suggested: Option<MappingFnId>,
+    },
+    /// An item's params spec reads data from an item that is not its
+    /// predecessor, so the data may not be available when the params are
+    /// resolved.
+    ParamsRefEdgeMissing {
+        /// ID of the item whose state or data is read.
+        item_id_src: ItemId,
+        /// ID of the item whose params spec reads the data.
+        item_id_dest: ItemId,
+    },
+}
+
+impl FlowIssue {
+    /// Returns the ID of the item that this issue is reported for.
+    ///
+    /// For issues between two items, this is the item that would need to
+    /// change.
+    pub fn item_id(&self) -> &ItemId {
+        match self {
+            Self::DataWriteConcurrent { item_id_b, .. } => item_id_b,
+            Self::DataNotFound { item_id, .. }
+            | Self::ParamsSpecNotFound { item_id, .. }
+            | Self::MappingFnNotRegistered { item_id, .. } => item_id,
+            Self::ParamsRefEdgeMissing { item_id_dest, .. } => item_id_dest,
+        }
+    }
+}
+
+impl fmt::Display for FlowIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DataWriteConcurrent {
+                item_id_a,
+                item_id_b,
+                type_name,
+            } => write!(
+                f,
+                "`{item_id_a}` and `{item_id_b}` both write `{type_name}`, \
+                but may run concurrently. \
+                Add a logic edge between them so the order is explicit."
+            ),
+            Self::DataNotFound {
+                item_id,
+                type_name,
+                mutable,
+            } => {
+                let (access, accessor_maybe) = if *mutable {
+                    ("writes", "WMaybe")
+                } else {
+                    ("reads", "RMaybe")
+                };
+                write!(
+                    f,
+                    "`{item_id}` {access} `{type_name}`, but it is not inserted into `Resources`. \
+                    Insert it in an item's `setup` or with `with_resource`, \
+                    or use `{accessor_maybe}<{type_name}>` if it is optional."
+                )
+            }
+            Self::ParamsSpecNotFound {
+                item_id,
+                item_id_suggested,
+            } => {
+                write!(f, "`{item_id}` has no params spec.")?;
+                match item_id_suggested {
+                    Some(item_id_suggested) => write!(
+                        f,
+                        " A params spec was provided for `{item_id_suggested}`, \
+                        did you mean `{item_id}`?"
+                    ),
+                    None => write!(f, " Provide one with `with_item_params`."),
+                }
+            }
+            Self::MappingFnNotRegistered {
+                item_id,
+                mapping_fn_id,
+                mapping_fn_id_suggested,
+            } => {
+                write!(
+                    f,
+                    "`{item_id}`'s params spec uses mapping function `{}`, \
+                    which is not registered.",
+                    mapping_fn_id.as_str()
+                )?;
+                match mapping_fn_id_suggested {
+                    Some(mapping_fn_id_suggested) => {
+                        write!(f, " Did you mean `{}`?", mapping_fn_id_suggested.as_str())
+                    }
+                    None => write!(f, " Add it to `CmdCtxTypes::MappingFns`."),
+                }
+            }
+            Self::ParamsRefEdgeMissing {
+                item_id_src,
+                item_id_dest,
+            } => write!(
+                f,
+                "`{item_id_dest}`'s params spec reads data from `{item_id_src}`, \
+                which is not its predecessor. \
+                Add a logic edge from `{item_id_src}` to `{item_id_dest}`."
+            ),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops::{Deref, DerefMut};
+
+use peace_fmt::{Presentable, Presenter};
+use serde::{Deserialize, Serialize};
+
+use crate::FlowIssue;
+
+/// Problems with a flow that would surface when the flow is executed.
+///
+/// `Vec<FlowIssue>` newtype.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FlowIssues(Vec<FlowIssue>);
+
+impl FlowIssues {
+    /// Returns a new `FlowIssues` list.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns the underlying list.
+    pub fn into_inner(self) -> Vec<FlowIssue> {
+        self.0
+    }
+}
+
+impl Deref for FlowIssues {
+    type Target = Vec<FlowIssue>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for FlowIssues {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<FlowIssue> for FlowIssues {
+    fn from_iter<I: IntoIterator<Item = FlowIssue>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
+
+#[peace_fmt::async_trait(?Send)]
+impl Presentable for FlowIssues {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        presenter
+            .list_numbered_with(self.iter(), FlowIssue::to_string)
+            .await
+    }
+}
@@ -0,0 +1,53 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use peace_item_model::ItemId;
+use serde::{Deserialize, Serialize};
+
+use crate::ParamsApplied;
+
+/// Params that each item was last successfully applied with.
+///
+/// `IndexMap<ItemId, ParamsApplied>` newtype.
+///
+/// This is stored in `params_applied.yaml` in the flow directory.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ItemsParamsApplied(IndexMap<ItemId, ParamsApplied>);
+
+impl ItemsParamsApplied {
+    /// Returns a new `ItemsParamsApplied` map.
+    pub fn new() -> Self {
+        Self(IndexMap::new())
+    }
+
+    /// Returns a new `ItemsParamsApplied` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<ItemId, ParamsApplied> {
+        self.0
+    }
+}
+
+impl Deref for ItemsParamsApplied {
+    type Target = IndexMap<ItemId, ParamsApplied>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ItemsParamsApplied {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(ItemId, ParamsApplied)> for ItemsParamsApplied {
+    fn from_iter<I: IntoIterator<Item = (ItemId, ParamsApplied)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
@@ -13,17 +13,41 @@ pub mod output;
 pub mod params;
 
 pub use crate::{
+    cmd_checkpoint::CmdCheckpoint,
     error::{
         ApplyCmdError, Error, ParamsSpecsDeserializeError, StateDowncastError,
         StatesDeserializeError,
     },
+    flow_issue::FlowIssue,
+    flow_issues::FlowIssues,
+    items_params_applied::ItemsParamsApplied,
     items_state_stored_stale::ItemsStateStoredStale,
+    params_applied::ParamsApplied,
+    params_diffs::ParamsDiffs,
+    params_field_diff::ParamsFieldDiff,
+    profile_bundle::ProfileBundle,
+    profile_bundle_flow::ProfileBundleFlow,
+    profile_bundle_flow_manifest::ProfileBundleFlowManifest,
+    profile_bundle_manifest::ProfileBundleManifest,
     state_stored_and_discovered::StateStoredAndDiscovered,
+    template_type_error::TemplateTypeError,
 };
 
+mod cmd_checkpoint;
 mod error;
+mod flow_issue;
+mod flow_issues;
+mod items_params_applied;
 mod items_state_stored_stale;
+mod params_applied;
+mod params_diffs;
+mod params_field_diff;
+mod profile_bundle;
+mod profile_bundle_flow;
+mod profile_bundle_flow_manifest;
+mod profile_bundle_manifest;
 mod state_stored_and_discovered;
+mod template_type_error;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ParamsFieldDiff;
+
+/// Params spec and resolved params that an item was applied with.
+///
+/// These are stored as YAML values, so that they can still be read and
+/// compared after the item's params type changes.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ParamsApplied {
+    /// The item's serialized params spec.
+    pub params_spec: serde_yaml::Value,
+    /// The item's serialized params, if they could be resolved.
+    pub params: Option<serde_yaml::Value>,
+}
+
+impl ParamsApplied {
+    /// Returns a new `ParamsApplied`.
+    pub fn new(params_spec: serde_yaml::Value, params: Option<serde_yaml::Value>) -> Self {
+        Self {
+            params_spec,
+            params,
+        }
+    }
+
+    /// Returns the fields whose values differ from `params_applied_previous`.
+    ///
+    /// Resolved params are compared when they are available for both, and
+    /// params specs are compared otherwise. If `params_applied_previous` is
+    /// `None`, every field is returned as added.
+    ///
+    /// Values of fields in `field_names_secret` are masked.
+    pub fn field_diffs(
+        &self,
+        params_applied_previous: Option<&ParamsApplied>,
+        field_names_secret: &[&str],
+    ) -> Vec<ParamsFieldDiff> {
+        let (value_previous, value_next) = match (params_applied_previous, self.params.as_ref()) {
+            (
+                Some(ParamsApplied {
+                    params: Some(params_previous),
+                    ..
+                }),
+                Some(params_next),
+            ) => (Some(params_previous), params_next),
+            (Some(params_applied_previous), _) => (
+                Some(params_spec_value(&params_applied_previous.params_spec)),
+                params_spec_value(&self.params_spec),
+            ),
+            (None, Some(params_next)) => (None, params_next),
+            (None, None) => (None, params_spec_value(&self.params_spec)),
+        };
+
+        let fields_none = serde_yaml::Mapping::new();
+        let fields_next = value_next.as_mapping();
+        let fields_previous = match value_previous {
+            Some(value_previous) => value_previous.as_mapping(),
+            None => fields_next.map(|_| &fields_none),
+        };
+        match (fields_previous, fields_next) {
+            (Some(fields_previous), Some(fields_next)) => {
+                let field_names = fields_next
+                    .keys()
+                    .chain(
+                        fields_previous
+                            .keys()
+                            .filter(|field_name| !fields_next.contains_key(*field_name)),
+                    )
+                    .filter_map(serde_yaml::Value::as_str);
+
+                field_names
+                    .filter_map(|field_name| {
+                        let field_previous = fields_previous.get(field_name);
+                        let field_next = fields_next.get(field_name);
+                        if field_previous == field_next {
+                            return None;
+                        }
+
+                        let is_secret = field_names_secret.contains(&field_name);
+                        Some(ParamsFieldDiff::new(
+                            Some(field_name.to_string()),
+                            field_previous.map(|value| value_display(value, is_secret)),
+                            field_next.map(|value| value_display(value, is_secret)),
+                        ))
+                    })
+                    .collect()
+            }
+            _ => {
+                if value_previous == Some(value_next) {
+                    Vec::new()
+                } else {
+                    vec![ParamsFieldDiff::new(
+                        None,
+                        value_previous.map(|value| value_display(value, false)),
+                        Some(value_display(value_next, false)),
+                    )]
+                }
+            }
+        }
+    }
+}
+
+/// Returns the value within a serialized `ParamsSpec::Value` or
+/// `ParamsSpec::FieldWise`, so that fields are compared instead of the spec
+/// variant.
+///
+/// This also unwraps field level `ValueSpec::Value`s.
+fn params_spec_value(params_spec: &serde_yaml::Value) -> &serde_yaml::Value {
+    match params_spec {
+        serde_yaml::Value::Tagged(tagged_value) if tagged_value.tag == "Value" => {
+            tagged_value.value.get("value").unwrap_or(&tagged_value.value)
+        }
+        serde_yaml::Value::Tagged(tagged_value) if tagged_value.tag == "FieldWise" => tagged_value
+            .value
+            .get("field_wise_spec")
+            .unwrap_or(&tagged_value.value),
+        _ => params_spec,
+    }
+}
+
+/// Returns the value to display for a field, masked if it is secret.
+fn value_display(value: &serde_yaml::Value, is_secret: bool) -> String {
+    if is_secret {
+        return String::from("***");
+    }
+
+    match params_spec_value(value) {
+        serde_yaml::Value::Null => String::from("null"),
+        serde_yaml::Value::Bool(value) => value.to_string(),
+        serde_yaml::Value::Number(value) => value.to_string(),
+        serde_yaml::Value::String(value) => format!("{value:?}"),
+        value @ (serde_yaml::Value::Sequence(_)
+        | serde_yaml::Value::Mapping(_)
+        | serde_yaml::Value::Tagged(_)) => {
+            serde_json::to_string(value).unwrap_or_else(|_| format!("{value:?}"))
+        }
+    }
+}
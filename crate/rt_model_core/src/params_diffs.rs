@@ -0,0 +1,72 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use peace_fmt::{Presentable, Presenter};
+use peace_item_model::ItemId;
+use serde::{Deserialize, Serialize};
+
+use crate::ParamsFieldDiff;
+
+/// Params fields that changed for each item since it was last applied.
+///
+/// `IndexMap<ItemId, Vec<ParamsFieldDiff>>` newtype.
+///
+/// Items whose params have not changed are not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ParamsDiffs(IndexMap<ItemId, Vec<ParamsFieldDiff>>);
+
+impl ParamsDiffs {
+    /// Returns a new `ParamsDiffs` map.
+    pub fn new() -> Self {
+        Self(IndexMap::new())
+    }
+
+    /// Returns a new `ParamsDiffs` map with the given preallocated capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<ItemId, Vec<ParamsFieldDiff>> {
+        self.0
+    }
+}
+
+impl Deref for ParamsDiffs {
+    type Target = IndexMap<ItemId, Vec<ParamsFieldDiff>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ParamsDiffs {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(ItemId, Vec<ParamsFieldDiff>)> for ParamsDiffs {
+    fn from_iter<I: IntoIterator<Item = (ItemId, Vec<ParamsFieldDiff>)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
+
+#[peace_fmt::async_trait(?Send)]
+impl Presentable for ParamsDiffs {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        presenter
+            .list_numbered_with(self.iter(), |(item_id, params_field_diffs)| {
+                let params_field_diffs = params_field_diffs
+                    .iter()
+                    .map(ParamsFieldDiff::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                (item_id, format!(": {params_field_diffs}"))
+            })
+            .await
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Change in the value of an item's params field since it was last applied.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ParamsFieldDiff {
+    /// Name of the field.
+    ///
+    /// This is `None` when the params are not a struct with named fields, in
+    /// which case the whole value is compared.
+    pub field_name: Option<String>,
+    /// Value when the item was last applied, if any.
+    pub value_previous: Option<String>,
+    /// Value that the item will be applied with, if any.
+    pub value_next: Option<String>,
+}
+
+impl ParamsFieldDiff {
+    /// Returns a new `ParamsFieldDiff`.
+    pub fn new(
+        field_name: Option<String>,
+        value_previous: Option<String>,
+        value_next: Option<String>,
+    ) -> Self {
+        Self {
+            field_name,
+            value_previous,
+            value_next,
+        }
+    }
+}
+
+impl fmt::Display for ParamsFieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ParamsFieldDiff {
+            field_name,
+            value_previous,
+            value_next,
+        } = self;
+
+        if let Some(field_name) = field_name {
+            write!(f, "{field_name}: ")?;
+        }
+        match (value_previous, value_next) {
+            (Some(value_previous), Some(value_next)) => {
+                write!(f, "{value_previous} -> {value_next}")
+            }
+            (None, Some(value_next)) => write!(f, "(added) {value_next}"),
+            (Some(value_previous), None) => write!(f, "{value_previous} (removed)"),
+            (None, None) => Ok(()),
+        }
+    }
+}
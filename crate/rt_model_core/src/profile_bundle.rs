@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ProfileBundleFlow, ProfileBundleManifest};
+
+/// A profile's params and stored states, packed into a single file.
+///
+/// This is written by `ProfileExportCmd` and read by `ProfileImportCmd`, so
+/// that a profile can be moved between workspaces or machines.
+///
+/// The file contents are stored as [`serde_yaml::Value`]s, so that a bundle
+/// can be read without knowing the concrete params and state types.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ProfileBundle {
+    /// Describes what is contained in the bundle.
+    pub manifest: ProfileBundleManifest,
+    /// Contents of the profile's `profile_params.yaml`, if present.
+    pub profile_params: Option<serde_yaml::Value>,
+    /// Stored files for each flow within the profile.
+    pub flows: Vec<ProfileBundleFlow>,
+}
+
+impl ProfileBundle {
+    /// Version of the bundle format written by this version of peace.
+    pub const VERSION: u32 = 1;
+}
@@ -0,0 +1,17 @@
+use peace_flow_model::FlowId;
+use serde::{Deserialize, Serialize};
+
+/// Stored files of a flow within a [`ProfileBundle`].
+///
+/// [`ProfileBundle`]: crate::ProfileBundle
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ProfileBundleFlow {
+    /// ID of the flow.
+    pub flow_id: FlowId,
+    /// Contents of the flow's `params_specs.yaml`, if present.
+    pub params_specs: Option<serde_yaml::Value>,
+    /// Contents of the flow's `states_current.yaml`, if present.
+    pub states_current: Option<serde_yaml::Value>,
+    /// Contents of the flow's `states_goal.yaml`, if present.
+    pub states_goal: Option<serde_yaml::Value>,
+}
@@ -0,0 +1,14 @@
+use peace_flow_model::FlowId;
+use peace_item_model::ItemId;
+use serde::{Deserialize, Serialize};
+
+/// Flow ID and item IDs of a flow within a [`ProfileBundle`].
+///
+/// [`ProfileBundle`]: crate::ProfileBundle
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProfileBundleFlowManifest {
+    /// ID of the flow.
+    pub flow_id: FlowId,
+    /// IDs of the items in the flow, in insertion order.
+    pub item_ids: Vec<ItemId>,
+}
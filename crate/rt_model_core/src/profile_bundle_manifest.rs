@@ -0,0 +1,20 @@
+use peace_core::AppName;
+use peace_profile_model::Profile;
+use serde::{Deserialize, Serialize};
+
+use crate::ProfileBundleFlowManifest;
+
+/// Describes what is contained in a [`ProfileBundle`].
+///
+/// [`ProfileBundle`]: crate::ProfileBundle
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProfileBundleManifest {
+    /// Version of the bundle format.
+    pub version: u32,
+    /// Name of the application that exported the bundle.
+    pub app_name: AppName,
+    /// Name of the profile when it was exported.
+    pub profile: Profile,
+    /// Flows and their items that were exported.
+    pub flows: Vec<ProfileBundleFlowManifest>,
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use peace_params::{MappingFnId, TemplateValueKind};
+
+/// A placeholder in a `ValueSpec::Template` whose mapping function's types do
+/// not match the data available or the field the template is used for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateTypeError {
+    /// The mapping function reads data that is not an item's state, is not
+    /// written by any item, and has not been inserted into `Resources`.
+    ArgsNotFound {
+        /// The template string.
+        template: String,
+        /// ID of the placeholder's mapping function.
+        mapping_fn_id: MappingFnId,
+        /// Names of the argument types that are not available.
+        arg_type_names: Vec<String>,
+    },
+    /// The mapping function's value cannot be interpolated into the field's
+    /// type, e.g. text into a number field.
+    ValueKindMismatch {
+        /// The template string.
+        template: String,
+        /// ID of the placeholder's mapping function.
+        mapping_fn_id: MappingFnId,
+        /// Name of the mapping function's output type.
+        output_type_name: String,
+        /// Kind of value the mapping function's output is serialized as.
+        output_kind: TemplateValueKind,
+        /// Name of the field's type.
+        field_type_name: String,
+        /// Kind of value the field's type is serialized as.
+        field_kind: TemplateValueKind,
+    },
+}
+
+impl fmt::Display for TemplateTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ArgsNotFound {
+                template,
+                mapping_fn_id,
+                arg_type_names,
+            } => write!(
+                f,
+                "`{{{mapping_fn_id}}}` in `{template}` reads {}, which is not available.",
+                arg_type_names
+                    .iter()
+                    .map(|arg_type_name| format!("`{arg_type_name}`"))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                mapping_fn_id = mapping_fn_id.as_str(),
+            ),
+            Self::ValueKindMismatch {
+                template,
+                mapping_fn_id,
+                output_type_name,
+                output_kind,
+                field_type_name,
+                field_kind,
+            } => write!(
+                f,
+                "`{{{mapping_fn_id}}}` in `{template}` maps to `{output_type_name}` ({output_kind}), \
+                which cannot be used for `{field_type_name}` ({field_kind}).",
+                mapping_fn_id = mapping_fn_id.as_str(),
+            ),
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use std::{fmt::Debug, hash::Hash, io::Write, path::Path, sync::Mutex};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
 use peace_resource_rt::type_reg::{
     common::UnknownEntriesSome,
@@ -199,6 +205,129 @@ impl Storage {
         serde_yaml::to_string(t).map_err(f_map_err)
     }
 
+    /// Recursively copies a directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `src`: Directory to copy from.
+    /// * `dest`: Directory to copy to, which is created if it does not exist.
+    /// * `file_names_excluded`: Names of files that should not be copied.
+    pub async fn dir_copy(
+        &self,
+        src: &Path,
+        dest: &Path,
+        file_names_excluded: &[&str],
+    ) -> Result<(), Error> {
+        let dir_copy_error = |error| {
+            Error::Native(NativeError::DirCopy {
+                src: src.to_path_buf(),
+                dest: dest.to_path_buf(),
+                error,
+            })
+        };
+
+        let mut dirs_to_copy = vec![(src.to_path_buf(), dest.to_path_buf())];
+        while let Some((dir_src, dir_dest)) = dirs_to_copy.pop() {
+            tokio::fs::create_dir_all(&dir_dest)
+                .await
+                .map_err(dir_copy_error)?;
+
+            let mut entries = tokio::fs::read_dir(&dir_src)
+                .await
+                .map_err(dir_copy_error)?;
+            while let Some(entry) = entries.next_entry().await.map_err(dir_copy_error)? {
+                let file_name = entry.file_name();
+                let entry_src = entry.path();
+                let entry_dest = dir_dest.join(&file_name);
+                if entry.file_type().await.map_err(dir_copy_error)?.is_dir() {
+                    dirs_to_copy.push((entry_src, entry_dest));
+                } else if !file_names_excluded
+                    .iter()
+                    .any(|file_name_excluded| file_name == *file_name_excluded)
+                {
+                    tokio::fs::copy(&entry_src, &entry_dest)
+                        .await
+                        .map_err(dir_copy_error)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renames a directory.
+    pub async fn dir_rename(&self, src: &Path, dest: &Path) -> Result<(), Error> {
+        tokio::fs::rename(src, dest).await.map_err(|error| {
+            Error::Native(NativeError::DirRename {
+                src: src.to_path_buf(),
+                dest: dest.to_path_buf(),
+                error,
+            })
+        })
+    }
+
+    /// Returns the paths of all files under the given directory, recursively.
+    ///
+    /// If the directory does not exist, an empty list is returned.
+    pub async fn dir_file_paths(&self, path: &Path) -> Result<Vec<PathBuf>, Error> {
+        let dir_read_error = |dir: &Path, error| {
+            Error::Native(NativeError::DirRead {
+                path: dir.to_path_buf(),
+                error,
+            })
+        };
+
+        let mut file_paths = Vec::new();
+        let mut dirs_to_read = vec![path.to_path_buf()];
+        while let Some(dir) = dirs_to_read.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(dir_read_error(&dir, error)),
+            };
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|error| dir_read_error(&dir, error))?
+            {
+                let entry_path = entry.path();
+                let file_type = entry
+                    .file_type()
+                    .await
+                    .map_err(|error| dir_read_error(&dir, error))?;
+                if file_type.is_dir() {
+                    dirs_to_read.push(entry_path);
+                } else {
+                    file_paths.push(entry_path);
+                }
+            }
+        }
+
+        Ok(file_paths)
+    }
+
+    /// Removes a directory and all of its contents.
+    pub async fn dir_remove(&self, path: &Path) -> Result<(), Error> {
+        tokio::fs::remove_dir_all(path).await.map_err(|error| {
+            Error::Native(NativeError::DirRemove {
+                path: path.to_path_buf(),
+                error,
+            })
+        })
+    }
+
+    /// Removes a file, if it exists.
+    pub async fn file_remove(&self, path: &Path) -> Result<(), Error> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(Error::Native(NativeError::FileRemove {
+                path: path.to_path_buf(),
+                error,
+            })),
+        }
+    }
+
     /// Reads from a file, bridging to libraries that take a synchronous `Write`
     /// type.
     ///
@@ -440,6 +440,86 @@ impl Storage {
         serde_yaml::to_string(t).map_err(f_map_err)
     }
 
+    /// Copies all items whose keys are under the `src` path to the `dest`
+    /// path.
+    ///
+    /// # Parameters
+    ///
+    /// * `src`: Path prefix to copy from.
+    /// * `dest`: Path prefix to copy to.
+    /// * `file_names_excluded`: Names of files that should not be copied.
+    pub async fn dir_copy(
+        &self,
+        src: &Path,
+        dest: &Path,
+        file_names_excluded: &[&str],
+    ) -> Result<(), Error> {
+        self.keys_under(src)?
+            .into_iter()
+            .filter(|path| {
+                path.file_name().map_or(true, |file_name| {
+                    !file_names_excluded
+                        .iter()
+                        .any(|file_name_excluded| file_name == *file_name_excluded)
+                })
+            })
+            .try_for_each(|path_src| {
+                let value = self.get_item(&path_src)?;
+                let path_dest = match path_src.strip_prefix(src) {
+                    Ok(path_relative) if !path_relative.as_os_str().is_empty() => {
+                        dest.join(path_relative)
+                    }
+                    _ => dest.to_path_buf(),
+                };
+                self.set_item(&path_dest, &value)
+            })
+    }
+
+    /// Moves all items whose keys are under the `src` path to the `dest` path.
+    pub async fn dir_rename(&self, src: &Path, dest: &Path) -> Result<(), Error> {
+        self.dir_copy(src, dest, &[]).await?;
+        self.dir_remove(src).await
+    }
+
+    /// Returns the keys of all items under the given path.
+    pub async fn dir_file_paths(&self, path: &Path) -> Result<Vec<PathBuf>, Error> {
+        self.keys_under(path)
+    }
+
+    /// Removes all items whose keys are under the given path.
+    pub async fn dir_remove(&self, path: &Path) -> Result<(), Error> {
+        self.keys_under(path)?
+            .into_iter()
+            .try_for_each(|path| self.remove_item(&path))
+    }
+
+    /// Removes the item at the given path, if it exists.
+    pub async fn file_remove(&self, path: &Path) -> Result<(), Error> {
+        self.remove_item(path)
+    }
+
+    /// Returns the keys of all items under the given path.
+    fn keys_under(&self, path: &Path) -> Result<Vec<PathBuf>, Error> {
+        let storage = self.get()?;
+        let storage_get_item_error = |js_value| {
+            Error::Web(WebError::StorageGetItem {
+                path: path.to_path_buf(),
+                error: crate::stringify_js_value(js_value),
+            })
+        };
+        let length = storage.length().map_err(storage_get_item_error)?;
+
+        (0..length).try_fold(Vec::new(), |mut keys, index| {
+            if let Some(key) = storage.key(index).map_err(storage_get_item_error)? {
+                let key = PathBuf::from(key);
+                if key.starts_with(path) {
+                    keys.push(key);
+                }
+            }
+            Ok(keys)
+        })
+    }
+
     /// Deletes an item from the web storage.
     pub fn remove_item(&self, path: &Path) -> Result<(), Error> {
         let storage = self.get()?;
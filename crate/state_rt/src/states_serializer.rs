@@ -1,5 +1,6 @@
 use std::{marker::PhantomData, path::Path};
 
+use indexmap::IndexMap;
 use peace_flow_model::FlowId;
 use peace_flow_rt::ItemGraph;
 use peace_item_model::ItemId;
@@ -42,15 +43,44 @@ where
         TS: Send + Sync,
     {
         let states_serde = item_graph.states_serde::<serde_yaml::Value, _>(states);
-        storage
-            .serialized_write(
-                #[cfg(not(target_arch = "wasm32"))]
-                "StatesSerializer::serialize".to_string(),
-                states_file_path,
-                &states_serde,
-                Error::StatesSerialize,
-            )
-            .await?;
+        let states_orphaned = Self::states_orphaned_read(storage, item_graph, states_file_path)
+            .await?
+            .unwrap_or_default();
+
+        if states_orphaned.is_empty() {
+            storage
+                .serialized_write(
+                    #[cfg(not(target_arch = "wasm32"))]
+                    "StatesSerializer::serialize".to_string(),
+                    states_file_path,
+                    &states_serde,
+                    Error::StatesSerialize,
+                )
+                .await?;
+        } else {
+            // Orphaned states are retained, so that the resources they describe can
+            // still be cleaned up by a retired item.
+            let mut states_serialized =
+                serde_yaml::to_value(&states_serde).map_err(Error::StatesSerialize)?;
+            if let serde_yaml::Value::Mapping(states_mapping) = &mut states_serialized {
+                states_mapping.extend(states_orphaned.into_iter().map(|(item_id, state)| {
+                    (
+                        serde_yaml::Value::String(item_id.as_str().to_string()),
+                        state,
+                    )
+                }));
+            }
+
+            storage
+                .serialized_write(
+                    #[cfg(not(target_arch = "wasm32"))]
+                    "StatesSerializer::serialize".to_string(),
+                    states_file_path,
+                    &states_serialized,
+                    Error::StatesSerialize,
+                )
+                .await?;
+        }
 
         Ok(())
     }
@@ -142,6 +172,134 @@ where
         .await
     }
 
+    /// Returns the IDs of the items in a states file, if it exists on disk.
+    ///
+    /// This does not deserialize the states themselves, so it can be used to
+    /// find item IDs that are not in the current flow, such as items created
+    /// by an [`ItemFanOut`] for list elements that have since been removed.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `states_file_path`: Path of the states file to read.
+    ///
+    /// [`ItemFanOut`]: peace_flow_rt::ItemFanOut
+    pub async fn deserialize_item_ids_opt(
+        storage: &Storage,
+        states_file_path: &Path,
+    ) -> Result<Option<Vec<ItemId>>, E> {
+        let item_ids = Self::deserialize_untyped_opt(storage, states_file_path)
+            .await?
+            .map(|states_serde| states_serde.into_keys().collect::<Vec<ItemId>>());
+
+        Ok(item_ids)
+    }
+
+    /// Returns the states in a states file as untyped values, if it exists on
+    /// disk.
+    ///
+    /// This is used to read the stored states of another flow, whose item
+    /// types are not registered in this flow's states type registry.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `states_file_path`: Path of the states file to read.
+    pub async fn deserialize_untyped_opt(
+        storage: &Storage,
+        states_file_path: &Path,
+    ) -> Result<Option<IndexMap<ItemId, serde_yaml::Value>>, E> {
+        let states_untyped = storage
+            .serialized_read_opt::<IndexMap<ItemId, serde_yaml::Value>, _>(
+                #[cfg(not(target_arch = "wasm32"))]
+                "StatesSerializer::deserialize_untyped_opt".to_string(),
+                states_file_path,
+                |error| Error::StatesItemIdsDeserialize {
+                    path: states_file_path.to_path_buf(),
+                    error,
+                },
+            )
+            .await?;
+
+        Ok(states_untyped)
+    }
+
+    /// Returns typed [`States`] from untyped states.
+    ///
+    /// This is used to deserialize states that have been updated in memory
+    /// after being read through [`Self::deserialize_untyped_opt`].
+    ///
+    /// # Parameters:
+    ///
+    /// * `states_type_reg`: Type registry with functions to deserialize each
+    ///   item state.
+    /// * `states_untyped`: States to deserialize.
+    pub fn deserialize_from_untyped<TS>(
+        states_type_reg: &TypeReg<ItemId, BoxDtDisplay>,
+        states_untyped: &IndexMap<ItemId, serde_yaml::Value>,
+    ) -> Result<States<TS>, E> {
+        let states_value =
+            serde_yaml::to_value(states_untyped).map_err(Error::StatesUntypedDeserialize)?;
+        let states = states_type_reg
+            .deserialize_map_opt_with_unknowns::<'_, serde_yaml::Value, _, _>(states_value)
+            .map(TypeMapOpt::into_type_map)
+            .map(States::from)
+            .map_err(Error::StatesUntypedDeserialize)?;
+
+        Ok(states)
+    }
+
+    /// Returns the IDs of orphaned items in a states file, if it exists on
+    /// disk.
+    ///
+    /// Orphaned items are items with a stored state that are no longer part of
+    /// the flow definition -- either they are not in `item_graph`, or they are
+    /// retired items in `item_graph`.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `item_graph`: Item graph of the flow.
+    /// * `states_file_path`: Path of the states file to read.
+    pub async fn deserialize_item_ids_orphaned_opt(
+        storage: &Storage,
+        item_graph: &ItemGraph<E>,
+        states_file_path: &Path,
+    ) -> Result<Option<Vec<ItemId>>, E> {
+        let item_ids_orphaned = Self::deserialize_item_ids_opt(storage, states_file_path)
+            .await?
+            .map(|item_ids| {
+                item_ids
+                    .into_iter()
+                    .filter(|item_id| {
+                        item_graph.item_ids_retired().contains(item_id)
+                            || !item_graph.iter_insertion().any(|item| item.id() == item_id)
+                    })
+                    .collect::<Vec<ItemId>>()
+            });
+
+        Ok(item_ids_orphaned)
+    }
+
+    /// Returns the stored states of items that are not in `item_graph`, if
+    /// the states file exists on disk.
+    async fn states_orphaned_read(
+        storage: &Storage,
+        item_graph: &ItemGraph<E>,
+        states_file_path: &Path,
+    ) -> Result<Option<IndexMap<ItemId, serde_yaml::Value>>, E> {
+        let states_orphaned = Self::deserialize_untyped_opt(storage, states_file_path)
+            .await?
+            .map(|mut states_serde| {
+                states_serde.retain(|item_id, _state| {
+                    !item_graph.iter_insertion().any(|item| item.id() == item_id)
+                });
+                states_serde
+            });
+
+        Ok(states_orphaned)
+    }
+
     /// Returns the [`States`] of all [`Item`]s if it exists on disk.
     ///
     /// # Parameters:
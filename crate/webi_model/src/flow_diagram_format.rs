@@ -0,0 +1,45 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::FlowDiagramFormatParseError;
+
+/// Format to render a flow diagram in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowDiagramFormat {
+    /// Graphviz [DOT] source.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    Dot,
+    /// [Mermaid] flowchart source, which can be embedded in markdown.
+    ///
+    /// [Mermaid]: https://mermaid.js.org/syntax/flowchart.html
+    Mermaid,
+    /// Standalone SVG image, rendered by the Graphviz `dot` executable.
+    Svg,
+}
+
+impl FlowDiagramFormat {
+    /// Returns the file extension for this format, without the leading `.`.
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Mermaid => "mmd",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for FlowDiagramFormat {
+    type Err = FlowDiagramFormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            "svg" => Ok(Self::Svg),
+            _ => Err(FlowDiagramFormatParseError(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt;
+
+/// Failed to parse flow diagram format from string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowDiagramFormatParseError(pub String);
+
+impl fmt::Display for FlowDiagramFormatParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#"Failed to parse flow diagram format from string: `"{}"`. Valid values are ["dot", "mermaid", "svg"]"#,
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for FlowDiagramFormatParseError {}
@@ -1,13 +1,17 @@
 //! Web interface data types for the peace automation framework.
 
 pub use crate::{
-    flow_info_graphs::FlowInfoGraphs, flow_outcome_info_graphs::FlowOutcomeInfoGraphs,
+    flow_diagram_format::FlowDiagramFormat,
+    flow_diagram_format_parse_error::FlowDiagramFormatParseError, flow_info_graphs::FlowInfoGraphs,
+    flow_outcome_info_graphs::FlowOutcomeInfoGraphs,
     flow_progress_info_graphs::FlowProgressInfoGraphs,
     outcome_info_graph_variant::OutcomeInfoGraphVariant,
     progress_info_graph_variant::ProgressInfoGraphVariant, web_ui_update::WebUiUpdate,
     webi_error::WebiError,
 };
 
+mod flow_diagram_format;
+mod flow_diagram_format_parse_error;
 mod flow_info_graphs;
 mod flow_outcome_info_graphs;
 mod flow_progress_info_graphs;
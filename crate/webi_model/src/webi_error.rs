@@ -68,4 +68,85 @@ pub enum WebiError {
         #[source]
         error: std::io::Error,
     },
+
+    /// Failed to create directory to write flow diagrams to.
+    #[error("Failed to create flow diagram directory: `{diagram_dir}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_webi_model::flow_diagram_dir_create),
+            help("Check if you have sufficient permission to write to the directory.")
+        )
+    )]
+    FlowDiagramDirCreate {
+        /// The directory attempted to be created.
+        diagram_dir: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to write flow diagram.
+    #[error("Failed to write flow diagram: `{diagram_path}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_webi_model::flow_diagram_write),
+            help("Check if you have sufficient permission to write to the file.")
+        )
+    )]
+    FlowDiagramWrite {
+        /// Path to the file attempted to be written to.
+        diagram_path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// The Graphviz `dot` executable was not found, which is needed to render
+    /// an SVG.
+    #[error("Graphviz `dot` executable not found, which is needed to render SVG flow diagrams.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_webi_model::flow_diagram_dot_not_found),
+            help(
+                "Install Graphviz from https://graphviz.org/download/ and make sure `dot` is \
+                on the `PATH`, or export the diagram in the `dot` or `mermaid` format instead."
+            )
+        )
+    )]
+    FlowDiagramDotNotFound,
+
+    /// Failed to run the Graphviz `dot` executable to render an SVG.
+    #[error("Failed to run the Graphviz `dot` executable to render an SVG.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_webi_model::flow_diagram_dot_spawn),
+            help(
+                "Install Graphviz and make sure `dot` is on the `PATH`, \
+                or export the diagram in the `dot` format instead."
+            )
+        )
+    )]
+    FlowDiagramDotSpawn {
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// The Graphviz `dot` executable failed to render an SVG.
+    #[error("The Graphviz `dot` executable failed to render an SVG:\n{stderr}")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_webi_model::flow_diagram_dot_render),
+            help("This may be a bug in the Peace framework or `dot_ix`.")
+        )
+    )]
+    FlowDiagramDotRender {
+        /// Error output from `dot`.
+        stderr: String,
+    },
 }
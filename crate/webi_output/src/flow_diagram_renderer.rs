@@ -0,0 +1,154 @@
+use std::{io::ErrorKind, process::Stdio};
+
+use dot_ix::{
+    model::{
+        common::{GraphvizDotTheme, NodeHierarchy, NodeId, NodeNames},
+        info_graph::{GraphDir, InfoGraph},
+    },
+    rt::IntoGraphvizDotSrc,
+};
+use peace_webi_model::{FlowDiagramFormat, WebiError};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+/// Renders an `InfoGraph` to DOT, Mermaid, or SVG source.
+///
+/// This does not need the web server to be running, so diagrams can be
+/// rendered in CI and embedded in documentation.
+#[derive(Debug)]
+pub struct FlowDiagramRenderer;
+
+impl FlowDiagramRenderer {
+    /// Returns the diagram rendered in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format is [`FlowDiagramFormat::Svg`] and the
+    /// Graphviz `dot` executable cannot be run, or fails to render the
+    /// diagram.
+    pub async fn render(
+        info_graph: &InfoGraph,
+        flow_diagram_format: FlowDiagramFormat,
+    ) -> Result<String, WebiError> {
+        match flow_diagram_format {
+            FlowDiagramFormat::Dot => Ok(Self::dot(info_graph)),
+            FlowDiagramFormat::Mermaid => Ok(Self::mermaid(info_graph)),
+            FlowDiagramFormat::Svg => Self::svg(info_graph).await,
+        }
+    }
+
+    /// Returns the Graphviz DOT source for the diagram.
+    pub fn dot(info_graph: &InfoGraph) -> String {
+        IntoGraphvizDotSrc::into(info_graph, &GraphvizDotTheme::default()).dot_src
+    }
+
+    /// Returns the Mermaid flowchart source for the diagram.
+    ///
+    /// Nodes with nested nodes are rendered as subgraphs. Styles from the
+    /// `InfoGraph`'s theme are not rendered.
+    ///
+    /// Node IDs are prefixed with `node_`, so that IDs which are Mermaid
+    /// keywords, such as `end`, do not break the diagram.
+    pub fn mermaid(info_graph: &InfoGraph) -> String {
+        let direction = match info_graph.direction() {
+            GraphDir::Horizontal => "LR",
+            GraphDir::Vertical => "TB",
+        };
+
+        let mut mermaid_src = format!("flowchart {direction}\n");
+        mermaid_nodes_write(
+            &mut mermaid_src,
+            info_graph.hierarchy(),
+            info_graph.node_names(),
+            1,
+        );
+        info_graph
+            .edges()
+            .values()
+            .for_each(|[node_id_src, node_id_dest]| {
+                mermaid_src.push_str(&format!(
+                    "    {node_id_src} --> {node_id_dest}\n",
+                    node_id_src = mermaid_node_id(node_id_src),
+                    node_id_dest = mermaid_node_id(node_id_dest),
+                ));
+            });
+
+        mermaid_src
+    }
+
+    /// Returns the SVG image for the diagram.
+    ///
+    /// This runs the Graphviz `dot` executable, which must be on the `PATH`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WebiError::FlowDiagramDotNotFound`] if Graphviz is not
+    /// installed, or another error if `dot` cannot be run, or fails to render
+    /// the diagram.
+    pub async fn svg(info_graph: &InfoGraph) -> Result<String, WebiError> {
+        let dot_src = Self::dot(info_graph);
+
+        let mut dot_process = Command::new("dot")
+            .arg("-Tsvg")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| match error.kind() {
+                ErrorKind::NotFound => WebiError::FlowDiagramDotNotFound,
+                _ => WebiError::FlowDiagramDotSpawn { error },
+            })?;
+        if let Some(mut stdin) = dot_process.stdin.take() {
+            stdin
+                .write_all(dot_src.as_bytes())
+                .await
+                .map_err(|error| WebiError::FlowDiagramDotSpawn { error })?;
+        }
+        let output = dot_process
+            .wait_with_output()
+            .await
+            .map_err(|error| WebiError::FlowDiagramDotSpawn { error })?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(WebiError::FlowDiagramDotRender {
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            })
+        }
+    }
+}
+
+/// Writes each node in the hierarchy, with nodes that contain other nodes
+/// written as subgraphs.
+fn mermaid_nodes_write(
+    mermaid_src: &mut String,
+    hierarchy: &NodeHierarchy,
+    node_names: &NodeNames,
+    depth: usize,
+) {
+    let indent = "    ".repeat(depth);
+    hierarchy.iter().for_each(|(node_id, node_hierarchy)| {
+        let node_name = node_names
+            .get(node_id)
+            .map(|node_name| node_name.replace('"', "#quot;"))
+            .unwrap_or_else(|| node_id.to_string());
+        let node_id = mermaid_node_id(node_id);
+
+        if node_hierarchy.is_empty() {
+            mermaid_src.push_str(&format!("{indent}{node_id}[\"{node_name}\"]\n"));
+        } else {
+            mermaid_src.push_str(&format!("{indent}subgraph {node_id}[\"{node_name}\"]\n"));
+            mermaid_nodes_write(mermaid_src, node_hierarchy, node_names, depth + 1);
+            mermaid_src.push_str(&format!("{indent}end\n"));
+        }
+    });
+}
+
+/// Returns the Mermaid ID for a node.
+///
+/// IDs are prefixed so that they are never a Mermaid keyword, such as `end` or
+/// `subgraph`, and do not begin with `o` or `x`, which Mermaid may parse as
+/// part of an edge.
+fn mermaid_node_id(node_id: &NodeId) -> String {
+    format!("node_{node_id}")
+}
@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+
+use dot_ix_model::info_graph::InfoGraph;
+use peace_flow_model::FlowId;
+use peace_flow_rt::Flow;
+use peace_params::{MappingFnReg, ParamsSpecs};
+use peace_resource_rt::{resources::ts::SetUp, Resources};
+use peace_rt_model::ItemIdsDisabled;
+use peace_webi_model::{FlowDiagramFormat, WebiError};
+
+use crate::FlowDiagramRenderer;
+
+#[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+use peace_webi_model::OutcomeInfoGraphVariant;
+
+#[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+use crate::OutcomeInfoGraphCalculator;
+
+/// The diagrams for a flow, calculated without running the web server.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let flow_diagrams = FlowDiagrams::calculate(
+///     cmd_ctx.flow(),
+///     cmd_ctx.params_specs(),
+///     cmd_ctx.mapping_fn_reg(),
+///     cmd_ctx.resources(),
+/// );
+/// let mermaid_src = FlowDiagramRenderer::mermaid(&flow_diagrams.item_graph);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowDiagrams {
+    /// ID of the flow.
+    pub flow_id: FlowId,
+    /// The flow's item graph, which shows the order that items are applied.
+    pub item_graph: InfoGraph,
+    /// The example interaction diagram, which shows the locations each item
+    /// interacts with using the items' example states.
+    #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+    pub outcome_example: InfoGraph,
+    /// The current state outcome diagram, which shows the locations each item
+    /// interacts with using the items' current states.
+    #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+    pub outcome_current: InfoGraph,
+}
+
+impl FlowDiagrams {
+    /// Returns the calculated diagrams for the flow.
+    ///
+    /// For the current state outcome diagram, `resources` should contain the
+    /// items' current states, e.g. after `StatesCurrentReadCmd` has run.
+    pub fn calculate<E>(
+        flow: &Flow<E>,
+        params_specs: &ParamsSpecs,
+        mapping_fn_reg: &MappingFnReg,
+        resources: &Resources<SetUp>,
+    ) -> Self
+    where
+        E: 'static,
+    {
+        let item_ids_disabled = resources
+            .try_borrow::<ItemIdsDisabled>()
+            .map(|item_ids_disabled| item_ids_disabled.item_ids().to_vec())
+            .unwrap_or_default();
+        let item_graph = flow
+            .flow_spec_info()
+            .with_params_refs(flow.params_specs_item_refs(params_specs, mapping_fn_reg))
+            .with_item_ids_disabled(item_ids_disabled)
+            .to_progress_info_graph();
+
+        #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+        let outcome_example = OutcomeInfoGraphCalculator::calculate::<E>(
+            flow,
+            params_specs,
+            mapping_fn_reg,
+            resources,
+            OutcomeInfoGraphVariant::Example,
+        );
+
+        #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+        let outcome_current = OutcomeInfoGraphCalculator::calculate::<E>(
+            flow,
+            params_specs,
+            mapping_fn_reg,
+            resources,
+            OutcomeInfoGraphVariant::Current {
+                #[cfg(feature = "output_progress")]
+                cmd_block_item_interaction_type:
+                    peace_progress_model::CmdBlockItemInteractionType::Local,
+                #[cfg(feature = "output_progress")]
+                item_location_states: std::collections::HashMap::new(),
+                #[cfg(feature = "output_progress")]
+                item_progress_statuses: std::collections::HashMap::new(),
+            },
+        );
+
+        Self {
+            flow_id: flow.flow_id().clone(),
+            item_graph,
+            #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+            outcome_example,
+            #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+            outcome_current,
+        }
+    }
+
+    /// Returns each diagram with the name used in its file name.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &InfoGraph)> + '_ {
+        [
+            ("item_graph", &self.item_graph),
+            #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+            ("outcome_example", &self.outcome_example),
+            #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+            ("outcome_current", &self.outcome_current),
+        ]
+        .into_iter()
+    }
+
+    /// Renders each diagram in each of the given formats, and writes them to
+    /// `diagram_dir`.
+    ///
+    /// Files are named `{flow_id}.{diagram_name}.{extension}`, e.g.
+    /// `app_upload.item_graph.mmd`. Returns the paths of the written files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written, or if an
+    /// SVG cannot be rendered.
+    pub async fn write_to_dir(
+        &self,
+        diagram_dir: &Path,
+        flow_diagram_formats: &[FlowDiagramFormat],
+    ) -> Result<Vec<PathBuf>, WebiError> {
+        tokio::fs::create_dir_all(diagram_dir)
+            .await
+            .map_err(|error| WebiError::FlowDiagramDirCreate {
+                diagram_dir: diagram_dir.to_path_buf(),
+                error,
+            })?;
+
+        let mut diagram_paths = Vec::new();
+        for (diagram_name, info_graph) in self.iter() {
+            for flow_diagram_format in flow_diagram_formats.iter().copied() {
+                let diagram_src =
+                    FlowDiagramRenderer::render(info_graph, flow_diagram_format).await?;
+                let diagram_path = diagram_dir.join(format!(
+                    "{flow_id}.{diagram_name}.{extension}",
+                    flow_id = self.flow_id,
+                    extension = flow_diagram_format.file_extension()
+                ));
+                tokio::fs::write(&diagram_path, diagram_src)
+                    .await
+                    .map_err(|error| WebiError::FlowDiagramWrite {
+                        diagram_path: diagram_path.clone(),
+                        error,
+                    })?;
+                diagram_paths.push(diagram_path);
+            }
+        }
+
+        Ok(diagram_paths)
+    }
+}
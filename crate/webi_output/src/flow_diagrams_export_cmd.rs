@@ -0,0 +1,55 @@
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use peace_cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, CmdCtxTypes};
+use peace_webi_model::{FlowDiagramFormat, WebiError};
+
+use crate::FlowDiagrams;
+
+/// Writes a flow's diagrams to files, without running the web server.
+///
+/// See [`FlowDiagrams`] for the diagrams that are written.
+#[derive(Debug)]
+pub struct FlowDiagramsExportCmd<CmdCtxTypesT>(PhantomData<CmdCtxTypesT>);
+
+impl<CmdCtxTypesT> FlowDiagramsExportCmd<CmdCtxTypesT>
+where
+    CmdCtxTypesT: CmdCtxTypes,
+{
+    /// Writes the command context's flow diagrams to `diagram_dir` in each of
+    /// the given formats, and returns the paths of the written files.
+    ///
+    /// For the current state outcome diagram to show the items' current
+    /// states, run `StatesCurrentReadCmd` or `StatesDiscoverCmd` first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written, or if an
+    /// SVG cannot be rendered.
+    pub async fn exec(
+        cmd_ctx: &CmdCtxSpsf<'_, CmdCtxTypesT>,
+        diagram_dir: &Path,
+        flow_diagram_formats: &[FlowDiagramFormat],
+    ) -> Result<Vec<PathBuf>, WebiError> {
+        let CmdCtxSpsfFields {
+            flow,
+            params_specs,
+            mapping_fn_reg,
+            resources,
+            ..
+        } = cmd_ctx.fields();
+
+        let flow_diagrams = FlowDiagrams::calculate(flow, params_specs, mapping_fn_reg, resources);
+        flow_diagrams
+            .write_to_dir(diagram_dir, flow_diagram_formats)
+            .await
+    }
+}
+
+impl<CmdCtxTypesT> Default for FlowDiagramsExportCmd<CmdCtxTypesT> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
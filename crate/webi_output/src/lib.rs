@@ -2,7 +2,9 @@
 
 pub use crate::{
     cmd_exec_spawn_ctx::CmdExecSpawnCtx, cmd_exec_to_leptos_ctx::CmdExecToLeptosCtx,
-    flow_webi_fns::FlowWebiFns, webi_output::WebiOutput, webi_server::WebiServer,
+    flow_diagram_renderer::FlowDiagramRenderer, flow_diagrams::FlowDiagrams,
+    flow_diagrams_export_cmd::FlowDiagramsExportCmd, flow_webi_fns::FlowWebiFns,
+    webi_output::WebiOutput, webi_server::WebiServer,
 };
 
 #[cfg(feature = "item_interactions")]
@@ -15,6 +17,9 @@ pub mod assets;
 
 mod cmd_exec_spawn_ctx;
 mod cmd_exec_to_leptos_ctx;
+mod flow_diagram_renderer;
+mod flow_diagrams;
+mod flow_diagrams_export_cmd;
 mod flow_webi_fns;
 mod webi_output;
 mod webi_server;
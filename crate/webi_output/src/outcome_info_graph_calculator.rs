@@ -359,7 +359,8 @@ fn theme_styles_augment(
                                                         ProgressComplete::Success,
                                                     ) => ControlFlow::Continue(progress_status),
                                                     ProgressStatus::Complete(
-                                                        ProgressComplete::Fail,
+                                                        ProgressComplete::Fail
+                                                        | ProgressComplete::Interrupted,
                                                     ) => ControlFlow::Break(progress_status),
                                                 }
                                             },
@@ -1,4 +1,4 @@
-use std::{net::SocketAddr, path::Path};
+use std::{cell::RefCell, net::SocketAddr, path::Path, rc::Rc};
 
 use axum::Router;
 use futures::stream::{self, StreamExt, TryStreamExt};
@@ -6,6 +6,7 @@ use leptos::view;
 use leptos_axum::LeptosRoutes;
 use peace_cmd_model::CmdExecutionId;
 use peace_flow_model::FlowId;
+use peace_rt_model::ItemIdsDisabled;
 use peace_webi_components::{App, ChildrenFn, Shell};
 use peace_webi_model::{WebUiUpdate, WebiError};
 use tokio::{io::AsyncWriteExt, sync::mpsc};
@@ -113,36 +114,58 @@ impl WebiServer {
         // Should we have one WebiOutput for the whole server? doesn't seem right.
         let (web_ui_update_tx, _web_ui_update_rx) = mpsc::channel(128);
         let mut webi_output_mock = WebiOutput::new(web_ui_update_tx);
-        let flow_spec_info = flow.flow_spec_info();
-        let flow_progress_example_info_graph = flow_spec_info.to_progress_info_graph();
+        // Item references from params specs are only known once the tool developer
+        // has instantiated the `CmdCtx`, so we capture them when calculating the
+        // example outcome graph.
+        let params_refs = Rc::new(RefCell::new(Vec::new()));
+        let item_ids_disabled = Rc::new(RefCell::new(Vec::new()));
         let flow_outcome_example_info_graph = outcome_info_graph_fn(
             &mut webi_output_mock,
-            Box::new(|flow, params_specs, mapping_fn_reg, resources| {
-                #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
-                {
-                    OutcomeInfoGraphCalculator::calculate::<E>(
-                        flow,
-                        params_specs,
-                        mapping_fn_reg,
-                        resources,
-                        OutcomeInfoGraphVariant::Example,
-                    )
-                }
+            Box::new({
+                let params_refs = Rc::clone(&params_refs);
+                let item_ids_disabled = Rc::clone(&item_ids_disabled);
+                move |flow, params_specs, mapping_fn_reg, resources| {
+                    *params_refs.borrow_mut() =
+                        flow.params_specs_item_refs(params_specs, mapping_fn_reg);
+                    *item_ids_disabled.borrow_mut() = resources
+                        .try_borrow::<ItemIdsDisabled>()
+                        .map(|item_ids_disabled| item_ids_disabled.item_ids().to_vec())
+                        .unwrap_or_default();
+
+                    #[cfg(all(feature = "item_interactions", feature = "item_state_example"))]
+                    {
+                        OutcomeInfoGraphCalculator::calculate::<E>(
+                            flow,
+                            params_specs,
+                            mapping_fn_reg,
+                            resources,
+                            OutcomeInfoGraphVariant::Example,
+                        )
+                    }
 
-                #[cfg(not(all(feature = "item_interactions", feature = "item_state_example")))]
-                {
-                    use dot_ix_model::info_graph::InfoGraph;
+                    #[cfg(not(all(
+                        feature = "item_interactions",
+                        feature = "item_state_example"
+                    )))]
+                    {
+                        use dot_ix_model::info_graph::InfoGraph;
 
-                    let _flow = flow;
-                    let _params_specs = params_specs;
-                    let _mapping_fn_reg = mapping_fn_reg;
-                    let _resources = resources;
+                        let _flow = flow;
+                        let _params_specs = params_specs;
+                        let _mapping_fn_reg = mapping_fn_reg;
+                        let _resources = resources;
 
-                    InfoGraph::default()
+                        InfoGraph::default()
+                    }
                 }
             }),
         )
         .await;
+        let flow_spec_info = flow
+            .flow_spec_info()
+            .with_params_refs(params_refs.take())
+            .with_item_ids_disabled(item_ids_disabled.take());
+        let flow_progress_example_info_graph = flow_spec_info.to_progress_info_graph();
 
         let flow_id = flow.flow_id();
         if let Ok(mut flow_progress_example_info_graphs) = flow_progress_example_info_graphs.lock()
@@ -3,11 +3,11 @@ use peace::{
     cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields},
     cmd_model::CmdOutcome,
     fmt::{
-        presentable::{Heading, HeadingLevel, ListNumberedAligned},
+        presentable::{Heading, HeadingLevel, ListBulleted, ListNumberedAligned},
         PresentableExt,
     },
     rt::cmds::StatesDiscoverCmd,
-    rt_model::output::OutputWrite,
+    rt_model::{output::OutputWrite, ItemIdsOrphaned},
 };
 
 use crate::{
@@ -118,6 +118,21 @@ where
                 "\n",
             ))
             .await?;
+
+        // Items that were removed from the flow, whose resources may be leaked.
+        let item_ids_orphaned = resources
+            .try_borrow::<ItemIdsOrphaned>()
+            .map(|item_ids_orphaned| item_ids_orphaned.item_ids().to_vec())
+            .unwrap_or_default();
+        if !item_ids_orphaned.is_empty() {
+            output
+                .present(&(
+                    Heading::new(HeadingLevel::Level1, "Orphaned Items"),
+                    ListBulleted::new(item_ids_orphaned),
+                    "\n",
+                ))
+                .await?;
+        }
     }
     if let CmdOutcome::ItemError { errors, .. } = &states_discover_outcome {
         crate::output::item_errors_present(&mut **output, errors).await?;
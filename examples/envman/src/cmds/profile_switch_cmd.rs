@@ -1,8 +1,5 @@
 use peace::{
-    cfg::app_name,
-    cmd_ctx::{CmdCtxMpnf, CmdCtxMpnfFields},
-    profile_model::Profile,
-    rt_model::output::OutputWrite,
+    cfg::app_name, cmd_ctx::CmdCtxMpnf, profile_model::Profile, rt_model::output::OutputWrite,
 };
 
 use crate::{
@@ -42,38 +39,27 @@ impl ProfileSwitchCmd {
         // .with_profile_param::<EnvType>(ProfileParamsKey::EnvType, None);
 
         let mut cmd_ctx = cmd_ctx_builder.await?;
-        let CmdCtxMpnf {
-            ref mut output,
-            fields:
-                CmdCtxMpnfFields {
-                    workspace,
-                    profiles,
-                    ..
-                },
-        } = cmd_ctx;
 
         match profile_switch {
             ProfileSwitch::ToExisting {
                 profile: profile_to_switch_to,
             } => {
-                if !profiles.contains(&profile_to_switch_to) {
+                if !cmd_ctx.fields().profiles().contains(&profile_to_switch_to) {
                     return Err(EnvManError::ProfileSwitchToNonExistent {
                         profile_to_switch_to,
                         app_name,
                     });
-                } else {
-                    // Switches profile
-                    let _cmd_ctx = CmdCtxMpnf::<EnvmanCmdCtxTypes<O>>::builder()
-                        .with_output(output.reborrow())
-                        .with_workspace(workspace)
-                        .with_workspace_param(
-                            WorkspaceParamsKey::Profile,
-                            Some(profile_to_switch_to.clone()),
-                        )
-                        .await?;
                 }
 
-                output
+                peace::rt::cmds::ProfileSwitchCmd::exec(
+                    &mut cmd_ctx,
+                    WorkspaceParamsKey::Profile,
+                    profile_to_switch_to.clone(),
+                )
+                .await?;
+
+                cmd_ctx
+                    .output_mut()
                     .present(&(
                         String::from("Switched to profile: "),
                         profile_to_switch_to,
@@ -88,8 +74,9 @@ impl ProfileSwitchCmd {
                 version,
                 url,
             } => {
+                let output = cmd_ctx.output_mut();
                 ProfileInitCmd::run(
-                    &mut **output,
+                    &mut *output,
                     profile_to_create.clone(),
                     env_man_flow,
                     env_type,
@@ -0,0 +1,227 @@
+use std::marker::PhantomData;
+
+use peace::cfg::{ApplyCheck, FnCtx};
+#[cfg(feature = "output_progress")]
+use peace::progress_model::ProgressLimit;
+
+use crate::{FileData, FileError, FileParams, FileState, FileStateDiff};
+
+/// ApplyFns for the file.
+#[derive(Debug)]
+pub struct FileApplyFns<Id>(PhantomData<Id>);
+
+impl<Id> FileApplyFns<Id>
+where
+    Id: Send + Sync + 'static,
+{
+    pub async fn apply_check(
+        _params: &FileParams<Id>,
+        _data: FileData<'_, Id>,
+        _state_current: &FileState,
+        _state_target: &FileState,
+        diff: &FileStateDiff,
+    ) -> Result<ApplyCheck, FileError> {
+        let apply_check = match diff {
+            FileStateDiff::InSync { .. } => ApplyCheck::ExecNotRequired,
+            FileStateDiff::Added { .. }
+            | FileStateDiff::Removed { .. }
+            | FileStateDiff::Replaced { .. }
+            | FileStateDiff::Modified { .. } => {
+                #[cfg(not(feature = "output_progress"))]
+                {
+                    ApplyCheck::ExecRequired
+                }
+                #[cfg(feature = "output_progress")]
+                {
+                    let progress_limit = ProgressLimit::Steps(1);
+                    ApplyCheck::ExecRequired { progress_limit }
+                }
+            }
+        };
+
+        Ok(apply_check)
+    }
+
+    pub async fn apply_dry(
+        _fn_ctx: FnCtx<'_>,
+        _params: &FileParams<Id>,
+        _data: FileData<'_, Id>,
+        _state_current: &FileState,
+        state_target: &FileState,
+        _diff: &FileStateDiff,
+    ) -> Result<FileState, FileError> {
+        Ok(state_target.clone())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn apply(
+        _fn_ctx: FnCtx<'_>,
+        params: &FileParams<Id>,
+        _data: FileData<'_, Id>,
+        state_current: &FileState,
+        state_target: &FileState,
+        diff: &FileStateDiff,
+    ) -> Result<FileState, FileError> {
+        use crate::{FileEntry, FileStateCurrentFn};
+
+        let dest = params.dest();
+
+        // Remove the current entry if it is not of the target kind.
+        if matches!(
+            diff,
+            FileStateDiff::Removed { .. } | FileStateDiff::Replaced { .. }
+        ) || matches!(
+            (state_current, diff),
+            (
+                FileState::Symlink { .. },
+                FileStateDiff::Modified {
+                    symlink_target: Some(_),
+                    ..
+                }
+            )
+        ) {
+            Self::entry_remove(state_current).await?;
+        }
+
+        if let FileState::NotExists { .. } = state_target {
+            return FileStateCurrentFn::<Id>::file_state(dest).await;
+        }
+
+        if let Some(parent) = dest
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            tokio::fs::create_dir_all(parent).await.map_err(|error| {
+                FileError::DestParentDirCreate {
+                    dest: dest.to_path_buf(),
+                    error,
+                }
+            })?;
+        }
+
+        match params.entry() {
+            FileEntry::File { contents } => {
+                let contents_changed = !matches!(
+                    diff,
+                    FileStateDiff::Modified {
+                        contents_diff: None,
+                        ..
+                    }
+                );
+                if contents_changed {
+                    let bytes = contents.bytes().await?;
+                    tokio::fs::write(dest, bytes)
+                        .await
+                        .map_err(|error| FileError::DestWrite {
+                            dest: dest.to_path_buf(),
+                            error,
+                        })?;
+                }
+                Self::attrs_apply(params).await?;
+            }
+            FileEntry::Dir => {
+                tokio::fs::create_dir_all(dest).await.map_err(|error| {
+                    FileError::DestDirCreate {
+                        dest: dest.to_path_buf(),
+                        error,
+                    }
+                })?;
+                Self::attrs_apply(params).await?;
+            }
+            FileEntry::Symlink { target } => {
+                if !matches!(state_current, FileState::Symlink { .. })
+                    || matches!(diff, FileStateDiff::Modified { .. })
+                {
+                    #[cfg(unix)]
+                    let symlink_create = tokio::fs::symlink(target, dest).await;
+                    #[cfg(windows)]
+                    let symlink_create = tokio::fs::symlink_file(target, dest).await;
+
+                    symlink_create.map_err(|error| FileError::SymlinkCreate {
+                        dest: dest.to_path_buf(),
+                        target: target.clone(),
+                        error,
+                    })?;
+                }
+            }
+        }
+
+        FileStateCurrentFn::<Id>::file_state(dest).await
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub async fn apply(
+        _fn_ctx: FnCtx<'_>,
+        _params: &FileParams<Id>,
+        _data: FileData<'_, Id>,
+        _state_current: &FileState,
+        _state_target: &FileState,
+        _diff: &FileStateDiff,
+    ) -> Result<FileState, FileError> {
+        Err(FileError::WasmUnsupported)
+    }
+
+    /// Removes the current entry.
+    ///
+    /// Directories are only removed if they are empty, so that files managed
+    /// by other items, or not managed at all, are not removed. A non-empty
+    /// directory results in [`FileError::DestDirNotEmpty`].
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn entry_remove(state_current: &FileState) -> Result<(), FileError> {
+        let remove_result = match state_current {
+            FileState::NotExists { .. } => return Ok(()),
+            FileState::File { path, .. } | FileState::Symlink { path, .. } => {
+                tokio::fs::remove_file(path).await
+            }
+            FileState::Dir { path, .. } => tokio::fs::remove_dir(path).await,
+        };
+
+        remove_result.map_err(|error| {
+            let dest = state_current.path().to_path_buf();
+            if error.kind() == std::io::ErrorKind::DirectoryNotEmpty {
+                FileError::DestDirNotEmpty { dest }
+            } else {
+                FileError::DestRemove { dest, error }
+            }
+        })
+    }
+
+    /// Sets the permission bits and owner of the entry, if specified.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn attrs_apply(params: &FileParams<Id>) -> Result<(), FileError> {
+        #[cfg(unix)]
+        {
+            use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+            let dest = params.dest();
+            if let Some(mode) = params.mode() {
+                tokio::fs::set_permissions(dest, Permissions::from_mode(mode))
+                    .await
+                    .map_err(|error| FileError::PermissionsSet {
+                        dest: dest.to_path_buf(),
+                        mode,
+                        error,
+                    })?;
+            }
+
+            if let Some(owner) = params.owner() {
+                let dest = dest.to_path_buf();
+                tokio::task::spawn_blocking(move || {
+                    std::os::unix::fs::chown(&dest, owner.uid, owner.gid)
+                        .map_err(|error| FileError::OwnerSet { dest, owner, error })
+                })
+                .await
+                .map_err(|error| FileError::OwnerSet {
+                    dest: params.dest().to_path_buf(),
+                    owner,
+                    error: std::io::Error::other(error),
+                })??;
+            }
+        }
+
+        #[cfg(not(unix))]
+        let _params = params;
+
+        Ok(())
+    }
+}
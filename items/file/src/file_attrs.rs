@@ -0,0 +1,49 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Permission bits and ownership of a file or directory.
+///
+/// In the goal state, `None` values are not managed, so they are not compared
+/// with the current state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileAttrs {
+    /// Permission bits, e.g. `0o644`.
+    pub mode: Option<u32>,
+    /// User ID of the owner.
+    pub uid: Option<u32>,
+    /// Group ID of the owner.
+    pub gid: Option<u32>,
+}
+
+impl FileAttrs {
+    /// Returns whether all of the managed attributes in `goal` match these
+    /// attributes.
+    pub fn satisfies(&self, goal: &FileAttrs) -> bool {
+        fn field_satisfies(current: Option<u32>, goal: Option<u32>) -> bool {
+            goal.is_none() || current == goal
+        }
+
+        field_satisfies(self.mode, goal.mode)
+            && field_satisfies(self.uid, goal.uid)
+            && field_satisfies(self.gid, goal.gid)
+    }
+}
+
+impl fmt::Display for FileAttrs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        if let Some(mode) = self.mode {
+            write!(f, "mode {mode:o}")?;
+            separator = ", ";
+        }
+        if let Some(uid) = self.uid {
+            write!(f, "{separator}uid {uid}")?;
+            separator = ", ";
+        }
+        if let Some(gid) = self.gid {
+            write!(f, "{separator}gid {gid}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::{collections::BTreeMap, fmt, path::PathBuf};
+
+use peace::params::{template_segments, TemplateSegment};
+use serde::{Deserialize, Serialize};
+
+use crate::FileError;
+
+/// Contents to write to a file.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FileContents {
+    /// Text to write to the file.
+    Text(String),
+    /// Bytes to write to the file.
+    Bytes(Vec<u8>),
+    /// Template to render and write to the file.
+    ///
+    /// Each `{name}` placeholder in the template is replaced with the value of
+    /// `name` in `vars`. Literal braces are written as `{{` and `}}`. This is
+    /// the same syntax as `ValueSpec::Template`.
+    ///
+    /// To use values from other params, provide this using a mapping
+    /// function.
+    Template {
+        /// The template text.
+        template: String,
+        /// Values to substitute into the template.
+        vars: BTreeMap<String, String>,
+    },
+    /// Path to a file whose contents are copied to the file.
+    ///
+    /// The source file is read when the goal state is discovered, so it may
+    /// be written by a predecessor item.
+    Source(PathBuf),
+}
+
+impl FileContents {
+    /// Returns `FileContents::Text` with the given text.
+    pub fn text<T>(text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Text(text.into())
+    }
+
+    /// Returns `FileContents::Template` with the given template and
+    /// variables.
+    pub fn template<T>(template: T, vars: BTreeMap<String, String>) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Template {
+            template: template.into(),
+            vars,
+        }
+    }
+
+    /// Returns the bytes to write to the file.
+    ///
+    /// This renders templates, and reads the source file if the contents come
+    /// from a file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn bytes(&self) -> Result<Vec<u8>, FileError> {
+        match self {
+            Self::Source(source_path) => {
+                tokio::fs::read(source_path)
+                    .await
+                    .map_err(|error| FileError::SourceRead {
+                        source_path: source_path.clone(),
+                        error,
+                    })
+            }
+            Self::Text(_) | Self::Bytes(_) | Self::Template { .. } => self.bytes_inline(),
+        }
+    }
+
+    /// Returns the bytes to write to the file.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn bytes(&self) -> Result<Vec<u8>, FileError> {
+        match self {
+            Self::Source(_) => Err(FileError::WasmUnsupported),
+            Self::Text(_) | Self::Bytes(_) | Self::Template { .. } => self.bytes_inline(),
+        }
+    }
+
+    /// Returns whether the contents are from a source file which does not
+    /// exist.
+    pub(crate) fn source_not_exists(&self) -> bool {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Source(source_path) => !source_path.exists(),
+            #[cfg(target_arch = "wasm32")]
+            Self::Source(_) => false,
+            Self::Text(_) | Self::Bytes(_) | Self::Template { .. } => false,
+        }
+    }
+
+    /// Returns the bytes of contents that do not need IO to resolve.
+    fn bytes_inline(&self) -> Result<Vec<u8>, FileError> {
+        match self {
+            Self::Text(text) => Ok(text.as_bytes().to_vec()),
+            Self::Bytes(bytes) => Ok(bytes.clone()),
+            Self::Template { template, vars } => {
+                Self::template_render(template, vars).map(String::into_bytes)
+            }
+            Self::Source(_) => unreachable!("Source contents are read from the file system."),
+        }
+    }
+
+    /// Replaces each `{name}` in the template with its value.
+    fn template_render(
+        template: &str,
+        vars: &BTreeMap<String, String>,
+    ) -> Result<String, FileError> {
+        let segments =
+            template_segments(template).map_err(|reason| FileError::TemplateInvalid {
+                template: template.to_string(),
+                reason,
+            })?;
+
+        segments.into_iter().try_fold(
+            String::with_capacity(template.len()),
+            |mut rendered, segment| {
+                match segment {
+                    TemplateSegment::Literal(literal) => rendered.push_str(&literal),
+                    TemplateSegment::Placeholder(var_name) => {
+                        let value =
+                            vars.get(var_name)
+                                .ok_or_else(|| FileError::TemplateVarNotFound {
+                                    var_name: var_name.to_string(),
+                                })?;
+                        rendered.push_str(value);
+                    }
+                }
+                Ok(rendered)
+            },
+        )
+    }
+}
+
+impl fmt::Debug for FileContents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            // Don't print all of the bytes.
+            Self::Bytes(bytes) => write!(f, "Bytes({} bytes)", bytes.len()),
+            Self::Template { template, vars } => f
+                .debug_struct("Template")
+                .field("template", template)
+                .field("vars", vars)
+                .finish(),
+            Self::Source(source_path) => f.debug_tuple("Source").field(source_path).finish(),
+        }
+    }
+}
+
+impl From<String> for FileContents {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for FileContents {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for FileContents {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
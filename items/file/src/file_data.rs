@@ -0,0 +1,33 @@
+use std::marker::PhantomData;
+
+use peace::{
+    data::{accessors::R, Data},
+    rt_model::Storage,
+};
+
+/// Data used to manage a file.
+///
+/// # Type Parameters
+///
+/// * `Id`: A zero-sized type used to distinguish different file parameters
+///   from each other.
+#[derive(Data, Debug)]
+pub struct FileData<'exec, Id>
+where
+    Id: Send + Sync + 'static,
+{
+    /// Storage to interact with to read or write the file.
+    storage: R<'exec, Storage>,
+
+    /// Marker.
+    marker: PhantomData<Id>,
+}
+
+impl<Id> FileData<'_, Id>
+where
+    Id: Send + Sync + 'static,
+{
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+}
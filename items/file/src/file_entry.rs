@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::FileContents;
+
+/// Kind of file system entry to manage at the destination path.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FileEntry {
+    /// A regular file with the given contents.
+    File {
+        /// Contents to write to the file.
+        contents: FileContents,
+    },
+    /// A directory.
+    ///
+    /// Only the directory itself is managed -- files within it are not
+    /// created or removed.
+    Dir,
+    /// A symbolic link to the given target.
+    Symlink {
+        /// Path that the symlink points to.
+        target: PathBuf,
+    },
+}
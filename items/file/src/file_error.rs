@@ -0,0 +1,249 @@
+use std::path::PathBuf;
+
+#[cfg(feature = "error_reporting")]
+use peace::miette;
+
+use crate::FileOwner;
+
+/// Error while managing a file.
+#[cfg_attr(feature = "error_reporting", derive(peace::miette::Diagnostic))]
+#[derive(Debug, thiserror::Error)]
+pub enum FileError {
+    /// Failed to read the source file for the file contents.
+    #[error("Failed to read source file: `{}`", source_path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::source_read)),
+        help("Make sure there is an item that writes the source file.")
+    )]
+    SourceRead {
+        /// Path to the source file.
+        source_path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// The template is not valid.
+    #[error("Template is not valid: {reason}")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file::template_invalid),
+            help(
+                "Placeholders are written as `{{name}}`, and literal braces as `{{{{` and `}}}}`."
+            )
+        )
+    )]
+    TemplateInvalid {
+        /// The template text.
+        template: String,
+        /// Why the template is not valid.
+        reason: String,
+    },
+
+    /// A template placeholder does not have a value.
+    #[error("Template variable `{var_name}` does not have a value.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file::template_var_not_found),
+            help("Add `{var_name}` to the template's `vars`.")
+        )
+    )]
+    TemplateVarNotFound {
+        /// Name of the variable in the template.
+        var_name: String,
+    },
+
+    /// Failed to read metadata of the destination path.
+    #[error("Failed to read metadata of: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_metadata_read))
+    )]
+    DestMetadataRead {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to read the destination file.
+    #[error("Failed to read file: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_read))
+    )]
+    DestRead {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to read the destination symlink's target.
+    #[error("Failed to read symlink target of: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_read_link))
+    )]
+    DestReadLink {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to create the parent directory of the destination.
+    #[error("Failed to create parent directory of: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_parent_dir_create))
+    )]
+    DestParentDirCreate {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to write the destination file.
+    #[error("Failed to write file: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_write))
+    )]
+    DestWrite {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to create the destination directory.
+    #[error("Failed to create directory: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_dir_create))
+    )]
+    DestDirCreate {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to remove the destination.
+    #[error("Failed to remove: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::dest_remove))
+    )]
+    DestRemove {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// The destination directory could not be removed as it is not empty.
+    ///
+    /// Directories are never removed recursively, so that entries managed by
+    /// other items, or not managed at all, are not removed.
+    #[error("Failed to remove directory as it is not empty: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file::dest_dir_not_empty),
+            help(
+                "Clean the items that manage entries within the directory first, \
+                or remove the remaining entries manually."
+            )
+        )
+    )]
+    DestDirNotEmpty {
+        /// Path to the destination directory.
+        dest: PathBuf,
+    },
+
+    /// Failed to create the symlink.
+    #[error("Failed to create symlink `{}` to `{}`", dest.display(), target.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::symlink_create))
+    )]
+    SymlinkCreate {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// Path that the symlink points to.
+        target: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to set the permission bits of the destination.
+    #[error("Failed to set mode {mode:o} on: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_file::permissions_set))
+    )]
+    PermissionsSet {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// The permission bits.
+        mode: u32,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to set the owner of the destination.
+    #[error("Failed to set owner {owner} on: `{}`", dest.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file::owner_set),
+            help("Changing the owner usually requires elevated privileges.")
+        )
+    )]
+    OwnerSet {
+        /// Path to the destination.
+        dest: PathBuf,
+        /// The owner to set.
+        owner: FileOwner,
+        /// Underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Managing files is not supported when compiled to WASM.
+    #[cfg(target_arch = "wasm32")]
+    #[error("Managing files is not supported when compiled to WASM.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file::wasm_unsupported),
+            help("Run this command from a native build of the application.")
+        )
+    )]
+    WasmUnsupported,
+
+    // === Framework errors === //
+    /// A `peace` runtime error occurred.
+    #[error("A `peace` runtime error occurred.")]
+    PeaceRtError(
+        #[cfg_attr(feature = "error_reporting", diagnostic_source)]
+        #[source]
+        #[from]
+        peace::rt_model::Error,
+    ),
+}
@@ -0,0 +1,205 @@
+use std::{marker::PhantomData, path::Path};
+
+use peace::{
+    cfg::{async_trait, ApplyCheck, FnCtx, Item},
+    item_model::ItemId,
+    params::Params,
+    resource_rt::{resources::ts::Empty, Resources},
+};
+
+use crate::{
+    FileApplyFns, FileData, FileError, FileParams, FileState, FileStateCurrentFn, FileStateDiff,
+    FileStateDiffFn, FileStateGoalFn,
+};
+
+/// Item for managing a file, directory, or symlink.
+///
+/// The `Id` type parameter is needed for each file params to be a distinct
+/// type.
+///
+/// The following are managed:
+///
+/// * File contents, from text, bytes, a template, or a source file.
+/// * Directories -- only the directory itself, not the files within it.
+/// * Symlinks and their targets.
+/// * Permission bits and ownership, on Unix targets.
+///
+/// Cleaning this item removes the file, symlink, or empty directory. Directories
+/// that still contain entries are not removed, and return an error.
+///
+/// # Type Parameters
+///
+/// * `Id`: A zero-sized type used to distinguish different file parameters
+///   from each other.
+#[derive(Debug)]
+pub struct FileItem<Id> {
+    /// ID of the item to manage the file.
+    item_id: ItemId,
+    /// Marker for unique file parameters type.
+    marker: PhantomData<Id>,
+}
+
+impl<Id> Clone for FileItem<Id> {
+    fn clone(&self) -> Self {
+        Self {
+            item_id: self.item_id.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Id> FileItem<Id> {
+    /// Returns a new `FileItem`.
+    pub fn new(item_id: ItemId) -> Self {
+        Self {
+            item_id,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<Id> Item for FileItem<Id>
+where
+    Id: Send + Sync + 'static,
+{
+    type Data<'exec> = FileData<'exec, Id>;
+    type Error = FileError;
+    type Params<'exec> = FileParams<Id>;
+    type State = FileState;
+    type StateDiff = FileStateDiff;
+
+    fn id(&self) -> &ItemId {
+        &self.item_id
+    }
+
+    async fn setup(&self, _resources: &mut Resources<Empty>) -> Result<(), FileError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "item_state_example")]
+    fn state_example(params: &Self::Params<'_>, _data: Self::Data<'_>) -> Self::State {
+        use crate::{FileAttrs, FileContentsState, FileEntry};
+
+        let path = params.dest().to_path_buf();
+        match params.entry() {
+            // Don't render the contents, as they may need IO.
+            FileEntry::File { .. } => FileState::File {
+                path,
+                contents: FileContentsState::new(b""),
+                attrs: FileAttrs::default(),
+            },
+            FileEntry::Dir => FileState::Dir {
+                path,
+                attrs: FileAttrs::default(),
+            },
+            FileEntry::Symlink { target } => FileState::Symlink {
+                path,
+                target: target.clone(),
+            },
+        }
+    }
+
+    async fn try_state_current(
+        fn_ctx: FnCtx<'_>,
+        params_partial: &<Self::Params<'_> as Params>::Partial,
+        data: FileData<'_, Id>,
+    ) -> Result<Option<Self::State>, FileError> {
+        FileStateCurrentFn::try_state_current(fn_ctx, params_partial, data).await
+    }
+
+    async fn state_current(
+        fn_ctx: FnCtx<'_>,
+        params: &Self::Params<'_>,
+        data: FileData<'_, Id>,
+    ) -> Result<Self::State, FileError> {
+        FileStateCurrentFn::state_current(fn_ctx, params, data).await
+    }
+
+    async fn try_state_goal(
+        fn_ctx: FnCtx<'_>,
+        params_partial: &<Self::Params<'_> as Params>::Partial,
+        data: FileData<'_, Id>,
+    ) -> Result<Option<Self::State>, FileError> {
+        FileStateGoalFn::try_state_goal(fn_ctx, params_partial, data).await
+    }
+
+    async fn state_goal(
+        fn_ctx: FnCtx<'_>,
+        params: &Self::Params<'_>,
+        data: FileData<'_, Id>,
+    ) -> Result<Self::State, FileError> {
+        FileStateGoalFn::state_goal(fn_ctx, params, data).await
+    }
+
+    async fn state_diff(
+        _params_partial: &<Self::Params<'_> as Params>::Partial,
+        _data: Self::Data<'_>,
+        state_current: &Self::State,
+        state_goal: &Self::State,
+    ) -> Result<Self::StateDiff, FileError> {
+        FileStateDiffFn::state_diff(state_current, state_goal).await
+    }
+
+    async fn state_clean(
+        params_partial: &<Self::Params<'_> as Params>::Partial,
+        _data: Self::Data<'_>,
+    ) -> Result<Self::State, FileError> {
+        let path = params_partial
+            .dest()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Ok(FileState::NotExists { path })
+    }
+
+    async fn apply_check(
+        params: &Self::Params<'_>,
+        data: Self::Data<'_>,
+        state_current: &Self::State,
+        state_target: &Self::State,
+        diff: &Self::StateDiff,
+    ) -> Result<ApplyCheck, Self::Error> {
+        FileApplyFns::<Id>::apply_check(params, data, state_current, state_target, diff).await
+    }
+
+    async fn apply_dry(
+        fn_ctx: FnCtx<'_>,
+        params: &Self::Params<'_>,
+        data: Self::Data<'_>,
+        state_current: &Self::State,
+        state_target: &Self::State,
+        diff: &Self::StateDiff,
+    ) -> Result<Self::State, Self::Error> {
+        FileApplyFns::<Id>::apply_dry(fn_ctx, params, data, state_current, state_target, diff).await
+    }
+
+    async fn apply(
+        fn_ctx: FnCtx<'_>,
+        params: &Self::Params<'_>,
+        data: Self::Data<'_>,
+        state_current: &Self::State,
+        state_target: &Self::State,
+        diff: &Self::StateDiff,
+    ) -> Result<Self::State, Self::Error> {
+        FileApplyFns::<Id>::apply(fn_ctx, params, data, state_current, state_target, diff).await
+    }
+
+    #[cfg(feature = "item_interactions")]
+    fn interactions(
+        params: &Self::Params<'_>,
+        _data: Self::Data<'_>,
+    ) -> Vec<peace::item_interaction_model::ItemInteraction> {
+        use peace::item_interaction_model::{
+            ItemInteractionWithin, ItemLocation, ItemLocationAncestors,
+        };
+
+        let location: ItemLocationAncestors = vec![
+            ItemLocation::localhost(),
+            ItemLocation::path(format!("📄 {}", params.dest().display())),
+        ]
+        .into();
+        let item_interaction = ItemInteractionWithin::new(location).into();
+
+        vec![item_interaction]
+    }
+}
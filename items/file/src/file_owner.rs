@@ -0,0 +1,34 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Numeric user and group to own a file.
+///
+/// This is only applied on Unix targets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileOwner {
+    /// User ID to own the file, or `None` to leave it unchanged.
+    pub uid: Option<u32>,
+    /// Group ID to own the file, or `None` to leave it unchanged.
+    pub gid: Option<u32>,
+}
+
+impl FileOwner {
+    /// Returns a new `FileOwner`.
+    pub fn new(uid: Option<u32>, gid: Option<u32>) -> Self {
+        Self { uid, gid }
+    }
+}
+
+impl fmt::Display for FileOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.uid {
+            Some(uid) => write!(f, "{uid}")?,
+            None => write!(f, "-")?,
+        }
+        match self.gid {
+            Some(gid) => write!(f, ":{gid}"),
+            None => write!(f, ":-"),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use derivative::Derivative;
+use peace::params::Params;
+use serde::{Deserialize, Serialize};
+
+use crate::{FileContents, FileEntry, FileOwner};
+
+/// File parameters.
+///
+/// The `Id` type parameter is needed for each file params to be a distinct
+/// type.
+///
+/// # Type Parameters
+///
+/// * `Id`: A zero-sized type used to distinguish different file parameters
+///   from each other.
+#[derive(Derivative, Params, PartialEq, Eq, Deserialize, Serialize)]
+#[derivative(Clone, Debug)]
+#[serde(bound = "")]
+pub struct FileParams<Id> {
+    /// Path of the file, directory, or symlink to manage.
+    dest: PathBuf,
+    /// Kind of entry to manage, and its contents.
+    entry: FileEntry,
+    /// Permission bits to set, e.g. `0o644`.
+    ///
+    /// This is only applied on Unix targets, and is not applied to symlinks.
+    mode: Option<u32>,
+    /// User and group to own the entry.
+    ///
+    /// This is only applied on Unix targets, and is not applied to symlinks.
+    owner: Option<FileOwner>,
+    /// Marker for unique file parameters type.
+    marker: PhantomData<Id>,
+}
+
+impl<Id> FileParams<Id> {
+    /// Returns new `FileParams`.
+    pub fn new(dest: PathBuf, entry: FileEntry) -> Self {
+        Self {
+            dest,
+            entry,
+            mode: None,
+            owner: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns new `FileParams` for a file with the given contents.
+    pub fn file(dest: PathBuf, contents: FileContents) -> Self {
+        Self::new(dest, FileEntry::File { contents })
+    }
+
+    /// Returns new `FileParams` for a directory.
+    pub fn dir(dest: PathBuf) -> Self {
+        Self::new(dest, FileEntry::Dir)
+    }
+
+    /// Returns new `FileParams` for a symlink to the given target.
+    pub fn symlink(dest: PathBuf, target: PathBuf) -> Self {
+        Self::new(dest, FileEntry::Symlink { target })
+    }
+
+    /// Sets the permission bits to set, e.g. `0o644`.
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets the user and group to own the entry.
+    pub fn with_owner(mut self, owner: FileOwner) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Returns the path of the file, directory, or symlink to manage.
+    pub fn dest(&self) -> &Path {
+        &self.dest
+    }
+
+    /// Returns the kind of entry to manage, and its contents.
+    pub fn entry(&self) -> &FileEntry {
+        &self.entry
+    }
+
+    /// Returns the permission bits to set.
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    /// Returns the user and group to own the entry.
+    pub fn owner(&self) -> Option<FileOwner> {
+        self.owner
+    }
+}
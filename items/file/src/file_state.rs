@@ -0,0 +1,157 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "output_progress")]
+use peace::item_interaction_model::ItemLocationState;
+
+use crate::FileAttrs;
+
+/// State of a file, directory, or symlink.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FileState {
+    /// Nothing exists at the path.
+    NotExists {
+        /// Path of the entry.
+        path: PathBuf,
+    },
+    /// A regular file exists at the path.
+    File {
+        /// Path of the file.
+        path: PathBuf,
+        /// Hash and length of the file's contents.
+        contents: FileContentsState,
+        /// Permission bits and ownership of the file.
+        attrs: FileAttrs,
+    },
+    /// A directory exists at the path.
+    Dir {
+        /// Path of the directory.
+        path: PathBuf,
+        /// Permission bits and ownership of the directory.
+        attrs: FileAttrs,
+    },
+    /// A symlink exists at the path.
+    Symlink {
+        /// Path of the symlink.
+        path: PathBuf,
+        /// Path that the symlink points to.
+        target: PathBuf,
+    },
+}
+
+impl FileState {
+    /// Returns the path of the entry.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::NotExists { path }
+            | Self::File { path, .. }
+            | Self::Dir { path, .. }
+            | Self::Symlink { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for FileState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotExists { path } => write!(f, "`{}` does not exist", path.display()),
+            Self::File {
+                path,
+                contents,
+                attrs,
+            } => {
+                write!(f, "`{}` exists with {contents}", path.display())?;
+                if *attrs != FileAttrs::default() {
+                    write!(f, " ({attrs})")?;
+                }
+                Ok(())
+            }
+            Self::Dir { path, attrs } => {
+                write!(f, "directory `{}` exists", path.display())?;
+                if *attrs != FileAttrs::default() {
+                    write!(f, " ({attrs})")?;
+                }
+                Ok(())
+            }
+            Self::Symlink { path, target } => {
+                write!(f, "`{}` links to `{}`", path.display(), target.display())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "output_progress")]
+impl<'state> From<&'state FileState> for ItemLocationState {
+    fn from(file_state: &'state FileState) -> ItemLocationState {
+        match file_state {
+            FileState::NotExists { .. } => ItemLocationState::NotExists,
+            FileState::File { .. } | FileState::Dir { .. } | FileState::Symlink { .. } => {
+                ItemLocationState::Exists
+            }
+        }
+    }
+}
+
+/// Hash and length of a file's contents.
+///
+/// Only the hash and length are stored, as file contents may contain secrets.
+/// Equality only compares the hash and length.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FileContentsState {
+    /// Hex encoded SHA-256 hash of the contents.
+    pub hash: String,
+    /// Number of bytes in the contents.
+    pub byte_len: u64,
+    /// The contents, if they are valid UTF-8 and at most
+    /// [`FileContentsState::TEXT_LEN_MAX`] bytes.
+    ///
+    /// This is used to show a text diff of the discovered contents, and is not
+    /// stored, so it is `None` for stored states.
+    #[serde(skip)]
+    pub text: Option<String>,
+}
+
+impl FileContentsState {
+    /// Maximum number of bytes of text to keep in memory for diffing.
+    pub const TEXT_LEN_MAX: usize = 64 * 1024;
+
+    /// Returns the `FileContentsState` for the given bytes.
+    pub fn new(bytes: &[u8]) -> Self {
+        let hash = Sha256::digest(bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        let byte_len = bytes.len() as u64;
+        let text = if bytes.len() <= Self::TEXT_LEN_MAX {
+            std::str::from_utf8(bytes).ok().map(str::to_string)
+        } else {
+            None
+        };
+
+        Self {
+            hash,
+            byte_len,
+            text,
+        }
+    }
+}
+
+impl PartialEq for FileContentsState {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.byte_len == other.byte_len
+    }
+}
+
+impl Eq for FileContentsState {}
+
+impl fmt::Display for FileContentsState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hash_short = self.hash.get(..12).unwrap_or(&self.hash);
+        write!(f, "{} bytes, sha256: {hash_short}", self.byte_len)
+    }
+}
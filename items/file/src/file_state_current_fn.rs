@@ -0,0 +1,96 @@
+use std::{marker::PhantomData, path::Path};
+
+use peace::{cfg::FnCtx, params::Params};
+
+use crate::{FileData, FileError, FileParams, FileState};
+
+/// Reads the current state of the file.
+#[derive(Debug)]
+pub struct FileStateCurrentFn<Id>(PhantomData<Id>);
+
+impl<Id> FileStateCurrentFn<Id>
+where
+    Id: Send + Sync,
+{
+    pub async fn try_state_current(
+        _fn_ctx: FnCtx<'_>,
+        params_partial: &<FileParams<Id> as Params>::Partial,
+        _data: FileData<'_, Id>,
+    ) -> Result<Option<FileState>, FileError> {
+        if let Some(dest) = params_partial.dest() {
+            Self::file_state(dest).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn state_current(
+        _fn_ctx: FnCtx<'_>,
+        params: &FileParams<Id>,
+        _data: FileData<'_, Id>,
+    ) -> Result<FileState, FileError> {
+        Self::file_state(params.dest()).await
+    }
+
+    /// Returns the state of the entry at `dest`.
+    ///
+    /// Symlinks are not followed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn file_state(dest: &Path) -> Result<FileState, FileError> {
+        use crate::{FileAttrs, FileContentsState};
+
+        let path = dest.to_path_buf();
+        let metadata = match tokio::fs::symlink_metadata(dest).await {
+            Ok(metadata) => metadata,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(FileState::NotExists { path });
+            }
+            Err(error) => return Err(FileError::DestMetadataRead { dest: path, error }),
+        };
+
+        #[cfg(unix)]
+        let attrs = {
+            use std::os::unix::fs::MetadataExt;
+
+            FileAttrs {
+                mode: Some(metadata.mode() & 0o7777),
+                uid: Some(metadata.uid()),
+                gid: Some(metadata.gid()),
+            }
+        };
+        #[cfg(not(unix))]
+        let attrs = FileAttrs::default();
+
+        let file_type = metadata.file_type();
+        if file_type.is_symlink() {
+            let target =
+                tokio::fs::read_link(dest)
+                    .await
+                    .map_err(|error| FileError::DestReadLink {
+                        dest: path.clone(),
+                        error,
+                    })?;
+            Ok(FileState::Symlink { path, target })
+        } else if file_type.is_dir() {
+            Ok(FileState::Dir { path, attrs })
+        } else {
+            let bytes = tokio::fs::read(dest)
+                .await
+                .map_err(|error| FileError::DestRead {
+                    dest: path.clone(),
+                    error,
+                })?;
+            let contents = FileContentsState::new(&bytes);
+            Ok(FileState::File {
+                path,
+                contents,
+                attrs,
+            })
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub async fn file_state(_dest: &Path) -> Result<FileState, FileError> {
+        Err(FileError::WasmUnsupported)
+    }
+}
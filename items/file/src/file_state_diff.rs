@@ -0,0 +1,132 @@
+use std::{fmt, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::FileAttrs;
+
+/// Diff between the current and goal file state.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FileStateDiff {
+    /// The entry is in sync with the goal state.
+    InSync {
+        /// Path of the entry.
+        path: PathBuf,
+    },
+    /// The entry does not exist, and will be created.
+    Added {
+        /// Path of the entry.
+        path: PathBuf,
+        /// Diff of the file contents from empty, if the entry is a file.
+        contents_diff: Option<FileContentsDiff>,
+    },
+    /// The entry exists, and will be removed.
+    Removed {
+        /// Path of the entry.
+        path: PathBuf,
+    },
+    /// The entry is a different kind, e.g. a directory instead of a file, and
+    /// will be replaced.
+    Replaced {
+        /// Path of the entry.
+        path: PathBuf,
+        /// Kind of the current entry.
+        kind_from: String,
+        /// Kind of the goal entry.
+        kind_to: String,
+    },
+    /// The entry exists, but differs from the goal state.
+    Modified {
+        /// Path of the entry.
+        path: PathBuf,
+        /// Diff of the file contents, if they differ.
+        contents_diff: Option<FileContentsDiff>,
+        /// Attributes which differ, with their current values.
+        attrs_from: FileAttrs,
+        /// Attributes which differ, with their goal values.
+        attrs_to: FileAttrs,
+        /// Current and goal symlink target, if they differ.
+        symlink_target: Option<(PathBuf, PathBuf)>,
+    },
+}
+
+impl fmt::Display for FileStateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InSync { path } => write!(f, "`{}` in sync", path.display()),
+            Self::Added {
+                path,
+                contents_diff,
+            } => {
+                write!(f, "`{}` will be created", path.display())?;
+                if let Some(contents_diff) = contents_diff {
+                    write!(f, "\n{contents_diff}")?;
+                }
+                Ok(())
+            }
+            Self::Removed { path } => write!(f, "`{}` will be removed", path.display()),
+            Self::Replaced {
+                path,
+                kind_from,
+                kind_to,
+            } => write!(
+                f,
+                "`{}` will be replaced from a {kind_from} with a {kind_to}",
+                path.display()
+            ),
+            Self::Modified {
+                path,
+                contents_diff,
+                attrs_from,
+                attrs_to,
+                symlink_target,
+            } => {
+                write!(f, "`{}` will change", path.display())?;
+                if let Some((target_from, target_to)) = symlink_target {
+                    write!(
+                        f,
+                        " target from `{}` to `{}`",
+                        target_from.display(),
+                        target_to.display()
+                    )?;
+                }
+                if *attrs_to != FileAttrs::default() {
+                    write!(f, " attributes from ({attrs_from}) to ({attrs_to})")?;
+                }
+                if let Some(contents_diff) = contents_diff {
+                    write!(f, "\n{contents_diff}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Diff of a file's contents.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FileContentsDiff {
+    /// Unified diff of the text contents.
+    Text(String),
+    /// The contents are not text, too large to diff, or are from stored
+    /// states, which do not include the text.
+    Binary {
+        /// Number of bytes in the current contents.
+        byte_len_from: u64,
+        /// Number of bytes in the goal contents.
+        byte_len_to: u64,
+    },
+}
+
+impl fmt::Display for FileContentsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(unified_diff) => unified_diff.trim_end().fmt(f),
+            Self::Binary {
+                byte_len_from,
+                byte_len_to,
+            } => write!(
+                f,
+                "contents change from {byte_len_from} bytes to {byte_len_to} bytes"
+            ),
+        }
+    }
+}
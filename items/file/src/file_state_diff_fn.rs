@@ -0,0 +1,165 @@
+use similar::TextDiff;
+
+use crate::{FileAttrs, FileContentsDiff, FileContentsState, FileError, FileState, FileStateDiff};
+
+/// File state diff function.
+#[derive(Debug)]
+pub struct FileStateDiffFn;
+
+impl FileStateDiffFn {
+    pub async fn state_diff(
+        state_current: &FileState,
+        state_goal: &FileState,
+    ) -> Result<FileStateDiff, FileError> {
+        let path = state_goal.path().to_path_buf();
+
+        let state_diff = match (state_current, state_goal) {
+            (FileState::NotExists { .. }, FileState::NotExists { .. }) => {
+                FileStateDiff::InSync { path }
+            }
+            (_, FileState::NotExists { .. }) => FileStateDiff::Removed { path },
+            (FileState::NotExists { .. }, state_goal) => {
+                let contents_diff = match state_goal {
+                    FileState::File { contents, .. } => {
+                        Some(Self::contents_diff(&path, None, contents))
+                    }
+                    FileState::NotExists { .. }
+                    | FileState::Dir { .. }
+                    | FileState::Symlink { .. } => None,
+                };
+                FileStateDiff::Added {
+                    path,
+                    contents_diff,
+                }
+            }
+            (
+                FileState::File {
+                    contents: contents_current,
+                    attrs: attrs_current,
+                    ..
+                },
+                FileState::File {
+                    contents: contents_goal,
+                    attrs: attrs_goal,
+                    ..
+                },
+            ) => {
+                let contents_diff = (contents_current.hash != contents_goal.hash)
+                    .then(|| Self::contents_diff(&path, Some(contents_current), contents_goal));
+                Self::modified_or_in_sync(path, contents_diff, attrs_current, attrs_goal, None)
+            }
+            (
+                FileState::Dir {
+                    attrs: attrs_current,
+                    ..
+                },
+                FileState::Dir {
+                    attrs: attrs_goal, ..
+                },
+            ) => Self::modified_or_in_sync(path, None, attrs_current, attrs_goal, None),
+            (
+                FileState::Symlink {
+                    target: target_current,
+                    ..
+                },
+                FileState::Symlink {
+                    target: target_goal,
+                    ..
+                },
+            ) => {
+                let symlink_target = (target_current != target_goal)
+                    .then(|| (target_current.clone(), target_goal.clone()));
+                Self::modified_or_in_sync(
+                    path,
+                    None,
+                    &FileAttrs::default(),
+                    &FileAttrs::default(),
+                    symlink_target,
+                )
+            }
+            (state_current, state_goal) => FileStateDiff::Replaced {
+                path,
+                kind_from: Self::kind_name(state_current).to_string(),
+                kind_to: Self::kind_name(state_goal).to_string(),
+            },
+        };
+
+        Ok(state_diff)
+    }
+
+    fn modified_or_in_sync(
+        path: std::path::PathBuf,
+        contents_diff: Option<FileContentsDiff>,
+        attrs_current: &FileAttrs,
+        attrs_goal: &FileAttrs,
+        symlink_target: Option<(std::path::PathBuf, std::path::PathBuf)>,
+    ) -> FileStateDiff {
+        // Only keep attributes that differ.
+        let attr_diff = |current: Option<u32>, goal: Option<u32>| match goal {
+            Some(_) if current != goal => (current, goal),
+            _ => (None, None),
+        };
+        let (mode_from, mode_to) = attr_diff(attrs_current.mode, attrs_goal.mode);
+        let (uid_from, uid_to) = attr_diff(attrs_current.uid, attrs_goal.uid);
+        let (gid_from, gid_to) = attr_diff(attrs_current.gid, attrs_goal.gid);
+        let attrs_from = FileAttrs {
+            mode: mode_from,
+            uid: uid_from,
+            gid: gid_from,
+        };
+        let attrs_to = FileAttrs {
+            mode: mode_to,
+            uid: uid_to,
+            gid: gid_to,
+        };
+
+        if contents_diff.is_none() && attrs_to == FileAttrs::default() && symlink_target.is_none() {
+            FileStateDiff::InSync { path }
+        } else {
+            FileStateDiff::Modified {
+                path,
+                contents_diff,
+                attrs_from,
+                attrs_to,
+                symlink_target,
+            }
+        }
+    }
+
+    fn contents_diff(
+        path: &std::path::Path,
+        contents_current: Option<&FileContentsState>,
+        contents_goal: &FileContentsState,
+    ) -> FileContentsDiff {
+        let text_current = match contents_current {
+            Some(contents_current) => contents_current.text.as_deref(),
+            None => Some(""),
+        };
+
+        match (text_current, contents_goal.text.as_deref()) {
+            (Some(text_current), Some(text_goal)) => {
+                let path_display = path.display().to_string();
+                let unified_diff = TextDiff::from_lines(text_current, text_goal)
+                    .unified_diff()
+                    .header(&path_display, &path_display)
+                    .to_string();
+                FileContentsDiff::Text(unified_diff)
+            }
+            _ => FileContentsDiff::Binary {
+                byte_len_from: contents_current
+                    .map(|contents_current| contents_current.byte_len)
+                    .unwrap_or(0),
+                byte_len_to: contents_goal.byte_len,
+            },
+        }
+    }
+
+    fn kind_name(file_state: &FileState) -> &'static str {
+        match file_state {
+            FileState::NotExists { .. } => "nothing",
+            FileState::File { .. } => "file",
+            FileState::Dir { .. } => "directory",
+            FileState::Symlink { .. } => "symlink",
+        }
+    }
+}
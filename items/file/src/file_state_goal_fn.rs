@@ -0,0 +1,88 @@
+use std::marker::PhantomData;
+
+use peace::{cfg::FnCtx, params::Params};
+
+use crate::{
+    FileAttrs, FileContentsState, FileData, FileEntry, FileError, FileOwner, FileParams, FileState,
+};
+
+/// Reads the goal state of the file.
+#[derive(Debug)]
+pub struct FileStateGoalFn<Id>(PhantomData<Id>);
+
+impl<Id> FileStateGoalFn<Id>
+where
+    Id: Send + Sync,
+{
+    pub async fn try_state_goal(
+        _fn_ctx: FnCtx<'_>,
+        params_partial: &<FileParams<Id> as Params>::Partial,
+        _data: FileData<'_, Id>,
+    ) -> Result<Option<FileState>, FileError> {
+        let (Some(dest), Some(entry), Some(mode), Some(owner)) = (
+            params_partial.dest(),
+            params_partial.entry(),
+            params_partial.mode(),
+            params_partial.owner(),
+        ) else {
+            return Ok(None);
+        };
+
+        // The source file may be written by a predecessor item.
+        if let FileEntry::File { contents } = entry {
+            if contents.source_not_exists() {
+                return Ok(None);
+            }
+        }
+
+        Self::file_state(dest.to_path_buf(), entry, *mode, *owner)
+            .await
+            .map(Some)
+    }
+
+    pub async fn state_goal(
+        _fn_ctx: FnCtx<'_>,
+        params: &FileParams<Id>,
+        _data: FileData<'_, Id>,
+    ) -> Result<FileState, FileError> {
+        Self::file_state(
+            params.dest().to_path_buf(),
+            params.entry(),
+            params.mode(),
+            params.owner(),
+        )
+        .await
+    }
+
+    async fn file_state(
+        path: std::path::PathBuf,
+        entry: &FileEntry,
+        mode: Option<u32>,
+        owner: Option<FileOwner>,
+    ) -> Result<FileState, FileError> {
+        let owner = owner.unwrap_or_default();
+        let attrs = FileAttrs {
+            mode,
+            uid: owner.uid,
+            gid: owner.gid,
+        };
+
+        let file_state = match entry {
+            FileEntry::File { contents } => {
+                let bytes = contents.bytes().await?;
+                FileState::File {
+                    path,
+                    contents: FileContentsState::new(&bytes),
+                    attrs,
+                }
+            }
+            FileEntry::Dir => FileState::Dir { path, attrs },
+            FileEntry::Symlink { target } => FileState::Symlink {
+                path,
+                target: target.clone(),
+            },
+        };
+
+        Ok(file_state)
+    }
+}
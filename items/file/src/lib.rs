@@ -0,0 +1,40 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+//! Manages a file, directory, or symlink for the peace framework.
+//!
+//! Files contents may be specified as text, bytes, a template, or copied from
+//! a source file. The state is the hash of the file's contents and its
+//! metadata, and the state diff shows a unified diff for text files when
+//! diffing discovered states. File contents are not stored.
+
+pub use crate::{
+    file_apply_fns::FileApplyFns,
+    file_attrs::FileAttrs,
+    file_contents::FileContents,
+    file_data::FileData,
+    file_entry::FileEntry,
+    file_error::FileError,
+    file_item::FileItem,
+    file_owner::FileOwner,
+    file_params::{FileParams, FileParamsFieldWise, FileParamsPartial},
+    file_state::{FileContentsState, FileState},
+    file_state_current_fn::FileStateCurrentFn,
+    file_state_diff::{FileContentsDiff, FileStateDiff},
+    file_state_diff_fn::FileStateDiffFn,
+    file_state_goal_fn::FileStateGoalFn,
+};
+
+mod file_apply_fns;
+mod file_attrs;
+mod file_contents;
+mod file_data;
+mod file_entry;
+mod file_error;
+mod file_item;
+mod file_owner;
+mod file_params;
+mod file_state;
+mod file_state_current_fn;
+mod file_state_diff;
+mod file_state_diff_fn;
+mod file_state_goal_fn;
@@ -17,8 +17,8 @@ use peace::cfg::{state::FetchedOpt, ApplyCheck, FnCtx, State};
 use reqwest::header::ETAG;
 
 use crate::{
-    ETag, FileDownloadData, FileDownloadError, FileDownloadParams, FileDownloadState,
-    FileDownloadStateDiff, FileDownloadStateLogical,
+    ETag, FileDownloadData, FileDownloadError, FileDownloadParams, FileDownloadRequest,
+    FileDownloadState, FileDownloadStateCurrentFn, FileDownloadStateDiff, FileDownloadStateLogical,
 };
 
 #[cfg(feature = "output_progress")]
@@ -35,13 +35,16 @@ impl<Id> FileDownloadApplyFns<Id>
 where
     Id: Send + Sync + 'static,
 {
+    /// Downloads the file, and returns its `ETag`.
+    ///
+    /// Returns `None` if the download was interrupted before it completed.
     async fn file_download(
-        #[cfg(not(feature = "output_progress"))] _fn_ctx: FnCtx<'_>,
-        #[cfg(feature = "output_progress")] fn_ctx: FnCtx<'_>,
+        fn_ctx: FnCtx<'_>,
         params: &FileDownloadParams<Id>,
-        data: FileDownloadData<'_, Id>,
-    ) -> Result<FetchedOpt<ETag>, FileDownloadError> {
-        let client = data.client();
+        data: &FileDownloadData<'_, Id>,
+    ) -> Result<Option<FetchedOpt<ETag>>, FileDownloadError> {
+        let file_download_request = FileDownloadRequest::from_params(params);
+        let client = file_download_request.client(data.client()).await?;
         let src_url = params.src();
 
         #[cfg(feature = "output_progress")]
@@ -49,8 +52,8 @@ where
             .progress_sender
             .tick(ProgressMsgUpdate::Set(String::from("starting download")));
 
-        let response = client
-            .get(src_url.clone())
+        let response = file_download_request
+            .get(&client, src_url)?
             .send()
             .await
             .map_err(|error| FileDownloadError::src_get(src_url.clone(), error))?;
@@ -64,41 +67,37 @@ where
             .unwrap_or(FetchedOpt::None);
 
         #[cfg(not(target_arch = "wasm32"))]
-        {
-            Self::stream_write(
-                #[cfg(feature = "output_progress")]
-                fn_ctx,
-                params,
-                response.bytes_stream(),
-            )
-            .await?;
-        }
+        let interrupted = Self::stream_write(fn_ctx, params, response.bytes_stream()).await?;
 
         // reqwest in wasm doesn't support streams
         // https://github.com/seanmonstar/reqwest/issues/1424
         #[cfg(target_arch = "wasm32")]
-        {
-            Self::stream_write(
-                #[cfg(feature = "output_progress")]
-                fn_ctx,
-                params.dest(),
-                data.storage(),
-                params.storage_form(),
-                response,
-            )
-            .await?;
+        let interrupted = Self::stream_write(
+            fn_ctx,
+            params.dest(),
+            data.storage(),
+            params.storage_form(),
+            response,
+        )
+        .await?;
+
+        if interrupted {
+            Ok(None)
+        } else {
+            Ok(Some(e_tag))
         }
-
-        Ok(e_tag)
     }
 
     /// Streams the content to disk.
+    ///
+    /// Returns whether the stream was interrupted before all of the content
+    /// was written.
     #[cfg(not(target_arch = "wasm32"))]
     async fn stream_write(
-        #[cfg(feature = "output_progress")] fn_ctx: FnCtx<'_>,
+        fn_ctx: FnCtx<'_>,
         file_download_params: &FileDownloadParams<Id>,
         byte_stream: impl Stream<Item = reqwest::Result<Bytes>>,
-    ) -> Result<(), FileDownloadError> {
+    ) -> Result<bool, FileDownloadError> {
         use std::{fmt::Write, path::Component};
 
         #[cfg(feature = "error_reporting")]
@@ -176,7 +175,12 @@ where
         let buffer = BufWriter::new(dest_file);
         #[cfg(feature = "output_progress")]
         let progress_sender = &fn_ctx.progress_sender;
+
+        // Stop reading chunks when the execution is interrupted. Bytes that have
+        // been received are still written, so that the partial file is kept.
+        let mut byte_stream = std::pin::pin!(byte_stream.take_until(fn_ctx.interrupted()));
         let mut buffer = byte_stream
+            .as_mut()
             .map(|bytes_result| bytes_result.map_err(FileDownloadError::ResponseBytesStream))
             .try_fold(buffer, |mut buffer, bytes| async move {
                 buffer
@@ -198,18 +202,22 @@ where
             .flush()
             .await
             .map_err(FileDownloadError::ResponseFileWrite)?;
-        Ok(())
+        Ok(byte_stream.is_stopped())
     }
 
     /// Streams the content to disk.
+    ///
+    /// The response is read in one go, so an interruption is only observed
+    /// before the content is written. Returns whether the content was not
+    /// written because of an interruption.
     #[cfg(target_arch = "wasm32")]
     async fn stream_write(
-        #[cfg(feature = "output_progress")] _fn_ctx: FnCtx<'_>,
+        fn_ctx: FnCtx<'_>,
         dest_path: &Path,
         storage: &Storage,
         storage_form: crate::StorageForm,
         response: reqwest::Response,
-    ) -> Result<(), FileDownloadError> {
+    ) -> Result<bool, FileDownloadError> {
         use crate::StorageForm;
 
         match storage_form {
@@ -218,6 +226,9 @@ where
                     .text()
                     .await
                     .map_err(FileDownloadError::ResponseTextRead)?;
+                if fn_ctx.is_interrupted() {
+                    return Ok(true);
+                }
                 storage.set_item(dest_path, &value)?;
             }
             StorageForm::Base64 => {
@@ -225,11 +236,14 @@ where
                     .bytes()
                     .await
                     .map_err(FileDownloadError::ResponseBytesRead)?;
+                if fn_ctx.is_interrupted() {
+                    return Ok(true);
+                }
                 storage.set_item_b64(dest_path, &bytes)?;
             }
         }
 
-        Ok(())
+        Ok(false)
     }
 }
 
@@ -368,7 +382,16 @@ where
                 Ok(file_download_state_goal.clone())
             }
             FileDownloadStateDiff::Change { .. } => {
-                let e_tag = Self::file_download(fn_ctx, params, data).await?;
+                let Some(e_tag) = Self::file_download(fn_ctx, params, &data).await? else {
+                    // The download was interrupted, so we return the state of the partially
+                    // downloaded file. The `ETag` is not recorded, as the file's content does
+                    // not match the server's content.
+                    let mut file_download_state_reached =
+                        FileDownloadStateCurrentFn::state_current(fn_ctx, params, data).await?;
+                    file_download_state_reached.0.physical = FetchedOpt::None;
+
+                    return Ok(file_download_state_reached);
+                };
 
                 let mut file_download_state_ensured = file_download_state_goal.clone();
                 file_download_state_ensured.0.physical = e_tag;
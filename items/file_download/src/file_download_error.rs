@@ -83,6 +83,36 @@ pub enum FileDownloadError {
         #[source]
         error: reqwest::Error,
     },
+    #[error("Failed to parse request header name: `{name}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file_download::request_header_name_invalid),
+            help("Header names must only contain visible ASCII characters, excluding separators.")
+        )
+    )]
+    RequestHeaderNameInvalid {
+        /// The header name.
+        name: String,
+        /// Underlying error.
+        #[source]
+        error: reqwest::header::InvalidHeaderName,
+    },
+    #[error("Failed to parse value for request header: `{name}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_file_download::request_header_value_invalid),
+            help("Header values must only contain visible ASCII characters.")
+        )
+    )]
+    RequestHeaderValueInvalid {
+        /// The header name.
+        name: String,
+        /// Underlying error.
+        #[source]
+        error: reqwest::header::InvalidHeaderValue,
+    },
     #[error("Failed to fetch source file metadata. Response status code: {status_code}")]
     SrcFileUndetermined { status_code: reqwest::StatusCode },
     #[error("Failed to read source file content.")]
@@ -104,6 +134,69 @@ pub enum FileDownloadError {
     #[error("Failed to format string in memory.")]
     FormatString(#[source] std::fmt::Error),
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Failed to use `{proxy}` as a proxy.")]
+    ProxyBuild {
+        /// The proxy URL.
+        proxy: url::Url,
+        /// Underlying error.
+        #[source]
+        error: reqwest::Error,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Failed to read CA certificate bundle: `{}`.", path.display())]
+    TlsCaCertRead {
+        /// Path to the CA certificate bundle.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Failed to parse CA certificate bundle: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(help("The CA certificate bundle must be PEM encoded."))
+    )]
+    TlsCaCertParse {
+        /// Path to the CA certificate bundle.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: reqwest::Error,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Failed to read client identity file: `{}`.", path.display())]
+    TlsClientIdentityRead {
+        /// Path to the client certificate or key.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Failed to parse client certificate and key.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(help(
+            "The client certificate chain must be PEM encoded, \
+            and the key must be a PEM encoded PKCS #8 private key."
+        ))
+    )]
+    TlsClientIdentityParse(#[source] reqwest::Error),
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Client certificate and key must be provided together.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(help(
+            "Set both the client certificate path and client key path in `HttpTls`."
+        ))
+    )]
+    TlsClientIdentityIncomplete,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Failed to build HTTP client.")]
+    ClientBuild(#[source] reqwest::Error),
+
     // WASM errors.
     #[cfg(target_arch = "wasm32")]
     #[error("Failed to read bytes from response.")]
@@ -3,14 +3,14 @@ use std::{marker::PhantomData, path::Path};
 use peace::{
     cfg::{async_trait, state::FetchedOpt, ApplyCheck, FnCtx, Item},
     item_model::ItemId,
-    params::Params,
+    params::{ItemOutputs, Params},
     resource_rt::{resources::ts::Empty, Resources},
 };
 
 use crate::{
-    FileDownloadApplyFns, FileDownloadData, FileDownloadError, FileDownloadParams,
-    FileDownloadState, FileDownloadStateCurrentFn, FileDownloadStateDiff, FileDownloadStateDiffFn,
-    FileDownloadStateGoalFn, FileDownloadStateLogical,
+    FileDownloadApplyFns, FileDownloadData, FileDownloadError, FileDownloadOutputs,
+    FileDownloadParams, FileDownloadState, FileDownloadStateCurrentFn, FileDownloadStateDiff,
+    FileDownloadStateDiffFn, FileDownloadStateGoalFn, FileDownloadStateLogical,
 };
 
 /// Item for downloading a file.
@@ -176,6 +176,10 @@ where
             .await
     }
 
+    fn outputs(state: &Self::State) -> ItemOutputs {
+        ItemOutputs::from(FileDownloadOutputs::from(state))
+    }
+
     #[cfg(feature = "item_interactions")]
     fn interactions(
         params: &Self::Params<'_>,
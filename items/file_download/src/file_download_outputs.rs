@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use peace::{cfg::state::FetchedOpt, params::ItemOutputs};
+use serde::{Deserialize, Serialize};
+
+use crate::{ETag, FileDownloadState, FileDownloadStateLogical};
+
+/// Values published by a `FileDownloadItem` after it is applied.
+///
+/// Read these from the item's outputs with
+/// `item_outputs.to_typed::<FileDownloadOutputs>()`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDownloadOutputs {
+    /// Path to the downloaded file, if it exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Number of bytes in the downloaded file, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
+    /// ETag returned by the server when the file was downloaded, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e_tag: Option<ETag>,
+}
+
+impl<'state> From<&'state FileDownloadState> for FileDownloadOutputs {
+    fn from(state: &'state FileDownloadState) -> Self {
+        let (path, byte_count) = match &state.0.logical {
+            FileDownloadStateLogical::None { .. } => (None, None),
+            FileDownloadStateLogical::StringContents { path, contents } => {
+                (Some(path.clone()), u64::try_from(contents.len()).ok())
+            }
+            FileDownloadStateLogical::Length { path, byte_count } => {
+                (Some(path.clone()), Some(*byte_count))
+            }
+            FileDownloadStateLogical::Unknown { path } => (Some(path.clone()), None),
+        };
+        let e_tag = match &state.0.physical {
+            FetchedOpt::Value(e_tag) => Some(e_tag.clone()),
+            FetchedOpt::Tbd | FetchedOpt::None => None,
+        };
+
+        Self {
+            path,
+            byte_count,
+            e_tag,
+        }
+    }
+}
+
+impl From<FileDownloadOutputs> for ItemOutputs {
+    fn from(file_download_outputs: FileDownloadOutputs) -> Self {
+        let FileDownloadOutputs {
+            path,
+            byte_count,
+            e_tag,
+        } = file_download_outputs;
+
+        let mut item_outputs = ItemOutputs::new();
+        if let Some(path) = path {
+            item_outputs = item_outputs.with_output("path", path.to_string_lossy().into_owned());
+        }
+        if let Some(byte_count) = byte_count {
+            item_outputs = item_outputs.with_output("byte_count", byte_count);
+        }
+        if let Some(e_tag) = e_tag {
+            item_outputs = item_outputs.with_output("e_tag", e_tag.to_string());
+        }
+        item_outputs
+    }
+}
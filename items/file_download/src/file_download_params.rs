@@ -1,13 +1,17 @@
 use std::{
+    collections::BTreeMap,
     fmt,
     marker::PhantomData,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use peace::params::Params;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::{HttpAuth, HttpTls};
+
 /// File download parameters.
 ///
 /// The `Id` type parameter is needed for each file download params to be a
@@ -26,6 +30,26 @@ pub struct FileDownloadParams<Id> {
     ///
     /// Must be a file path, and not a directory.
     dest: PathBuf,
+    /// Additional headers to send with the request.
+    ///
+    /// Header values may be provided by a mapping function, e.g. when a token
+    /// is produced by a predecessor item.
+    headers: BTreeMap<String, String>,
+    /// Authentication to use for the request.
+    auth: Option<HttpAuth>,
+    /// Timeout for each request, from when the request starts connecting
+    /// until the response body has finished.
+    ///
+    /// This is only applied on native targets.
+    timeout: Option<Duration>,
+    /// Proxy to send requests through.
+    ///
+    /// This is only applied on native targets.
+    proxy: Option<Url>,
+    /// TLS configuration, e.g. a CA bundle or client certificate.
+    ///
+    /// This is only applied on native targets.
+    tls: HttpTls,
     /// How to store the content of the download -- text or base64 encoded.
     ///
     /// For URLs that return binary content, this must be `Base64` as browser
@@ -42,6 +66,11 @@ impl<Id> Clone for FileDownloadParams<Id> {
         Self {
             src: self.src.clone(),
             dest: self.dest.clone(),
+            headers: self.headers.clone(),
+            auth: self.auth.clone(),
+            timeout: self.timeout,
+            proxy: self.proxy.clone(),
+            tls: self.tls.clone(),
             #[cfg(target_arch = "wasm32")]
             storage_form: self.storage_form.clone(),
             marker: PhantomData,
@@ -54,12 +83,21 @@ impl<Id> fmt::Debug for FileDownloadParams<Id> {
         f.debug_struct("FileDownloadParams")
             .field("src", &self.src)
             .field("dest", &self.dest)
+            // Header values may contain credentials.
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("auth", &self.auth)
+            .field("timeout", &self.timeout)
+            .field("proxy", &self.proxy)
+            .field("tls", &self.tls)
             .finish()
     }
 }
 
 impl<Id> FileDownloadParams<Id> {
     /// Returns new `FileDownloadParams`.
+    ///
+    /// The request is sent without additional headers, authentication, or
+    /// timeout. Use the `with_*` methods to customize the request.
     pub fn new(
         src: Url,
         dest: PathBuf,
@@ -68,12 +106,53 @@ impl<Id> FileDownloadParams<Id> {
         Self {
             src,
             dest,
+            headers: BTreeMap::new(),
+            auth: None,
+            timeout: None,
+            proxy: None,
+            tls: HttpTls::default(),
             #[cfg(target_arch = "wasm32")]
             storage_form,
             marker: PhantomData,
         }
     }
 
+    /// Adds a header to send with the request.
+    ///
+    /// If the header was already set, its value is replaced.
+    pub fn with_header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the authentication to use for the request.
+    pub fn with_auth(mut self, auth: HttpAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Sets the timeout for each request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the proxy to send requests through.
+    pub fn with_proxy(mut self, proxy: Url) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Sets the TLS configuration for the request.
+    pub fn with_tls(mut self, tls: HttpTls) -> Self {
+        self.tls = tls;
+        self
+    }
+
     /// Returns the URL to download from.
     pub fn src(&self) -> &Url {
         &self.src
@@ -84,6 +163,31 @@ impl<Id> FileDownloadParams<Id> {
         &self.dest
     }
 
+    /// Returns the additional headers to send with the request.
+    pub fn headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// Returns the authentication to use for the request.
+    pub fn auth(&self) -> Option<&HttpAuth> {
+        self.auth.as_ref()
+    }
+
+    /// Returns the timeout for each request.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the proxy to send requests through.
+    pub fn proxy(&self) -> Option<&Url> {
+        self.proxy.as_ref()
+    }
+
+    /// Returns the TLS configuration for the request.
+    pub fn tls(&self) -> &HttpTls {
+        &self.tls
+    }
+
     /// Returns the storage form for the response.
     ///
     /// This only applies to the WASM target.
@@ -0,0 +1,197 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use peace::params::Params;
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Client, RequestBuilder,
+};
+use url::Url;
+
+use crate::{FileDownloadError, FileDownloadParams, HttpAuth, HttpTls};
+
+/// Customizations to apply to the requests made for a file download.
+///
+/// This is constructed from either `FileDownloadParams` or its partial, so
+/// that both state discovery and apply send the same request.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FileDownloadRequest<'params> {
+    /// Additional headers to send with the request.
+    headers: &'params BTreeMap<String, String>,
+    /// Authentication to use for the request.
+    auth: Option<&'params HttpAuth>,
+    /// Timeout for each request.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    timeout: Option<Duration>,
+    /// Proxy to send requests through.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    proxy: Option<&'params Url>,
+    /// TLS configuration.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    tls: &'params HttpTls,
+}
+
+impl<'params> FileDownloadRequest<'params> {
+    /// Returns the request customizations from the given params.
+    pub(crate) fn from_params<Id>(params: &'params FileDownloadParams<Id>) -> Self {
+        Self {
+            headers: params.headers(),
+            auth: params.auth(),
+            timeout: params.timeout(),
+            proxy: params.proxy(),
+            tls: params.tls(),
+        }
+    }
+
+    /// Returns the request customizations from the given params partial, or
+    /// `None` if any of them have not been resolved.
+    ///
+    /// An unresolved value is not treated as "no customization", as that
+    /// would send an unauthenticated request when a token has not yet been
+    /// produced by a predecessor item.
+    pub(crate) fn try_from_params_partial<Id>(
+        params_partial: &'params <FileDownloadParams<Id> as Params>::Partial,
+    ) -> Option<Self>
+    where
+        Id: Send + Sync + 'static,
+    {
+        let headers = params_partial.headers()?;
+        let auth = params_partial.auth()?.as_ref();
+        let timeout = *params_partial.timeout()?;
+        let proxy = params_partial.proxy()?.as_ref();
+        let tls = params_partial.tls()?;
+
+        Some(Self {
+            headers,
+            auth,
+            timeout,
+            proxy,
+            tls,
+        })
+    }
+
+    /// Returns the client to use for the request.
+    ///
+    /// When no proxy or TLS configuration is specified, the shared client is
+    /// used. Otherwise a client is built for this request.
+    pub(crate) async fn client(&self, client: &Client) -> Result<Client, FileDownloadError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.proxy.is_some() || !self.tls.is_default() {
+            return self.client_build().await;
+        }
+
+        Ok(client.clone())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn client_build(&self) -> Result<Client, FileDownloadError> {
+        let mut client_builder = Client::builder();
+
+        if let Some(proxy) = self.proxy {
+            let proxy = reqwest::Proxy::all(proxy.clone()).map_err(|error| {
+                FileDownloadError::ProxyBuild {
+                    proxy: proxy.clone(),
+                    error,
+                }
+            })?;
+            client_builder = client_builder.proxy(proxy);
+        }
+
+        if let Some(ca_cert_path) = self.tls.ca_cert_path() {
+            let ca_cert_pem = tokio::fs::read(ca_cert_path).await.map_err(|error| {
+                FileDownloadError::TlsCaCertRead {
+                    path: ca_cert_path.to_path_buf(),
+                    error,
+                }
+            })?;
+            let ca_certs =
+                reqwest::Certificate::from_pem_bundle(&ca_cert_pem).map_err(|error| {
+                    FileDownloadError::TlsCaCertParse {
+                        path: ca_cert_path.to_path_buf(),
+                        error,
+                    }
+                })?;
+            client_builder = ca_certs
+                .into_iter()
+                .fold(client_builder, |client_builder, ca_cert| {
+                    client_builder.add_root_certificate(ca_cert)
+                });
+        }
+
+        match (self.tls.client_cert_path(), self.tls.client_key_path()) {
+            (Some(client_cert_path), Some(client_key_path)) => {
+                let client_cert_pem = tokio::fs::read(client_cert_path).await.map_err(|error| {
+                    FileDownloadError::TlsClientIdentityRead {
+                        path: client_cert_path.to_path_buf(),
+                        error,
+                    }
+                })?;
+                let client_key_pem = tokio::fs::read(client_key_path).await.map_err(|error| {
+                    FileDownloadError::TlsClientIdentityRead {
+                        path: client_key_path.to_path_buf(),
+                        error,
+                    }
+                })?;
+                let identity = reqwest::Identity::from_pkcs8_pem(&client_cert_pem, &client_key_pem)
+                    .map_err(FileDownloadError::TlsClientIdentityParse)?;
+                client_builder = client_builder.identity(identity);
+            }
+            (None, None) => {}
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(FileDownloadError::TlsClientIdentityIncomplete);
+            }
+        }
+
+        client_builder
+            .build()
+            .map_err(FileDownloadError::ClientBuild)
+    }
+
+    /// Returns a `GET` request to `src` with the headers, authentication, and
+    /// timeout applied.
+    pub(crate) fn get(
+        &self,
+        client: &Client,
+        src: &Url,
+    ) -> Result<RequestBuilder, FileDownloadError> {
+        let request_builder = client.get(src.clone());
+        self.request_customize(request_builder)
+    }
+
+    fn request_customize(
+        &self,
+        mut request_builder: RequestBuilder,
+    ) -> Result<RequestBuilder, FileDownloadError> {
+        for (name, value) in self.headers.iter() {
+            let header_name = HeaderName::try_from(name.as_str()).map_err(|error| {
+                FileDownloadError::RequestHeaderNameInvalid {
+                    name: name.clone(),
+                    error,
+                }
+            })?;
+            let mut header_value = HeaderValue::try_from(value.as_str()).map_err(|error| {
+                FileDownloadError::RequestHeaderValueInvalid {
+                    name: name.clone(),
+                    error,
+                }
+            })?;
+            header_value.set_sensitive(true);
+
+            request_builder = request_builder.header(header_name, header_value);
+        }
+
+        request_builder = match self.auth {
+            Some(HttpAuth::Basic { username, password }) => {
+                request_builder.basic_auth(username, password.as_ref())
+            }
+            Some(HttpAuth::Bearer { token }) => request_builder.bearer_auth(token),
+            None => request_builder,
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = self.timeout {
+            request_builder = request_builder.timeout(timeout);
+        }
+
+        Ok(request_builder)
+    }
+}
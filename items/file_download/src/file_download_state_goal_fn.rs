@@ -7,8 +7,8 @@ use peace::{
 use reqwest::{header::ETAG, Url};
 
 use crate::{
-    ETag, FileDownloadData, FileDownloadError, FileDownloadParams, FileDownloadState,
-    FileDownloadStateLogical,
+    ETag, FileDownloadData, FileDownloadError, FileDownloadParams, FileDownloadRequest,
+    FileDownloadState, FileDownloadStateLogical,
 };
 
 /// Reads the goal state of the file to download.
@@ -24,8 +24,16 @@ where
         params_partial: &<FileDownloadParams<Id> as Params>::Partial,
         data: FileDownloadData<'_, Id>,
     ) -> Result<Option<FileDownloadState>, FileDownloadError> {
-        if let Some((src, dest)) = params_partial.src().zip(params_partial.dest()) {
-            Self::file_state_goal(&data, src, dest).await.map(Some)
+        let file_download_request =
+            FileDownloadRequest::try_from_params_partial::<Id>(params_partial);
+        if let Some(((src, dest), file_download_request)) = params_partial
+            .src()
+            .zip(params_partial.dest())
+            .zip(file_download_request)
+        {
+            Self::file_state_goal(&data, file_download_request, src, dest)
+                .await
+                .map(Some)
         } else {
             Ok(None)
         }
@@ -36,19 +44,23 @@ where
         params: &FileDownloadParams<Id>,
         data: FileDownloadData<'_, Id>,
     ) -> Result<FileDownloadState, FileDownloadError> {
-        let file_state_goal = Self::file_state_goal(&data, params.src(), params.dest()).await?;
+        let file_download_request = FileDownloadRequest::from_params(params);
+        let file_state_goal =
+            Self::file_state_goal(&data, file_download_request, params.src(), params.dest())
+                .await?;
 
         Ok(file_state_goal)
     }
 
     async fn file_state_goal(
         data: &FileDownloadData<'_, Id>,
+        file_download_request: FileDownloadRequest<'_>,
         src_url: &Url,
         dest: &Path,
     ) -> Result<FileDownloadState, FileDownloadError> {
-        let client = data.client();
-        let response = client
-            .get(src_url.clone())
+        let client = file_download_request.client(data.client()).await?;
+        let response = file_download_request
+            .get(&client, src_url)?
             .send()
             .await
             .map_err(|error| FileDownloadError::src_get(src_url.clone(), error))?;
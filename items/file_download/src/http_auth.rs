@@ -0,0 +1,64 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Authentication to use when requesting the file to download.
+///
+/// The `Debug` implementation masks credentials, so that they are not
+/// accidentally written to logs.
+///
+/// To avoid storing credentials in the `params_specs.yaml` file, the `auth`
+/// field of `FileDownloadParams` can be provided using a mapping function, e.g.
+/// with a token produced by a predecessor item.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HttpAuth {
+    /// HTTP basic authentication.
+    Basic {
+        /// Username to authenticate as.
+        username: String,
+        /// Password for the user, if any.
+        password: Option<String>,
+    },
+    /// Bearer token authentication, sent as `Authorization: Bearer <token>`.
+    Bearer {
+        /// The token to send.
+        token: String,
+    },
+}
+
+impl HttpAuth {
+    /// Returns `HttpAuth::Basic` with the given username and password.
+    pub fn basic<U, P>(username: U, password: Option<P>) -> Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        Self::Basic {
+            username: username.into(),
+            password: password.map(Into::into),
+        }
+    }
+
+    /// Returns `HttpAuth::Bearer` with the given token.
+    pub fn bearer<T>(token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Bearer {
+            token: token.into(),
+        }
+    }
+}
+
+impl fmt::Debug for HttpAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic { username, password } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &password.as_ref().map(|_| "***"))
+                .finish(),
+            Self::Bearer { token: _ } => f.debug_struct("Bearer").field("token", &"***").finish(),
+        }
+    }
+}
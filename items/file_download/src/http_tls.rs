@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// TLS configuration to use when requesting the file to download.
+///
+/// Paths are read when the request is made, so they may refer to files
+/// written by predecessor items.
+///
+/// This is only applied on native targets.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HttpTls {
+    /// Path to a PEM encoded CA certificate bundle to trust, in addition to
+    /// the system's root certificates.
+    ca_cert_path: Option<PathBuf>,
+    /// Path to a PEM encoded client certificate chain, for mutual TLS.
+    ///
+    /// Must be provided together with `client_key_path`.
+    client_cert_path: Option<PathBuf>,
+    /// Path to a PEM encoded PKCS #8 private key for the client certificate.
+    ///
+    /// Must be provided together with `client_cert_path`.
+    client_key_path: Option<PathBuf>,
+}
+
+impl HttpTls {
+    /// Returns a new `HttpTls` that uses the system's default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path to a PEM encoded CA certificate bundle to trust.
+    pub fn with_ca_cert_path(mut self, ca_cert_path: PathBuf) -> Self {
+        self.ca_cert_path = Some(ca_cert_path);
+        self
+    }
+
+    /// Sets the paths to the PEM encoded client certificate chain and private
+    /// key, for mutual TLS.
+    pub fn with_client_identity(
+        mut self,
+        client_cert_path: PathBuf,
+        client_key_path: PathBuf,
+    ) -> Self {
+        self.client_cert_path = Some(client_cert_path);
+        self.client_key_path = Some(client_key_path);
+        self
+    }
+
+    /// Returns the path to the CA certificate bundle to trust.
+    pub fn ca_cert_path(&self) -> Option<&Path> {
+        self.ca_cert_path.as_deref()
+    }
+
+    /// Returns the path to the client certificate chain.
+    pub fn client_cert_path(&self) -> Option<&Path> {
+        self.client_cert_path.as_deref()
+    }
+
+    /// Returns the path to the client private key.
+    pub fn client_key_path(&self) -> Option<&Path> {
+        self.client_key_path.as_deref()
+    }
+
+    /// Returns whether this uses the system's default configuration.
+    pub fn is_default(&self) -> bool {
+        self.ca_cert_path.is_none()
+            && self.client_cert_path.is_none()
+            && self.client_key_path.is_none()
+    }
+}
@@ -6,6 +6,7 @@ pub use crate::{
     file_download_data::FileDownloadData,
     file_download_error::FileDownloadError,
     file_download_item::FileDownloadItem,
+    file_download_outputs::FileDownloadOutputs,
     file_download_params::{
         FileDownloadParams, FileDownloadParamsFieldWise, FileDownloadParamsPartial,
     },
@@ -15,8 +16,12 @@ pub use crate::{
     file_download_state_diff_fn::FileDownloadStateDiffFn,
     file_download_state_goal_fn::FileDownloadStateGoalFn,
     file_download_state_logical::FileDownloadStateLogical,
+    http_auth::HttpAuth,
+    http_tls::HttpTls,
 };
 
+pub(crate) use crate::file_download_request::FileDownloadRequest;
+
 #[cfg(target_arch = "wasm32")]
 pub use crate::storage_form::StorageForm;
 
@@ -25,13 +30,17 @@ mod file_download_apply_fns;
 mod file_download_data;
 mod file_download_error;
 mod file_download_item;
+mod file_download_outputs;
 mod file_download_params;
+mod file_download_request;
 mod file_download_state;
 mod file_download_state_current_fn;
 mod file_download_state_diff;
 mod file_download_state_diff_fn;
 mod file_download_state_goal_fn;
 mod file_download_state_logical;
+mod http_auth;
+mod http_tls;
 
 #[cfg(target_arch = "wasm32")]
 mod storage_form;
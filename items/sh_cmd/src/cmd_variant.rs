@@ -1,6 +1,7 @@
 use std::fmt;
 
-/// Command variants which take in scripts in `ShCmdParams`.
+/// Command variants which take in scripts in `ShCmdParams` and
+/// `ShCmdScriptParams`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CmdVariant {
     /// The `state_clean` command.
@@ -15,6 +16,10 @@ pub enum CmdVariant {
     ApplyCheck,
     /// The `apply_exec` command.
     ApplyExec,
+    /// The `apply` command of `ShCmdScriptParams`.
+    ScriptApply,
+    /// The `clean` command of `ShCmdScriptParams`.
+    ScriptClean,
 }
 
 impl fmt::Display for CmdVariant {
@@ -26,6 +31,8 @@ impl fmt::Display for CmdVariant {
             Self::StateDiff => "state_diff".fmt(f),
             Self::ApplyCheck => "apply_check".fmt(f),
             Self::ApplyExec => "apply_exec".fmt(f),
+            Self::ScriptApply => "apply".fmt(f),
+            Self::ScriptClean => "clean".fmt(f),
         }
     }
 }
@@ -12,6 +12,15 @@
 //! * Clean check, whose exit status defines if clean execution needs to run --
 //!   `true` means execution is required, `false` means execution is required.
 //! * Clean execution.
+//!
+//! When applying, the ensure execution command's stdout and stderr are sent
+//! as progress messages line by line, and written to a log file in
+//! `$flow_dir/sh_cmd_logs/$item_id/`. The log file path is recorded in the
+//! `ShCmdExecutionRecord`.
+//!
+//! To wrap an existing idempotent script, [`ShCmdItem::new_script`] only needs
+//! a check command -- whose exit code `0` means the item is in sync -- an apply
+//! command, and optionally a clean command.
 
 pub use crate::{
     cmd_variant::CmdVariant,
@@ -22,13 +31,16 @@ pub use crate::{
     sh_cmd_execution_record::ShCmdExecutionRecord,
     sh_cmd_item::ShCmdItem,
     sh_cmd_params::{ShCmdParams, ShCmdParamsFieldWise, ShCmdParamsPartial},
+    sh_cmd_script_params::{
+        ShCmdScriptParams, ShCmdScriptParamsFieldWise, ShCmdScriptParamsPartial,
+    },
     sh_cmd_state::ShCmdState,
     sh_cmd_state_diff::ShCmdStateDiff,
     sh_cmd_state_diff_fn::ShCmdStateDiffFn,
     sh_cmd_state_logical::ShCmdStateLogical,
 };
 
-pub(crate) use sh_cmd_executor::ShCmdExecutor;
+pub(crate) use crate::{sh_cmd_executor::ShCmdExecutor, sh_cmd_script_fns::ShCmdScriptFns};
 
 mod cmd_variant;
 mod sh_cmd;
@@ -39,7 +51,13 @@ mod sh_cmd_execution_record;
 mod sh_cmd_executor;
 mod sh_cmd_item;
 mod sh_cmd_params;
+mod sh_cmd_script_fns;
+mod sh_cmd_script_params;
 mod sh_cmd_state;
 mod sh_cmd_state_diff;
 mod sh_cmd_state_diff_fn;
 mod sh_cmd_state_logical;
+
+/// Name of the directory within the flow directory to write command output
+/// logs to.
+pub const SH_CMD_LOG_DIR_NAME: &str = "sh_cmd_logs";
@@ -1,16 +1,51 @@
-use std::{ffi::OsString, fmt};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsString,
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use peace::params::Params;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 
 /// Shell command to execute.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Params)]
+///
+/// The `Debug` representation does not include environment variable values or
+/// stdin content, as they may contain secrets.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Params)]
 pub struct ShCmd {
     /// Command to run.
     program: OsString,
     /// Arguments to pass to the command.
     args: Vec<OsString>,
+    /// Working directory for the command.
+    ///
+    /// If `None`, the current process's working directory is inherited.
+    #[serde(default)]
+    current_dir: Option<PathBuf>,
+    /// Whether to clear the environment inherited from the current process.
+    #[serde(default)]
+    env_clear: bool,
+    /// Environment variables to set (`Some`) or remove (`None`).
+    ///
+    /// These are applied after the environment is cleared.
+    #[serde(default)]
+    envs: BTreeMap<String, Option<String>>,
+    /// Content to write to the command's stdin.
+    ///
+    /// If `None`, stdin is not connected.
+    #[serde(default)]
+    stdin: Option<String>,
+    /// Maximum duration the command may run for before it is killed.
+    #[serde(default)]
+    timeout: Option<Duration>,
+    /// Exit codes that indicate the command was successful.
+    ///
+    /// If `None`, the exit code is recorded but not checked.
+    #[serde(default)]
+    exit_codes: Option<BTreeSet<i32>>,
 }
 
 impl ShCmd {
@@ -51,6 +86,12 @@ impl ShCmd {
         Self {
             program: program.into(),
             args: Vec::new(),
+            current_dir: None,
+            env_clear: false,
+            envs: BTreeMap::new(),
+            stdin: None,
+            timeout: None,
+            exit_codes: None,
         }
     }
 
@@ -126,27 +167,255 @@ impl ShCmd {
         });
         self
     }
+
+    /// Sets the working directory for the command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("ls").current_dir("/path/to/repo");
+    /// ```
+    pub fn current_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets an environment variable for the command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("ls").env("LANG", "C");
+    /// ```
+    pub fn env<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.envs.insert(key.into(), Some(value.into()));
+        self
+    }
+
+    /// Sets multiple environment variables for the command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("ls").envs([("LANG", "C"), ("TZ", "UTC")]);
+    /// ```
+    pub fn envs<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        vars.into_iter().for_each(|(key, value)| {
+            self.envs.insert(key.into(), Some(value.into()));
+        });
+        self
+    }
+
+    /// Removes an environment variable from the command's environment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("ls").env_remove("LS_COLORS");
+    /// ```
+    pub fn env_remove<K: Into<String>>(mut self, key: K) -> Self {
+        self.envs.insert(key.into(), None);
+        self
+    }
+
+    /// Clears the environment inherited from the current process, and any
+    /// previously set environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("ls").env_clear().env("PATH", "/bin");
+    /// ```
+    pub fn env_clear(mut self) -> Self {
+        self.env_clear = true;
+        self.envs.clear();
+        self
+    }
+
+    /// Sets the content to write to the command's stdin.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("cat").stdin("hello");
+    /// ```
+    pub fn stdin<S: Into<String>>(mut self, stdin: S) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Sets the maximum duration the command may run for before it is killed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("sleep").arg("10").timeout(Duration::from_secs(5));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the exit codes that indicate the command was successful.
+    ///
+    /// When the command exits with a code that is not in this set, or is
+    /// terminated by a signal, execution returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use peace_item_sh_cmd::ShCmd;
+    ///
+    /// let sh_cmd = ShCmd::new("grep").arg("-q").arg("x").exit_codes([0, 1]);
+    /// ```
+    pub fn exit_codes<I>(mut self, exit_codes: I) -> Self
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        self.exit_codes = Some(exit_codes.into_iter().collect());
+        self
+    }
+
+    /// Returns the program to run.
+    pub fn get_program(&self) -> &OsString {
+        &self.program
+    }
+
+    /// Returns the arguments to pass to the program.
+    pub fn get_args(&self) -> &[OsString] {
+        &self.args
+    }
+
+    /// Returns the working directory for the command, if set.
+    pub fn get_current_dir(&self) -> Option<&Path> {
+        self.current_dir.as_deref()
+    }
+
+    /// Returns whether the inherited environment is cleared.
+    pub fn get_env_clear(&self) -> bool {
+        self.env_clear
+    }
+
+    /// Returns the environment variables to set (`Some`) or remove (`None`).
+    pub fn get_envs(&self) -> &BTreeMap<String, Option<String>> {
+        &self.envs
+    }
+
+    /// Returns the content to write to the command's stdin, if any.
+    pub fn get_stdin(&self) -> Option<&str> {
+        self.stdin.as_deref()
+    }
+
+    /// Returns the maximum duration the command may run for, if any.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the exit codes that indicate the command was successful.
+    pub fn get_exit_codes(&self) -> Option<&BTreeSet<i32>> {
+        self.exit_codes.as_ref()
+    }
+
+    /// Returns whether the given exit code indicates the command was
+    /// successful.
+    ///
+    /// When no exit codes are specified, any exit code is accepted, but a
+    /// process terminated by a signal (`None`) is not.
+    pub fn is_exit_code_success(&self, exit_code: Option<i32>) -> bool {
+        match (self.exit_codes.as_ref(), exit_code) {
+            (Some(exit_codes), Some(exit_code)) => exit_codes.contains(&exit_code),
+            (None, Some(_)) => true,
+            (_, None) => false,
+        }
+    }
 }
 
 impl From<&ShCmd> for Command {
     fn from(sh_cmd: &ShCmd) -> Command {
-        let mut command = Command::new(&sh_cmd.program);
-        command.args(&sh_cmd.args);
-
-        command
+        let std_command = std::process::Command::from(sh_cmd);
+        Command::from(std_command)
     }
 }
 
-#[cfg(feature = "item_state_example")]
+/// Converts the `ShCmd` into a `std::process::Command`.
+///
+/// Stdin content, the timeout, and exit code checks are not part of the
+/// `Command`, and are applied when the command is executed.
 impl From<&ShCmd> for std::process::Command {
     fn from(sh_cmd: &ShCmd) -> std::process::Command {
         let mut command = std::process::Command::new(&sh_cmd.program);
         command.args(&sh_cmd.args);
 
+        if let Some(current_dir) = sh_cmd.current_dir.as_deref() {
+            command.current_dir(current_dir);
+        }
+        if sh_cmd.env_clear {
+            command.env_clear();
+        }
+        sh_cmd
+            .envs
+            .iter()
+            .for_each(|(key, value)| match value.as_deref() {
+                Some(value) => {
+                    command.env(key, value);
+                }
+                None => {
+                    command.env_remove(key);
+                }
+            });
+
         command
     }
 }
 
+/// Environment variable values and stdin content are masked, in the same way
+/// as they are in the `ShCmdExecutionRecord`.
+impl fmt::Debug for ShCmd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let envs = self
+            .envs
+            .iter()
+            .map(|(key, value)| (key, value.as_ref().map(|_| "***")))
+            .collect::<BTreeMap<&String, Option<&str>>>();
+
+        f.debug_struct("ShCmd")
+            .field("program", &self.program)
+            .field("args", &self.args)
+            .field("current_dir", &self.current_dir)
+            .field("env_clear", &self.env_clear)
+            .field("envs", &envs)
+            .field("stdin", &self.stdin.as_ref().map(|_| "***"))
+            .field("timeout", &self.timeout)
+            .field("exit_codes", &self.exit_codes)
+            .finish()
+    }
+}
+
 impl fmt::Display for ShCmd {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.program.to_string_lossy().fmt(f)?;
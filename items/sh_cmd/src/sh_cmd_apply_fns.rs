@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, path::Path};
 
 use peace::cfg::{ApplyCheck, FnCtx};
 #[cfg(feature = "output_progress")]
@@ -56,14 +56,14 @@ where
                     }
                     Some("false") => Ok(ApplyCheck::ExecNotRequired),
                     _ => Err(ShCmdError::EnsureCheckValueNotBoolean {
-                        sh_cmd: apply_check_sh_cmd.clone(),
+                        sh_cmd: Box::new(apply_check_sh_cmd.clone()),
                         #[cfg(feature = "error_reporting")]
                         sh_cmd_string: format!("{apply_check_sh_cmd}"),
                         stdout: Some(stdout),
                     }),
                 },
                 _ => Err(ShCmdError::EnsureCheckValueNotBoolean {
-                    sh_cmd: apply_check_sh_cmd.clone(),
+                    sh_cmd: Box::new(apply_check_sh_cmd.clone()),
                     #[cfg(feature = "error_reporting")]
                     sh_cmd_string: format!("{apply_check_sh_cmd}"),
                     stdout: None,
@@ -99,9 +99,9 @@ where
     }
 
     pub async fn apply(
-        _fn_ctx: FnCtx<'_>,
+        fn_ctx: FnCtx<'_>,
         params: &ShCmdParams<Id>,
-        _data: ShCmdData<'_, Id>,
+        data: ShCmdData<'_, Id>,
         state_current: &ShCmdState<Id>,
         state_goal: &ShCmdState<Id>,
         state_diff: &ShCmdStateDiff,
@@ -121,7 +121,21 @@ where
             .arg(state_goal_arg)
             .arg(&**state_diff);
 
-        ShCmdExecutor::<Id>::exec(&apply_exec_sh_cmd).await?;
-        ShCmdExecutor::<Id>::exec(params.state_current_sh_cmd()).await
+        let log_dir = data.log_dir(fn_ctx.item_id);
+        let apply_exec_state =
+            ShCmdExecutor::<Id>::exec_streamed(fn_ctx, &apply_exec_sh_cmd, log_dir.as_deref())
+                .await?;
+        let mut state_current = ShCmdExecutor::<Id>::exec(params.state_current_sh_cmd()).await?;
+
+        // Reference the apply command's output log from the stored state.
+        state_current.0.physical.log_path_replace(
+            apply_exec_state
+                .0
+                .physical
+                .log_path()
+                .map(Path::to_path_buf),
+        );
+
+        Ok(state_current)
     }
 }
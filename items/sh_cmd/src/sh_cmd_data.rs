@@ -1,8 +1,10 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, path::PathBuf};
 
 use peace::{
     cfg::{accessors::Stored, State},
-    data::Data,
+    data::{accessors::RMaybe, Data},
+    item_model::ItemId,
+    resource_rt::paths::FlowDir,
 };
 
 use crate::{ShCmdExecutionRecord, ShCmdStateLogical};
@@ -21,6 +23,9 @@ where
     /// Stored states of this item's previous execution.
     state_current_stored: Stored<'exec, State<ShCmdStateLogical<Id>, ShCmdExecutionRecord>>,
 
+    /// Directory of the flow, used to store command output logs.
+    flow_dir: RMaybe<'exec, FlowDir>,
+
     /// Marker.
     marker: PhantomData<Id>,
 }
@@ -35,4 +40,19 @@ where
     ) -> Option<&State<ShCmdStateLogical<Id>, ShCmdExecutionRecord>> {
         self.state_current_stored.get()
     }
+
+    /// Returns the flow directory, if available.
+    pub fn flow_dir(&self) -> Option<&FlowDir> {
+        self.flow_dir.as_deref()
+    }
+
+    /// Returns the directory to write command output logs to for the given
+    /// item, if the flow directory is available.
+    pub(crate) fn log_dir(&self, item_id: &ItemId) -> Option<PathBuf> {
+        self.flow_dir().map(|flow_dir| {
+            flow_dir
+                .join(crate::SH_CMD_LOG_DIR_NAME)
+                .join(item_id.as_str())
+        })
+    }
 }
@@ -1,3 +1,5 @@
+use std::{path::PathBuf, time::Duration};
+
 #[cfg(feature = "error_reporting")]
 use peace::miette::{self, SourceSpan};
 
@@ -32,7 +34,7 @@ pub enum ShCmdError {
     )]
     CmdExecFail {
         /// The command that failed to be executed.
-        sh_cmd: ShCmd,
+        sh_cmd: Box<ShCmd>,
         /// The command that failed to be executed as a string.
         #[cfg(feature = "error_reporting")]
         #[source_code]
@@ -42,6 +44,118 @@ pub enum ShCmdError {
         error: std::io::Error,
     },
 
+    /// Failed to write to the command's stdin.
+    #[error("Failed to write to stdin of command: `{}`", sh_cmd)]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_sh_cmd::cmd_stdin_write))
+    )]
+    CmdStdinWrite {
+        /// The command whose stdin failed to be written to.
+        sh_cmd: Box<ShCmd>,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Command did not complete within its timeout, and was killed.
+    #[error("Command did not complete within {}s: `{}`", timeout.as_secs_f64(), sh_cmd)]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_sh_cmd::cmd_timeout),
+            help("Check if the command is waiting for input, or increase the timeout.")
+        )
+    )]
+    CmdTimeout {
+        /// The command that timed out.
+        sh_cmd: Box<ShCmd>,
+        /// The timeout that elapsed.
+        timeout: Duration,
+    },
+
+    /// Command exited with an exit code that is not one of its expected exit
+    /// codes.
+    #[error(
+        "Command exited with {}: `{}`",
+        match exit_code {
+            Some(exit_code) => format!("code {exit_code}"),
+            None => String::from("no code"),
+        },
+        sh_cmd
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_sh_cmd::cmd_exit_code_unexpected),
+            help("The command's stderr was:\n{stderr}")
+        )
+    )]
+    CmdExitCodeUnexpected {
+        /// The command that exited.
+        sh_cmd: Box<ShCmd>,
+        /// Exit code of the process, `None` if it was terminated by a signal.
+        exit_code: Option<i32>,
+        /// stderr output.
+        stderr: String,
+    },
+
+    /// A script item's check command did not reflect the changes made by its
+    /// apply or clean command.
+    #[error(
+        "Check command {} after running the `{cmd_variant}` command: `{}`",
+        if *in_sync { "reported in sync" } else { "reported out of sync" },
+        check_sh_cmd
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_item_sh_cmd::script_check_mismatch),
+            help(
+                "The check command should exit with code 0 after `apply`, \
+                and a non-zero code after `clean`."
+            )
+        )
+    )]
+    ScriptCheckMismatch {
+        /// The check command.
+        check_sh_cmd: Box<ShCmd>,
+        /// The command that was run before the check.
+        cmd_variant: CmdVariant,
+        /// Whether the check command reported in sync.
+        in_sync: bool,
+        /// Exit code of the check command.
+        exit_code: Option<i32>,
+    },
+
+    /// Failed to create the log file for a command's output.
+    #[error("Failed to create command log file: `{}`", log_path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_sh_cmd::log_file_create))
+    )]
+    LogFileCreate {
+        /// Path to the log file.
+        log_path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to write a command's output to its log file.
+    #[error("Failed to write to command log file: `{}`", log_path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_item_sh_cmd::log_file_write))
+    )]
+    LogFileWrite {
+        /// Path to the log file.
+        log_path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
     /// Command produced non-UTF-8 stdout output.
     #[error("Command produced non-UTF-8 stdout output: `{}`", sh_cmd)]
     #[cfg_attr(
@@ -55,7 +169,7 @@ pub enum ShCmdError {
     )]
     StdoutNonUtf8 {
         /// The command whose stdout is not a valid UTF-8 string.
-        sh_cmd: ShCmd,
+        sh_cmd: Box<ShCmd>,
         /// Lossy UTF-8 conversion of stdout.
         #[cfg_attr(feature = "error_reporting", source_code)]
         stdout_lossy: String,
@@ -81,7 +195,7 @@ pub enum ShCmdError {
     )]
     StderrNonUtf8 {
         /// The command whose stderr is not a valid UTF-8 string.
-        sh_cmd: ShCmd,
+        sh_cmd: Box<ShCmd>,
         /// Lossy UTF-8 conversion of stderr.
         #[cfg_attr(feature = "error_reporting", source_code)]
         stderr_lossy: String,
@@ -108,7 +222,7 @@ pub enum ShCmdError {
     )]
     EnsureCheckValueNotBoolean {
         /// The ensure check shell command.
-        sh_cmd: ShCmd,
+        sh_cmd: Box<ShCmd>,
         /// The ensure check shell command as a string.
         #[cfg(feature = "error_reporting")]
         #[source_code]
@@ -131,7 +245,7 @@ pub enum ShCmdError {
     )]
     CleanCheckValueNotBoolean {
         /// The clean check shell command.
-        sh_cmd: ShCmd,
+        sh_cmd: Box<ShCmd>,
         /// The clean check shell command as a string.
         #[cfg(feature = "error_reporting")]
         #[source_code]
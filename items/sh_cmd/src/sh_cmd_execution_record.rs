@@ -1,4 +1,9 @@
-use std::fmt;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use chrono::{offset::Local, DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -23,9 +28,65 @@ pub enum ShCmdExecutionRecord {
         ///
         /// [`ExitStatus::code()`]: std::process::ExitStatus::code
         exit_code: Option<i32>,
+        /// Working directory the command was run in, if not inherited.
+        #[serde(default)]
+        current_dir: Option<PathBuf>,
+        /// Whether the inherited environment was cleared.
+        #[serde(default)]
+        env_clear: bool,
+        /// Names of environment variables that were set (`true`) or removed
+        /// (`false`).
+        ///
+        /// Values are not recorded, as they may contain secrets.
+        #[serde(default)]
+        envs: BTreeMap<String, bool>,
+        /// Whether content was written to the command's stdin.
+        #[serde(default)]
+        stdin: bool,
+        /// Maximum duration the command was allowed to run for.
+        #[serde(default)]
+        timeout: Option<Duration>,
+        /// Exit codes that indicate the command was successful.
+        ///
+        /// If `None`, any exit code was accepted.
+        #[serde(default)]
+        exit_codes: Option<BTreeSet<i32>>,
+        /// Path to the file that the command's stdout and stderr were written
+        /// to, if the output was captured.
+        #[serde(default)]
+        log_path: Option<PathBuf>,
     },
 }
 
+impl ShCmdExecutionRecord {
+    /// Returns the exit code of the last execution, if any.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self {
+            Self::None => None,
+            Self::Some { exit_code, .. } => *exit_code,
+        }
+    }
+
+    /// Returns the path to the file that the command's output was written to,
+    /// if any.
+    pub fn log_path(&self) -> Option<&Path> {
+        match self {
+            Self::None => None,
+            Self::Some { log_path, .. } => log_path.as_deref(),
+        }
+    }
+
+    /// Sets the log path of this record to `log_path`, if both are present.
+    ///
+    /// This is used to reference the log of a command that changed the state
+    /// from the record of the command that discovered the state.
+    pub(crate) fn log_path_replace(&mut self, log_path_new: Option<PathBuf>) {
+        if let (Self::Some { log_path, .. }, Some(log_path_new)) = (self, log_path_new) {
+            *log_path = Some(log_path_new);
+        }
+    }
+}
+
 impl fmt::Display for ShCmdExecutionRecord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -33,9 +94,15 @@ impl fmt::Display for ShCmdExecutionRecord {
             Self::Some {
                 start_datetime,
                 exit_code,
+                exit_codes,
                 ..
             } => match exit_code {
-                Some(0) => {
+                Some(code)
+                    if exit_codes
+                        .as_ref()
+                        .map(|exit_codes| exit_codes.contains(code))
+                        .unwrap_or(*code == 0) =>
+                {
                     let start_datetime_local = DateTime::<Local>::from(*start_datetime);
                     write!(f, "executed successfully at {start_datetime_local}")
                 }
@@ -57,14 +124,12 @@ impl PartialEq for ShCmdExecutionRecord {
             | (ShCmdExecutionRecord::Some { .. }, ShCmdExecutionRecord::None) => false,
             (
                 ShCmdExecutionRecord::Some {
-                    start_datetime: _,
-                    end_datetime: _,
                     exit_code: exit_code_self,
+                    ..
                 },
                 ShCmdExecutionRecord::Some {
-                    start_datetime: _,
-                    end_datetime: _,
                     exit_code: exit_code_other,
+                    ..
                 },
             ) => exit_code_self == exit_code_other,
         }
@@ -1,110 +1,391 @@
-use std::{marker::PhantomData, process::Stdio};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    process::{ExitStatus, Stdio},
+};
 
-use chrono::Utc;
-use tokio::process::Command;
+use chrono::{DateTime, Utc};
+use peace::cfg::FnCtx;
+use tokio::{
+    fs::File,
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter},
+    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command},
+};
+
+#[cfg(feature = "output_progress")]
+use peace::progress_model::ProgressMsgUpdate;
 
 use crate::{ShCmd, ShCmdError, ShCmdExecutionRecord, ShCmdState, ShCmdStateLogical};
 
+/// Which output stream a line was read from.
+#[derive(Clone, Copy, Debug)]
+enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
 /// Common code to run `ShCmd`s.
 #[derive(Debug)]
 pub(crate) struct ShCmdExecutor<Id>(PhantomData<Id>);
 
 impl<Id> ShCmdExecutor<Id> {
     /// Executes the provided `ShCmd` and returns execution information.
+    ///
+    /// If the command does not complete within its timeout, it is killed and
+    /// `ShCmdError::CmdTimeout` is returned.
     pub async fn exec(sh_cmd: &ShCmd) -> Result<ShCmdState<Id>, ShCmdError> {
         let start_datetime = Utc::now();
+        let mut child = Self::spawn(sh_cmd)?;
+
+        let stdin_write = Self::stdin_write(sh_cmd, child.stdin.take());
+        let output_wait = async {
+            let (stdin_write_result, output_result) =
+                tokio::join!(stdin_write, child.wait_with_output());
+            stdin_write_result?;
+            output_result.map_err(|error| Self::cmd_exec_fail(sh_cmd, error))
+        };
+
+        let output = Self::timeout_apply(sh_cmd, output_wait).await?;
+        let end_datetime = Utc::now();
+
+        Self::state_from_output(sh_cmd, start_datetime, end_datetime, output, None)
+    }
+
+    /// Executes the provided `ShCmd`, streaming its output, and returns
+    /// execution information.
+    ///
+    /// Each line of stdout and stderr is:
+    ///
+    /// * sent as a progress message, when the `"output_progress"` feature is
+    ///   enabled.
+    /// * written to a log file in `log_dir`, if provided.
+    ///
+    /// The log file path is recorded in the returned state's
+    /// `ShCmdExecutionRecord`.
+    ///
+    /// If the execution is interrupted, the command is killed, and the
+    /// returned state is `ShCmdStateLogical::None` with no exit code.
+    pub async fn exec_streamed(
+        fn_ctx: FnCtx<'_>,
+        sh_cmd: &ShCmd,
+        log_dir: Option<&Path>,
+    ) -> Result<ShCmdState<Id>, ShCmdError> {
+        let start_datetime = Utc::now();
+        let log_path = log_dir.map(|log_dir| {
+            let log_file_name = format!("{}.log", start_datetime.format("%Y%m%dT%H%M%S%.3fZ"));
+            log_dir.join(log_file_name)
+        });
+        let log_file = match log_path.as_deref() {
+            Some(log_path) => Some(Self::log_file_create(log_path).await?),
+            None => None,
+        };
+
+        let mut child = Self::spawn(sh_cmd)?;
+        let stdin_write = Self::stdin_write(sh_cmd, child.stdin.take());
+        let child_stdout = child.stdout.take();
+        let child_stderr = child.stderr.take();
+        let output_stream = async {
+            let (stdin_write_result, output_read_result, exit_status_result) = tokio::join!(
+                stdin_write,
+                Self::output_stream(
+                    fn_ctx,
+                    sh_cmd,
+                    child_stdout,
+                    child_stderr,
+                    log_path.as_deref().zip(log_file),
+                ),
+                Self::child_wait(fn_ctx, &mut child)
+            );
+            stdin_write_result?;
+            let (stdout, stderr) = output_read_result?;
+            let status = exit_status_result.map_err(|error| Self::cmd_exec_fail(sh_cmd, error))?;
+
+            Ok(status.map(|status| std::process::Output {
+                status,
+                stdout,
+                stderr,
+            }))
+        };
+
+        let output = Self::timeout_apply(sh_cmd, output_stream).await?;
+        let end_datetime = Utc::now();
+
+        match output {
+            Some(output) => {
+                Self::state_from_output(sh_cmd, start_datetime, end_datetime, output, log_path)
+            }
+            None => Ok(ShCmdState::new(
+                ShCmdStateLogical::None,
+                Self::execution_record(sh_cmd, start_datetime, end_datetime, None, log_path),
+            )),
+        }
+    }
+
+    /// Waits for the child process to exit, or kills it if the execution is
+    /// interrupted.
+    ///
+    /// Returns `None` if the child process was killed.
+    async fn child_wait(
+        fn_ctx: FnCtx<'_>,
+        child: &mut Child,
+    ) -> std::io::Result<Option<ExitStatus>> {
+        tokio::select! {
+            exit_status = child.wait() => exit_status.map(Some),
+            () = fn_ctx.interrupted() => child.kill().await.map(|()| None),
+        }
+    }
+
+    /// Spawns the command with piped stdout and stderr.
+    fn spawn(sh_cmd: &ShCmd) -> Result<Child, ShCmdError> {
         let mut command: Command = sh_cmd.into();
-        let output = command
-            .stdin(Stdio::null())
+        let stdin = if sh_cmd.get_stdin().is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        };
+        command
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .kill_on_drop(true)
-            .output()
-            .await
-            .map_err(|error| {
-                #[cfg(feature = "error_reporting")]
-                let sh_cmd_string = format!("{sh_cmd}");
+            .spawn()
+            .map_err(|error| Self::cmd_exec_fail(sh_cmd, error))
+    }
 
-                ShCmdError::CmdExecFail {
-                    sh_cmd: sh_cmd.clone(),
-                    #[cfg(feature = "error_reporting")]
-                    sh_cmd_string,
+    /// Writes the command's stdin content, then closes stdin.
+    async fn stdin_write(
+        sh_cmd: &ShCmd,
+        child_stdin: Option<ChildStdin>,
+    ) -> Result<(), ShCmdError> {
+        if let Some((mut child_stdin, stdin)) = child_stdin.zip(sh_cmd.get_stdin()) {
+            match child_stdin.write_all(stdin.as_bytes()).await {
+                Ok(()) => {}
+                // The command may exit without reading all of stdin.
+                Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => {}
+                Err(error) => {
+                    return Err(ShCmdError::CmdStdinWrite {
+                        sh_cmd: Box::new(sh_cmd.clone()),
+                        error,
+                    })
+                }
+            }
+            // `child_stdin` is dropped here, closing the pipe.
+        }
+        Ok(())
+    }
+
+    /// Runs the future to completion, or returns `ShCmdError::CmdTimeout` if
+    /// the command's timeout elapses.
+    ///
+    /// When the timeout elapses, the future is dropped, which drops the child
+    /// process, and `kill_on_drop` kills it.
+    async fn timeout_apply<Fut, T>(sh_cmd: &ShCmd, fut: Fut) -> Result<T, ShCmdError>
+    where
+        Fut: Future<Output = Result<T, ShCmdError>>,
+    {
+        match sh_cmd.get_timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, fut)
+                .await
+                .map_err(|_elapsed| ShCmdError::CmdTimeout {
+                    sh_cmd: Box::new(sh_cmd.clone()),
+                    timeout,
+                })?,
+            None => fut.await,
+        }
+    }
+
+    async fn log_file_create(log_path: &Path) -> Result<BufWriter<File>, ShCmdError> {
+        if let Some(log_dir) = log_path.parent() {
+            tokio::fs::create_dir_all(log_dir).await.map_err(|error| {
+                ShCmdError::LogFileCreate {
+                    log_path: log_path.to_path_buf(),
                     error,
                 }
             })?;
-        let end_datetime = Utc::now();
+        }
+        File::create(log_path)
+            .await
+            .map(BufWriter::new)
+            .map_err(|error| ShCmdError::LogFileCreate {
+                log_path: log_path.to_path_buf(),
+                error,
+            })
+    }
 
-        let stdout = String::from_utf8(output.stdout).map_err(|from_utf8_error| {
-            let stdout_lossy = String::from_utf8_lossy(from_utf8_error.as_bytes()).to_string();
-            let error = from_utf8_error.utf8_error();
-            #[cfg(feature = "error_reporting")]
-            let invalid_span = {
-                let start = error.valid_up_to();
-                let len = error.error_len().unwrap_or(1);
-                peace::miette::SourceSpan::from((start, len))
+    /// Reads stdout and stderr line by line, until both are closed, or the
+    /// execution is interrupted.
+    ///
+    /// Returns the bytes read from stdout and stderr.
+    async fn output_stream(
+        fn_ctx: FnCtx<'_>,
+        sh_cmd: &ShCmd,
+        child_stdout: Option<ChildStdout>,
+        child_stderr: Option<ChildStderr>,
+        mut log_path_and_file: Option<(&Path, BufWriter<File>)>,
+    ) -> Result<(Vec<u8>, Vec<u8>), ShCmdError> {
+        let mut stdout_reader = child_stdout.map(BufReader::new);
+        let mut stderr_reader = child_stderr.map(BufReader::new);
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut stdout_line = Vec::new();
+        let mut stderr_line = Vec::new();
+
+        // `read_until` appends partially read bytes to the line buffer when the
+        // other branch completes first, so the line buffers are only cleared
+        // once a full line is read.
+        while stdout_reader.is_some() || stderr_reader.is_some() {
+            let (output_stream, line_read_result) = tokio::select! {
+                result = Self::line_read(stdout_reader.as_mut(), &mut stdout_line) => {
+                    (OutputStream::Stdout, result)
+                }
+                result = Self::line_read(stderr_reader.as_mut(), &mut stderr_line) => {
+                    (OutputStream::Stderr, result)
+                }
+                () = fn_ctx.interrupted() => break,
             };
+            let (reader_closed, line, output) = match output_stream {
+                OutputStream::Stdout => (
+                    line_read_result.map_err(|error| Self::cmd_exec_fail(sh_cmd, error))? == 0,
+                    &mut stdout_line,
+                    &mut stdout,
+                ),
+                OutputStream::Stderr => (
+                    line_read_result.map_err(|error| Self::cmd_exec_fail(sh_cmd, error))? == 0,
+                    &mut stderr_line,
+                    &mut stderr,
+                ),
+            };
+            if reader_closed {
+                match output_stream {
+                    OutputStream::Stdout => stdout_reader = None,
+                    OutputStream::Stderr => stderr_reader = None,
+                }
+            }
+            if line.is_empty() {
+                continue;
+            }
 
-            ShCmdError::StdoutNonUtf8 {
-                sh_cmd: sh_cmd.clone(),
-                stdout_lossy,
-                #[cfg(feature = "error_reporting")]
-                invalid_span,
-                error,
+            if let Some((log_path, log_file)) = log_path_and_file.as_mut() {
+                log_file
+                    .write_all(line)
+                    .await
+                    .map_err(|error| ShCmdError::LogFileWrite {
+                        log_path: log_path.to_path_buf(),
+                        error,
+                    })?;
             }
-        })?;
 
-        let stderr = String::from_utf8(output.stderr)
-            .map_err(|from_utf8_error| {
-                let stderr_lossy = String::from_utf8_lossy(from_utf8_error.as_bytes()).to_string();
-                let error = from_utf8_error.utf8_error();
-                #[cfg(feature = "error_reporting")]
-                let invalid_span = {
-                    let start = error.valid_up_to();
-                    let len = error.error_len().unwrap_or(1);
-                    peace::miette::SourceSpan::from((start, len))
-                };
+            #[cfg(feature = "output_progress")]
+            {
+                let line_lossy = String::from_utf8_lossy(line).trim_end().to_string();
+                fn_ctx
+                    .progress_sender
+                    .tick(ProgressMsgUpdate::Set(line_lossy));
+            }
 
-                ShCmdError::StderrNonUtf8 {
-                    sh_cmd: sh_cmd.clone(),
-                    stderr_lossy,
-                    #[cfg(feature = "error_reporting")]
-                    invalid_span,
+            // Moves the line's bytes into the output, clearing the line buffer.
+            output.append(line);
+        }
+
+        if let Some((log_path, mut log_file)) = log_path_and_file {
+            log_file
+                .flush()
+                .await
+                .map_err(|error| ShCmdError::LogFileWrite {
+                    log_path: log_path.to_path_buf(),
                     error,
-                }
-            })?
-            .trim()
-            .to_string();
+                })?;
+        }
 
-        Ok(ShCmdState::new(
-            ShCmdStateLogical::Some {
-                stdout,
-                stderr,
-                marker: PhantomData,
-            },
-            ShCmdExecutionRecord::Some {
-                start_datetime,
-                end_datetime,
-                exit_code: output.status.code(),
-            },
-        ))
+        Ok((stdout, stderr))
     }
 
     /// Executes the provided `ShCmd` and returns execution information.
+    ///
+    /// The command's timeout is not applied, as this is used to produce an
+    /// example state, which is expected to be fast.
     #[cfg(feature = "item_state_example")]
     pub fn exec_blocking(sh_cmd: &ShCmd) -> Result<ShCmdState<Id>, ShCmdError> {
+        use std::io::Write;
+
         let start_datetime = Utc::now();
         let mut command: std::process::Command = sh_cmd.into();
-        let output = command.stdin(Stdio::null()).output().map_err(|error| {
-            #[cfg(feature = "error_reporting")]
-            let sh_cmd_string = format!("{sh_cmd}");
+        let stdin = if sh_cmd.get_stdin().is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        };
+        let mut child = command
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| Self::cmd_exec_fail(sh_cmd, error))?;
 
-            ShCmdError::CmdExecFail {
-                sh_cmd: sh_cmd.clone(),
-                #[cfg(feature = "error_reporting")]
-                sh_cmd_string,
-                error,
-            }
+        let output = std::thread::scope(|scope| {
+            let child_stdin = child.stdin.take();
+            let stdin_write = scope.spawn(move || {
+                if let Some((mut child_stdin, stdin)) = child_stdin.zip(sh_cmd.get_stdin()) {
+                    match child_stdin.write_all(stdin.as_bytes()) {
+                        Ok(()) => {}
+                        Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => {}
+                        Err(error) => return Err(error),
+                    }
+                }
+                Ok(())
+            });
+            let output_result = child.wait_with_output();
+            stdin_write
+                .join()
+                .expect("Failed to join stdin writer thread.")
+                .map_err(|error| ShCmdError::CmdStdinWrite {
+                    sh_cmd: Box::new(sh_cmd.clone()),
+                    error,
+                })?;
+            output_result.map_err(|error| Self::cmd_exec_fail(sh_cmd, error))
         })?;
         let end_datetime = Utc::now();
 
+        Self::state_from_output(sh_cmd, start_datetime, end_datetime, output, None)
+    }
+
+    /// Reads a line into `line`, or never completes if the reader is closed.
+    async fn line_read<R>(reader: Option<&mut R>, line: &mut Vec<u8>) -> std::io::Result<usize>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        match reader {
+            Some(reader) => reader.read_until(b'\n', line).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    fn cmd_exec_fail(sh_cmd: &ShCmd, error: std::io::Error) -> ShCmdError {
+        #[cfg(feature = "error_reporting")]
+        let sh_cmd_string = format!("{sh_cmd}");
+
+        ShCmdError::CmdExecFail {
+            sh_cmd: Box::new(sh_cmd.clone()),
+            #[cfg(feature = "error_reporting")]
+            sh_cmd_string,
+            error,
+        }
+    }
+
+    /// Returns the `ShCmdState` from the command's output.
+    ///
+    /// Returns an error if the exit code is not one of the command's expected
+    /// exit codes, or stdout or stderr are not valid UTF-8.
+    fn state_from_output(
+        sh_cmd: &ShCmd,
+        start_datetime: DateTime<Utc>,
+        end_datetime: DateTime<Utc>,
+        output: std::process::Output,
+        log_path: Option<PathBuf>,
+    ) -> Result<ShCmdState<Id>, ShCmdError> {
+        let exit_code = output.status.code();
+
         let stdout = String::from_utf8(output.stdout).map_err(|from_utf8_error| {
             let stdout_lossy = String::from_utf8_lossy(from_utf8_error.as_bytes()).to_string();
             let error = from_utf8_error.utf8_error();
@@ -116,7 +397,7 @@ impl<Id> ShCmdExecutor<Id> {
             };
 
             ShCmdError::StdoutNonUtf8 {
-                sh_cmd: sh_cmd.clone(),
+                sh_cmd: Box::new(sh_cmd.clone()),
                 stdout_lossy,
                 #[cfg(feature = "error_reporting")]
                 invalid_span,
@@ -136,7 +417,7 @@ impl<Id> ShCmdExecutor<Id> {
                 };
 
                 ShCmdError::StderrNonUtf8 {
-                    sh_cmd: sh_cmd.clone(),
+                    sh_cmd: Box::new(sh_cmd.clone()),
                     stderr_lossy,
                     #[cfg(feature = "error_reporting")]
                     invalid_span,
@@ -146,17 +427,47 @@ impl<Id> ShCmdExecutor<Id> {
             .trim()
             .to_string();
 
+        if sh_cmd.get_exit_codes().is_some() && !sh_cmd.is_exit_code_success(exit_code) {
+            return Err(ShCmdError::CmdExitCodeUnexpected {
+                sh_cmd: Box::new(sh_cmd.clone()),
+                exit_code,
+                stderr,
+            });
+        }
+
         Ok(ShCmdState::new(
             ShCmdStateLogical::Some {
                 stdout,
                 stderr,
                 marker: PhantomData,
             },
-            ShCmdExecutionRecord::Some {
-                start_datetime,
-                end_datetime,
-                exit_code: output.status.code(),
-            },
+            Self::execution_record(sh_cmd, start_datetime, end_datetime, exit_code, log_path),
         ))
     }
+
+    /// Returns the execution record for the given `ShCmd`.
+    fn execution_record(
+        sh_cmd: &ShCmd,
+        start_datetime: DateTime<Utc>,
+        end_datetime: DateTime<Utc>,
+        exit_code: Option<i32>,
+        log_path: Option<PathBuf>,
+    ) -> ShCmdExecutionRecord {
+        ShCmdExecutionRecord::Some {
+            start_datetime,
+            end_datetime,
+            exit_code,
+            current_dir: sh_cmd.get_current_dir().map(std::path::Path::to_path_buf),
+            env_clear: sh_cmd.get_env_clear(),
+            envs: sh_cmd
+                .get_envs()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.is_some()))
+                .collect(),
+            stdin: sh_cmd.get_stdin().is_some(),
+            timeout: sh_cmd.get_timeout(),
+            exit_codes: sh_cmd.get_exit_codes().cloned(),
+            log_path,
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use std::marker::PhantomData;
 
+use derivative::Derivative;
 use peace::{
     cfg::{async_trait, ApplyCheck, FnCtx, Item},
     item_model::ItemId,
@@ -8,12 +9,15 @@ use peace::{
 };
 
 use crate::{
-    ShCmdApplyFns, ShCmdData, ShCmdError, ShCmdExecutor, ShCmdParams, ShCmdState, ShCmdStateDiff,
-    ShCmdStateDiffFn,
+    CmdVariant, ShCmdApplyFns, ShCmdData, ShCmdError, ShCmdExecutor, ShCmdParams, ShCmdScriptFns,
+    ShCmdScriptParams, ShCmdState, ShCmdStateDiff, ShCmdStateDiffFn,
 };
 
 /// Item for executing a shell command.
 ///
+/// To wrap an idempotent script without implementing each state command, see
+/// [`ShCmdItem::new_script`].
+///
 /// The `Id` type parameter is needed for each command execution params to be a
 /// distinct type.
 ///
@@ -21,19 +25,26 @@ use crate::{
 ///
 /// * `Id`: A zero-sized type used to distinguish different command execution
 ///   parameters from each other.
-#[derive(Debug)]
-pub struct ShCmdItem<Id> {
+/// * `P`: The commands used to manage the item, either [`ShCmdParams`] or
+///   [`ShCmdScriptParams`].
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct ShCmdItem<Id, P = ShCmdParams<Id>> {
     /// ID to easily tell what the item command is for.
     item_id: ItemId,
     /// Marker for unique command execution parameters type.
     marker: PhantomData<Id>,
+    /// Marker for the commands used to manage the item.
+    #[derivative(Debug = "ignore")]
+    params_marker: PhantomData<P>,
 }
 
-impl<Id> Clone for ShCmdItem<Id> {
+impl<Id, P> Clone for ShCmdItem<Id, P> {
     fn clone(&self) -> Self {
         Self {
             item_id: self.item_id.clone(),
             marker: PhantomData,
+            params_marker: PhantomData,
         }
     }
 }
@@ -48,6 +59,28 @@ impl<Id> ShCmdItem<Id> {
         Self {
             item_id,
             marker: PhantomData,
+            params_marker: PhantomData,
+        }
+    }
+}
+
+impl<Id> ShCmdItem<Id, ShCmdScriptParams<Id>> {
+    /// Returns a new `ShCmdItem` that wraps an idempotent shell script.
+    ///
+    /// Instead of a command for each state function, this item takes a check
+    /// command, an apply command, and optionally a clean command, through
+    /// [`ShCmdScriptParams`]. The state, state diff, and apply check are
+    /// derived from the check command's exit code and output, so the commands
+    /// do not need to output state strings.
+    ///
+    /// # Parameters
+    ///
+    /// * `item_id`: ID of this `ShCmdItem`.
+    pub fn new_script(item_id: ItemId) -> Self {
+        Self {
+            item_id,
+            marker: PhantomData,
+            params_marker: PhantomData,
         }
     }
 }
@@ -132,7 +165,7 @@ where
             params_partial
                 .state_diff_sh_cmd()
                 .ok_or(ShCmdError::CmdScriptNotResolved {
-                    cmd_variant: crate::CmdVariant::StateDiff,
+                    cmd_variant: CmdVariant::StateDiff,
                 })?;
 
         ShCmdStateDiffFn::state_diff(state_diff_sh_cmd.clone(), state_current, state_goal).await
@@ -146,7 +179,7 @@ where
             params_partial
                 .state_clean_sh_cmd()
                 .ok_or(ShCmdError::CmdScriptNotResolved {
-                    cmd_variant: crate::CmdVariant::StateClean,
+                    cmd_variant: CmdVariant::StateClean,
                 })?;
 
         ShCmdExecutor::exec(state_clean_sh_cmd).await
@@ -198,3 +231,138 @@ where
         vec![item_interaction]
     }
 }
+
+#[async_trait(?Send)]
+impl<Id> Item for ShCmdItem<Id, ShCmdScriptParams<Id>>
+where
+    Id: Send + Sync + 'static,
+{
+    type Data<'exec> = ShCmdData<'exec, Id>;
+    type Error = ShCmdError;
+    type Params<'exec> = ShCmdScriptParams<Id>;
+    type State = ShCmdState<Id>;
+    type StateDiff = ShCmdStateDiff;
+
+    fn id(&self) -> &ItemId {
+        &self.item_id
+    }
+
+    async fn setup(&self, _resources: &mut Resources<Empty>) -> Result<(), ShCmdError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "item_state_example")]
+    fn state_example(_params: &Self::Params<'_>, _data: Self::Data<'_>) -> Self::State {
+        ShCmdScriptFns::<Id>::state_goal()
+    }
+
+    async fn try_state_current(
+        _fn_ctx: FnCtx<'_>,
+        params_partial: &<Self::Params<'_> as Params>::Partial,
+        _data: ShCmdData<'_, Id>,
+    ) -> Result<Option<Self::State>, ShCmdError> {
+        if let Some(check_sh_cmd) = params_partial.check_sh_cmd() {
+            ShCmdScriptFns::<Id>::state_current(check_sh_cmd)
+                .await
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn state_current(
+        _fn_ctx: FnCtx<'_>,
+        params: &Self::Params<'_>,
+        _data: ShCmdData<'_, Id>,
+    ) -> Result<Self::State, ShCmdError> {
+        ShCmdScriptFns::<Id>::state_current(params.check_sh_cmd()).await
+    }
+
+    async fn try_state_goal(
+        _fn_ctx: FnCtx<'_>,
+        _params_partial: &<Self::Params<'_> as Params>::Partial,
+        _data: ShCmdData<'_, Id>,
+    ) -> Result<Option<Self::State>, ShCmdError> {
+        Ok(Some(ShCmdScriptFns::<Id>::state_goal()))
+    }
+
+    async fn state_goal(
+        _fn_ctx: FnCtx<'_>,
+        _params: &Self::Params<'_>,
+        _data: ShCmdData<'_, Id>,
+    ) -> Result<Self::State, ShCmdError> {
+        Ok(ShCmdScriptFns::<Id>::state_goal())
+    }
+
+    async fn state_diff(
+        params_partial: &<Self::Params<'_> as Params>::Partial,
+        _data: Self::Data<'_>,
+        state_current: &Self::State,
+        state_target: &Self::State,
+    ) -> Result<Self::StateDiff, ShCmdError> {
+        let clean_sh_cmd_exists = params_partial
+            .clean_sh_cmd()
+            .ok_or(ShCmdError::CmdScriptNotResolved {
+                cmd_variant: CmdVariant::ScriptClean,
+            })?
+            .is_some();
+
+        Ok(ShCmdScriptFns::<Id>::state_diff(
+            clean_sh_cmd_exists,
+            state_current,
+            state_target,
+        ))
+    }
+
+    async fn state_clean(
+        _params_partial: &<Self::Params<'_> as Params>::Partial,
+        _data: Self::Data<'_>,
+    ) -> Result<Self::State, ShCmdError> {
+        Ok(ShCmdScriptFns::<Id>::state_clean())
+    }
+
+    async fn apply_check(
+        _params: &Self::Params<'_>,
+        _data: Self::Data<'_>,
+        _state_current: &Self::State,
+        _state_target: &Self::State,
+        diff: &Self::StateDiff,
+    ) -> Result<ApplyCheck, Self::Error> {
+        Ok(ShCmdScriptFns::<Id>::apply_check(diff))
+    }
+
+    async fn apply_dry(
+        _fn_ctx: FnCtx<'_>,
+        _params: &Self::Params<'_>,
+        _data: Self::Data<'_>,
+        _state_current: &Self::State,
+        state_target: &Self::State,
+        _diff: &Self::StateDiff,
+    ) -> Result<Self::State, Self::Error> {
+        Ok(state_target.clone())
+    }
+
+    async fn apply(
+        fn_ctx: FnCtx<'_>,
+        params: &Self::Params<'_>,
+        data: Self::Data<'_>,
+        _state_current: &Self::State,
+        state_target: &Self::State,
+        _diff: &Self::StateDiff,
+    ) -> Result<Self::State, Self::Error> {
+        ShCmdScriptFns::<Id>::apply(fn_ctx, params, data, state_target).await
+    }
+
+    #[cfg(feature = "item_interactions")]
+    fn interactions(
+        _params: &Self::Params<'_>,
+        _data: Self::Data<'_>,
+    ) -> Vec<peace::item_interaction_model::ItemInteraction> {
+        use peace::item_interaction_model::{ItemInteractionWithin, ItemLocation};
+
+        let item_interaction =
+            ItemInteractionWithin::new(vec![ItemLocation::localhost()].into()).into();
+
+        vec![item_interaction]
+    }
+}
@@ -0,0 +1,178 @@
+use std::{marker::PhantomData, path::Path};
+
+use peace::cfg::{ApplyCheck, FnCtx, State};
+#[cfg(feature = "output_progress")]
+use peace::progress_model::ProgressLimit;
+
+use crate::{
+    CmdVariant, ShCmd, ShCmdData, ShCmdError, ShCmdExecutionRecord, ShCmdExecutor,
+    ShCmdScriptParams, ShCmdState, ShCmdStateDiff, ShCmdStateLogical,
+};
+
+/// State, diff, and apply functions for a `ShCmdItem` created with
+/// `ShCmdItem::new_script`.
+///
+/// The states are synthesized from the check command's exit code:
+///
+/// * `ShCmdStateLogical::Some` means the check command exited with `0`. Its
+///   `stdout` is `"in_sync"`.
+/// * `ShCmdStateLogical::None` means the check command exited with any other
+///   code, or the item is cleaned.
+#[derive(Debug)]
+pub(crate) struct ShCmdScriptFns<Id>(PhantomData<Id>);
+
+impl<Id> ShCmdScriptFns<Id>
+where
+    Id: Send + Sync + 'static,
+{
+    /// State and state diff string when the item is in sync.
+    pub(crate) const IN_SYNC: &'static str = "in_sync";
+    /// State diff string when the apply command needs to be run.
+    pub(crate) const APPLY_REQUIRED: &'static str = "apply_required";
+    /// State diff string when the clean command needs to be run.
+    pub(crate) const CLEAN_REQUIRED: &'static str = "clean_required";
+    /// State diff string when the item needs to be cleaned, but there is no
+    /// clean command.
+    pub(crate) const CLEAN_UNSUPPORTED: &'static str = "clean_unsupported";
+
+    /// Runs the check command and returns the current state.
+    pub(crate) async fn state_current(check_sh_cmd: &ShCmd) -> Result<ShCmdState<Id>, ShCmdError> {
+        let check_state = ShCmdExecutor::<Id>::exec(check_sh_cmd).await?;
+        Ok(Self::state_from_check(check_state))
+    }
+
+    /// Returns the goal state, which is the item being in sync.
+    pub(crate) fn state_goal() -> ShCmdState<Id> {
+        ShCmdState::new(
+            ShCmdStateLogical::Some {
+                stdout: String::from(Self::IN_SYNC),
+                stderr: String::from("in sync"),
+                marker: PhantomData,
+            },
+            ShCmdExecutionRecord::None,
+        )
+    }
+
+    /// Returns the clean state.
+    pub(crate) fn state_clean() -> ShCmdState<Id> {
+        ShCmdState::new(ShCmdStateLogical::None, ShCmdExecutionRecord::None)
+    }
+
+    /// Returns the diff between the current and target states.
+    pub(crate) fn state_diff(
+        clean_sh_cmd_exists: bool,
+        state_current: &ShCmdState<Id>,
+        state_target: &ShCmdState<Id>,
+    ) -> ShCmdStateDiff {
+        let (stdout, stderr) = match (&state_current.0.logical, &state_target.0.logical) {
+            (ShCmdStateLogical::Some { .. }, ShCmdStateLogical::Some { .. })
+            | (ShCmdStateLogical::None, ShCmdStateLogical::None) => (Self::IN_SYNC, "in sync"),
+            (ShCmdStateLogical::None, ShCmdStateLogical::Some { .. }) => {
+                (Self::APPLY_REQUIRED, "apply script will be run")
+            }
+            (ShCmdStateLogical::Some { .. }, ShCmdStateLogical::None) => {
+                if clean_sh_cmd_exists {
+                    (Self::CLEAN_REQUIRED, "clean script will be run")
+                } else {
+                    (Self::CLEAN_UNSUPPORTED, "no clean script, nothing to do")
+                }
+            }
+        };
+
+        ShCmdStateDiff::new(String::from(stdout), String::from(stderr))
+    }
+
+    pub(crate) fn apply_check(state_diff: &ShCmdStateDiff) -> ApplyCheck {
+        match state_diff.stdout() {
+            Self::APPLY_REQUIRED | Self::CLEAN_REQUIRED => {
+                #[cfg(not(feature = "output_progress"))]
+                {
+                    ApplyCheck::ExecRequired
+                }
+
+                #[cfg(feature = "output_progress")]
+                ApplyCheck::ExecRequired {
+                    progress_limit: ProgressLimit::Unknown,
+                }
+            }
+            _ => ApplyCheck::ExecNotRequired,
+        }
+    }
+
+    /// Runs the apply or clean command, then returns the state from the check
+    /// command.
+    ///
+    /// Returns an error if the check command does not report the target
+    /// state, unless the execution was interrupted, in which case the command
+    /// is killed and the state it reached is returned.
+    pub(crate) async fn apply(
+        fn_ctx: FnCtx<'_>,
+        params: &ShCmdScriptParams<Id>,
+        data: ShCmdData<'_, Id>,
+        state_target: &ShCmdState<Id>,
+    ) -> Result<ShCmdState<Id>, ShCmdError> {
+        let (cmd_variant, sh_cmd) = match &state_target.0.logical {
+            ShCmdStateLogical::Some { .. } => (CmdVariant::ScriptApply, params.apply_sh_cmd()),
+            ShCmdStateLogical::None => {
+                let clean_sh_cmd =
+                    params
+                        .clean_sh_cmd()
+                        .ok_or(ShCmdError::CmdScriptNotResolved {
+                            cmd_variant: CmdVariant::ScriptClean,
+                        })?;
+                (CmdVariant::ScriptClean, clean_sh_cmd)
+            }
+        };
+        // Unlike state commands, a non-zero exit code means the script failed.
+        let sh_cmd = if sh_cmd.get_exit_codes().is_some() {
+            sh_cmd.clone()
+        } else {
+            sh_cmd.clone().exit_codes([0])
+        };
+
+        let log_dir = data.log_dir(fn_ctx.item_id);
+        let script_state =
+            ShCmdExecutor::<Id>::exec_streamed(fn_ctx, &sh_cmd, log_dir.as_deref()).await?;
+        let mut state_current = Self::state_current(params.check_sh_cmd()).await?;
+
+        let in_sync = matches!(state_current.0.logical, ShCmdStateLogical::Some { .. });
+        let in_sync_expected = matches!(state_target.0.logical, ShCmdStateLogical::Some { .. });
+        if in_sync != in_sync_expected && !fn_ctx.is_interrupted() {
+            return Err(ShCmdError::ScriptCheckMismatch {
+                check_sh_cmd: Box::new(params.check_sh_cmd().clone()),
+                cmd_variant,
+                in_sync,
+                exit_code: state_current.0.physical.exit_code(),
+            });
+        }
+
+        // Reference the script's output log from the stored state.
+        state_current
+            .0
+            .physical
+            .log_path_replace(script_state.0.physical.log_path().map(Path::to_path_buf));
+
+        Ok(state_current)
+    }
+
+    /// Returns the state synthesized from the check command's execution.
+    fn state_from_check(check_state: ShCmdState<Id>) -> ShCmdState<Id> {
+        let ShCmdState(State {
+            logical,
+            physical: execution_record,
+        }) = check_state;
+
+        let logical = match logical {
+            ShCmdStateLogical::Some { marker, .. } if execution_record.exit_code() == Some(0) => {
+                ShCmdStateLogical::Some {
+                    stdout: String::from(Self::IN_SYNC),
+                    stderr: String::from("in sync"),
+                    marker,
+                }
+            }
+            ShCmdStateLogical::Some { .. } | ShCmdStateLogical::None => ShCmdStateLogical::None,
+        };
+
+        ShCmdState::new(logical, execution_record)
+    }
+}
@@ -0,0 +1,89 @@
+use std::marker::PhantomData;
+
+use derivative::Derivative;
+use peace::params::Params;
+use serde::{Deserialize, Serialize};
+
+use crate::ShCmd;
+
+/// Commands to run an idempotent shell script, for a `ShCmdItem` created with
+/// `ShCmdItem::new_script`.
+///
+/// Unlike `ShCmdParams`, the commands do not need to output state strings --
+/// the state is derived from the check command's exit code and output.
+///
+/// The `Id` type parameter is needed for each command execution params to be a
+/// distinct type.
+///
+/// # Type Parameters
+///
+/// * `Id`: A zero-sized type used to distinguish different command execution
+///   parameters from each other.
+#[derive(Derivative, Params, PartialEq, Eq, Deserialize, Serialize)]
+#[derivative(Clone, Debug)]
+#[serde(bound = "")]
+pub struct ShCmdScriptParams<Id> {
+    /// Shell command to run to check if the script needs to be applied.
+    ///
+    /// An exit code of `0` means the item is in sync, and any other exit code
+    /// means the apply command needs to be run.
+    ///
+    /// The command's output is not recorded, so changes to the output alone do
+    /// not cause the apply command to be run.
+    check_sh_cmd: ShCmd,
+    /// Shell command to run to bring the item in sync.
+    ///
+    /// This is expected to exit with code `0`, unless the command's expected
+    /// exit codes are specified. After it is run, the check command must
+    /// report that the item is in sync.
+    apply_sh_cmd: ShCmd,
+    /// Shell command to run to clean up the item, if any.
+    ///
+    /// This is expected to exit with code `0`, unless the command's expected
+    /// exit codes are specified. After it is run, the check command must
+    /// report that the item is out of sync.
+    ///
+    /// If this is `None`, cleaning this item does nothing.
+    clean_sh_cmd: Option<ShCmd>,
+    /// Marker for unique command execution parameters type.
+    marker: PhantomData<Id>,
+}
+
+impl<Id> ShCmdScriptParams<Id> {
+    /// Returns new `ShCmdScriptParams`.
+    ///
+    /// # Parameters
+    ///
+    /// * `check_sh_cmd`: Command whose exit code `0` means the item is in sync.
+    /// * `apply_sh_cmd`: Command to bring the item in sync.
+    pub fn new(check_sh_cmd: ShCmd, apply_sh_cmd: ShCmd) -> Self {
+        Self {
+            check_sh_cmd,
+            apply_sh_cmd,
+            clean_sh_cmd: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the shell command to run to clean up the item.
+    pub fn with_clean_sh_cmd(mut self, clean_sh_cmd: ShCmd) -> Self {
+        self.clean_sh_cmd = Some(clean_sh_cmd);
+        self
+    }
+
+    /// Returns the shell command to run to check if the script needs to be
+    /// applied.
+    pub fn check_sh_cmd(&self) -> &ShCmd {
+        &self.check_sh_cmd
+    }
+
+    /// Returns the shell command to run to bring the item in sync.
+    pub fn apply_sh_cmd(&self) -> &ShCmd {
+        &self.apply_sh_cmd
+    }
+
+    /// Returns the shell command to run to clean up the item, if any.
+    pub fn clean_sh_cmd(&self) -> Option<&ShCmd> {
+        self.clean_sh_cmd.as_ref()
+    }
+}
@@ -29,6 +29,8 @@
 // Re-exports
 #[cfg(feature = "blank")]
 pub use peace_item_blank as blank;
+#[cfg(feature = "file")]
+pub use peace_item_file as file;
 #[cfg(feature = "file_download")]
 pub use peace_item_file_download as file_download;
 #[cfg(feature = "sh_cmd")]
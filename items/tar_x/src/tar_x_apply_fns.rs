@@ -6,6 +6,9 @@ use peace::progress_model::ProgressLimit;
 
 use crate::{FileMetadatas, TarXData, TarXError, TarXParams, TarXStateDiff};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::TarXStateCurrentFn;
+
 /// ApplyFns for the tar to extract.
 pub struct TarXApplyFns<Id>(PhantomData<Id>);
 
@@ -68,7 +71,7 @@ where
 
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn apply(
-        _fn_ctx: FnCtx<'_>,
+        fn_ctx: FnCtx<'_>,
         params: &TarXParams<Id>,
         data: TarXData<'_, Id>,
         _state_current: &FileMetadatas,
@@ -94,24 +97,57 @@ where
         // need to be unpacked.
         //
         // Then we can send proper progress updates via `fn_ctx.progress_tx`.
-        if tar_path.exists() {
+        let interrupted = if tar_path.exists() {
             storage
                 .read_with_sync_api(
                     "TarXApplyFns::exec".to_string(),
                     tar_path,
                     |sync_io_bridge| {
-                        tar::Archive::new(sync_io_bridge).unpack(dest).map_err(
-                            #[cfg_attr(coverage_nightly, coverage(off))]
-                            |error| TarXError::TarUnpack {
-                                tar_path: tar_path.to_path_buf(),
-                                dest: dest.to_path_buf(),
-                                error,
-                            },
-                        )?;
-                        Result::<_, TarXError>::Ok(())
+                        let tar_unpack_error = |error| TarXError::TarUnpack {
+                            tar_path: tar_path.to_path_buf(),
+                            dest: dest.to_path_buf(),
+                            error,
+                        };
+
+                        // Entries are unpacked one at a time, so that we stop between entries
+                        // when the execution is interrupted.
+                        //
+                        // Like `tar::Archive::unpack`, directory entries are applied after all
+                        // other entries, deepest first, so that a directory's permissions and
+                        // mtime are not changed by unpacking its descendants.
+                        let mut archive = tar::Archive::new(sync_io_bridge);
+                        let mut directories = Vec::new();
+                        for entry in archive.entries().map_err(tar_unpack_error)? {
+                            if fn_ctx.is_interrupted() {
+                                return Result::<_, TarXError>::Ok(true);
+                            }
+                            let mut entry = entry.map_err(tar_unpack_error)?;
+                            if entry.header().entry_type() == tar::EntryType::Directory {
+                                directories.push(entry);
+                            } else {
+                                entry.unpack_in(dest).map_err(tar_unpack_error)?;
+                            }
+                        }
+
+                        directories.sort_by(|directory_a, directory_b| {
+                            directory_b.path_bytes().cmp(&directory_a.path_bytes())
+                        });
+                        for mut directory in directories {
+                            directory.unpack_in(dest).map_err(tar_unpack_error)?;
+                        }
+
+                        Result::<_, TarXError>::Ok(false)
                     },
                 )
-                .await?;
+                .await?
+        } else {
+            false
+        };
+
+        if interrupted {
+            // Return the files that were extracted before the interruption.
+            let files_extracted = TarXStateCurrentFn::<Id>::files_extracted(fn_ctx, dest).await?;
+            return Ok(FileMetadatas::from(files_extracted));
         }
 
         if let TarXStateDiff::ExtractionOutOfSync {
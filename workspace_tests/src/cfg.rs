@@ -1,4 +1,6 @@
 mod app_name;
 mod apply_check;
+mod fn_ctx;
+mod interrupt_token;
 mod state;
 mod stored;
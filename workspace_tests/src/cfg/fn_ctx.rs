@@ -0,0 +1,56 @@
+use futures::FutureExt;
+use peace::{
+    cfg::{FnCtx, InterruptToken},
+    item_model::item_id,
+};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "output_progress")] {
+        use peace::progress_model::ProgressSender;
+        use tokio::sync::mpsc;
+    }
+}
+
+#[test]
+fn is_interrupted_returns_false_without_interrupt_token() {
+    let item_id = item_id!("item");
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(&item_id, &progress_tx);
+        }
+    }
+    let fn_ctx = FnCtx::new(
+        &item_id,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    assert!(!fn_ctx.is_interrupted());
+    assert!(fn_ctx.interrupted().now_or_never().is_none());
+}
+
+#[tokio::test]
+async fn is_interrupted_returns_true_when_interrupt_token_interrupted() {
+    let item_id = item_id!("item");
+    let interrupt_token = InterruptToken::new();
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(&item_id, &progress_tx);
+        }
+    }
+    let fn_ctx = FnCtx::new(
+        &item_id,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    )
+    .with_interrupt_token(&interrupt_token);
+
+    assert!(!fn_ctx.is_interrupted());
+
+    interrupt_token.interrupt();
+
+    assert!(fn_ctx.is_interrupted());
+    fn_ctx.interrupted().await;
+}
@@ -0,0 +1,39 @@
+use peace::cfg::InterruptToken;
+
+#[test]
+fn new_is_not_interrupted() {
+    let interrupt_token = InterruptToken::new();
+
+    assert!(!interrupt_token.is_interrupted());
+}
+
+#[test]
+fn interrupt_is_observed_by_clones() {
+    let interrupt_token = InterruptToken::new();
+    let interrupt_token_clone = interrupt_token.clone();
+
+    interrupt_token.interrupt();
+
+    assert!(interrupt_token.is_interrupted());
+    assert!(interrupt_token_clone.is_interrupted());
+}
+
+#[tokio::test]
+async fn interrupted_completes_when_interrupted() {
+    let interrupt_token = InterruptToken::new();
+    let interrupt_token_clone = interrupt_token.clone();
+
+    let ((), ()) = tokio::join!(interrupt_token.interrupted(), async move {
+        interrupt_token_clone.interrupt();
+    });
+
+    assert!(interrupt_token.is_interrupted());
+}
+
+#[tokio::test]
+async fn interrupted_completes_when_already_interrupted() {
+    let interrupt_token = InterruptToken::new();
+    interrupt_token.interrupt();
+
+    interrupt_token.interrupted().await;
+}
@@ -1,4 +1,7 @@
-use peace::cfg::state::External;
+use peace::{
+    cfg::state::External,
+    diff::{Equality, MaybeEq},
+};
 
 #[test]
 fn display() {
@@ -37,3 +40,23 @@ fn deserialize() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(External::<u8>::Value(123), external);
     Ok(())
 }
+
+#[test]
+fn maybe_eq() {
+    assert_eq!(
+        Equality::Equal,
+        External::<u8>::Value(1).maybe_eq(&External::<u8>::Value(1))
+    );
+    assert_eq!(
+        Equality::NotEqual,
+        External::<u8>::Value(1).maybe_eq(&External::<u8>::Value(2))
+    );
+    assert_eq!(
+        Equality::Unknown,
+        External::<u8>::Tbd.maybe_eq(&External::<u8>::Value(1))
+    );
+    assert_eq!(
+        Equality::Unknown,
+        External::<u8>::Tbd.maybe_eq(&External::<u8>::Tbd)
+    );
+}
@@ -1,4 +1,7 @@
-use peace::cfg::state::ExternalOpt;
+use peace::{
+    cfg::state::ExternalOpt,
+    diff::{Equality, MaybeEq},
+};
 
 #[test]
 fn display() {
@@ -54,3 +57,23 @@ fn deserialize() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(ExternalOpt::<u8>::Value(123), external);
     Ok(())
 }
+
+#[test]
+fn maybe_eq() {
+    assert_eq!(
+        Equality::Equal,
+        ExternalOpt::<u8>::Value(1).maybe_eq(&ExternalOpt::<u8>::Value(1))
+    );
+    assert_eq!(
+        Equality::Equal,
+        ExternalOpt::<u8>::None.maybe_eq(&ExternalOpt::<u8>::None)
+    );
+    assert_eq!(
+        Equality::NotEqual,
+        ExternalOpt::<u8>::Value(1).maybe_eq(&ExternalOpt::<u8>::None)
+    );
+    assert_eq!(
+        Equality::Unknown,
+        ExternalOpt::<u8>::Tbd.maybe_eq(&ExternalOpt::<u8>::None)
+    );
+}
@@ -1,27 +1,31 @@
 use peace::{
     cfg::{app_name, Item},
-    cmd_ctx::{CmdCtxSpsf, CmdCtxTypes, ProfileSelection},
+    cmd_ctx::{CmdCtxSpsf, CmdCtxTypes, ItemGraphEdgesCheck, ProfileSelection},
+    data::fn_graph::{Edge, WouldCycle},
     enum_iterator::Sequence,
     flow_model::flow_id,
     flow_rt::{Flow, ItemGraphBuilder},
     item_model::item_id,
     params::{
-        FromFunc, MappingFn, MappingFnId, MappingFnImpl, MappingFnReg, MappingFns, Params,
-        ParamsSpec, ValueResolutionCtx, ValueResolutionMode, ValueSpec,
+        FieldValueSetError, FromFunc, MappingFn, MappingFnId, MappingFnImpl, MappingFnReg,
+        MappingFns, Params, ParamsOverride, ParamsSpec, TemplateValueKind, ValueResolutionCtx,
+        ValueResolutionMode, ValueSpec,
     },
     profile_model::{profile, Profile},
     resource_rt::{
         internal::WorkspaceParamsFile,
-        paths::{FlowDir, ProfileDir, ProfileHistoryDir},
+        paths::{FlowDir, ProfileDir, ProfileHistoryDir, StatesCurrentFile},
         type_reg::untagged::BoxDataTypeDowncast,
     },
+    rt_model::{ItemBoxed, ItemEnableCondition, ItemIdsDisabled, TemplateTypeError},
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    mock_item::{MockItem, MockSrc, MockSrcFieldWise},
     no_op_output::NoOpOutput,
     test_support::{assert_flow_params, assert_profile_params, assert_workspace_params, workspace},
-    vec_copy_item::{VecA, VecAFieldWise, VecCopyItem},
+    vec_copy_item::{VecA, VecAFieldWise, VecCopyItem, VecCopyState},
     PeaceTestError,
 };
 
@@ -1007,6 +1011,67 @@ async fn build_with_item_params_returns_ok_when_spec_provided_for_previous_mappi
     Ok(())
 }
 
+#[tokio::test]
+async fn build_with_item_params_flow_state_reads_other_flow_states_current(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy();
+
+    let profile_dir = ProfileDir::from((workspace.dirs().peace_app_dir(), &profile));
+    let flow_dir_env_deploy = FlowDir::from((&profile_dir, &flow_id!("env_deploy")));
+    tokio::fs::create_dir_all(&flow_dir_env_deploy).await?;
+    tokio::fs::write(
+        StatesCurrentFile::from(&flow_dir_env_deploy),
+        "s3_bucket:\n  objects: [1, 2, 3]\n",
+    )
+    .await?;
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            ParamsSpec::FieldWise {
+                field_wise_spec: VecAFieldWise(ValueSpec::<Vec<u8>>::FlowState {
+                    flow_id: flow_id!("env_deploy"),
+                    item_id: item_id!("s3_bucket"),
+                    key_path: String::from("objects"),
+                }),
+            },
+        )
+        .await?;
+
+    let fields = cmd_ctx.fields();
+    let params_specs = fields.params_specs();
+    let mapping_fn_reg = fields.mapping_fn_reg();
+    let resources = fields.resources();
+    let vec_a_spec = params_specs
+        .get::<ParamsSpec<<VecCopyItem as Item>::Params<'_>>, _>(VecCopyItem::ID_DEFAULT);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        VecCopyItem::ID_DEFAULT.clone(),
+        tynm::type_name::<VecA>(),
+    );
+    assert_eq!(
+        Some(VecA(vec![1u8, 2, 3])),
+        vec_a_spec.and_then(|vec_a_spec| vec_a_spec
+            .resolve(mapping_fn_reg, resources, &mut value_resolution_ctx)
+            .ok()),
+    );
+    assert_eq!(
+        vec![&flow_id!("env_deploy")],
+        flow.flow_info_with_params_specs(params_specs)
+            .flow_ids_dependencies()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn build_with_item_params_returns_ok_when_spec_fully_not_provided_for_previous_mapping_fn(
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -1356,6 +1421,622 @@ async fn build_with_item_params_returns_ok_when_new_item_added_with_params_provi
     Ok(())
 }
 
+#[tokio::test]
+async fn build_with_item_params_returns_err_when_template_mapping_fn_not_registered(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let item_graph = {
+        let mut item_graph_builder = ItemGraphBuilder::new();
+        item_graph_builder.add_fn(VecCopyItem::default().into());
+        item_graph_builder.build()
+    };
+    let flow = Flow::<PeaceTestError>::new(flow_id, item_graph);
+
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            ParamsSpec::FieldWise {
+                field_wise_spec: VecAFieldWise(ValueSpec::<Vec<u8>>::Template {
+                    template: String::from("[{Vec1u8}, {NotRegistered}]"),
+                }),
+            },
+        )
+        .await;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &cmd_ctx_result,
+                    Err(PeaceTestError::PeaceRt(
+                        peace::rt_model::Error::ParamsSpecsMappingFnsNotRegistered {
+                            item_mapping_fn_ids,
+                        }
+                    ))
+                    if item_mapping_fn_ids == &vec![(
+                        VecCopyItem::ID_DEFAULT.clone(),
+                        MappingFnId::new(String::from("NotRegistered")),
+                    )]
+                ),
+                "was {cmd_ctx_result:#?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_item_params_returns_ok_when_template_mapping_fn_types_match(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(true)?;
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::FieldWise {
+                field_wise_spec: MockSrcFieldWise(ValueSpec::<u8>::Template {
+                    template: String::from("{MockSrcFromVecCopyState}"),
+                }),
+            },
+        )
+        .await?;
+
+    assert!(std::ptr::eq(&flow, cmd_ctx.fields().flow()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_item_params_returns_err_when_template_mapping_fn_types_mismatch(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(true)?;
+
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::FieldWise {
+                field_wise_spec: MockSrcFieldWise(ValueSpec::<u8>::Template {
+                    template: String::from("{StringFromVecCopyState}{U8FromU16}"),
+                }),
+            },
+        )
+        .await;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            let template = String::from("{StringFromVecCopyState}{U8FromU16}");
+            assert!(
+                matches!(
+                    &cmd_ctx_result,
+                    Err(PeaceTestError::PeaceRt(
+                        peace::rt_model::Error::ParamsSpecsTemplateTypesMismatch {
+                            item_template_type_errors,
+                        }
+                    ))
+                    if item_template_type_errors == &vec![
+                        (
+                            MockItem::<()>::ID_DEFAULT.clone(),
+                            TemplateTypeError::ValueKindMismatch {
+                                template: template.clone(),
+                                mapping_fn_id: TestMappingFns::StringFromVecCopyState.id(),
+                                output_type_name: String::from("String"),
+                                output_kind: TemplateValueKind::Text,
+                                field_type_name: String::from("u8"),
+                                field_kind: TemplateValueKind::Number,
+                            },
+                        ),
+                        (
+                            MockItem::<()>::ID_DEFAULT.clone(),
+                            TemplateTypeError::ArgsNotFound {
+                                template: template.clone(),
+                                mapping_fn_id: TestMappingFns::U8FromU16.id(),
+                                arg_type_names: vec![String::from("u16")],
+                            },
+                        ),
+                    ]
+                ),
+                "was {cmd_ctx_result:#?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_returns_err_when_item_graph_edge_missing_for_params_spec_mapping_fn(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(false)?;
+
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::mapping_fn(None, TestMappingFns::MockSrcFromVecCopyState),
+        )
+        .with_item_graph_edges_check(ItemGraphEdgesCheck::Error)
+        .await;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &cmd_ctx_result,
+                    Err(PeaceTestError::PeaceRt(
+                        peace::rt_model::Error::ItemGraphEdgesMissing { item_refs }
+                    ))
+                    if item_refs == &vec![(
+                        VecCopyItem::ID_DEFAULT.clone(),
+                        MockItem::<()>::ID_DEFAULT.clone(),
+                    )]
+                ),
+                "was {cmd_ctx_result:#?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_returns_ok_when_item_graph_edge_present_for_params_spec_mapping_fn(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(true)?;
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::mapping_fn(None, TestMappingFns::MockSrcFromVecCopyState),
+        )
+        .await?;
+
+    assert!(std::ptr::eq(&flow, cmd_ctx.fields().flow()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_item_graph_edges_check_infer_adds_missing_edges(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(false)?;
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::mapping_fn(None, TestMappingFns::MockSrcFromVecCopyState),
+        )
+        .with_item_graph_edges_check(ItemGraphEdgesCheck::Infer)
+        .await?;
+
+    let fields = cmd_ctx.fields();
+    let flow_inferred = fields.flow();
+    let item_refs_missing = flow_inferred
+        .params_specs_item_refs_missing(fields.params_specs(), fields.mapping_fn_reg());
+    assert!(!std::ptr::eq(&flow, flow_inferred));
+    assert_eq!(flow.flow_id(), flow_inferred.flow_id());
+    assert_eq!(
+        vec![(
+            VecCopyItem::ID_DEFAULT.clone(),
+            MockItem::<()>::ID_DEFAULT.clone(),
+        )],
+        flow.params_specs_item_refs_missing(fields.params_specs(), fields.mapping_fn_reg())
+    );
+    assert!(
+        item_refs_missing.is_empty(),
+        "Expected no missing edges, but was: {item_refs_missing:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_item_graph_edges_check_default_warns_when_item_graph_edge_missing(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(false)?;
+    let warn_capture = WarnCapture::default();
+    let _subscriber_guard = tracing::subscriber::set_default(warn_capture.clone());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::mapping_fn(None, TestMappingFns::MockSrcFromVecCopyState),
+        )
+        .await?;
+
+    assert!(std::ptr::eq(&flow, cmd_ctx.fields().flow()));
+    assert_eq!(
+        vec![String::from(
+            "`mock`'s params read data from `vec_copy`, \
+            but `vec_copy` is not a predecessor of `mock` in the item graph."
+        )],
+        warn_capture.messages()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_item_graph_edges_check_ignore_does_not_warn_when_item_graph_edge_missing(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy_and_mock(false)?;
+    let warn_capture = WarnCapture::default();
+    let _subscriber_guard = tracing::subscriber::set_default(warn_capture.clone());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_item_params::<MockItem<()>>(
+            MockItem::<()>::ID_DEFAULT.clone(),
+            ParamsSpec::mapping_fn(None, TestMappingFns::MockSrcFromVecCopyState),
+        )
+        .with_item_graph_edges_check(ItemGraphEdgesCheck::Ignore)
+        .await?;
+
+    assert!(std::ptr::eq(&flow, cmd_ctx.fields().flow()));
+    assert!(warn_capture.messages().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_params_override_applies_override_for_execution_only(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy();
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA::field_wise_spec().with_0(vec![1u8]).build(),
+        )
+        .with_params_override(ParamsOverride::new(
+            VecCopyItem::ID_DEFAULT.clone(),
+            String::from("0"),
+            String::from("[2]"),
+        ))
+        .await?;
+    let vec_a_spec = cmd_ctx
+        .fields()
+        .params_specs()
+        .get::<ParamsSpec<<VecCopyItem as Item>::Params<'_>>, _>(VecCopyItem::ID_DEFAULT)
+        .cloned();
+
+    let cmd_ctx_from_stored = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .await?;
+    let vec_a_spec_stored = cmd_ctx_from_stored
+        .fields()
+        .params_specs()
+        .get::<ParamsSpec<<VecCopyItem as Item>::Params<'_>>, _>(VecCopyItem::ID_DEFAULT);
+
+    assert!(
+        matches!(
+            &vec_a_spec,
+            Some(ParamsSpec::FieldWise {
+                field_wise_spec: VecAFieldWise(ValueSpec::Value { value }),
+            })
+            if value == &[2u8]
+        ),
+        "was {vec_a_spec:?}"
+    );
+    assert!(
+        matches!(
+            vec_a_spec_stored,
+            Some(ParamsSpec::FieldWise {
+                field_wise_spec: VecAFieldWise(ValueSpec::Value { value }),
+            })
+            if value == &[1u8]
+        ),
+        "was {vec_a_spec_stored:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_params_override_persist_stores_override(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy();
+
+    let mut output = NoOpOutput;
+    let _cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA::field_wise_spec().with_0(vec![1u8]).build(),
+        )
+        .with_params_override(ParamsOverride::new(
+            VecCopyItem::ID_DEFAULT.clone(),
+            String::from("0"),
+            String::from("[2]"),
+        ))
+        .with_params_overrides_persist(true)
+        .await?;
+
+    let cmd_ctx_from_stored = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .await?;
+    let vec_a_spec_stored = cmd_ctx_from_stored
+        .fields()
+        .params_specs()
+        .get::<ParamsSpec<<VecCopyItem as Item>::Params<'_>>, _>(VecCopyItem::ID_DEFAULT);
+
+    assert!(
+        matches!(
+            vec_a_spec_stored,
+            Some(ParamsSpec::FieldWise {
+                field_wise_spec: VecAFieldWise(ValueSpec::Value { value }),
+            })
+            if value == &[2u8]
+        ),
+        "was {vec_a_spec_stored:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_params_override_returns_err_when_item_not_found(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy();
+    let params_override =
+        ParamsOverride::new(item_id!("vec_cpy"), String::from("0"), String::from("[2]"));
+
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1u8]).into())
+        .with_params_override(params_override.clone())
+        .await;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &cmd_ctx_result,
+                    Err(PeaceTestError::PeaceRt(
+                        peace::rt_model::Error::ParamsOverrideItemNotFound {
+                            params_override: params_override_actual,
+                            item_ids,
+                        }
+                    ))
+                    if params_override_actual == &params_override
+                    && item_ids == &vec![VecCopyItem::ID_DEFAULT.clone()]
+                ),
+                "was {cmd_ctx_result:#?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_params_override_returns_err_when_field_not_found(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = flow_vec_copy();
+
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+        .with_output((&mut output).into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA::field_wise_spec().with_0(vec![1u8]).build(),
+        )
+        .with_params_override(ParamsOverride::new(
+            VecCopyItem::ID_DEFAULT.clone(),
+            String::from("1"),
+            String::from("[2]"),
+        ))
+        .await;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &cmd_ctx_result,
+                    Err(PeaceTestError::PeaceRt(
+                        peace::rt_model::Error::ParamsOverrideApply {
+                            error: FieldValueSetError::FieldNotFound { field_name, field_names },
+                            ..
+                        }
+                    ))
+                    if field_name == "1"
+                    && field_names == &vec![String::from("0")]
+                ),
+                "was {cmd_ctx_result:#?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_evaluates_item_enable_conditions_from_params(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_cmd_ctx_spsf_params")).await?;
+    let profile = profile!("test_profile");
+    let flow = {
+        let item_graph = {
+            let mut item_graph_builder = ItemGraphBuilder::new();
+            item_graph_builder.add_fns([
+                ItemBoxed::from(VecCopyItem::default())
+                    .with_enable_condition(ItemEnableCondition::param_eq(true)),
+                MockItem::<()>::default().into(),
+            ]);
+            item_graph_builder.build()
+        };
+        Flow::<PeaceTestError>::new(flow_id!("test_flow_id"), item_graph)
+    };
+
+    let item_ids_disabled = |flow_param_bool: bool| {
+        let workspace = &workspace;
+        let profile = profile.clone();
+        let flow = &flow;
+        async move {
+            let mut output = NoOpOutput;
+            let cmd_ctx = CmdCtxSpsf::<TestCctCmdCtxSpsf>::builder()
+                .with_output((&mut output).into())
+                .with_workspace(workspace.into())
+                .with_profile_selection(ProfileSelection::Specified(profile))
+                .with_flow(flow.into())
+                .with_flow_param(FlowParamsKey::BoolParam, Some(flow_param_bool))
+                .with_item_params::<VecCopyItem>(
+                    VecCopyItem::ID_DEFAULT.clone(),
+                    VecA(vec![1u8]).into(),
+                )
+                .with_item_params::<MockItem<()>>(
+                    MockItem::<()>::ID_DEFAULT.clone(),
+                    MockSrc(1).into(),
+                )
+                .await?;
+            let item_ids_disabled =
+                ItemIdsDisabled::clone(&cmd_ctx.fields().resources().borrow::<ItemIdsDisabled>());
+            Ok::<_, PeaceTestError>(item_ids_disabled)
+        }
+    };
+
+    assert_eq!(
+        vec![VecCopyItem::ID_DEFAULT.clone()],
+        item_ids_disabled(false).await?.item_ids()
+    );
+    assert!(item_ids_disabled(true).await?.is_empty());
+    Ok(())
+}
+
+fn flow_vec_copy() -> Flow<PeaceTestError> {
+    let flow_id = flow_id!("test_flow_id");
+    let item_graph = {
+        let mut item_graph_builder = ItemGraphBuilder::new();
+        item_graph_builder.add_fn(VecCopyItem::default().into());
+        item_graph_builder.build()
+    };
+    Flow::<PeaceTestError>::new(flow_id, item_graph)
+}
+
+fn flow_vec_copy_and_mock(with_edge: bool) -> Result<Flow<PeaceTestError>, WouldCycle<Edge>> {
+    let item_graph = {
+        let mut item_graph_builder = ItemGraphBuilder::new();
+        let [vec_copy_id, mock_id] = item_graph_builder.add_fns([
+            VecCopyItem::default().into(),
+            MockItem::<()>::default().into(),
+        ]);
+        if with_edge {
+            item_graph_builder.add_logic_edge(vec_copy_id, mock_id)?;
+        }
+        item_graph_builder.build()
+    };
+
+    Ok(Flow::new(flow_id!("test_flow_id"), item_graph))
+}
+
 #[derive(Debug)]
 pub struct TestCctCmdCtxSpsf;
 
@@ -1372,18 +2053,83 @@ impl CmdCtxTypes for TestCctCmdCtxSpsf {
 #[enum_iterator(crate = peace::enum_iterator)]
 pub enum TestMappingFns {
     Vec1u8,
+    MockSrcFromVecCopyState,
+    StringFromVecCopyState,
+    U8FromU16,
 }
 
 impl MappingFns for TestMappingFns {
     fn id(self) -> MappingFnId {
         match self {
             Self::Vec1u8 => MappingFnId::new("Vec1u8".into()),
+            Self::MockSrcFromVecCopyState => MappingFnId::new("MockSrcFromVecCopyState".into()),
+            Self::StringFromVecCopyState => MappingFnId::new("StringFromVecCopyState".into()),
+            Self::U8FromU16 => MappingFnId::new("U8FromU16".into()),
         }
     }
 
     fn mapping_fn(self) -> Box<dyn MappingFn> {
         match self {
             Self::Vec1u8 => MappingFnImpl::from_func(|_: &u8| Some(vec![1u8])),
+            Self::MockSrcFromVecCopyState => {
+                MappingFnImpl::from_func(|_: &VecCopyState| Some(MockSrc(1)))
+            }
+            Self::StringFromVecCopyState => {
+                MappingFnImpl::from_func(|_: &VecCopyState| Some(String::from("one")))
+            }
+            Self::U8FromU16 => MappingFnImpl::from_func(|_: &u16| Some(1u8)),
         }
     }
 }
+
+/// Records the messages of `WARN` level events.
+#[derive(Clone, Debug, Default)]
+struct WarnCapture(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+impl WarnCapture {
+    fn messages(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .expect("Failed to lock warn messages.")
+            .clone()
+    }
+}
+
+impl tracing::Subscriber for WarnCapture {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        *metadata.level() == tracing::Level::WARN
+    }
+
+    fn new_span(&self, _span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        tracing::span::Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        struct MessageVisitor(Option<String>);
+
+        impl tracing::field::Visit for MessageVisitor {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = Some(format!("{value:?}"));
+                }
+            }
+        }
+
+        let mut message_visitor = MessageVisitor(None);
+        event.record(&mut message_visitor);
+        if let Some(message) = message_visitor.0 {
+            self.0
+                .lock()
+                .expect("Failed to lock warn messages.")
+                .push(message);
+        }
+    }
+
+    fn enter(&self, _span: &tracing::span::Id) {}
+
+    fn exit(&self, _span: &tracing::span::Id) {}
+}
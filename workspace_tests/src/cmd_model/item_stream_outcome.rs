@@ -96,6 +96,19 @@ fn item_ids_not_processed() {
     );
 }
 
+#[test]
+fn item_ids_interrupted() {
+    let mut item_stream_outcome = ItemStreamOutcome::finished_with(1u16, vec![item_id!("mock")]);
+    assert!(item_stream_outcome.item_ids_interrupted().is_empty());
+
+    item_stream_outcome.item_ids_interrupted = vec![item_id!("mock")];
+
+    assert_eq!(
+        &[item_id!("mock")],
+        item_stream_outcome.item_ids_interrupted()
+    );
+}
+
 #[test]
 fn default() {
     let item_stream_outcome = ItemStreamOutcome::<u16>::default();
@@ -104,4 +117,5 @@ fn default() {
     assert_eq!(StreamOutcomeState::NotStarted, item_stream_outcome.state());
     assert!(item_stream_outcome.item_ids_processed().is_empty());
     assert!(item_stream_outcome.item_ids_not_processed().is_empty());
+    assert!(item_stream_outcome.item_ids_interrupted().is_empty());
 }
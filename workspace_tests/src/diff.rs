@@ -1,4 +1,5 @@
 mod changeable;
 mod equality;
 mod maybe_eq;
+mod state_diff;
 mod tracked;
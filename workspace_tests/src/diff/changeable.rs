@@ -25,6 +25,13 @@ fn debug() {
     );
 }
 
+#[test]
+fn display() {
+    let changeable = Changeable::new(Tracked::<u8>::Unknown, Tracked::<u8>::Known(1));
+
+    assert_eq!("unknown -> 1", changeable.to_string());
+}
+
 #[test]
 fn partial_eq() {
     let changeable_0 = Changeable::new(Tracked::<u8>::Unknown, Tracked::<u8>::Known(1));
@@ -41,7 +48,7 @@ fn serialize() -> Result<(), serde_yaml::Error> {
 
     assert_eq!(
         "from: Unknown\n\
-        to: !Known 1\n\
+        to:\n  Known: 1\n\
         ",
         serde_yaml::to_string(&changeable)?
     );
@@ -0,0 +1,111 @@
+use peace::{
+    cfg::state::Generated,
+    diff::{Changeable, Equality, StateDiff, Tracked},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, StateDiff, Serialize, Deserialize)]
+pub struct ServerState {
+    name: String,
+    port: u16,
+    ip: Generated<String>,
+}
+
+fn server_state(port: u16, ip: Generated<String>) -> ServerState {
+    ServerState {
+        name: String::from("server_1"),
+        port,
+        ip,
+    }
+}
+
+#[test]
+fn new_wraps_each_field_in_changeable() {
+    let server_state_diff = ServerStateDiff::new(
+        &server_state(80, Generated::Value(String::from("10.0.0.1"))),
+        &server_state(8080, Generated::Value(String::from("10.0.0.1"))),
+    );
+
+    assert_eq!(
+        Changeable::known(String::from("server_1"), String::from("server_1")),
+        server_state_diff.name
+    );
+    assert_eq!(Changeable::known(80, 8080), server_state_diff.port);
+}
+
+#[test]
+fn is_unchanged_when_all_fields_equal() {
+    let server_state_diff = ServerStateDiff::new(
+        &server_state(80, Generated::Value(String::from("10.0.0.1"))),
+        &server_state(80, Generated::Value(String::from("10.0.0.1"))),
+    );
+
+    assert_eq!(Equality::Equal, server_state_diff.equality());
+    assert!(server_state_diff.is_unchanged());
+    assert_eq!("unchanged", server_state_diff.to_string());
+}
+
+#[test]
+fn equality_not_equal_when_any_field_not_equal() {
+    let server_state_diff = ServerStateDiff::new(
+        &server_state(80, Generated::Tbd),
+        &server_state(8080, Generated::Value(String::from("10.0.0.1"))),
+    );
+
+    assert_eq!(Equality::NotEqual, server_state_diff.equality());
+    assert!(!server_state_diff.is_unchanged());
+}
+
+#[test]
+fn equality_unknown_when_field_value_tbd() {
+    let server_state_diff = ServerStateDiff::new(
+        &server_state(80, Generated::Value(String::from("10.0.0.1"))),
+        &server_state(80, Generated::Tbd),
+    );
+
+    assert_eq!(Equality::Unknown, server_state_diff.equality());
+    assert!(!server_state_diff.is_unchanged());
+}
+
+#[test]
+fn display_lists_changed_fields() {
+    let server_state_diff = ServerStateDiff::new(
+        &server_state(80, Generated::Value(String::from("10.0.0.1"))),
+        &server_state(8080, Generated::Tbd),
+    );
+
+    assert_eq!(
+        "`port`: 80 -> 8080, `ip`: String: 10.0.0.1 -> String not yet determined",
+        server_state_diff.to_string()
+    );
+}
+
+#[test]
+fn serialize_round_trips() -> Result<(), serde_yaml::Error> {
+    let server_state_diff = ServerStateDiff {
+        name: Changeable::known(String::from("server_1"), String::from("server_1")),
+        port: Changeable::new(Tracked::None, Tracked::Known(80)),
+        ip: Changeable::new(Tracked::Unknown, Tracked::Known(Generated::Tbd)),
+    };
+
+    let serialized = serde_yaml::to_string(&server_state_diff)?;
+    let deserialized = serde_yaml::from_str::<ServerStateDiff>(&serialized)?;
+
+    // `Tracked::Unknown` is never equal to itself, so the round trip is
+    // compared through the serialized form.
+    assert_eq!(
+        "name:\n  from:\n    Known: server_1\n  to:\n    Known: server_1\n\
+        port:\n  from: None\n  to:\n    Known: 80\n\
+        ip:\n  from: Unknown\n  to:\n    Known: !Tbd null\n",
+        serialized
+    );
+    assert_eq!(serialized, serde_yaml::to_string(&deserialized)?);
+    assert!(matches!(
+        deserialized.ip,
+        Changeable {
+            from: Tracked::Unknown,
+            to: Tracked::Known(Generated::Tbd),
+        }
+    ));
+    Ok(())
+}
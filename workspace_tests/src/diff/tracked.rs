@@ -3,7 +3,10 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use peace::diff::{Equality, MaybeEq, Tracked};
+use peace::{
+    cfg::state::Generated,
+    diff::{Equality, MaybeEq, Tracked},
+};
 
 mod maybe_eq {
     use peace::diff::{Equality, MaybeEq, Tracked};
@@ -108,6 +111,28 @@ fn deserialize() -> Result<(), serde_yaml::Error> {
     Ok(())
 }
 
+#[test]
+fn serialize_known_nested_enum() -> Result<(), serde_yaml::Error> {
+    assert_eq!(
+        "Known: !Tbd null\n",
+        serde_yaml::to_string(&Tracked::<Generated<String>>::Known(Generated::Tbd))?
+    );
+    Ok(())
+}
+
+#[test]
+fn deserialize_known_from_map_or_tag() -> Result<(), serde_yaml::Error> {
+    assert!(matches!(
+        serde_yaml::from_str("Known: !Tbd")?,
+        Tracked::<Generated<String>>::Known(Generated::Tbd)
+    ));
+    assert!(matches!(
+        serde_yaml::from_str("!Known 1")?,
+        Tracked::<u8>::Known(1)
+    ));
+    Ok(())
+}
+
 #[test]
 fn hash() {
     assert_eq!(hash_code(Tracked::None), hash_code(Tracked::None));
@@ -150,6 +175,13 @@ fn debug() {
     assert_eq!("Known(Value(0))", format!("{tracked:?}"))
 }
 
+#[test]
+fn display() {
+    assert_eq!("none", Tracked::<u8>::None.to_string());
+    assert_eq!("unknown", Tracked::<u8>::Unknown.to_string());
+    assert_eq!("1", Tracked::<u8>::Known(1).to_string());
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Value(u8);
 
@@ -1,6 +1,8 @@
 use peace::{
     data::fn_graph::{daggy::Dag, Edge, WouldCycle},
-    flow_model::{flow_id, FlowInfo, FlowSpecInfo, GraphInfo, ItemInfo, ItemSpecInfo},
+    flow_model::{
+        flow_id, FlowInfo, FlowSpecInfo, FlowStateRef, GraphInfo, ItemInfo, ItemSpecInfo,
+    },
     flow_rt::{Flow, ItemGraph, ItemGraphBuilder},
     item_model::item_id,
 };
@@ -25,7 +27,8 @@ fn debug() -> Result<(), Box<dyn std::error::Error>> {
             flow_id: FlowId(\"flow_id\"), \
             graph_info: GraphInfo { \
                 graph: Dag { graph: Graph { Ty: \"Directed\", node_count: 6, edge_count: 9, edges: (0, 1), (0, 2), (1, 4), (2, 3), (3, 4), (5, 4), (1, 2), (5, 1), (0, 5), node weights: {0: ItemInfo { item_id: ItemId(\"a\") }, 1: ItemInfo { item_id: ItemId(\"b\") }, 2: ItemInfo { item_id: ItemId(\"c\") }, 3: ItemInfo { item_id: ItemId(\"d\") }, 4: ItemInfo { item_id: ItemId(\"e\") }, 5: ItemInfo { item_id: ItemId(\"f\") }}, edge weights: {0: Logic, 1: Logic, 2: Logic, 3: Logic, 4: Logic, 5: Logic, 6: Data, 7: Data, 8: Data} }, cycle_state: DfsSpace { dfs: Dfs { stack: [], discovered: FixedBitSet { data: 0x10, capacity: 0, length: 0 } } } } \
-            } \
+            }, \
+            flow_state_refs: [] \
         }",
         format!("{flow_info:?}")
     );
@@ -118,11 +121,61 @@ graph_info:
     Ok(())
 }
 
+#[test]
+fn flow_ids_dependencies_returns_distinct_flow_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_info = flow_info()?.with_flow_state_refs(vec![
+        (
+            item_id!("a"),
+            FlowStateRef::new(flow_id!("env_deploy"), item_id!("s3_bucket")),
+        ),
+        (
+            item_id!("b"),
+            FlowStateRef::new(flow_id!("app_build"), item_id!("app_file")),
+        ),
+        (
+            item_id!("c"),
+            FlowStateRef::new(flow_id!("env_deploy"), item_id!("iam_role")),
+        ),
+    ]);
+
+    assert_eq!(
+        vec![&flow_id!("env_deploy"), &flow_id!("app_build")],
+        flow_info.flow_ids_dependencies()
+    );
+    Ok(())
+}
+
+#[test]
+fn serialize_flow_state_refs() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_info = flow_info()?.with_flow_state_refs(vec![(
+        item_id!("a"),
+        FlowStateRef::new(flow_id!("env_deploy"), item_id!("s3_bucket")),
+    )]);
+
+    let serialized = serde_yaml::to_string(&flow_info)?;
+
+    assert!(
+        serialized.ends_with(
+            r#"flow_state_refs:
+- - a
+  - flow_id: env_deploy
+    item_id: s3_bucket
+"#
+        ),
+        "was {serialized}"
+    );
+    assert_eq!(flow_info, serde_yaml::from_str(&serialized)?);
+    Ok(())
+}
+
 fn flow_info() -> Result<FlowInfo, WouldCycle<Edge>> {
     let flow = Flow::new(flow_id!("flow_id"), complex_graph()?);
     let FlowSpecInfo {
         flow_id,
         graph_info,
+        params_refs: _,
+        sub_flows: _,
+        item_ids_disabled: _,
     } = flow.flow_spec_info();
 
     let mut graph = graph_info.iter_insertion_with_indices().fold(
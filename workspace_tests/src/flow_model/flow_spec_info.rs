@@ -62,6 +62,150 @@ fn to_progress_info_graph() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn to_progress_info_graph_includes_params_refs_edges() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_spec_info = flow_spec_info()?.with_params_refs(vec![
+        (item_id!("a"), item_id!("e")),
+        (item_id!("b"), item_id!("e")),
+    ]);
+
+    let info_graph = flow_spec_info.to_progress_info_graph();
+
+    let info_graph_expected = {
+        let mut node_hierarchy = NodeHierarchy::new();
+        node_hierarchy.insert(node_id!("a"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("b"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("c"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("d"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("e"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("f"), NodeHierarchy::new());
+
+        let mut edges = Edges::new();
+        edges.insert(edge_id!("a__b"), [node_id!("a"), node_id!("b")]);
+        edges.insert(edge_id!("a__c"), [node_id!("a"), node_id!("c")]);
+        edges.insert(edge_id!("b__e"), [node_id!("b"), node_id!("e")]);
+        edges.insert(edge_id!("c__d"), [node_id!("c"), node_id!("d")]);
+        edges.insert(edge_id!("d__e"), [node_id!("d"), node_id!("e")]);
+        edges.insert(edge_id!("f__e"), [node_id!("f"), node_id!("e")]);
+        edges.insert(edge_id!("a__e"), [node_id!("a"), node_id!("e")]);
+
+        let mut node_names = NodeNames::new();
+        node_names.insert(node_id!("a"), String::from("a"));
+        node_names.insert(node_id!("b"), String::from("b"));
+        node_names.insert(node_id!("c"), String::from("c"));
+        node_names.insert(node_id!("d"), String::from("d"));
+        node_names.insert(node_id!("e"), String::from("e"));
+        node_names.insert(node_id!("f"), String::from("f"));
+
+        InfoGraph::default()
+            .with_graph_style(GraphStyle::Circle)
+            .with_direction(GraphDir::Vertical)
+            .with_hierarchy(node_hierarchy)
+            .with_node_names(node_names)
+            .with_edges(edges)
+    };
+
+    assert_eq!(info_graph_expected, info_graph);
+    Ok(())
+}
+
+#[test]
+fn to_progress_info_graph_nests_sub_flow_items() -> Result<(), Box<dyn std::error::Error>> {
+    let sub_flow = {
+        let mut item_graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let [fn_id_a, fn_id_b] = item_graph_builder.add_fns([
+            BlankItem::<()>::new(item_id!("a")).into(),
+            BlankItem::<()>::new(item_id!("b")).into(),
+        ]);
+        item_graph_builder.add_logic_edge(fn_id_a, fn_id_b)?;
+        Flow::new(flow_id!("upload"), item_graph_builder.build())
+    };
+    let flow_spec_info = {
+        let mut item_graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let sub_flow_fn_ids = item_graph_builder.add_flow(&sub_flow);
+        let fn_id_c = item_graph_builder.add_fn(BlankItem::<()>::new(item_id!("c")).into());
+        item_graph_builder.add_logic_edge_from_sub_flow(&sub_flow_fn_ids, fn_id_c)?;
+        Flow::new(flow_id!("flow_id"), item_graph_builder.build()).flow_spec_info()
+    };
+
+    let info_graph = flow_spec_info.to_progress_info_graph();
+
+    let info_graph_expected = {
+        let mut node_hierarchy = NodeHierarchy::new();
+        let mut sub_flow_hierarchy = NodeHierarchy::new();
+        sub_flow_hierarchy.insert(node_id!("upload__a"), NodeHierarchy::new());
+        sub_flow_hierarchy.insert(node_id!("upload__b"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("upload__sub_flow"), sub_flow_hierarchy);
+        node_hierarchy.insert(node_id!("c"), NodeHierarchy::new());
+
+        let mut edges = Edges::new();
+        edges.insert(
+            edge_id!("upload__a__upload__b"),
+            [node_id!("upload__a"), node_id!("upload__b")],
+        );
+        edges.insert(
+            edge_id!("upload__b__c"),
+            [node_id!("upload__b"), node_id!("c")],
+        );
+
+        let mut node_names = NodeNames::new();
+        node_names.insert(node_id!("upload__a"), String::from("upload__a"));
+        node_names.insert(node_id!("upload__b"), String::from("upload__b"));
+        node_names.insert(node_id!("c"), String::from("c"));
+        node_names.insert(node_id!("upload__sub_flow"), String::from("upload"));
+
+        InfoGraph::default()
+            .with_graph_style(GraphStyle::Circle)
+            .with_direction(GraphDir::Vertical)
+            .with_hierarchy(node_hierarchy)
+            .with_node_names(node_names)
+            .with_edges(edges)
+    };
+
+    assert_eq!(info_graph_expected, info_graph);
+    Ok(())
+}
+
+#[test]
+fn to_progress_info_graph_names_disabled_items() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_spec_info = {
+        let mut item_graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let [fn_id_a, fn_id_b] = item_graph_builder.add_fns([
+            BlankItem::<()>::new(item_id!("a")).into(),
+            BlankItem::<()>::new(item_id!("b")).into(),
+        ]);
+        item_graph_builder.add_logic_edge(fn_id_a, fn_id_b)?;
+        Flow::new(flow_id!("flow_id"), item_graph_builder.build())
+            .flow_spec_info()
+            .with_item_ids_disabled(vec![item_id!("b")])
+    };
+
+    let info_graph = flow_spec_info.to_progress_info_graph();
+
+    let info_graph_expected = {
+        let mut node_hierarchy = NodeHierarchy::new();
+        node_hierarchy.insert(node_id!("a"), NodeHierarchy::new());
+        node_hierarchy.insert(node_id!("b"), NodeHierarchy::new());
+
+        let mut edges = Edges::new();
+        edges.insert(edge_id!("a__b"), [node_id!("a"), node_id!("b")]);
+
+        let mut node_names = NodeNames::new();
+        node_names.insert(node_id!("a"), String::from("a"));
+        node_names.insert(node_id!("b"), String::from("b (disabled)"));
+
+        InfoGraph::default()
+            .with_graph_style(GraphStyle::Circle)
+            .with_direction(GraphDir::Vertical)
+            .with_hierarchy(node_hierarchy)
+            .with_node_names(node_names)
+            .with_edges(edges)
+    };
+
+    assert_eq!(info_graph_expected, info_graph);
+    Ok(())
+}
+
 #[test]
 fn clone() -> Result<(), Box<dyn std::error::Error>> {
     let flow_spec_info = flow_spec_info()?;
@@ -79,7 +223,10 @@ fn debug() -> Result<(), Box<dyn std::error::Error>> {
             flow_id: FlowId(\"flow_id\"), \
             graph_info: GraphInfo { \
                 graph: Dag { graph: Graph { Ty: \"Directed\", node_count: 6, edge_count: 9, edges: (0, 1), (0, 2), (1, 4), (2, 3), (3, 4), (5, 4), (1, 2), (5, 1), (0, 5), node weights: {0: ItemSpecInfo { item_id: ItemId(\"a\") }, 1: ItemSpecInfo { item_id: ItemId(\"b\") }, 2: ItemSpecInfo { item_id: ItemId(\"c\") }, 3: ItemSpecInfo { item_id: ItemId(\"d\") }, 4: ItemSpecInfo { item_id: ItemId(\"e\") }, 5: ItemSpecInfo { item_id: ItemId(\"f\") }}, edge weights: {0: Contains, 1: Logic, 2: Logic, 3: Contains, 4: Logic, 5: Logic, 6: Data, 7: Data, 8: Data} }, cycle_state: DfsSpace { dfs: Dfs { stack: [], discovered: FixedBitSet { data: 0x10, capacity: 0, length: 0 } } } } \
-            } \
+            }, \
+            params_refs: [], \
+            sub_flows: [], \
+            item_ids_disabled: [] \
         }",
         format!("{flow_spec_info:?}")
     );
@@ -0,0 +1,188 @@
+use peace::{
+    flow_model::flow_id,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
+    params::{MappingFnId, MappingFnReg, ParamsSpec, ParamsSpecs},
+    resource_rt::{resources::ts::SetUp, Resources},
+    rt_model::{FlowIssue, FlowIssues},
+};
+
+use crate::{PeaceTestError, VecA, VecB, VecCopyItem};
+
+#[test]
+fn validate_returns_ok_when_flow_has_no_issues() -> Result<(), Box<dyn std::error::Error>> {
+    let flow = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let fn_id_a = graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_a")).into());
+        let fn_id_b = graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_b")).into());
+        graph_builder.add_logic_edge(fn_id_a, fn_id_b)?;
+        Flow::new(flow_id!("test_flow"), graph_builder.build())
+    };
+    let mut params_specs = ParamsSpecs::new();
+    params_specs.insert(
+        item_id!("vec_copy_a"),
+        ParamsSpec::<VecA>::from(VecA(vec![1])),
+    );
+    params_specs.insert(
+        item_id!("vec_copy_b"),
+        ParamsSpec::<VecA>::from(VecA(vec![2])),
+    );
+
+    flow.validate(&params_specs, &MappingFnReg::new())?;
+
+    Ok(())
+}
+
+#[test]
+fn validate_returns_all_issues() {
+    let flow = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_a")).into());
+        graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_b")).into());
+        Flow::new(flow_id!("test_flow"), graph_builder.build())
+    };
+    let mut params_specs = ParamsSpecs::new();
+    params_specs.insert(
+        item_id!("vec_copy_a"),
+        ParamsSpec::<VecA>::MappingFn {
+            field_name: None,
+            mapping_fn_id: MappingFnId::new(String::from("vec_a_from_u8")),
+        },
+    );
+    params_specs.insert(
+        item_id!("vec_copy_c"),
+        ParamsSpec::<VecA>::from(VecA(vec![2])),
+    );
+
+    let result = flow.validate(&params_specs, &MappingFnReg::new());
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(peace::rt_model::Error::FlowInvalid {
+                        flow_id,
+                        flow_issues,
+                    })
+                    if flow_id == &flow_id!("test_flow")
+                    && flow_issues.len() == 3
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+    let flow_issues = flow.flow_issues(&params_specs, &MappingFnReg::new());
+    assert_eq!(
+        FlowIssues::from_iter([
+            FlowIssue::DataWriteConcurrent {
+                item_id_a: item_id!("vec_copy_a"),
+                item_id_b: item_id!("vec_copy_b"),
+                type_name: String::from("VecB"),
+            },
+            FlowIssue::ParamsSpecNotFound {
+                item_id: item_id!("vec_copy_b"),
+                item_id_suggested: Some(item_id!("vec_copy_c")),
+            },
+            FlowIssue::MappingFnNotRegistered {
+                item_id: item_id!("vec_copy_a"),
+                mapping_fn_id: MappingFnId::new(String::from("vec_a_from_u8")),
+                mapping_fn_id_suggested: None,
+            },
+        ]),
+        flow_issues
+    );
+}
+
+#[test]
+fn flow_issues_with_resources_returns_data_not_found() {
+    let flow = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        Flow::new(flow_id!("test_flow"), graph_builder.build())
+    };
+    let mut params_specs = ParamsSpecs::new();
+    params_specs.insert(
+        VecCopyItem::ID_DEFAULT.clone(),
+        ParamsSpec::<VecA>::from(VecA(vec![1])),
+    );
+    let mapping_fn_reg = MappingFnReg::new();
+
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let flow_issues = flow.flow_issues_with_resources(&params_specs, &mapping_fn_reg, &resources);
+    assert_eq!(
+        FlowIssues::from_iter([FlowIssue::DataNotFound {
+            item_id: VecCopyItem::ID_DEFAULT.clone(),
+            type_name: String::from("VecB"),
+            mutable: true,
+        }]),
+        flow_issues
+    );
+
+    let resources = {
+        let mut resources = Resources::new();
+        resources.insert(VecB(vec![]));
+        Resources::<SetUp>::from(resources)
+    };
+    let flow_issues = flow.flow_issues_with_resources(&params_specs, &mapping_fn_reg, &resources);
+    assert!(flow_issues.is_empty(), "was {flow_issues:?}");
+}
+
+#[test]
+fn flow_issue_display_includes_suggestion() {
+    let flow_issue = FlowIssue::ParamsSpecNotFound {
+        item_id: item_id!("vec_copy"),
+        item_id_suggested: Some(item_id!("vec_cpy")),
+    };
+
+    assert_eq!(
+        "`vec_copy` has no params spec. \
+        A params spec was provided for `vec_cpy`, did you mean `vec_copy`?",
+        flow_issue.to_string()
+    );
+}
+
+#[test]
+fn with_logic_edges_adds_edges_and_retains_retired_items() -> Result<(), Box<dyn std::error::Error>>
+{
+    let flow = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_b")).into());
+        graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_a")).into());
+        graph_builder.add_retired(VecCopyItem::new(item_id!("vec_copy_retired")).into());
+        Flow::new(flow_id!("test_flow"), graph_builder.build())
+    };
+
+    let flow_with_edges =
+        flow.with_logic_edges(&[(item_id!("vec_copy_a"), item_id!("vec_copy_b"))])?;
+
+    let item_ids_ordered = flow_with_edges
+        .graph()
+        .iter()
+        .map(|item| item.id().clone())
+        .collect::<Vec<ItemId>>();
+    let index_a = item_ids_ordered
+        .iter()
+        .position(|item_id| item_id == &item_id!("vec_copy_a"));
+    let index_b = item_ids_ordered
+        .iter()
+        .position(|item_id| item_id == &item_id!("vec_copy_b"));
+    assert!(
+        index_a < index_b,
+        "Expected `vec_copy_a` to be ordered before `vec_copy_b`, but order was: \
+        {item_ids_ordered:?}"
+    );
+    assert_eq!(
+        &[item_id!("vec_copy_retired")],
+        flow_with_edges.graph().item_ids_retired()
+    );
+    let item_retired = flow_with_edges
+        .graph()
+        .iter_insertion()
+        .find(|item| item.id() == &item_id!("vec_copy_retired"))
+        .expect("Expected retired item to be in the graph.");
+    assert!(item_retired.state_goal_is_clean());
+
+    Ok(())
+}
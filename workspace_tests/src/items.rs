@@ -1,2 +1,8 @@
+mod file_download_item;
+mod file_item;
+mod sh_cmd;
+#[cfg(unix)]
+mod sh_cmd_executor;
 mod sh_cmd_item;
+mod sh_cmd_script_item;
 mod tar_x_item;
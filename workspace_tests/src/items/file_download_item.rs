@@ -0,0 +1,380 @@
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use peace::{
+    cfg::{app_name, profile, state::FetchedOpt, Item},
+    cmd_ctx::{CmdCtxSpsf, CmdCtxTypes, ProfileSelection},
+    cmd_model::CmdOutcome,
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
+    params::ItemOutputs,
+    resource_rt::states::StatesGoal,
+    rt::cmds::StatesDiscoverCmd,
+    rt_model::{InMemoryTextOutput, Workspace, WorkspaceSpec},
+};
+use peace_items::file_download::{
+    ETag, FileDownloadError, FileDownloadItem, FileDownloadOutputs, FileDownloadParams,
+    FileDownloadState, FileDownloadStateLogical, HttpAuth, HttpTls,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    task::JoinHandle,
+};
+use url::Url;
+
+/// Downloads a file from a local HTTP server.
+#[derive(Clone, Copy, Debug)]
+pub struct TestFileDownloadItem;
+
+impl TestFileDownloadItem {
+    /// ID
+    pub const ID: ItemId = item_id!("test_file_download");
+}
+
+#[tokio::test]
+async fn state_goal_request_sends_headers_and_bearer_auth() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let (addr, request_head) = http_server_spawn(Duration::ZERO).await?;
+    let params = FileDownloadParams::new(
+        Url::parse(&format!("http://{addr}/file.txt"))?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_header("x-peace-test", "header_value")
+    .with_auth(HttpAuth::bearer("bearer_token"));
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    assert_eq!("hello", state_goal_contents(cmd_outcome));
+    let request_head = request_head.await?;
+    let request_head_lines = request_head.lines().collect::<Vec<_>>();
+    assert!(
+        request_head_lines.contains(&"x-peace-test: header_value"),
+        "request head was: {request_head}"
+    );
+    assert!(
+        request_head_lines.contains(&"authorization: Bearer bearer_token"),
+        "request head was: {request_head}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_request_sends_basic_auth() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let (addr, request_head) = http_server_spawn(Duration::ZERO).await?;
+    let params = FileDownloadParams::new(
+        Url::parse(&format!("http://{addr}/file.txt"))?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_auth(HttpAuth::basic("user", Some("pass")));
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    assert_eq!("hello", state_goal_contents(cmd_outcome));
+    let request_head = request_head.await?;
+    // base64 of `user:pass`
+    assert!(
+        request_head
+            .lines()
+            .any(|line| line == "authorization: Basic dXNlcjpwYXNz"),
+        "request head was: {request_head}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_request_returns_err_when_timeout_elapses(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let (addr, _request_head) = http_server_spawn(Duration::from_secs(5)).await?;
+    let params = FileDownloadParams::new(
+        Url::parse(&format!("http://{addr}/file.txt"))?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_timeout(Duration::from_millis(100));
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    let error = item_error(cmd_outcome);
+    assert!(
+        matches!(&error, FileDownloadError::SrcGet { error, .. } if error.is_timeout()),
+        "Expected `FileDownloadError::SrcGet` timeout error, but was: {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_request_is_sent_through_proxy() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let (addr, request_head) = http_server_spawn(Duration::ZERO).await?;
+    let params = FileDownloadParams::new(
+        Url::parse("http://peace.invalid/file.txt")?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_proxy(Url::parse(&format!("http://{addr}"))?);
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    assert_eq!("hello", state_goal_contents(cmd_outcome));
+    let request_head = request_head.await?;
+    assert_eq!(
+        Some("GET http://peace.invalid/file.txt HTTP/1.1"),
+        request_head.lines().next()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_request_returns_err_when_tls_ca_cert_not_exists(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let ca_cert_path = tempdir.path().join("ca.pem");
+    let params = FileDownloadParams::new(
+        Url::parse("https://peace.invalid/file.txt")?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_tls(HttpTls::new().with_ca_cert_path(ca_cert_path.clone()));
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    let error = item_error(cmd_outcome);
+    assert!(
+        matches!(&error, FileDownloadError::TlsCaCertRead { path, .. } if path == &ca_cert_path),
+        "Expected `FileDownloadError::TlsCaCertRead`, but was: {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_request_returns_err_when_tls_client_identity_invalid(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let client_cert_path = tempdir.path().join("client.pem");
+    let client_key_path = tempdir.path().join("client.key");
+    tokio::fs::write(&client_cert_path, "not a certificate").await?;
+    tokio::fs::write(&client_key_path, "not a key").await?;
+    let params = FileDownloadParams::new(
+        Url::parse("https://peace.invalid/file.txt")?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_tls(HttpTls::new().with_client_identity(client_cert_path, client_key_path));
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    let error = item_error(cmd_outcome);
+    assert!(
+        matches!(&error, FileDownloadError::TlsClientIdentityParse(_)),
+        "Expected `FileDownloadError::TlsClientIdentityParse`, but was: {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_request_returns_err_when_header_name_invalid(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let params = FileDownloadParams::new(
+        Url::parse("http://peace.invalid/file.txt")?,
+        tempdir.path().join("file.txt"),
+    )
+    .with_header("invalid header", "value");
+
+    let cmd_outcome = state_goal_discover(tempdir.path(), params).await?;
+
+    let error = item_error(cmd_outcome);
+    assert!(
+        matches!(
+            &error,
+            FileDownloadError::RequestHeaderNameInvalid { name, .. } if name == "invalid header"
+        ),
+        "Expected `FileDownloadError::RequestHeaderNameInvalid`, but was: {error:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn http_auth_debug_masks_credentials() {
+    let basic = HttpAuth::basic("user", Some("pass"));
+    let bearer = HttpAuth::bearer("bearer_token");
+
+    assert_eq!(
+        r#"Basic { username: "user", password: Some("***") }"#,
+        format!("{basic:?}")
+    );
+    assert_eq!(r#"Bearer { token: "***" }"#, format!("{bearer:?}"));
+}
+
+#[test]
+fn outputs_publishes_path_byte_count_and_e_tag() -> Result<(), serde_yaml::Error> {
+    let state = FileDownloadState::new(
+        FileDownloadStateLogical::Length {
+            path: PathBuf::from("dest/file.bin"),
+            byte_count: 2048,
+        },
+        FetchedOpt::Value(ETag::new("abc123")),
+    );
+
+    let item_outputs = <FileDownloadItem<TestFileDownloadItem> as Item>::outputs(&state);
+
+    assert_eq!(
+        ItemOutputs::new()
+            .with_output("path", "dest/file.bin")
+            .with_output("byte_count", 2048u64)
+            .with_output("e_tag", "abc123"),
+        item_outputs
+    );
+    assert_eq!(
+        FileDownloadOutputs {
+            path: Some(PathBuf::from("dest/file.bin")),
+            byte_count: Some(2048),
+            e_tag: Some(ETag::new("abc123")),
+        },
+        item_outputs.to_typed::<FileDownloadOutputs>()?
+    );
+    Ok(())
+}
+
+#[test]
+fn outputs_publishes_nothing_when_file_not_exists() -> Result<(), serde_yaml::Error> {
+    let state = FileDownloadState::new(
+        FileDownloadStateLogical::None {
+            path: Some(PathBuf::from("dest/file.bin")),
+        },
+        FetchedOpt::Tbd,
+    );
+
+    let item_outputs = <FileDownloadItem<TestFileDownloadItem> as Item>::outputs(&state);
+
+    assert!(item_outputs.is_empty());
+    assert_eq!(
+        FileDownloadOutputs {
+            path: None,
+            byte_count: None,
+            e_tag: None,
+        },
+        item_outputs.to_typed::<FileDownloadOutputs>()?
+    );
+    Ok(())
+}
+
+/// Spawns an HTTP server that accepts one connection, and responds with
+/// `hello` after `response_delay`.
+///
+/// The returned handle resolves to the request head that was received.
+async fn http_server_spawn(
+    response_delay: Duration,
+) -> Result<(SocketAddr, JoinHandle<String>), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let request_head = tokio::spawn(async move {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .expect("Failed to accept connection.");
+        let mut request_bytes = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request_bytes.ends_with(b"\r\n\r\n") {
+            let byte_count = stream
+                .read(&mut buffer)
+                .await
+                .expect("Failed to read request.");
+            if byte_count == 0 {
+                break;
+            }
+            request_bytes.extend_from_slice(&buffer[..byte_count]);
+        }
+
+        tokio::time::sleep(response_delay).await;
+        let _ = stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello")
+            .await;
+
+        String::from_utf8(request_bytes).expect("Expected request head to be UTF-8.")
+    });
+
+    Ok((addr, request_head))
+}
+
+/// Discovers the goal state of a `FileDownloadItem` with the given params.
+async fn state_goal_discover(
+    dir: &Path,
+    params: FileDownloadParams<TestFileDownloadItem>,
+) -> Result<CmdOutcome<StatesGoal, FileDownloadError>, Box<dyn std::error::Error>> {
+    let workspace = Workspace::new(app_name!(), WorkspaceSpec::Path(dir.to_path_buf()))?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<FileDownloadError>::new();
+        graph_builder
+            .add_fn(FileDownloadItem::<TestFileDownloadItem>::new(TestFileDownloadItem::ID).into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctFileDownload>::builder()
+        .with_workspace(workspace.into())
+        .with_output(InMemoryTextOutput::new().into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<FileDownloadItem<TestFileDownloadItem>>(
+            TestFileDownloadItem::ID,
+            params.into(),
+        )
+        .await?;
+
+    let cmd_outcome = StatesDiscoverCmd::goal(&mut cmd_ctx).await?;
+
+    Ok(cmd_outcome)
+}
+
+/// Returns the downloaded contents in the goal state, panicking if discovery
+/// failed.
+fn state_goal_contents(cmd_outcome: CmdOutcome<StatesGoal, FileDownloadError>) -> String {
+    let CmdOutcome::Complete {
+        value: states_goal,
+        cmd_blocks_processed: _,
+    } = cmd_outcome
+    else {
+        panic!("Expected `StatesDiscoverCmd::goal` to complete successfully.");
+    };
+    let state_goal = states_goal
+        .get::<FileDownloadState, _>(&TestFileDownloadItem::ID)
+        .cloned()
+        .expect("Expected `FileDownloadState` to be discovered.");
+    let FileDownloadStateLogical::StringContents { contents, .. } = state_goal.0.logical else {
+        panic!("Expected `state_goal` to be `FileDownloadStateLogical::StringContents`.");
+    };
+    contents
+}
+
+/// Returns the item error from the outcome, panicking if there is none.
+fn item_error(cmd_outcome: CmdOutcome<StatesGoal, FileDownloadError>) -> FileDownloadError {
+    let CmdOutcome::ItemError { mut errors, .. } = cmd_outcome else {
+        panic!("Expected `StatesDiscoverCmd::goal` to return an item error.");
+    };
+    errors
+        .swap_remove(&TestFileDownloadItem::ID)
+        .expect("Expected error to be for `TestFileDownloadItem`.")
+}
+
+#[derive(Debug)]
+pub struct TestCctFileDownload;
+
+impl CmdCtxTypes for TestCctFileDownload {
+    type AppError = FileDownloadError;
+    type FlowParamsKey = ();
+    type MappingFns = ();
+    type Output = InMemoryTextOutput;
+    type ProfileParamsKey = ();
+    type WorkspaceParamsKey = ();
+}
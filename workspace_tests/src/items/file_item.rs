@@ -0,0 +1,434 @@
+use std::{collections::BTreeMap, path::Path};
+
+use peace::{
+    cfg::app_name,
+    cmd_ctx::{CmdCtxSpsf, CmdCtxTypes, ProfileSelection},
+    cmd_model::CmdOutcome,
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
+    profile_model::profile,
+    resource_rt::{
+        paths::StatesCurrentFile,
+        states::ts::{Current, Goal},
+    },
+    rt::cmds::{CleanCmd, DiffCmd, EnsureCmd, StatesDiscoverCmd},
+    rt_model::{InMemoryTextOutput, Workspace, WorkspaceSpec},
+};
+use peace_items::file::{
+    FileContents, FileContentsDiff, FileError, FileItem, FileParams, FileState, FileStateDiff,
+};
+use pretty_assertions::assert_eq;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FileTest;
+
+impl FileTest {
+    const ID: &'static ItemId = &item_id!("file_test");
+}
+
+#[test]
+fn clone() {
+    let _file_item = Clone::clone(&FileItem::<FileTest>::new(FileTest::ID.clone()));
+}
+
+#[tokio::test]
+async fn state_current_is_not_exists_when_file_does_not_exist(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("file.txt");
+    let params = FileParams::<FileTest>::file(dest.clone(), FileContents::text("hello\n"));
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    let CmdOutcome::Complete {
+        value: states_current,
+        cmd_blocks_processed: _,
+    } = StatesDiscoverCmd::current(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `StatesDiscoverCmd::current` to complete successfully.");
+    };
+    let state_current = states_current.get::<FileState, _>(FileTest::ID).unwrap();
+
+    assert_eq!(&FileState::NotExists { path: dest }, state_current);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_writes_text_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("sub").join("file.txt");
+    let params = FileParams::<FileTest>::file(dest.clone(), FileContents::text("hello\n"));
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+    let state_ensured = states_ensured.get::<FileState, _>(FileTest::ID).unwrap();
+
+    assert_eq!("hello\n", std::fs::read_to_string(&dest)?);
+    assert!(matches!(state_ensured, FileState::File { .. }));
+
+    // File contents are not stored, as they may contain secrets.
+    let states_current_file = StatesCurrentFile::from(cmd_ctx.fields().flow_dir());
+    let states_current_content = std::fs::read_to_string(&states_current_file)?;
+    assert!(
+        !states_current_content.contains("hello"),
+        "states_current.yaml was: {states_current_content}"
+    );
+
+    // Run again, for idempotence check
+    let CmdOutcome::Complete {
+        value: state_diffs,
+        cmd_blocks_processed: _,
+    } = DiffCmd::diff_stored(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `DiffCmd::diff_stored` to complete successfully.");
+    };
+    let state_diff = state_diffs.get::<FileStateDiff, _>(FileTest::ID).unwrap();
+    assert_eq!(&FileStateDiff::InSync { path: dest }, state_diff);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn diff_shows_unified_diff_when_text_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("file.txt");
+    std::fs::write(&dest, "a\nb\nc\n")?;
+    let params = FileParams::<FileTest>::file(dest.clone(), FileContents::text("a\nB\nc\n"));
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    let CmdOutcome::Complete {
+        value: state_diffs,
+        cmd_blocks_processed: _,
+    } = DiffCmd::diff::<Current, Goal>(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `DiffCmd::diff` to complete successfully.");
+    };
+    let state_diff = state_diffs.get::<FileStateDiff, _>(FileTest::ID).unwrap();
+
+    let FileStateDiff::Modified {
+        contents_diff: Some(FileContentsDiff::Text(unified_diff)),
+        ..
+    } = state_diff
+    else {
+        panic!("Expected `state_diff` to be `Modified` with a text diff, but was: {state_diff:?}");
+    };
+    assert!(unified_diff.contains("-b\n"), "diff was: {unified_diff}");
+    assert!(unified_diff.contains("+B\n"), "diff was: {unified_diff}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn diff_stored_shows_byte_lengths_when_text_changes() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("file.txt");
+    std::fs::write(&dest, "a\nb\nc\n")?;
+    let params = FileParams::<FileTest>::file(dest.clone(), FileContents::text("a\nBB\nc\n"));
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    let CmdOutcome::Complete {
+        value: state_diffs,
+        cmd_blocks_processed: _,
+    } = DiffCmd::diff_stored(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `DiffCmd::diff_stored` to complete successfully.");
+    };
+    let state_diff = state_diffs.get::<FileStateDiff, _>(FileTest::ID).unwrap();
+
+    // Stored states do not include the contents' text.
+    let FileStateDiff::Modified {
+        contents_diff: Some(contents_diff),
+        ..
+    } = state_diff
+    else {
+        panic!("Expected `state_diff` to be `Modified`, but was: {state_diff:?}");
+    };
+    assert_eq!(
+        &FileContentsDiff::Binary {
+            byte_len_from: 6,
+            byte_len_to: 7,
+        },
+        contents_diff
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_renders_template() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("greeting.txt");
+    let vars = BTreeMap::from([(String::from("name"), String::from("peace"))]);
+    let params = FileParams::<FileTest>::file(
+        dest.clone(),
+        FileContents::template("hello {name} {{literal}}\n", vars),
+    );
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    assert_eq!("hello peace {literal}\n", std::fs::read_to_string(&dest)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_returns_error_when_template_var_not_found(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("greeting.txt");
+    let params = FileParams::<FileTest>::file(
+        dest,
+        FileContents::template("hello {name}\n", BTreeMap::new()),
+    );
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    let CmdOutcome::ItemError { errors, .. } = StatesDiscoverCmd::goal(&mut cmd_ctx).await? else {
+        panic!("Expected `StatesDiscoverCmd::goal` to return an item error.");
+    };
+
+    assert!(
+        matches!(
+            errors.get(FileTest::ID),
+            Some(FileError::TemplateVarNotFound { var_name }) if var_name == "name"
+        ),
+        "errors were: {errors:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_goal_returns_error_when_template_invalid() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("greeting.txt");
+    let vars = BTreeMap::from([(String::from("name"), String::from("peace"))]);
+    let params = FileParams::<FileTest>::file(dest, FileContents::template("hello {name\n", vars));
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    let CmdOutcome::ItemError { errors, .. } = StatesDiscoverCmd::goal(&mut cmd_ctx).await? else {
+        panic!("Expected `StatesDiscoverCmd::goal` to return an item error.");
+    };
+
+    assert!(
+        matches!(
+            errors.get(FileTest::ID),
+            Some(FileError::TemplateInvalid { template, .. }) if template == "hello {name\n"
+        ),
+        "errors were: {errors:?}"
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_sets_mode() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("script.sh");
+    let params = FileParams::<FileTest>::file(dest.clone(), FileContents::text("#!/bin/sh\n"))
+        .with_mode(0o750);
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    let mode = std::fs::metadata(&dest)?.permissions().mode() & 0o7777;
+    assert_eq!(0o750, mode);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_creates_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("a").join("b");
+    let params = FileParams::<FileTest>::dir(dest.clone());
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(dest.is_dir());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_replaces_symlink_target() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("link");
+    std::os::unix::fs::symlink("old_target", &dest)?;
+    let params = FileParams::<FileTest>::symlink(dest.clone(), "new_target".into());
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    assert_eq!(Path::new("new_target"), std::fs::read_link(&dest)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_removes_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("file.txt");
+    let params = FileParams::<FileTest>::file(dest.clone(), FileContents::text("hello\n"));
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    assert!(dest.exists());
+
+    let CmdOutcome::Complete {
+        value: states_cleaned,
+        cmd_blocks_processed: _,
+    } = CleanCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `CleanCmd::exec` to complete successfully.");
+    };
+    let state_cleaned = states_cleaned.get::<FileState, _>(FileTest::ID).unwrap();
+
+    assert!(!dest.exists());
+    assert_eq!(&FileState::NotExists { path: dest }, state_cleaned);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_returns_error_when_dir_not_empty() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let dest = tempdir.path().join("dir");
+    let params = FileParams::<FileTest>::dir(dest.clone());
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        FlowId::new(crate::fn_name_short!())?,
+        params,
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    std::fs::write(dest.join("unmanaged.txt"), "unmanaged\n")?;
+
+    let CmdOutcome::ItemError { errors, .. } = CleanCmd::exec(&mut cmd_ctx).await? else {
+        panic!("Expected `CleanCmd::exec` to return an item error.");
+    };
+
+    assert!(
+        matches!(
+            errors.get(FileTest::ID),
+            Some(FileError::DestDirNotEmpty { dest: error_dest }) if error_dest == &dest
+        ),
+        "errors were: {errors:?}"
+    );
+    assert!(dest.join("unmanaged.txt").exists());
+
+    Ok(())
+}
+
+async fn cmd_ctx<'ctx>(
+    dir: &Path,
+    flow_id: FlowId,
+    params: FileParams<FileTest>,
+) -> Result<CmdCtxSpsf<'ctx, TestCctFile>, Box<dyn std::error::Error>> {
+    let workspace = Workspace::new(app_name!(), WorkspaceSpec::Path(dir.to_path_buf()))?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<FileError>::new();
+        graph_builder.add_fn(FileItem::<FileTest>::new(FileTest::ID.clone()).into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(flow_id, graph);
+    let cmd_ctx = CmdCtxSpsf::<TestCctFile>::builder()
+        .with_workspace(workspace.into())
+        .with_output(InMemoryTextOutput::new().into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow(flow.into())
+        .with_item_params::<FileItem<FileTest>>(FileTest::ID.clone(), params.into())
+        .await?;
+
+    Ok(cmd_ctx)
+}
+
+#[derive(Debug)]
+pub struct TestCctFile;
+
+impl CmdCtxTypes for TestCctFile {
+    type AppError = FileError;
+    type FlowParamsKey = ();
+    type MappingFns = ();
+    type Output = InMemoryTextOutput;
+    type ProfileParamsKey = ();
+    type WorkspaceParamsKey = ();
+}
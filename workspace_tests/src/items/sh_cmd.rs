@@ -0,0 +1,128 @@
+use std::{ffi::OsStr, path::Path, time::Duration};
+
+use peace_items::sh_cmd::ShCmd;
+
+#[test]
+fn std_command_from_sh_cmd_sets_current_dir_and_envs() {
+    let sh_cmd = ShCmd::new("ls")
+        .arg("-l")
+        .current_dir("/tmp")
+        .env("LANG", "C")
+        .env_remove("LS_COLORS");
+
+    let command = std::process::Command::from(&sh_cmd);
+
+    assert_eq!(OsStr::new("ls"), command.get_program());
+    assert_eq!(
+        vec![OsStr::new("-l")],
+        command.get_args().collect::<Vec<_>>()
+    );
+    assert_eq!(Some(Path::new("/tmp")), command.get_current_dir());
+    assert_eq!(
+        vec![
+            (OsStr::new("LANG"), Some(OsStr::new("C"))),
+            (OsStr::new("LS_COLORS"), None),
+        ],
+        command.get_envs().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn env_clear_removes_previously_set_envs() {
+    let sh_cmd = ShCmd::new("ls")
+        .env("LANG", "C")
+        .env_clear()
+        .env("PATH", "/bin");
+
+    assert!(sh_cmd.get_env_clear());
+    assert_eq!(
+        vec![(&String::from("PATH"), &Some(String::from("/bin")))],
+        sh_cmd.get_envs().iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn is_exit_code_success_accepts_any_code_when_exit_codes_not_set() {
+    let sh_cmd = ShCmd::new("ls");
+
+    assert!(sh_cmd.is_exit_code_success(Some(0)));
+    assert!(sh_cmd.is_exit_code_success(Some(1)));
+    assert!(!sh_cmd.is_exit_code_success(None));
+}
+
+#[test]
+fn is_exit_code_success_accepts_only_expected_codes_when_exit_codes_set() {
+    let sh_cmd = ShCmd::new("grep").exit_codes([0, 1]);
+
+    assert!(sh_cmd.is_exit_code_success(Some(0)));
+    assert!(sh_cmd.is_exit_code_success(Some(1)));
+    assert!(!sh_cmd.is_exit_code_success(Some(2)));
+    assert!(!sh_cmd.is_exit_code_success(None));
+}
+
+#[test]
+fn serialize_round_trip() -> Result<(), serde_yaml::Error> {
+    let sh_cmd = ShCmd::new("cat")
+        .current_dir("/tmp")
+        .env("LANG", "C")
+        .stdin("hello")
+        .timeout(Duration::from_secs(5))
+        .exit_codes([0]);
+
+    let serialized = serde_yaml::to_string(&sh_cmd)?;
+    let deserialized = serde_yaml::from_str::<ShCmd>(&serialized)?;
+
+    assert_eq!(sh_cmd, deserialized);
+    Ok(())
+}
+
+#[test]
+fn deserialize_defaults_fields_not_present() -> Result<(), serde_yaml::Error> {
+    let sh_cmd = ShCmd::new("ls");
+    let mut sh_cmd_value = serde_yaml::to_value(&sh_cmd)?;
+    let sh_cmd_mapping = sh_cmd_value
+        .as_mapping_mut()
+        .expect("Expected `ShCmd` to be serialized as a mapping.");
+    [
+        "current_dir",
+        "env_clear",
+        "envs",
+        "stdin",
+        "timeout",
+        "exit_codes",
+    ]
+    .into_iter()
+    .for_each(|key| {
+        sh_cmd_mapping.remove(key);
+    });
+
+    let deserialized = serde_yaml::from_value::<ShCmd>(sh_cmd_value)?;
+
+    assert_eq!(sh_cmd, deserialized);
+    Ok(())
+}
+
+#[test]
+fn debug_masks_env_values_and_stdin() {
+    let sh_cmd = ShCmd::new("login")
+        .env("PASSWORD", "hunter2")
+        .env_remove("LS_COLORS")
+        .stdin("hunter2");
+
+    let debug_str = format!("{sh_cmd:?}");
+
+    assert!(!debug_str.contains("hunter2"), "{debug_str}");
+    assert_eq!(
+        "ShCmd { \
+            program: \"login\", \
+            args: [], \
+            current_dir: None, \
+            env_clear: false, \
+            envs: {\"LS_COLORS\": None, \"PASSWORD\": Some(\"***\")}, \
+            stdin: Some(\"***\"), \
+            timeout: None, \
+            exit_codes: None \
+        }",
+        debug_str
+    );
+}
@@ -0,0 +1,422 @@
+use std::{path::Path, time::Duration};
+
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, CmdCtxTypes, ProfileSelection},
+    cmd_model::CmdOutcome,
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
+    resource_rt::states::StatesCurrent,
+    rt::cmds::{EnsureCmd, StatesDiscoverCmd},
+    rt_model::{InMemoryTextOutput, Workspace, WorkspaceSpec},
+};
+use peace_items::sh_cmd::{
+    ShCmd, ShCmdError, ShCmdItem, ShCmdParams, ShCmdState, ShCmdStateLogical, SH_CMD_LOG_DIR_NAME,
+};
+
+/// Runs a `ShCmd` to discover its current state.
+#[derive(Clone, Copy, Debug)]
+pub struct TestExecShCmdItem;
+
+pub type TestExecShCmdState = ShCmdState<TestExecShCmdItem>;
+
+impl TestExecShCmdItem {
+    /// ID
+    pub const ID: ItemId = item_id!("test_exec");
+
+    /// Returns `ShCmdParams` with the given current state and apply commands.
+    ///
+    /// The apply check always reports that the apply command needs to be run.
+    fn params(
+        state_current_sh_cmd: ShCmd,
+        apply_exec_sh_cmd: ShCmd,
+    ) -> ShCmdParams<TestExecShCmdItem> {
+        ShCmdParams::<TestExecShCmdItem>::new(
+            #[cfg(feature = "item_state_example")]
+            ShCmd::new("true"),
+            ShCmd::new("true"),
+            state_current_sh_cmd,
+            ShCmd::new("true"),
+            ShCmd::new("true"),
+            ShCmd::new("echo").arg("true"),
+            apply_exec_sh_cmd,
+        )
+    }
+}
+
+/// Discovers the current state of an item whose current state command is
+/// `sh_cmd`.
+async fn state_current_discover(
+    sh_cmd: ShCmd,
+) -> Result<CmdOutcome<StatesCurrent, ShCmdError>, Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        TestExecShCmdItem::params(sh_cmd, ShCmd::new("true")),
+    )
+    .await?;
+
+    let cmd_outcome = StatesDiscoverCmd::current(&mut cmd_ctx).await?;
+
+    Ok(cmd_outcome)
+}
+
+async fn cmd_ctx<'ctx>(
+    dir: &Path,
+    params: ShCmdParams<TestExecShCmdItem>,
+) -> Result<CmdCtxSpsf<'ctx, TestCctShCmdExec>, Box<dyn std::error::Error>> {
+    let workspace = Workspace::new(app_name!(), WorkspaceSpec::Path(dir.to_path_buf()))?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(ShCmdItem::<TestExecShCmdItem>::new(TestExecShCmdItem::ID).into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let cmd_ctx = CmdCtxSpsf::<TestCctShCmdExec>::builder()
+        .with_workspace(workspace.into())
+        .with_output(InMemoryTextOutput::new().into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow(flow.into())
+        .with_item_params::<ShCmdItem<TestExecShCmdItem>>(TestExecShCmdItem::ID, params.into())
+        .await?;
+
+    Ok(cmd_ctx)
+}
+
+/// Returns the stdout of the discovered state, panicking if discovery failed.
+fn stdout(cmd_outcome: CmdOutcome<StatesCurrent, ShCmdError>) -> String {
+    let CmdOutcome::Complete {
+        value: states_current,
+        cmd_blocks_processed: _,
+    } = cmd_outcome
+    else {
+        panic!("Expected `StatesDiscoverCmd::current` to complete successfully.");
+    };
+    let state_current = states_current
+        .get::<TestExecShCmdState, _>(&TestExecShCmdItem::ID)
+        .cloned()
+        .expect("Expected `TestExecShCmdState` to be discovered.");
+    let ShCmdStateLogical::Some { stdout, .. } = state_current.0.logical else {
+        panic!("Expected `state_current` to be `ShCmdStateLogical::Some`.");
+    };
+    stdout
+}
+
+/// Returns the item error from the outcome, panicking if there is none.
+fn item_error(cmd_outcome: CmdOutcome<StatesCurrent, ShCmdError>) -> ShCmdError {
+    let CmdOutcome::ItemError { mut errors, .. } = cmd_outcome else {
+        panic!("Expected `StatesDiscoverCmd::current` to return an item error.");
+    };
+    errors
+        .swap_remove(&TestExecShCmdItem::ID)
+        .expect("Expected error to be for `TestExecShCmdItem`.")
+}
+
+#[tokio::test]
+async fn exec_kills_command_when_timeout_elapses() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let marker_path = tempdir.path().join("marker");
+    let sh_cmd = ShCmd::new("sh")
+        .arg("-c")
+        .arg("sleep 0.5; touch marker")
+        .current_dir(tempdir.path())
+        .timeout(Duration::from_millis(100));
+
+    let cmd_outcome = state_current_discover(sh_cmd).await?;
+
+    let error = item_error(cmd_outcome);
+    assert!(
+        matches!(
+            &error,
+            ShCmdError::CmdTimeout { timeout, .. }
+            if *timeout == Duration::from_millis(100)
+        ),
+        "Expected `ShCmdError::CmdTimeout`, but was: {error:?}"
+    );
+    tokio::time::sleep(Duration::from_millis(800)).await;
+    assert!(
+        !marker_path.exists(),
+        "Expected command to be killed before creating the marker file."
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_writes_stdin_to_command() -> Result<(), Box<dyn std::error::Error>> {
+    let sh_cmd = ShCmd::new("cat").stdin("hello\nworld");
+
+    let cmd_outcome = state_current_discover(sh_cmd).await?;
+
+    assert_eq!("hello\nworld", stdout(cmd_outcome));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_runs_command_in_current_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let sh_cmd = ShCmd::new("pwd").arg("-P").current_dir(tempdir.path());
+
+    let cmd_outcome = state_current_discover(sh_cmd).await?;
+
+    assert_eq!(
+        format!("{}\n", tempdir.path().canonicalize()?.display()),
+        stdout(cmd_outcome)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_clears_inherited_env_when_env_clear() -> Result<(), Box<dyn std::error::Error>> {
+    let sh_cmd = ShCmd::new("/usr/bin/env")
+        .env_clear()
+        .env("PEACE_TEST", "1");
+
+    let cmd_outcome = state_current_discover(sh_cmd).await?;
+
+    assert_eq!("PEACE_TEST=1\n", stdout(cmd_outcome));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_err_when_exit_code_unexpected() -> Result<(), Box<dyn std::error::Error>> {
+    let sh_cmd = ShCmd::new("sh").arg("-c").arg("exit 3").exit_codes([0]);
+
+    let cmd_outcome = state_current_discover(sh_cmd).await?;
+
+    let error = item_error(cmd_outcome);
+    assert!(
+        matches!(
+            &error,
+            ShCmdError::CmdExitCodeUnexpected {
+                exit_code: Some(3),
+                ..
+            }
+        ),
+        "Expected `ShCmdError::CmdExitCodeUnexpected`, but was: {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_ok_when_exit_code_expected() -> Result<(), Box<dyn std::error::Error>> {
+    let sh_cmd = ShCmd::new("sh")
+        .arg("-c")
+        .arg("printf done; exit 3")
+        .exit_codes([0, 3]);
+
+    let cmd_outcome = state_current_discover(sh_cmd).await?;
+
+    assert_eq!("done", stdout(cmd_outcome));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_streamed_writes_output_lines_to_log_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let apply_exec_sh_cmd = ShCmd::new("sh").arg("-c").arg(
+        "printf 'line 1\\n'; \
+        printf 'error 1\\n' >&2; \
+        sleep 0.2; \
+        printf 'line 2\\npartial'",
+    );
+    let mut cmd_ctx = cmd_ctx(
+        tempdir.path(),
+        TestExecShCmdItem::params(ShCmd::new("true"), apply_exec_sh_cmd),
+    )
+    .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+
+    let state_ensured = states_ensured
+        .get::<TestExecShCmdState, _>(&TestExecShCmdItem::ID)
+        .unwrap();
+    let log_path = state_ensured
+        .0
+        .physical
+        .log_path()
+        .expect("Expected apply output log path to be recorded.");
+    let log_dir = cmd_ctx
+        .fields()
+        .flow_dir()
+        .join(SH_CMD_LOG_DIR_NAME)
+        .join(TestExecShCmdItem::ID.as_str());
+    assert_eq!(Some(log_dir.as_path()), log_path.parent());
+
+    // stdout and stderr lines may be interleaved in either order, but the
+    // partial line is written last, when stdout is closed.
+    let log_contents = std::fs::read_to_string(log_path)?;
+    let mut log_lines = log_contents.lines().collect::<Vec<_>>();
+    log_lines.sort_unstable();
+    assert_eq!(vec!["error 1", "line 1", "line 2", "partial"], log_lines);
+    assert!(
+        log_contents.ends_with("line 2\npartial"),
+        "log contents were: {log_contents:?}"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "output_progress")]
+#[tokio::test]
+async fn exec_streamed_sends_output_lines_as_progress_messages(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(ShCmdItem::<TestExecShCmdItem>::new(TestExecShCmdItem::ID).into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let apply_exec_sh_cmd = ShCmd::new("sh").arg("-c").arg(
+        "printf 'line 1\\n'; \
+        sleep 0.1; \
+        printf 'error 1\\n' >&2; \
+        sleep 0.1; \
+        printf 'partial'",
+    );
+    let mut output = ProgressMsgOutput::default();
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctShCmdProgress>::builder()
+        .with_workspace(workspace.into())
+        .with_output((&mut output).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<ShCmdItem<TestExecShCmdItem>>(
+            TestExecShCmdItem::ID,
+            TestExecShCmdItem::params(ShCmd::new("true"), apply_exec_sh_cmd).into(),
+        )
+        .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+
+    // Partial lines are sent when the stream is closed.
+    assert_eq!(
+        vec![
+            "State Current in sync",
+            "State Goal in sync",
+            "in progress",
+            "line 1",
+            "error 1",
+            "partial",
+            "done!",
+        ],
+        output.msgs
+    );
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct TestCctShCmdExec;
+
+impl CmdCtxTypes for TestCctShCmdExec {
+    type AppError = ShCmdError;
+    type FlowParamsKey = ();
+    type MappingFns = ();
+    type Output = InMemoryTextOutput;
+    type ProfileParamsKey = ();
+    type WorkspaceParamsKey = ();
+}
+
+/// An `OutputWrite` implementation that records progress messages.
+#[cfg(feature = "output_progress")]
+#[derive(Debug, Default)]
+pub struct ProgressMsgOutput {
+    /// Messages set through progress updates, in order.
+    msgs: Vec<String>,
+}
+
+#[cfg(feature = "output_progress")]
+#[peace::cfg::async_trait(?Send)]
+impl peace::rt_model::output::OutputWrite for ProgressMsgOutput {
+    type Error = peace::rt_model::Error;
+
+    async fn progress_begin(
+        &mut self,
+        _cmd_progress_tracker: &peace::rt_model::CmdProgressTracker,
+    ) {
+    }
+
+    async fn cmd_block_start(
+        &mut self,
+        _cmd_block_item_interaction_type: peace::progress_model::CmdBlockItemInteractionType,
+    ) {
+    }
+
+    async fn item_location_state(
+        &mut self,
+        _item_id: ItemId,
+        _item_location_state: peace::item_interaction_model::ItemLocationState,
+    ) {
+    }
+
+    async fn progress_update(
+        &mut self,
+        _progress_tracker: &peace::progress_model::ProgressTracker,
+        progress_update_and_id: &peace::progress_model::ProgressUpdateAndId,
+    ) {
+        if let peace::progress_model::ProgressMsgUpdate::Set(msg) =
+            &progress_update_and_id.msg_update
+        {
+            self.msgs.push(msg.clone());
+        }
+    }
+
+    async fn progress_end(&mut self, _cmd_progress_tracker: &peace::rt_model::CmdProgressTracker) {}
+
+    async fn present<P>(&mut self, _presentable: P) -> Result<(), Self::Error>
+    where
+        P: peace::fmt::Presentable,
+    {
+        Ok(())
+    }
+
+    #[cfg(not(feature = "error_reporting"))]
+    async fn write_err<E>(&mut self, _error: &E) -> Result<(), Self::Error>
+    where
+        E: std::error::Error,
+    {
+        Ok(())
+    }
+
+    #[cfg(feature = "error_reporting")]
+    async fn write_err<E>(&mut self, _error: &E) -> Result<(), Self::Error>
+    where
+        E: miette::Diagnostic,
+    {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "output_progress")]
+#[derive(Debug)]
+pub struct TestCctShCmdProgress;
+
+#[cfg(feature = "output_progress")]
+impl CmdCtxTypes for TestCctShCmdProgress {
+    type AppError = ShCmdError;
+    type FlowParamsKey = ();
+    type MappingFns = ();
+    type Output = ProgressMsgOutput;
+    type ProfileParamsKey = ();
+    type WorkspaceParamsKey = ();
+}
@@ -0,0 +1,328 @@
+use std::path::Path;
+
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, ProfileSelection},
+    cmd_model::CmdOutcome,
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
+    resource_rt::states::ts::{Current, CurrentStored},
+    rt::cmds::{CleanCmd, DiffCmd, EnsureCmd, StatesDiscoverCmd},
+    rt_model::{InMemoryTextOutput, Workspace, WorkspaceSpec},
+};
+use peace_items::sh_cmd::{
+    CmdVariant, ShCmd, ShCmdError, ShCmdItem, ShCmdScriptParams, ShCmdState, ShCmdStateDiff,
+    ShCmdStateLogical,
+};
+
+use crate::items::sh_cmd_item::TestCctShCmd;
+
+/// Creates a file using an idempotent script.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestFileCreationShCmdScriptItem;
+
+pub type TestFileCreationShCmdScriptState = ShCmdState<TestFileCreationShCmdScriptItem>;
+
+impl TestFileCreationShCmdScriptItem {
+    /// ID
+    pub const ID: ItemId = item_id!("test_file_creation_script");
+
+    /// Returns a new `TestFileCreationShCmdScriptItem`.
+    pub fn new() -> ShCmdItem<Self, ShCmdScriptParams<Self>> {
+        ShCmdItem::new_script(Self::ID)
+    }
+
+    fn params(dir: &Path) -> ShCmdScriptParams<TestFileCreationShCmdScriptItem> {
+        #[cfg(unix)]
+        let (check_sh_cmd, apply_sh_cmd, clean_sh_cmd) = (
+            Self::sh_cmd(dir, "test -f test_file"),
+            Self::sh_cmd(dir, "echo applied >> apply_count && touch test_file"),
+            Self::sh_cmd(dir, "rm test_file"),
+        );
+
+        #[cfg(windows)]
+        let (check_sh_cmd, apply_sh_cmd, clean_sh_cmd) = (
+            Self::sh_cmd(dir, "if (Test-Path test_file) { exit 0 } else { exit 1 }"),
+            Self::sh_cmd(
+                dir,
+                "Add-Content -Path apply_count -Value applied; \
+                New-Item -ItemType File -Path test_file | Out-Null",
+            ),
+            Self::sh_cmd(dir, "Remove-Item -Path test_file"),
+        );
+
+        ShCmdScriptParams::new(check_sh_cmd, apply_sh_cmd).with_clean_sh_cmd(clean_sh_cmd)
+    }
+
+    #[cfg(unix)]
+    fn sh_cmd(dir: &Path, script: &str) -> ShCmd {
+        ShCmd::new("bash").arg("-c").arg(script).current_dir(dir)
+    }
+
+    #[cfg(windows)]
+    fn sh_cmd(dir: &Path, script: &str) -> ShCmd {
+        ShCmd::new("Powershell.exe")
+            .arg("-Command")
+            .arg(script)
+            .current_dir(dir)
+    }
+}
+
+#[test]
+fn clone() {
+    let _sh_cmd_script_item = Clone::clone(&TestFileCreationShCmdScriptItem::new());
+}
+
+#[tokio::test]
+async fn state_current_and_goal_are_derived_from_check_exit_code(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let mut cmd_ctx = cmd_ctx(tempdir.path(), FlowId::new(crate::fn_name_short!())?).await?;
+
+    let CmdOutcome::Complete {
+        value: (states_current, states_goal),
+        cmd_blocks_processed: _,
+    } = StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `StatesDiscoverCmd::current_and_goal` to complete successfully.");
+    };
+    let state_current = states_current
+        .get::<TestFileCreationShCmdScriptState, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+    let state_goal = states_goal
+        .get::<TestFileCreationShCmdScriptState, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+
+    assert_eq!(ShCmdStateLogical::None, state_current.0.logical);
+    assert_eq!(Some(1), state_current.0.physical.exit_code());
+    if let ShCmdStateLogical::Some { stdout, .. } = &state_goal.0.logical {
+        assert_eq!("in_sync", stdout);
+    } else {
+        panic!("Expected `state_goal` to be `ShCmdStateLogical::Some`.");
+    }
+
+    let CmdOutcome::Complete {
+        value: state_diffs,
+        cmd_blocks_processed: _,
+    } = DiffCmd::diff_stored(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `DiffCmd::diff_stored` to complete successfully.");
+    };
+    let state_diff = state_diffs
+        .get::<ShCmdStateDiff, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+    assert_eq!("apply_required", state_diff.stdout());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn state_diff_is_in_sync_when_check_output_changes(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(TestFileCreationShCmdScriptItem::new().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let params = ShCmdScriptParams::<TestFileCreationShCmdScriptItem>::new(
+        TestFileCreationShCmdScriptItem::sh_cmd(tempdir.path(), "cat test_file"),
+        TestFileCreationShCmdScriptItem::sh_cmd(tempdir.path(), "echo a > test_file"),
+    );
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctShCmd>::builder()
+        .with_workspace(workspace.into())
+        .with_output(InMemoryTextOutput::new().into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<ShCmdItem<
+            TestFileCreationShCmdScriptItem,
+            ShCmdScriptParams<TestFileCreationShCmdScriptItem>,
+        >>(TestFileCreationShCmdScriptItem::ID, params.into())
+        .await?;
+
+    std::fs::write(tempdir.path().join("test_file"), "a")?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    std::fs::write(tempdir.path().join("test_file"), "b")?;
+
+    let CmdOutcome::Complete {
+        value: state_diffs,
+        cmd_blocks_processed: _,
+    } = DiffCmd::diff::<CurrentStored, Current>(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `DiffCmd::diff` to complete successfully.");
+    };
+    let state_diff = state_diffs
+        .get::<ShCmdStateDiff, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+    assert_eq!("in_sync", state_diff.stdout());
+
+    // The item is still in sync with its goal state.
+    let CmdOutcome::Complete {
+        value: state_diffs,
+        cmd_blocks_processed: _,
+    } = DiffCmd::diff_stored(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `DiffCmd::diff_stored` to complete successfully.");
+    };
+    let state_diff = state_diffs
+        .get::<ShCmdStateDiff, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+    assert_eq!("in_sync", state_diff.stdout());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_runs_apply_script_once() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let mut cmd_ctx = cmd_ctx(tempdir.path(), FlowId::new(crate::fn_name_short!())?).await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+    let state_ensured = states_ensured
+        .get::<TestFileCreationShCmdScriptState, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+    if let ShCmdStateLogical::Some { stdout, .. } = &state_ensured.0.logical {
+        assert_eq!("in_sync", stdout);
+    } else {
+        panic!("Expected `state_ensured` to be `ShCmdStateLogical::Some`.");
+    }
+    let log_path = state_ensured
+        .0
+        .physical
+        .log_path()
+        .expect("Expected apply script output to be logged.");
+    assert!(log_path.exists());
+
+    // Run again, for idempotence check
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(tempdir.path().join("test_file").exists());
+    let apply_count = std::fs::read_to_string(tempdir.path().join("apply_count"))?;
+    assert_eq!(1, apply_count.lines().count());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_runs_clean_script() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let mut cmd_ctx = cmd_ctx(tempdir.path(), FlowId::new(crate::fn_name_short!())?).await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    assert!(tempdir.path().join("test_file").exists());
+
+    let CmdOutcome::Complete {
+        value: states_cleaned,
+        cmd_blocks_processed: _,
+    } = CleanCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `CleanCmd::exec` to complete successfully.");
+    };
+    let state_cleaned = states_cleaned
+        .get::<TestFileCreationShCmdScriptState, _>(&TestFileCreationShCmdScriptItem::ID)
+        .unwrap();
+
+    assert!(!tempdir.path().join("test_file").exists());
+    assert_eq!(ShCmdStateLogical::None, state_cleaned.0.logical);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_returns_error_when_check_fails_after_apply(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(TestFileCreationShCmdScriptItem::new().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let params = ShCmdScriptParams::<TestFileCreationShCmdScriptItem>::new(
+        TestFileCreationShCmdScriptItem::sh_cmd(tempdir.path(), "exit 1"),
+        TestFileCreationShCmdScriptItem::sh_cmd(tempdir.path(), "exit 0"),
+    );
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctShCmd>::builder()
+        .with_workspace(workspace.into())
+        .with_output(InMemoryTextOutput::new().into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<ShCmdItem<
+            TestFileCreationShCmdScriptItem,
+            ShCmdScriptParams<TestFileCreationShCmdScriptItem>,
+        >>(TestFileCreationShCmdScriptItem::ID, params.into())
+        .await?;
+
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    let CmdOutcome::ItemError {
+        item_stream_outcome: _,
+        cmd_blocks_processed: _,
+        cmd_blocks_not_processed: _,
+        errors,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to return an item error.");
+    };
+
+    assert!(
+        matches!(
+            errors.get(&TestFileCreationShCmdScriptItem::ID),
+            Some(ShCmdError::ScriptCheckMismatch {
+                cmd_variant: CmdVariant::ScriptApply,
+                in_sync: false,
+                exit_code: Some(1),
+                ..
+            })
+        ),
+        "errors were: {errors:?}"
+    );
+
+    Ok(())
+}
+
+async fn cmd_ctx<'ctx>(
+    dir: &Path,
+    flow_id: FlowId,
+) -> Result<CmdCtxSpsf<'ctx, TestCctShCmd>, Box<dyn std::error::Error>> {
+    let workspace = Workspace::new(app_name!(), WorkspaceSpec::Path(dir.to_path_buf()))?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(TestFileCreationShCmdScriptItem::new().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(flow_id, graph);
+    let cmd_ctx = CmdCtxSpsf::<TestCctShCmd>::builder()
+        .with_workspace(workspace.into())
+        .with_output(InMemoryTextOutput::new().into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow(flow.into())
+        .with_item_params::<ShCmdItem<
+            TestFileCreationShCmdScriptItem,
+            ShCmdScriptParams<TestFileCreationShCmdScriptItem>,
+        >>(
+            TestFileCreationShCmdScriptItem::ID,
+            TestFileCreationShCmdScriptItem::params(dir).into(),
+        )
+        .await?;
+
+    Ok(cmd_ctx)
+}
@@ -671,6 +671,81 @@ async fn ensure_unpacks_tar_when_files_not_exists() -> Result<(), Box<dyn std::e
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_unpacks_directories_after_their_contents() -> Result<(), Box<dyn std::error::Error>>
+{
+    use std::os::unix::fs::PermissionsExt;
+
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let tar_bytes = {
+        // The read only `sub` directory entry is before the file within it, so
+        // `sub/e` cannot be unpacked if `sub`'s permissions are applied first.
+        let mut tar_builder = tar::Builder::new(Vec::new());
+        let mut dir_header = tar::Header::new_gnu();
+        dir_header.set_entry_type(tar::EntryType::Directory);
+        dir_header.set_mode(0o555);
+        dir_header.set_mtime(TAR_X2_MTIME);
+        dir_header.set_size(0);
+        tar_builder.append_data(&mut dir_header, "sub/", std::io::empty())?;
+        let mut file_header = tar::Header::new_gnu();
+        file_header.set_mode(0o644);
+        file_header.set_mtime(TAR_X2_MTIME);
+        file_header.set_size(1);
+        tar_builder.append_data(&mut file_header, "sub/e", &b"e"[..])?;
+        tar_builder.into_inner()?
+    };
+    let TestEnv {
+        tempdir: _tempdir,
+        workspace,
+        profile,
+        graph,
+        output,
+        tar_path,
+        dest,
+    } = test_env(&flow_id, &tar_bytes).await?;
+    let flow = Flow::new(flow_id, graph);
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctTarX>::builder()
+        .with_workspace(workspace.into())
+        .with_output(output.into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow((&flow).into())
+        .with_item_params::<TarXItem<TarXTest>>(
+            TarXTest::ID.clone(),
+            TarXParams::<TarXTest>::new(tar_path, dest.clone()).into(),
+        )
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+
+    let cmd_outcome = EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    let sub_dir = dest.join("sub");
+    let sub_mode = tokio::fs::metadata(&sub_dir).await?.permissions().mode();
+    // Allow the temporary directory to be removed.
+    tokio::fs::set_permissions(&sub_dir, std::fs::Permissions::from_mode(0o755)).await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = cmd_outcome
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully, but was: {cmd_outcome:?}");
+    };
+    let state_ensured = states_ensured
+        .get::<FileMetadatas, _>(TarXTest::ID)
+        .unwrap();
+    assert_eq!(
+        &FileMetadatas::from(vec![FileMetadata::new(
+            PathBuf::from("sub").join("e"),
+            TAR_X2_MTIME
+        )]),
+        state_ensured
+    );
+    assert_eq!(0o555, sub_mode & 0o777);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn ensure_removes_other_files_and_is_idempotent() -> Result<(), Box<dyn std::error::Error>> {
     let flow_id = FlowId::new(crate::fn_name_short!())?;
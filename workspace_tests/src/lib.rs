@@ -24,6 +24,7 @@ mod cmd_rt;
 mod data;
 mod diff;
 mod flow_model;
+mod flow_rt;
 mod fmt;
 #[cfg(feature = "item_interactions")]
 mod item_interaction_model;
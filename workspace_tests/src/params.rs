@@ -2,10 +2,17 @@ mod any_spec_rt;
 mod any_spec_rt_boxed;
 mod derive;
 mod field_name_and_type;
+mod item_outputs;
+mod items_outputs;
 mod mapping_fn_impl;
+mod params_override;
+#[cfg(feature = "params_schema")]
+mod params_schema;
 mod params_spec;
 mod params_spec_fieldless;
 mod params_specs;
+mod params_validate;
+mod template_value_kind;
 mod value_resolution_ctx;
 mod value_resolution_mode;
 mod value_spec;
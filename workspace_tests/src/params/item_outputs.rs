@@ -0,0 +1,59 @@
+use peace::params::ItemOutputs;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct BucketOutputs {
+    bucket_arn: String,
+    object_count: u32,
+}
+
+#[test]
+fn to_typed_returns_outputs_as_struct() -> Result<(), serde_yaml::Error> {
+    let item_outputs = ItemOutputs::new()
+        .with_output("bucket_arn", "arn:aws:s3:::app-bucket")
+        .with_output("object_count", 3u32);
+
+    assert_eq!(
+        BucketOutputs {
+            bucket_arn: String::from("arn:aws:s3:::app-bucket"),
+            object_count: 3,
+        },
+        item_outputs.to_typed::<BucketOutputs>()?
+    );
+    Ok(())
+}
+
+#[test]
+fn to_typed_returns_err_when_output_missing() {
+    let item_outputs = ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket");
+
+    let error = item_outputs.to_typed::<BucketOutputs>().unwrap_err();
+
+    assert!(
+        error.to_string().contains("missing field `object_count`"),
+        "error was: {error}"
+    );
+}
+
+#[test]
+fn output_get_typed_returns_value_as_type() {
+    let item_outputs = ItemOutputs::new()
+        .with_output("bucket_arn", "arn:aws:s3:::app-bucket")
+        .with_output("object_count", 3u32);
+
+    assert_eq!(
+        Some(3u32),
+        item_outputs
+            .output_get_typed::<u32>("object_count")
+            .transpose()
+            .ok()
+            .flatten()
+    );
+    assert!(matches!(
+        item_outputs.output_get_typed::<u32>("bucket_arn"),
+        Some(Err(_))
+    ));
+    assert!(item_outputs
+        .output_get_typed::<u32>("bucket_name")
+        .is_none());
+}
@@ -0,0 +1,101 @@
+use peace::{
+    item_model::item_id,
+    params::{ItemOutputs, ItemsOutputs},
+};
+use serde::Deserialize;
+
+#[test]
+fn output_get_returns_published_value() {
+    let items_outputs = std::iter::once((
+        item_id!("s3_bucket"),
+        ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket"),
+    ))
+    .collect::<ItemsOutputs>();
+
+    assert_eq!(
+        Some(&serde_yaml::Value::from("arn:aws:s3:::app-bucket")),
+        items_outputs.output_get(&item_id!("s3_bucket"), "bucket_arn")
+    );
+    assert_eq!(
+        None,
+        items_outputs.output_get(&item_id!("s3_bucket"), "bucket_name")
+    );
+}
+
+#[test]
+fn item_outputs_typed_returns_item_outputs_as_struct() -> Result<(), serde_yaml::Error> {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct BucketOutputs {
+        bucket_arn: String,
+    }
+
+    let items_outputs = std::iter::once((
+        item_id!("s3_bucket"),
+        ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket"),
+    ))
+    .collect::<ItemsOutputs>();
+
+    assert_eq!(
+        Some(BucketOutputs {
+            bucket_arn: String::from("arn:aws:s3:::app-bucket"),
+        }),
+        items_outputs
+            .item_outputs_typed::<BucketOutputs>(&item_id!("s3_bucket"))
+            .transpose()?
+    );
+    assert_eq!(
+        None,
+        items_outputs
+            .item_outputs_typed::<BucketOutputs>(&item_id!("s3_object"))
+            .transpose()?
+    );
+    Ok(())
+}
+
+#[test]
+fn item_outputs_set_removes_entry_when_outputs_empty() {
+    let mut items_outputs = ItemsOutputs::new();
+    items_outputs.item_outputs_set(
+        item_id!("s3_bucket"),
+        ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket"),
+    );
+    assert!(items_outputs.contains_key(&item_id!("s3_bucket")));
+
+    items_outputs.item_outputs_set(item_id!("s3_bucket"), ItemOutputs::new());
+
+    assert!(items_outputs.is_empty());
+}
+
+#[test]
+fn to_env_string_writes_quoted_env_vars() {
+    let items_outputs = std::iter::once((
+        item_id!("s3_bucket"),
+        ItemOutputs::new()
+            .with_output("bucket_arn", "arn:aws:s3:::app-bucket")
+            .with_output("object-count", 3u32)
+            .with_output("note", "say \"hi\" to $USER"),
+    ))
+    .collect::<ItemsOutputs>();
+
+    assert_eq!(
+        "S3_BUCKET_BUCKET_ARN=\"arn:aws:s3:::app-bucket\"\n\
+        S3_BUCKET_OBJECT_COUNT=\"3\"\n\
+        S3_BUCKET_NOTE=\"say \\\"hi\\\" to \\$USER\"\n",
+        items_outputs.to_env_string()
+    );
+}
+
+#[test]
+fn serialize_json() -> Result<(), serde_json::Error> {
+    let items_outputs = std::iter::once((
+        item_id!("s3_bucket"),
+        ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket"),
+    ))
+    .collect::<ItemsOutputs>();
+
+    assert_eq!(
+        r#"{"s3_bucket":{"bucket_arn":"arn:aws:s3:::app-bucket"}}"#,
+        serde_json::to_string(&items_outputs)?
+    );
+    Ok(())
+}
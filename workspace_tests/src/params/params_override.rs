@@ -0,0 +1,277 @@
+use std::str::FromStr;
+
+use peace::{
+    item_model::item_id,
+    params::{
+        AnySpecRt, FieldValueSetError, Params, ParamsOverride, ParamsOverrideParseError,
+        ParamsSpec, ParamsSpecFieldless, ValueSpec,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::vec_copy_item::{VecA, VecAFieldWise};
+
+#[derive(Clone, Debug, Params, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerParams {
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn from_str_parses_item_id_field_name_and_value() {
+    let params_override = ParamsOverride::from_str("server.port=8080").unwrap();
+
+    assert_eq!(
+        ParamsOverride::new(
+            item_id!("server"),
+            String::from("port"),
+            String::from("8080")
+        ),
+        params_override
+    );
+}
+
+#[test]
+fn from_str_keeps_equals_signs_in_value() {
+    let params_override = ParamsOverride::from_str("server.name=a=b").unwrap();
+
+    assert_eq!("a=b", params_override.value);
+}
+
+#[test]
+fn from_str_returns_err_when_value_missing() {
+    let result = ParamsOverride::from_str("server.port");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(ParamsOverrideParseError::ValueMissing { params_override })
+                    if params_override == "server.port"
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn from_str_returns_err_when_field_name_missing() {
+    let result = ParamsOverride::from_str("server=8080");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(ParamsOverrideParseError::FieldNameMissing { params_override })
+                    if params_override == "server=8080"
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn from_str_returns_err_when_item_id_invalid() {
+    let result = ParamsOverride::from_str("1server.port=8080");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(ParamsOverrideParseError::ItemIdInvalid { params_override, .. })
+                    if params_override == "1server.port=8080"
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn display_round_trips_through_from_str() {
+    let params_override = ParamsOverride::new(
+        item_id!("server"),
+        String::from("port"),
+        String::from("8080"),
+    );
+
+    assert_eq!("server.port=8080", params_override.to_string());
+    assert_eq!(
+        params_override,
+        ParamsOverride::from_str(&params_override.to_string()).unwrap()
+    );
+}
+
+#[test]
+fn field_value_set_on_field_wise_sets_field_value() {
+    let mut params_spec = ServerParams::field_wise_spec()
+        .with_name(String::from("server_1"))
+        .with_port(80)
+        .build();
+
+    params_spec.field_value_set("port", "8080").unwrap();
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &params_spec,
+                    ParamsSpec::FieldWise {
+                        field_wise_spec: ServerParamsFieldWise {
+                            name: ValueSpec::Value { value: name },
+                            port: ValueSpec::Value { value: 8080 },
+                        }
+                    }
+                    if name == "server_1"
+                ),
+                "was {params_spec:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn field_value_set_on_tuple_field_wise_uses_field_index() {
+    let mut params_spec = ParamsSpec::<VecA>::FieldWise {
+        field_wise_spec: VecAFieldWise(ValueSpec::Stored),
+    };
+
+    params_spec.field_value_set("0", "[1, 2, 3]").unwrap();
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &params_spec,
+                    ParamsSpec::FieldWise {
+                        field_wise_spec: VecAFieldWise(ValueSpec::Value { value }),
+                    }
+                    if value == &[1u8, 2, 3]
+                ),
+                "was {params_spec:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn field_value_set_on_value_sets_field_value() {
+    let mut params_spec = ParamsSpec::Value {
+        value: ServerParams {
+            name: String::from("server_1"),
+            port: 80,
+        },
+    };
+
+    params_spec.field_value_set("name", "server_2").unwrap();
+    params_spec.field_value_set("port", "8080").unwrap();
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &params_spec,
+                    ParamsSpec::Value {
+                        value: ServerParams { name, port: 8080 },
+                    }
+                    if name == "server_2"
+                ),
+                "was {params_spec:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn field_value_set_returns_err_when_field_not_found() {
+    let mut params_spec = ServerParams::field_wise_spec()
+        .with_name(String::from("server_1"))
+        .with_port(80)
+        .build();
+
+    let result = params_spec.field_value_set("prot", "8080");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(FieldValueSetError::FieldNotFound { field_name, field_names })
+                    if field_name == "prot"
+                    && field_names == &[String::from("name"), String::from("port")]
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn field_value_set_returns_err_when_value_invalid() {
+    let mut params_spec = ServerParams::field_wise_spec()
+        .with_name(String::from("server_1"))
+        .with_port(80)
+        .build();
+
+    let result = params_spec.field_value_set("port", "not_a_port");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(FieldValueSetError::ValueDeserialize { field_name, value, .. })
+                    if field_name == "port"
+                    && value == "not_a_port"
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+}
+
+#[test]
+fn field_value_set_returns_err_when_spec_is_in_memory_or_fieldless() {
+    let mut params_spec = ParamsSpec::<ServerParams>::InMemory;
+    let result = params_spec.field_value_set("port", "8080");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &result,
+                    Err(FieldValueSetError::FieldsNotSettable { spec_variant, .. })
+                    if *spec_variant == "InMemory"
+                ),
+                "was {result:?}"
+            );
+        }
+    })();
+
+    let mut params_spec = ParamsSpecFieldless::<u16>::Value { value: 80 };
+    let result = params_spec.field_value_set("0", "8080");
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(&result, Err(FieldValueSetError::FieldsNotSettable { .. })),
+                "was {result:?}"
+            );
+        }
+    })();
+}
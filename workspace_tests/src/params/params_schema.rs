@@ -0,0 +1,86 @@
+use peace::{
+    flow_model::flow_id,
+    flow_rt::{Flow, ItemGraphBuilder},
+    params::{
+        schemars::{self, JsonSchema},
+        Params, ParamsSpec,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{PeaceTestError, VecCopyItem};
+
+#[derive(Clone, Debug, Params, JsonSchema, Serialize, Deserialize)]
+#[params(schema)]
+#[schemars(crate = "peace::params::schemars")]
+pub struct ServerParams {
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn params_spec_schema_includes_all_variants() -> Result<(), Box<dyn std::error::Error>> {
+    let schema = schemars::schema_for!(ParamsSpec<ServerParams>);
+    let schema_json = serde_json::to_string(&schema)?;
+
+    ["Stored", "Value", "InMemory", "MappingFn", "FieldWise"]
+        .iter()
+        .for_each(|variant| {
+            assert!(
+                schema_json.contains(&format!("\"{variant}\"")),
+                "Expected `{variant}` in schema: {schema_json}"
+            )
+        });
+    assert!(schema_json.contains("\"mapping_fn_id\""));
+    assert!(schema_json.contains("\"ServerParamsFieldWise\""));
+
+    Ok(())
+}
+
+#[test]
+fn params_spec_schema_references_params_spec_definition() {
+    let mut generator = schemars::SchemaGenerator::default();
+
+    let schema = ServerParams::params_spec_schema(&mut generator);
+
+    let definition_name = schema
+        .get("$ref")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|schema_ref| schema_ref.strip_prefix("#/$defs/"))
+        .expect("Expected `params_spec_schema` to reference a definition.");
+    assert!(definition_name.starts_with("ParamsSpec"));
+    assert!(generator.definitions().contains_key(definition_name));
+}
+
+#[test]
+fn params_spec_schema_defaults_to_any_value() {
+    let mut generator = schemars::SchemaGenerator::default();
+
+    let schema = crate::VecA::params_spec_schema(&mut generator);
+
+    assert_eq!(Some(true), schema.as_bool());
+}
+
+#[test]
+fn flow_params_specs_schema_is_keyed_by_item_id() {
+    let flow = Flow::new(flow_id!("test_flow"), {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    });
+
+    let schema = flow.params_specs_schema();
+    let item_id = VecCopyItem::ID_DEFAULT.to_string();
+
+    assert_eq!(Some("object"), schema.get("type").and_then(|v| v.as_str()));
+    assert_eq!(
+        Some(&serde_json::Value::Bool(true)),
+        schema
+            .get("properties")
+            .and_then(|properties| properties.get(item_id.as_str()))
+    );
+    assert_eq!(
+        Some(&serde_json::Value::Bool(false)),
+        schema.get("additionalProperties")
+    );
+}
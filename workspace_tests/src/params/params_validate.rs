@@ -0,0 +1,299 @@
+use peace::params::regex;
+use peace::{
+    item_model::item_id,
+    params::{
+        Params, ParamsResolveError, ParamsValidators, ValueResolutionCtx, ValueResolutionMode,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Params, Serialize, Deserialize)]
+pub struct ServerParams {
+    #[validate(non_empty, regex = "^[a-z][a-z0-9_]*$")]
+    name: String,
+    #[validate(range(min = 1024, max = 49151))]
+    port: u16,
+    #[validate(custom = tags_unique)]
+    tags: Vec<String>,
+    /// Not validated.
+    description: String,
+}
+
+#[derive(Clone, Debug, Params, Serialize, Deserialize)]
+pub enum EndpointParams {
+    Local,
+    Remote(#[validate(non_empty)] String, u16),
+    Named {
+        #[validate(range(max = 10))]
+        retries: u8,
+    },
+}
+
+fn tags_unique(tags: &[String]) -> Result<(), String> {
+    let mut tags_sorted = tags.to_vec();
+    tags_sorted.sort();
+    tags_sorted.dedup();
+    if tags_sorted.len() == tags.len() {
+        Ok(())
+    } else {
+        Err(String::from("tags must be unique"))
+    }
+}
+
+fn server_params() -> ServerParams {
+    ServerParams {
+        name: String::from("server_1"),
+        port: 8080,
+        tags: vec![String::from("a"), String::from("b")],
+        description: String::new(),
+    }
+}
+
+fn value_resolution_ctx_new<P>() -> ValueResolutionCtx {
+    ValueResolutionCtx::new(
+        ValueResolutionMode::Goal,
+        item_id!("item_id"),
+        tynm::type_name::<P>(),
+    )
+}
+
+#[test]
+fn validate_returns_ok_when_all_fields_valid() {
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    server_params()
+        .validate(&mut value_resolution_ctx)
+        .expect("Expected params to be valid.");
+    assert!(value_resolution_ctx.resolution_chain().is_empty());
+}
+
+#[test]
+fn validate_returns_err_when_non_empty_violated() {
+    let params = ServerParams {
+        name: String::new(),
+        ..server_params()
+    };
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    let result = params.validate(&mut value_resolution_ctx);
+
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { value_resolution_ctx, reason })
+            if value_resolution_ctx.item_id() == &item_id!("item_id")
+            && value_resolution_ctx.resolution_chain()[0].field_name() == "name"
+            && value_resolution_ctx.resolution_chain()[0].type_name() == "String"
+            && reason == "value must not be empty"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn validate_returns_err_when_regex_violated() {
+    let params = ServerParams {
+        name: String::from("Server 1"),
+        ..server_params()
+    };
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    let result = params.validate(&mut value_resolution_ctx);
+
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { reason, .. })
+            if reason == "`Server 1` does not match `^[a-z][a-z0-9_]*$`"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn validate_returns_err_when_range_violated() {
+    let params = ServerParams {
+        port: 80,
+        ..server_params()
+    };
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    let result = params.validate(&mut value_resolution_ctx);
+
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { value_resolution_ctx, reason })
+            if value_resolution_ctx.resolution_chain()[0].field_name() == "port"
+            && reason == "`80` is less than `1024`"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn validate_returns_err_when_custom_violated() {
+    let params = ServerParams {
+        tags: vec![String::from("a"), String::from("a")],
+        ..server_params()
+    };
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    let result = params.validate(&mut value_resolution_ctx);
+
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { reason, .. })
+            if reason == "tags must be unique"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn validate_enum_checks_active_variant_fields() {
+    let mut value_resolution_ctx = value_resolution_ctx_new::<EndpointParams>();
+
+    assert!(EndpointParams::Local
+        .validate(&mut value_resolution_ctx)
+        .is_ok());
+    assert!(EndpointParams::Remote(String::from("host"), 0)
+        .validate(&mut value_resolution_ctx)
+        .is_ok());
+
+    let result = EndpointParams::Remote(String::new(), 0).validate(&mut value_resolution_ctx);
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { value_resolution_ctx, .. })
+            if value_resolution_ctx.resolution_chain()[0].field_name() == "0"
+        ),
+        "result was: {result:?}"
+    );
+
+    let mut value_resolution_ctx = value_resolution_ctx_new::<EndpointParams>();
+    let result = EndpointParams::Named { retries: 11 }.validate(&mut value_resolution_ctx);
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { reason, .. })
+            if reason == "`11` is greater than `10`"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn display_names_item_and_field() {
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+    let error = ServerParams {
+        port: 80,
+        ..server_params()
+    }
+    .validate(&mut value_resolution_ctx)
+    .unwrap_err();
+
+    assert_eq!(
+        "Invalid value for `port` in `item_id`: `80` is less than `1024`\n\
+        \n\
+        ```rust\n\
+        ServerParams {\n    port: u16,\n    ..\n}\n\
+        ```",
+        error.to_string()
+    );
+}
+
+#[test]
+fn validators_range_allows_bounds() {
+    assert!(ParamsValidators::range(&1u8, Some(1), Some(2)).is_ok());
+    assert!(ParamsValidators::range(&2u8, Some(1), Some(2)).is_ok());
+    assert!(ParamsValidators::range(&3u8, None, None).is_ok());
+}
+
+#[test]
+fn validators_regex_returns_err_when_value_does_not_match() {
+    let regex = regex::Regex::new("^[a-z]+$").expect("Expected pattern to be valid.");
+
+    assert!(ParamsValidators::regex("abc", &regex).is_ok());
+    assert_eq!(
+        Err(String::from("`ABC` does not match `^[a-z]+$`")),
+        ParamsValidators::regex("ABC", &regex)
+    );
+}
+
+#[test]
+fn validate_partial_validates_fields_with_values() {
+    let params_partial = ServerParamsPartial {
+        name: Some(String::new()),
+        port: None,
+        tags: None,
+        description: None,
+    };
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    let result = ServerParams::validate_partial(&params_partial, &mut value_resolution_ctx);
+
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { value_resolution_ctx, reason })
+            if value_resolution_ctx.resolution_chain()[0].field_name() == "name"
+            && reason == "value must not be empty"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn validate_partial_skips_fields_without_values() {
+    let params_partial = ServerParamsPartial {
+        name: None,
+        port: Some(8080),
+        tags: None,
+        description: None,
+    };
+    let mut value_resolution_ctx = value_resolution_ctx_new::<ServerParams>();
+
+    ServerParams::validate_partial(&params_partial, &mut value_resolution_ctx)
+        .expect("Expected resolved fields to be valid.");
+    assert!(value_resolution_ctx.resolution_chain().is_empty());
+
+    let params_partial = ServerParamsPartial {
+        port: Some(80),
+        ..params_partial
+    };
+    let result = ServerParams::validate_partial(&params_partial, &mut value_resolution_ctx);
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { reason, .. })
+            if reason == "`80` is less than `1024`"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn validate_partial_enum_checks_active_variant_fields() {
+    let mut value_resolution_ctx = value_resolution_ctx_new::<EndpointParams>();
+
+    assert!(EndpointParams::validate_partial(
+        &EndpointParamsPartial::Remote(None, Some(0)),
+        &mut value_resolution_ctx
+    )
+    .is_ok());
+
+    let result = EndpointParams::validate_partial(
+        &EndpointParamsPartial::Named { retries: Some(11) },
+        &mut value_resolution_ctx,
+    );
+    assert!(
+        matches!(
+            &result,
+            Err(ParamsResolveError::Validate { reason, .. })
+            if reason == "`11` is greater than `10`"
+        ),
+        "result was: {result:?}"
+    );
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use peace::params::TemplateValueKind;
+
+use crate::{mock_item::MockSrc, vec_copy_item::VecA};
+
+#[test]
+fn of_returns_kind_of_serialized_value() {
+    assert_eq!(TemplateValueKind::Text, TemplateValueKind::of::<String>());
+    assert_eq!(TemplateValueKind::Text, TemplateValueKind::of::<PathBuf>());
+    assert_eq!(TemplateValueKind::Number, TemplateValueKind::of::<u16>());
+    assert_eq!(TemplateValueKind::Number, TemplateValueKind::of::<f64>());
+    assert_eq!(TemplateValueKind::Bool, TemplateValueKind::of::<bool>());
+    assert_eq!(
+        TemplateValueKind::Structured,
+        TemplateValueKind::of::<Vec<u8>>()
+    );
+    assert_eq!(
+        TemplateValueKind::Unknown,
+        TemplateValueKind::of::<serde_yaml::Value>()
+    );
+}
+
+#[test]
+fn of_looks_through_options_and_newtypes() {
+    assert_eq!(
+        TemplateValueKind::Text,
+        TemplateValueKind::of::<Option<String>>()
+    );
+    assert_eq!(
+        TemplateValueKind::Number,
+        TemplateValueKind::of::<MockSrc>()
+    );
+    assert_eq!(
+        TemplateValueKind::Structured,
+        TemplateValueKind::of::<VecA>()
+    );
+}
+
+#[test]
+fn is_interpolatable_into() {
+    assert!(TemplateValueKind::Number.is_interpolatable_into(TemplateValueKind::Text));
+    assert!(TemplateValueKind::Bool.is_interpolatable_into(TemplateValueKind::Text));
+    assert!(TemplateValueKind::Number.is_interpolatable_into(TemplateValueKind::Number));
+    assert!(TemplateValueKind::Text.is_interpolatable_into(TemplateValueKind::Structured));
+    assert!(TemplateValueKind::Unknown.is_interpolatable_into(TemplateValueKind::Bool));
+    assert!(!TemplateValueKind::Text.is_interpolatable_into(TemplateValueKind::Number));
+    assert!(!TemplateValueKind::Bool.is_interpolatable_into(TemplateValueKind::Number));
+    assert!(!TemplateValueKind::Structured.is_interpolatable_into(TemplateValueKind::Text));
+}
+
+#[test]
+fn display() {
+    assert_eq!("text", TemplateValueKind::Text.to_string());
+    assert_eq!("structured", TemplateValueKind::Structured.to_string());
+}
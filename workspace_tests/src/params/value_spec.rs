@@ -1,12 +1,13 @@
 use peace::{
     enum_iterator::Sequence,
+    flow_model::{flow_id, FlowStateRef},
     item_model::item_id,
     params::{
-        AnySpecRt, AnySpecRtBoxed, FromFunc, MappingFn, MappingFnId, MappingFnImpl, MappingFnReg,
-        MappingFns, ParamsResolveError, ValueResolutionCtx, ValueResolutionMode, ValueSpec,
-        ValueSpecRt,
+        AnySpecRt, AnySpecRtBoxed, FlowsStatesCurrentStored, FromFunc, ItemOutputs, ItemsOutputs,
+        MappingFn, MappingFnId, MappingFnImpl, MappingFnReg, MappingFns, ParamsResolveError,
+        ValueResolutionCtx, ValueResolutionMode, ValueSpec, ValueSpecRt,
     },
-    resource_rt::{resources::ts::SetUp, Resources},
+    resource_rt::{paths::WorkspaceDir, resources::ts::SetUp, Resources},
 };
 use serde::{Deserialize, Serialize};
 
@@ -180,6 +181,19 @@ fn is_usable_returns_true_for_value_and_in_memory() {
     assert!(ValueSpec::<u8>::InMemory.is_usable());
 }
 
+#[test]
+fn is_usable_returns_true_for_env_and_file() {
+    assert!(ValueSpec::<u8>::Env {
+        name: String::from("PEACE_U8"),
+        default: None,
+    }
+    .is_usable());
+    assert!(ValueSpec::<u8>::File {
+        path: std::path::PathBuf::from("u8.txt"),
+    }
+    .is_usable());
+}
+
 #[test]
 fn is_usable_returns_true_when_mapping_fn_is_some() {
     assert!(ValueSpec::<u8>::mapping_fn(None, TestMappingFns::U8NoneFromU8).is_usable());
@@ -653,6 +667,817 @@ fn try_resolve_mapping_fn_returns_err_when_mutably_borrowed() -> Result<(), Para
     Ok(())
 }
 
+#[test]
+fn serialize_env() -> Result<(), serde_yaml::Error> {
+    let u16_spec = ValueSpec::<u16>::Env {
+        name: String::from("PEACE_PORT"),
+        default: Some(8080),
+    };
+    assert_eq!(
+        r#"!Env
+name: PEACE_PORT
+default: 8080
+"#,
+        serde_yaml::to_string(&u16_spec)?,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn deserialize_file_key() -> Result<(), serde_yaml::Error> {
+    let deserialized = serde_yaml::from_str(
+        r#"!FileKey
+path: config.yaml
+key_path: server.port
+"#,
+    )?;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &deserialized,
+                    ValueSpec::<u16>::FileKey { path, key_path }
+                    if path == std::path::Path::new("config.yaml")
+                    && key_path == "server.port"
+                ),
+                "was {deserialized:?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[test]
+fn resolve_env() -> Result<(), ParamsResolveError> {
+    std::env::set_var("PEACE_TEST_RESOLVE_ENV", "8080");
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_env"),
+        tynm::type_name::<u16>(),
+    );
+    let u16_spec = ValueSpec::<u16>::Env {
+        name: String::from("PEACE_TEST_RESOLVE_ENV"),
+        default: None,
+    };
+
+    let value = ValueSpecRt::resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!(8080, value);
+    Ok(())
+}
+
+#[test]
+fn resolve_env_uses_default_when_not_present() -> Result<(), ParamsResolveError> {
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_env_uses_default_when_not_present"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::Env {
+        name: String::from("PEACE_TEST_RESOLVE_ENV_NOT_PRESENT"),
+        default: Some(String::from("default")),
+    };
+
+    let value = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!("default", value);
+    Ok(())
+}
+
+#[test]
+fn resolve_env_returns_err_when_not_present() {
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_env_returns_err_when_not_present"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::Env {
+        name: String::from("PEACE_TEST_RESOLVE_ENV_NOT_PRESENT"),
+        default: None,
+    };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::EnvVarNotPresent { env_var_name, .. })
+            if env_var_name == "PEACE_TEST_RESOLVE_ENV_NOT_PRESENT"
+        ),
+        "was {resolve_result:?}"
+    );
+    assert!(
+        matches!(try_resolve_result, Ok(None)),
+        "was {try_resolve_result:?}"
+    );
+}
+
+#[test]
+fn resolve_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("token");
+    std::fs::write(&path, "a: b")?;
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::File { path };
+
+    let value = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!("a: b", value);
+    Ok(())
+}
+
+#[test]
+fn resolve_file_trims_trailing_newline() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path_lf = tempdir.path().join("token_lf");
+    let path_crlf = tempdir.path().join("token_crlf");
+    std::fs::write(&path_lf, "secret\n\n")?;
+    std::fs::write(&path_crlf, "secret\r\n")?;
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file_trims_trailing_newline"),
+        tynm::type_name::<String>(),
+    );
+
+    let value_lf = ValueSpecRt::resolve(
+        &ValueSpec::<String>::File { path: path_lf },
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+    let value_crlf = ValueSpecRt::resolve(
+        &ValueSpec::<String>::File { path: path_crlf },
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    // Only one trailing newline is removed.
+    assert_eq!("secret\n", value_lf);
+    assert_eq!("secret", value_crlf);
+    Ok(())
+}
+
+#[test]
+fn resolve_file_relative_path_uses_workspace_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    std::fs::create_dir(tempdir.path().join("config"))?;
+    std::fs::write(tempdir.path().join("config/token"), "secret")?;
+    std::fs::write(tempdir.path().join("config/app.yaml"), "port: 8080\n")?;
+    let mapping_fn_reg = MappingFnReg::new();
+    let mut resources = Resources::new();
+    resources.insert(WorkspaceDir::from(tempdir.path().to_path_buf()));
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file_relative_path_uses_workspace_dir"),
+        tynm::type_name::<String>(),
+    );
+
+    let token = ValueSpecRt::resolve(
+        &ValueSpec::<String>::File {
+            path: std::path::PathBuf::from("config/token"),
+        },
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+    let port = ValueSpecRt::resolve(
+        &ValueSpec::<u16>::FileKey {
+            path: std::path::PathBuf::from("config/app.yaml"),
+            key_path: String::from("port"),
+        },
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!("secret", token);
+    assert_eq!(8080, port);
+    Ok(())
+}
+
+#[test]
+fn resolve_file_returns_err_when_not_exists() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("not_exists");
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file_returns_err_when_not_exists"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::File { path: path.clone() };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::FileRead { path: error_path, .. })
+            if error_path == &path
+        ),
+        "was {resolve_result:?}"
+    );
+    assert!(
+        matches!(try_resolve_result, Ok(None)),
+        "was {try_resolve_result:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn resolve_file_key() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("config.json");
+    std::fs::write(&path, r#"{ "servers": [{ "port": 8080 }] }"#)?;
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file_key"),
+        tynm::type_name::<u16>(),
+    );
+    let u16_spec = ValueSpec::<u16>::FileKey {
+        path,
+        key_path: String::from("servers.0.port"),
+    };
+
+    let value = ValueSpecRt::resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!(8080, value);
+    Ok(())
+}
+
+#[test]
+fn resolve_file_key_returns_err_when_key_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("config.yaml");
+    std::fs::write(&path, "server:\n  port: 8080\n")?;
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file_key_returns_err_when_key_not_found"),
+        tynm::type_name::<u16>(),
+    );
+    let u16_spec = ValueSpec::<u16>::FileKey {
+        path,
+        key_path: String::from("server.host"),
+    };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::FileKeyNotFound { key_path, .. })
+            if key_path == "server.host"
+        ),
+        "was {resolve_result:?}"
+    );
+    assert!(
+        matches!(try_resolve_result, Ok(None)),
+        "was {try_resolve_result:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn resolve_file_key_returns_err_when_value_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("config.yaml");
+    std::fs::write(&path, "server:\n  port: not_a_number\n")?;
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_file_key_returns_err_when_value_invalid"),
+        tynm::type_name::<u16>(),
+    );
+    let u16_spec = ValueSpec::<u16>::FileKey {
+        path,
+        key_path: String::from("server.port"),
+    };
+
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &try_resolve_result,
+            Err(ParamsResolveError::FileKeyDeserialize { key_path, .. })
+            if key_path == "server.port"
+        ),
+        "was {try_resolve_result:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn deserialize_flow_state() -> Result<(), serde_yaml::Error> {
+    let deserialized = serde_yaml::from_str(
+        r#"!FlowState
+flow_id: env_deploy
+item_id: s3_bucket
+key_path: name
+"#,
+    )?;
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &deserialized,
+                    ValueSpec::<String>::FlowState { flow_id, item_id, key_path }
+                    if flow_id == &flow_id!("env_deploy")
+                    && item_id == &item_id!("s3_bucket")
+                    && key_path == "name"
+                ),
+                "was {deserialized:?}"
+            );
+        }
+    })();
+
+    Ok(())
+}
+
+#[test]
+fn flow_state_refs_returns_flow_state_ref_for_flow_state() {
+    let string_spec = ValueSpec::<String>::FlowState {
+        flow_id: flow_id!("env_deploy"),
+        item_id: item_id!("s3_bucket"),
+        key_path: String::from("name"),
+    };
+
+    assert_eq!(
+        vec![FlowStateRef::new(
+            flow_id!("env_deploy"),
+            item_id!("s3_bucket")
+        )],
+        string_spec.flow_state_refs()
+    );
+    assert!(ValueSpec::<String>::InMemory.flow_state_refs().is_empty());
+}
+
+#[test]
+fn resolve_flow_state() -> Result<(), Box<dyn std::error::Error>> {
+    let mapping_fn_reg = MappingFnReg::new();
+    let mut flows_states_current_stored = FlowsStatesCurrentStored::new();
+    flows_states_current_stored.insert(
+        flow_id!("env_deploy"),
+        std::iter::once((
+            item_id!("s3_bucket"),
+            serde_yaml::from_str("name: app-bucket\nobjects: [a, b]\n")?,
+        ))
+        .collect(),
+    );
+    let mut resources = Resources::new();
+    resources.insert(flows_states_current_stored);
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_flow_state"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::FlowState {
+        flow_id: flow_id!("env_deploy"),
+        item_id: item_id!("s3_bucket"),
+        key_path: String::from("objects.1"),
+    };
+
+    let value = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!("b", value);
+    Ok(())
+}
+
+#[test]
+fn resolve_flow_state_returns_err_when_flow_not_applied() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mapping_fn_reg = MappingFnReg::new();
+    let mut resources = Resources::new();
+    resources.insert(FlowsStatesCurrentStored::new());
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_flow_state_returns_err_when_flow_not_applied"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::FlowState {
+        flow_id: flow_id!("env_deploy"),
+        item_id: item_id!("s3_bucket"),
+        key_path: String::from("name"),
+    };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::FlowStatesNotFound { flow_id, item_id, .. })
+            if flow_id == &flow_id!("env_deploy")
+            && item_id == &item_id!("s3_bucket")
+        ),
+        "was {resolve_result:?}"
+    );
+    assert!(
+        matches!(try_resolve_result, Ok(None)),
+        "was {try_resolve_result:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn resolve_flow_state_returns_err_when_key_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let mapping_fn_reg = MappingFnReg::new();
+    let mut flows_states_current_stored = FlowsStatesCurrentStored::new();
+    flows_states_current_stored.insert(
+        flow_id!("env_deploy"),
+        std::iter::once((
+            item_id!("s3_bucket"),
+            serde_yaml::from_str("name: app-bucket\n")?,
+        ))
+        .collect(),
+    );
+    let mut resources = Resources::new();
+    resources.insert(flows_states_current_stored);
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_flow_state_returns_err_when_key_not_found"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::FlowState {
+        flow_id: flow_id!("env_deploy"),
+        item_id: item_id!("s3_bucket"),
+        key_path: String::from("region"),
+    };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::FlowStateKeyNotFound { key_path, .. })
+            if key_path == "region"
+        ),
+        "was {resolve_result:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn item_output_refs_returns_item_id_for_item_output() {
+    let string_spec = ValueSpec::<String>::ItemOutput {
+        item_id: item_id!("s3_bucket"),
+        output_name: String::from("bucket_arn"),
+    };
+
+    assert_eq!(vec![item_id!("s3_bucket")], string_spec.item_output_refs());
+    assert!(ValueSpec::<String>::InMemory.item_output_refs().is_empty());
+}
+
+#[test]
+fn resolve_item_output() -> Result<(), ParamsResolveError> {
+    let mapping_fn_reg = MappingFnReg::new();
+    let items_outputs = std::iter::once((
+        item_id!("s3_bucket"),
+        ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket"),
+    ))
+    .collect::<ItemsOutputs>();
+    let mut resources = Resources::new();
+    resources.insert(items_outputs);
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_item_output"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::ItemOutput {
+        item_id: item_id!("s3_bucket"),
+        output_name: String::from("bucket_arn"),
+    };
+
+    let value = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!("arn:aws:s3:::app-bucket", value);
+    Ok(())
+}
+
+#[test]
+fn resolve_item_output_returns_err_when_output_not_published() {
+    let mapping_fn_reg = MappingFnReg::new();
+    let mut resources = Resources::new();
+    resources.insert(ItemsOutputs::new());
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_item_output_returns_err_when_output_not_published"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::ItemOutput {
+        item_id: item_id!("s3_bucket"),
+        output_name: String::from("bucket_arn"),
+    };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::ItemOutputNotFound { item_id, output_name, .. })
+            if item_id == &item_id!("s3_bucket")
+            && output_name == "bucket_arn"
+        ),
+        "was {resolve_result:?}"
+    );
+    assert!(
+        matches!(try_resolve_result, Ok(None)),
+        "was {try_resolve_result:?}"
+    );
+}
+
+#[test]
+fn resolve_item_output_returns_err_when_output_deserialize_fails() {
+    let mapping_fn_reg = MappingFnReg::new();
+    let items_outputs = std::iter::once((
+        item_id!("s3_bucket"),
+        ItemOutputs::new().with_output("bucket_arn", "arn:aws:s3:::app-bucket"),
+    ))
+    .collect::<ItemsOutputs>();
+    let mut resources = Resources::new();
+    resources.insert(items_outputs);
+    let resources = Resources::<SetUp>::from(resources);
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_item_output_returns_err_when_output_deserialize_fails"),
+        tynm::type_name::<u16>(),
+    );
+    let u16_spec = ValueSpec::<u16>::ItemOutput {
+        item_id: item_id!("s3_bucket"),
+        output_name: String::from("bucket_arn"),
+    };
+
+    let try_resolve_result = ValueSpecRt::try_resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &try_resolve_result,
+            Err(ParamsResolveError::ItemOutputDeserialize { output_name, .. })
+            if output_name == "bucket_arn"
+        ),
+        "was {try_resolve_result:?}"
+    );
+}
+
+#[test]
+fn resolve_template() -> Result<(), ParamsResolveError> {
+    let mut mapping_fn_reg = MappingFnReg::new();
+    mapping_fn_reg.register_all::<TestMappingFns>();
+    let resources = {
+        let mut resources = Resources::new();
+        resources.insert(1u8);
+        Resources::<SetUp>::from(resources)
+    };
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_template"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::Template {
+        template: String::from("s3://bucket/{MockSrcFromU8}/{{app}}.tar"),
+    };
+
+    let value = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!("s3://bucket/1/{app}.tar", value);
+    Ok(())
+}
+
+#[test]
+fn resolve_template_parses_non_string_value() -> Result<(), ParamsResolveError> {
+    let mut mapping_fn_reg = MappingFnReg::new();
+    mapping_fn_reg.register_all::<TestMappingFns>();
+    let resources = {
+        let mut resources = Resources::new();
+        resources.insert(1u8);
+        Resources::<SetUp>::from(resources)
+    };
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_template_parses_non_string_value"),
+        tynm::type_name::<u16>(),
+    );
+    let u16_spec = ValueSpec::<u16>::Template {
+        template: String::from("{MockSrcFromU8}0"),
+    };
+
+    let value = ValueSpecRt::resolve(
+        &u16_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!(10, value);
+    Ok(())
+}
+
+#[test]
+fn resolve_template_returns_err_when_template_invalid() {
+    let mapping_fn_reg = MappingFnReg::new();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("resolve_template_returns_err_when_template_invalid"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::Template {
+        template: String::from("s3://{MockSrcFromU8"),
+    };
+
+    let resolve_result = ValueSpecRt::resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    );
+
+    assert!(
+        matches!(
+            &resolve_result,
+            Err(ParamsResolveError::TemplateInvalid { template, .. })
+            if template == "s3://{MockSrcFromU8"
+        ),
+        "was {resolve_result:?}"
+    );
+}
+
+#[test]
+fn try_resolve_template_returns_none_when_value_not_found() -> Result<(), ParamsResolveError> {
+    let mut mapping_fn_reg = MappingFnReg::new();
+    mapping_fn_reg.register_all::<TestMappingFns>();
+    let resources = Resources::<SetUp>::from(Resources::new());
+    let mut value_resolution_ctx = ValueResolutionCtx::new(
+        ValueResolutionMode::Current,
+        item_id!("try_resolve_template_returns_none_when_value_not_found"),
+        tynm::type_name::<String>(),
+    );
+    let string_spec = ValueSpec::<String>::Template {
+        template: String::from("s3://bucket/{MockSrcFromU8}"),
+    };
+
+    let value = ValueSpecRt::try_resolve(
+        &string_spec,
+        &mapping_fn_reg,
+        &resources,
+        &mut value_resolution_ctx,
+    )?;
+
+    assert_eq!(None, value);
+    Ok(())
+}
+
+#[test]
+fn mapping_fn_ids_returns_template_placeholders() {
+    let string_spec = ValueSpec::<String>::Template {
+        template: String::from("{MockSrcFromU8}/{{literal}}/{U8NoneFromU8}/{MockSrcFromU8}"),
+    };
+
+    assert_eq!(
+        vec![
+            TestMappingFns::MockSrcFromU8.id(),
+            TestMappingFns::U8NoneFromU8.id()
+        ],
+        string_spec.mapping_fn_ids()
+    );
+}
+
 #[test]
 fn merge_stored_with_other_uses_other() {
     let mut value_spec_a = ValueSpec::<MockSrc>::Stored;
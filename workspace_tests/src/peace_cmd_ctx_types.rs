@@ -1,6 +1,8 @@
 pub use self::{
     test_cct_fn_tracker_output::TestCctFnTrackerOutput, test_cct_no_op_output::TestCctNoOpOutput,
+    test_cct_profile_cmd::TestCctProfileCmd,
 };
 
 mod test_cct_fn_tracker_output;
 mod test_cct_no_op_output;
+mod test_cct_profile_cmd;
@@ -0,0 +1,18 @@
+use peace::cmd_ctx::CmdCtxTypes;
+
+use crate::{
+    cmd_ctx::{ProfileParamsKey, WorkspaceParamsKey},
+    NoOpOutput, PeaceTestError,
+};
+
+#[derive(Debug)]
+pub struct TestCctProfileCmd;
+
+impl CmdCtxTypes for TestCctProfileCmd {
+    type AppError = PeaceTestError;
+    type FlowParamsKey = ();
+    type MappingFns = ();
+    type Output = NoOpOutput;
+    type ProfileParamsKey = ProfileParamsKey;
+    type WorkspaceParamsKey = WorkspaceParamsKey;
+}
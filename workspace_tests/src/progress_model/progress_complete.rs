@@ -30,10 +30,12 @@ fn serialize() {
 fn is_successful() {
     assert!(ProgressComplete::Success.is_successful());
     assert!(!ProgressComplete::Fail.is_successful());
+    assert!(!ProgressComplete::Interrupted.is_successful());
 }
 
 #[test]
 fn is_failure() {
     assert!(!ProgressComplete::Success.is_failure());
     assert!(ProgressComplete::Fail.is_failure());
+    assert!(!ProgressComplete::Interrupted.is_failure());
 }
@@ -2,6 +2,17 @@ mod apply_stored_state_sync;
 mod clean_cmd;
 mod diff_cmd;
 mod ensure_cmd;
+mod flow_check_cmd;
+mod outputs_show_cmd;
+mod params_diff_cmd;
+mod profile_copy_cmd;
+mod profile_create_cmd;
+mod profile_delete_cmd;
+mod profile_export_cmd;
+mod profile_import_cmd;
+mod profile_list_cmd;
+mod profile_rename_cmd;
+mod profile_switch_cmd;
 mod states_current_read_cmd;
 mod states_current_stored_display_cmd;
 mod states_discover_cmd;
@@ -4,17 +4,21 @@ use peace::{
         interruptible::{InterruptSignal, InterruptStrategy, Interruptibility},
         CmdCtxSpsf, ProfileSelection,
     },
-    cmd_model::{CmdBlockDesc, CmdOutcome},
+    cmd_model::{CmdBlockDesc, CmdExecutionId, CmdOutcome},
     flow_model::FlowId,
-    flow_rt::{Flow, ItemGraphBuilder},
+    flow_rt::{Flow, ItemFanOut, ItemFanOutFnIds, ItemGraphBuilder, ItemsRetired},
+    item_model::{item_id, ItemId},
     resource_rt::{
-        paths::{StatesCurrentFile, StatesGoalFile},
+        paths::{CmdCheckpointFile, StatesCurrentFile, StatesGoalFile},
         type_reg::untagged::BoxDataTypeDowncast,
     },
     rt::cmds::{ApplyStoredStateSync, EnsureCmd, StatesCurrentReadCmd, StatesDiscoverCmd},
     rt_model::{
-        ApplyCmdError, Error as PeaceRtError, StateStoredAndDiscovered, Workspace, WorkspaceSpec,
+        ApplyCmdError, CmdCheckpoint, CmdCheckpointSerializer, Error as PeaceRtError, ItemBoxed,
+        ItemEnableCondition, ItemIdsDisabled, ItemIdsOrphaned, ItemIdsResumed,
+        StateStoredAndDiscovered, Storage, Workspace, WorkspaceSpec,
     },
+    state_rt::StatesSerializer,
 };
 use tokio::sync::mpsc;
 
@@ -1894,6 +1898,280 @@ mock: 1
     Ok(())
 }
 
+#[tokio::test]
+async fn exec_resume_skips_items_completed_before_interrupt(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let vec_copy_id = graph_builder.add_fn(VecCopyItem::default().into());
+        let mock_id = graph_builder.add_fn(MockItem::<()>::default().into());
+        graph_builder.add_logic_edge(vec_copy_id, mock_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let (interrupt_tx, interrupt_rx) = mpsc::channel::<InterruptSignal>(16);
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_interruptibility(Interruptibility::new(
+            interrupt_rx.into(),
+            InterruptStrategy::PollNextN(9),
+        ))
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .with_item_params::<MockItem<()>>(MockItem::<()>::ID_DEFAULT.clone(), MockSrc(1).into())
+        .await?;
+
+    let flow_dir = cmd_ctx.fields().flow_dir();
+    let states_current_file = StatesCurrentFile::from(flow_dir);
+    tokio::fs::write(&states_current_file, b"vec_copy: []\nmock: 0\n").await?;
+    let states_goal_file = StatesGoalFile::from(flow_dir);
+    tokio::fs::write(
+        &states_goal_file,
+        b"vec_copy: [0, 1, 2, 3, 4, 5, 6, 7]\nmock: 1\n",
+    )
+    .await?;
+    let cmd_checkpoint_file = CmdCheckpointFile::from(flow_dir);
+
+    // Interrupt between `vec_copy` and `mock` items.
+    interrupt_tx.send(InterruptSignal).await?;
+    let cmd_outcome = EnsureCmd::exec(&mut cmd_ctx).await?;
+    assert!(
+        cmd_outcome.is_interrupted(),
+        "Expected `EnsureCmd::exec` to be interrupted, but was: {cmd_outcome:?}"
+    );
+    let cmd_checkpoint =
+        CmdCheckpointSerializer::<PeaceTestError>::deserialize_opt(&Storage, &cmd_checkpoint_file)
+            .await?
+            .expect("Expected `cmd_checkpoint.yaml` to exist after interruption.");
+    assert_eq!("EnsureCmd", cmd_checkpoint.cmd_name());
+    assert_eq!(
+        vec![VecCopyItem::ID_DEFAULT],
+        cmd_checkpoint.item_ids_completed().collect::<Vec<_>>()
+    );
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .with_item_params::<MockItem<()>>(MockItem::<()>::ID_DEFAULT.clone(), MockSrc(1).into())
+        .await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec_resume(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec_resume` to complete successfully.");
+    };
+
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItem::ID_DEFAULT)
+    );
+    assert_eq!(
+        Some(MockState(1)).as_ref(),
+        states_ensured.get::<MockState, _>(MockItem::<()>::ID_DEFAULT)
+    );
+    assert!(!cmd_ctx.fields().resources().contains::<ItemIdsResumed>());
+    assert!(!cmd_ctx.fields().resources().contains::<CmdCheckpoint>());
+    assert!(!tokio::fs::try_exists(&cmd_checkpoint_file).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_resume_returns_error_when_checkpoint_stale() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+
+    // The stored state is neither the state before nor after the item was
+    // applied in the checkpoint.
+    let flow_dir = cmd_ctx.fields().flow_dir();
+    let states_current_file = StatesCurrentFile::from(flow_dir);
+    tokio::fs::write(&states_current_file, b"vec_copy: [9]\n").await?;
+    let cmd_checkpoint_file = CmdCheckpointFile::from(flow_dir);
+    let mut cmd_checkpoint = CmdCheckpoint::new(CmdExecutionId::new(1), "EnsureCmd".to_string());
+    cmd_checkpoint.item_completed_insert(
+        VecCopyItem::ID_DEFAULT.clone(),
+        Some(serde_yaml::from_str("[]")?),
+        serde_yaml::from_str("[0, 1, 2, 3, 4, 5, 6, 7]")?,
+    );
+    CmdCheckpointSerializer::<PeaceTestError>::serialize(
+        &Storage,
+        &cmd_checkpoint,
+        &cmd_checkpoint_file,
+    )
+    .await?;
+
+    let error = EnsureCmd::exec_resume(&mut cmd_ctx).await.unwrap_err();
+
+    ({
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        || {
+            assert!(
+                matches!(
+                    &error,
+                    PeaceTestError::PeaceRt(PeaceRtError::CmdCheckpointStale { item_ids })
+                    if item_ids == &[VecCopyItem::ID_DEFAULT.clone()]
+                ),
+                "was {error:?}"
+            );
+        }
+    })();
+    assert!(tokio::fs::try_exists(&cmd_checkpoint_file).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_resume_does_not_write_states_until_applied() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let vec_copy_id = graph_builder.add_fn(VecCopyItem::default().into());
+        let mock_id = graph_builder.add_fn(MockItem::<()>::default().into());
+        graph_builder.add_logic_edge(vec_copy_id, mock_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let (interrupt_tx, interrupt_rx) = mpsc::channel::<InterruptSignal>(16);
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_interruptibility(Interruptibility::new(
+            interrupt_rx.into(),
+            InterruptStrategy::FinishCurrent,
+        ))
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .with_item_params::<MockItem<()>>(MockItem::<()>::ID_DEFAULT.clone(), MockSrc(1).into())
+        .await?;
+
+    // `vec_copy` was applied by the interrupted execution, but states were not
+    // stored before it exited.
+    let flow_dir = cmd_ctx.fields().flow_dir();
+    let states_current_content = "vec_copy: []\nmock: 0\n";
+    let states_current_file = StatesCurrentFile::from(flow_dir);
+    tokio::fs::write(&states_current_file, states_current_content.as_bytes()).await?;
+    let states_goal_content = "vec_copy: [0, 1, 2, 3, 4, 5, 6, 7]\nmock: 1\n";
+    let states_goal_file = StatesGoalFile::from(flow_dir);
+    tokio::fs::write(&states_goal_file, states_goal_content.as_bytes()).await?;
+    let cmd_checkpoint_file = CmdCheckpointFile::from(flow_dir);
+    let mut cmd_checkpoint = CmdCheckpoint::new(CmdExecutionId::new(1), "EnsureCmd".to_string());
+    cmd_checkpoint.item_completed_insert(
+        VecCopyItem::ID_DEFAULT.clone(),
+        Some(serde_yaml::from_str("[]")?),
+        serde_yaml::from_str("[0, 1, 2, 3, 4, 5, 6, 7]")?,
+    );
+    CmdCheckpointSerializer::<PeaceTestError>::serialize(
+        &Storage,
+        &cmd_checkpoint,
+        &cmd_checkpoint_file,
+    )
+    .await?;
+
+    // Interrupt before any item is applied.
+    interrupt_tx.send(InterruptSignal).await?;
+    let cmd_outcome = EnsureCmd::exec_resume(&mut cmd_ctx).await?;
+    assert!(
+        cmd_outcome.is_interrupted(),
+        "Expected `EnsureCmd::exec_resume` to be interrupted, but was: {cmd_outcome:?}"
+    );
+    assert_eq!(
+        states_current_content,
+        tokio::fs::read_to_string(&states_current_file).await?
+    );
+    assert_eq!(
+        states_goal_content,
+        tokio::fs::read_to_string(&states_goal_file).await?
+    );
+    assert!(tokio::fs::try_exists(&cmd_checkpoint_file).await?);
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .with_item_params::<MockItem<()>>(MockItem::<()>::ID_DEFAULT.clone(), MockSrc(1).into())
+        .await?;
+    let CmdOutcome::Complete { .. } = EnsureCmd::exec_resume(&mut cmd_ctx).await? else {
+        panic!("Expected `EnsureCmd::exec_resume` to complete successfully.");
+    };
+
+    let CmdOutcome::Complete {
+        value: states_current_stored,
+        cmd_blocks_processed: _,
+    } = StatesCurrentReadCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `StatesCurrentReadCmd::exec` to complete successfully.");
+    };
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_current_stored.get::<VecCopyState, _>(VecCopyItem::ID_DEFAULT)
+    );
+    assert_eq!(
+        Some(MockState(1)).as_ref(),
+        states_current_stored.get::<MockState, _>(MockItem::<()>::ID_DEFAULT)
+    );
+    assert!(!tokio::fs::try_exists(&cmd_checkpoint_file).await?);
+
+    Ok(())
+}
+
 #[test]
 fn debug() {
     let debug_str = format!("{:?}", EnsureCmd::<TestCctNoOpOutput>::default());
@@ -1902,3 +2180,412 @@ fn debug() {
         debug_str,
     );
 }
+
+#[tokio::test]
+async fn exec_skips_disabled_item_and_retains_stored_state(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let output = &mut NoOpOutput;
+
+    // Ensure the item while it is enabled.
+    let flow = flow_vec_copy_with_enable_condition(flow_id.clone(), true);
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    // Ensure with different params while the item is disabled.
+    let flow = flow_vec_copy_with_enable_condition(flow_id, false);
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![1, 2]).into())
+        .await?;
+    let item_ids_disabled =
+        ItemIdsDisabled::clone(&cmd_ctx.fields().resources().borrow::<ItemIdsDisabled>());
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+
+    assert_eq!(
+        ItemIdsDisabled::new(
+            vec![VecCopyItem::ID_DEFAULT.clone()],
+            vec![VecCopyItem::ID_DEFAULT.clone()],
+            false
+        ),
+        item_ids_disabled
+    );
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItem::ID_DEFAULT)
+    );
+    assert_eq!(
+        Some(VecB(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        cmd_ctx
+            .fields()
+            .resources()
+            .try_borrow::<VecB>()
+            .ok()
+            .as_deref()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_cleans_disabled_item_when_items_disabled_clean(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let output = &mut NoOpOutput;
+
+    // Ensure the item while it is enabled.
+    let flow = flow_vec_copy_with_enable_condition(flow_id.clone(), true);
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    // Ensure while the item is disabled, cleaning it up.
+    let flow = flow_vec_copy_with_enable_condition(flow_id, false);
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_items_disabled_clean(true)
+        .await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItem::ID_DEFAULT)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_skips_retired_item_and_retains_stored_state() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let output = &mut NoOpOutput;
+
+    // Ensure the item while it is part of the flow.
+    let flow = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        Flow::new(flow_id.clone(), graph_builder.build())
+    };
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    let states_current_file = StatesCurrentFile::from(cmd_ctx.fields().flow_dir());
+
+    // Ensure after the item is removed from the flow, and registered as retired.
+    let flow = flow_vec_copy_retired(flow_id, &states_current_file).await?;
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .await?;
+    let item_ids_orphaned =
+        ItemIdsOrphaned::clone(&cmd_ctx.fields().resources().borrow::<ItemIdsOrphaned>());
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+
+    assert_eq!(
+        ItemIdsOrphaned::new(
+            vec![VecCopyItem::ID_DEFAULT.clone()],
+            vec![VecCopyItem::ID_DEFAULT.clone()],
+        ),
+        item_ids_orphaned
+    );
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItem::ID_DEFAULT)
+    );
+    let states_current_serialized = tokio::fs::read_to_string(&*states_current_file).await?;
+    assert!(states_current_serialized.contains("vec_copy"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_orphans_clean_cleans_retired_item() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let output = &mut NoOpOutput;
+
+    // Ensure the item while it is part of the flow.
+    let flow = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        Flow::new(flow_id.clone(), graph_builder.build())
+    };
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    let states_current_file = StatesCurrentFile::from(cmd_ctx.fields().flow_dir());
+
+    // Ensure after the item is removed from the flow, cleaning it up.
+    let flow = flow_vec_copy_retired(flow_id, &states_current_file).await?;
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec_with_orphans_clean(&mut cmd_ctx, ApplyStoredStateSync::Both).await?
+    else {
+        panic!("Expected `EnsureCmd::exec_with_orphans_clean` to complete successfully.");
+    };
+
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItem::ID_DEFAULT)
+    );
+    assert!(!cmd_ctx
+        .fields()
+        .resources()
+        .borrow::<ItemIdsOrphaned>()
+        .clean());
+    let states_current_serialized = tokio::fs::read_to_string(&*states_current_file).await?;
+    assert!(!states_current_serialized.contains("vec_copy"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_cleans_removed_fan_out_element_and_does_not_store_it(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let output = &mut NoOpOutput;
+    let item_fan_out = ItemFanOut::new(item_id!("vec_copy"), VecCopyItem::new);
+    let item_id_web_app = item_fan_out.item_id("web_app")?;
+
+    // Ensure the item for the added element.
+    let (flow, item_fan_out_fn_ids) =
+        flow_vec_copy_fan_out(&item_fan_out, flow_id.clone(), &["web_app"], &[])?;
+    assert_eq!(1, item_fan_out_fn_ids.fn_ids.len());
+    assert!(item_fan_out_fn_ids.fn_ids_removed.is_empty());
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            item_id_web_app.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .with_item_params::<MockItem<()>>(MockItem::<()>::ID_DEFAULT.clone(), MockSrc(1).into())
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    let states_current_file = StatesCurrentFile::from(cmd_ctx.fields().flow_dir());
+    let CmdOutcome::Complete {
+        value: states_current_stored,
+        cmd_blocks_processed: _,
+    } = StatesCurrentReadCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `StatesCurrentReadCmd::exec` to complete successfully.");
+    };
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_current_stored.get::<VecCopyState, _>(&item_id_web_app)
+    );
+
+    // Ensure after the element is removed, cleaning up its item.
+    let item_ids_stored = StatesSerializer::<PeaceTestError>::deserialize_item_ids_opt(
+        &Storage,
+        &states_current_file,
+    )
+    .await?
+    .unwrap_or_default();
+    let (flow, item_fan_out_fn_ids) =
+        flow_vec_copy_fan_out(&item_fan_out, flow_id.clone(), &[], &item_ids_stored)?;
+    assert!(item_fan_out_fn_ids.fn_ids.is_empty());
+    assert_eq!(1, item_fan_out_fn_ids.fn_ids_removed.len());
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .await?;
+    let CmdOutcome::Complete {
+        value: states_ensured,
+        cmd_blocks_processed: _,
+    } = EnsureCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_ensured.get::<VecCopyState, _>(&item_id_web_app)
+    );
+    let CmdOutcome::Complete {
+        value: states_current_stored,
+        cmd_blocks_processed: _,
+    } = StatesCurrentReadCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `StatesCurrentReadCmd::exec` to complete successfully.");
+    };
+    assert!(!states_current_stored.contains_key(&item_id_web_app));
+
+    // Ensure again, which does not add the removed element's item back.
+    let item_ids_stored = StatesSerializer::<PeaceTestError>::deserialize_item_ids_opt(
+        &Storage,
+        &states_current_file,
+    )
+    .await?
+    .unwrap_or_default();
+    let (flow, item_fan_out_fn_ids) =
+        flow_vec_copy_fan_out(&item_fan_out, flow_id, &[], &item_ids_stored)?;
+    assert!(item_fan_out_fn_ids.fn_ids.is_empty());
+    assert!(item_fan_out_fn_ids.fn_ids_removed.is_empty());
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+    let CmdOutcome::Complete {
+        value: states_current_stored,
+        cmd_blocks_processed: _,
+    } = StatesCurrentReadCmd::exec(&mut cmd_ctx).await?
+    else {
+        panic!("Expected `StatesCurrentReadCmd::exec` to complete successfully.");
+    };
+    assert!(!states_current_stored.contains_key(&item_id_web_app));
+
+    Ok(())
+}
+
+async fn flow_vec_copy_retired(
+    flow_id: FlowId,
+    states_current_file: &StatesCurrentFile,
+) -> Result<Flow<PeaceTestError>, PeaceTestError> {
+    let item_ids_stored =
+        StatesSerializer::<PeaceTestError>::deserialize_item_ids_opt(&Storage, states_current_file)
+            .await?
+            .unwrap_or_default();
+    let items_retired = ItemsRetired::<PeaceTestError>::new().with_item(VecCopyItem::default());
+
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        items_retired.add_to(&mut graph_builder, &item_ids_stored);
+        graph_builder.build()
+    };
+    Ok(Flow::new(flow_id, graph))
+}
+
+fn flow_vec_copy_fan_out<F>(
+    item_fan_out: &ItemFanOut<VecCopyItem, F>,
+    flow_id: FlowId,
+    element_keys: &[&str],
+    item_ids_stored: &[ItemId],
+) -> Result<(Flow<PeaceTestError>, ItemFanOutFnIds), PeaceTestError>
+where
+    F: Fn(ItemId) -> VecCopyItem,
+{
+    let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+    graph_builder.add_fn(MockItem::<()>::default().into());
+    let item_fan_out_fn_ids =
+        item_fan_out.add_to(&mut graph_builder, element_keys, item_ids_stored)?;
+
+    Ok((
+        Flow::new(flow_id, graph_builder.build()),
+        item_fan_out_fn_ids,
+    ))
+}
+
+fn flow_vec_copy_with_enable_condition(flow_id: FlowId, enabled: bool) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(
+            ItemBoxed::from(VecCopyItem::default())
+                .with_enable_condition(ItemEnableCondition::new(move |_| enabled)),
+        );
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
@@ -0,0 +1,82 @@
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::item_id,
+    rt::cmds::FlowCheckCmd,
+    rt_model::{FlowIssue, FlowIssues, Workspace, WorkspaceSpec},
+};
+
+use crate::{
+    peace_cmd_ctx_types::TestCctNoOpOutput, NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn exec_returns_empty_when_flow_has_no_issues() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![0, 1]).into())
+        .await?;
+
+    let flow_issues = FlowCheckCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(flow_issues.is_empty(), "was {flow_issues:?}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_concurrent_writes() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_a")).into());
+        graph_builder.add_fn(VecCopyItem::new(item_id!("vec_copy_b")).into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(item_id!("vec_copy_a"), VecA(vec![0]).into())
+        .with_item_params::<VecCopyItem>(item_id!("vec_copy_b"), VecA(vec![1]).into())
+        .await?;
+
+    let flow_issues = FlowCheckCmd::exec(&mut cmd_ctx).await?;
+
+    assert_eq!(
+        FlowIssues::from_iter([FlowIssue::DataWriteConcurrent {
+            item_id_a: item_id!("vec_copy_a"),
+            item_id_b: item_id!("vec_copy_b"),
+            type_name: String::from("VecB"),
+        }]),
+        flow_issues
+    );
+
+    Ok(())
+}
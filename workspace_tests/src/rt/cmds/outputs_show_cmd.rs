@@ -0,0 +1,92 @@
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, ProfileSelection},
+    cmd_model::CmdOutcome,
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    rt::cmds::{EnsureCmd, OutputsShowCmd, StatesDiscoverCmd},
+    rt_model::{Workspace, WorkspaceSpec},
+};
+
+use crate::{
+    peace_cmd_ctx_types::TestCctNoOpOutput, NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn exec_returns_empty_when_flow_not_applied() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![0, 1]).into())
+        .await?;
+
+    let items_outputs = OutputsShowCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(items_outputs.is_empty(), "was {items_outputs:?}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_outputs_stored_by_previous_ensure() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2]).into(),
+        )
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    let CmdOutcome::Complete { .. } = EnsureCmd::exec(&mut cmd_ctx).await? else {
+        panic!("Expected `EnsureCmd::exec` to complete successfully.");
+    };
+
+    // Outputs are read from disk without re-applying the item.
+    let output = &mut NoOpOutput;
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .await?;
+    let items_outputs = OutputsShowCmd::exec(&mut cmd_ctx).await?;
+
+    assert_eq!(
+        Some(&serde_yaml::Value::from(3usize)),
+        items_outputs.output_get(VecCopyItem::ID_DEFAULT, "len")
+    );
+
+    Ok(())
+}
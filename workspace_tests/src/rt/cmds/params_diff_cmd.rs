@@ -0,0 +1,144 @@
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    rt::cmds::{EnsureCmd, ParamsDiffCmd, StatesDiscoverCmd},
+    rt_model::{ParamsDiffs, ParamsFieldDiff, Workspace, WorkspaceSpec},
+};
+
+use crate::{
+    peace_cmd_ctx_types::TestCctNoOpOutput, NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn exec_returns_all_params_when_not_applied() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![0, 1]).into())
+        .await?;
+
+    let params_diffs = ParamsDiffCmd::exec(&cmd_ctx).await?;
+
+    assert_eq!(
+        Some(&vec![ParamsFieldDiff::new(
+            None,
+            None,
+            Some(String::from("[0,1]"))
+        )]),
+        params_diffs.get(VecCopyItem::ID_DEFAULT)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_changed_params_since_last_ensure() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![0, 1]).into())
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx).await?;
+
+    // Unchanged params have no diffs.
+    let params_diffs = ParamsDiffCmd::exec(&cmd_ctx).await?;
+    assert!(params_diffs.is_empty(), "was {params_diffs:?}");
+
+    // Changed params show the previous and next values.
+    let cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2]).into(),
+        )
+        .await?;
+    let params_diffs = ParamsDiffCmd::exec(&cmd_ctx).await?;
+
+    assert_eq!(
+        Some(&vec![ParamsFieldDiff::new(
+            None,
+            Some(String::from("[0,1]")),
+            Some(String::from("[0,1,2]"))
+        )]),
+        params_diffs.get(VecCopyItem::ID_DEFAULT)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_exec_dry_inserts_params_diffs_into_resources(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+
+    let mut cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(VecCopyItem::ID_DEFAULT.clone(), VecA(vec![0, 1]).into())
+        .await?;
+    StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    EnsureCmd::exec_dry(&mut cmd_ctx).await?;
+
+    let resources = cmd_ctx.fields().resources();
+    let params_diffs = resources.borrow::<ParamsDiffs>();
+
+    assert_eq!(
+        Some(&vec![ParamsFieldDiff::new(
+            None,
+            None,
+            Some(String::from("[0,1]"))
+        )]),
+        params_diffs.get(VecCopyItem::ID_DEFAULT)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use peace::{
+    cfg::app_name,
+    cmd_ctx::CmdCtxMpnf,
+    profile_model::profile,
+    resource_rt::paths::{ParamsSpecsFile, ProfileDir, StatesCurrentFile},
+    rt::cmds::ProfileCopyCmd,
+    rt_model::Error as PeaceRtError,
+};
+
+use crate::{
+    cmd_ctx::ProfileParamsKey, peace_cmd_ctx_types::TestCctProfileCmd,
+    test_support::workspace_with, NoOpOutput, PeaceTestError,
+};
+
+#[tokio::test]
+async fn copies_params_without_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_src = profile!("profile_src");
+    let profile_dest = profile!("profile_dest");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile_src.clone()], None).await?;
+
+    let peace_app_dir = workspace.dirs().peace_app_dir();
+    let flow_dir_src = ProfileDir::from((peace_app_dir, &profile_src)).join("flow");
+    tokio::fs::create_dir_all(&flow_dir_src).await?;
+    tokio::fs::write(flow_dir_src.join(ParamsSpecsFile::NAME), "{}").await?;
+    tokio::fs::write(flow_dir_src.join(StatesCurrentFile::NAME), "{}").await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    ProfileCopyCmd::exec(&mut cmd_ctx, &profile_src, profile_dest.clone()).await?;
+
+    let flow_dir_dest = ProfileDir::from((peace_app_dir, &profile_dest)).join("flow");
+    assert!(flow_dir_dest.join(ParamsSpecsFile::NAME).exists());
+    assert!(!flow_dir_dest.join(StatesCurrentFile::NAME).exists());
+    assert_eq!(
+        &[profile_dest.clone(), profile_src],
+        cmd_ctx.fields().profiles()
+    );
+    assert_eq!(
+        Some(&1u32),
+        cmd_ctx
+            .fields()
+            .profile_to_profile_params()
+            .get(&profile_dest)
+            .and_then(|profile_params| profile_params.get(&ProfileParamsKey::U32Param))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_dest_exists() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_src = profile!("profile_src");
+    let profile_dest = profile!("profile_dest");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_src.clone(), profile_dest.clone()],
+        None,
+    )
+    .await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileCopyCmd::exec(&mut cmd_ctx, &profile_src, profile_dest.clone()).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileExists { profile }))
+            if profile == &profile_dest
+        ),
+        "result was: {result:?}"
+    );
+
+    Ok(())
+}
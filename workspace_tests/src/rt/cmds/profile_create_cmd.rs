@@ -0,0 +1,83 @@
+use peace::{
+    cfg::app_name,
+    cmd_ctx::CmdCtxMpnf,
+    profile_model::profile,
+    resource_rt::{internal::ProfileParamsFile, paths::ProfileDir},
+    rt::cmds::ProfileCreateCmd,
+    rt_model::{params::ProfileParams, Error as PeaceRtError},
+};
+
+use crate::{
+    cmd_ctx::ProfileParamsKey, peace_cmd_ctx_types::TestCctProfileCmd,
+    test_support::workspace_with, NoOpOutput, PeaceTestError,
+};
+
+#[tokio::test]
+async fn creates_profile_dir_and_profile_params() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_existing = profile!("profile_existing");
+    let profile_new = profile!("profile_new");
+    let workspace =
+        workspace_with(&tempdir, app_name!(), &[profile_existing.clone()], None).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let mut profile_params = ProfileParams::new();
+    profile_params.insert(ProfileParamsKey::U32Param, 123u32);
+    ProfileCreateCmd::exec(&mut cmd_ctx, profile_new.clone(), profile_params).await?;
+
+    let profile_dir = ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_new));
+    assert!(ProfileParamsFile::from(&profile_dir).exists());
+    assert_eq!(
+        &[profile_existing.clone(), profile_new.clone()],
+        cmd_ctx.fields().profiles()
+    );
+
+    // Re-read from storage.
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    assert_eq!(
+        &[profile_existing, profile_new.clone()],
+        cmd_ctx.fields().profiles()
+    );
+    assert_eq!(
+        Some(&123u32),
+        cmd_ctx
+            .fields()
+            .profile_to_profile_params()
+            .get(&profile_new)
+            .and_then(|profile_params| profile_params.get(&ProfileParamsKey::U32Param))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_exists() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_existing = profile!("profile_existing");
+    let workspace =
+        workspace_with(&tempdir, app_name!(), &[profile_existing.clone()], None).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result =
+        ProfileCreateCmd::exec(&mut cmd_ctx, profile_existing.clone(), ProfileParams::new()).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileExists { profile }))
+            if profile == &profile_existing
+        ),
+        "result was: {result:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use peace::{
+    cfg::app_name,
+    cmd_ctx::{CmdCtxMpsf, CmdCtxSpsf, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::item_id,
+    profile_model::{profile, Profile},
+    resource_rt::paths::{FlowDir, ProfileDir, StatesCurrentFile},
+    rt::cmds::{CleanCmd, EnsureCmd, ProfileDeleteCmd, StatesDiscoverCmd},
+    rt_model::{Error as PeaceRtError, Workspace},
+};
+
+use crate::{
+    cmd_ctx::WorkspaceParamsKey, peace_cmd_ctx_types::TestCctProfileCmd,
+    test_support::workspace_with, NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn returns_error_when_profile_is_active() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_active = profile!("profile_active");
+    let profile_other = profile!("profile_other");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_active.clone(), profile_other.clone()],
+        None,
+    )
+    .await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    for profile in [&profile_active, &profile_other] {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow, profile).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+
+    let mut cmd_ctx = CmdCtxMpsf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .with_flow((&flow).into())
+        .await?;
+    let result = ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[],
+        &WorkspaceParamsKey::Profile,
+        &profile_active,
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileDeleteActive { profile }))
+            if profile == &profile_active
+        ),
+        "result was: {result:?}"
+    );
+    assert!(ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_active)).exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deletes_profile_only_when_states_are_clean() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_active = profile!("profile_active");
+    let profile_other = profile!("profile_other");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_active.clone(), profile_other.clone()],
+        None,
+    )
+    .await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow, &profile_active).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow, &profile_other).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+        EnsureCmd::exec(&mut cmd_ctx).await?;
+    }
+
+    let mut cmd_ctx = CmdCtxMpsf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .with_flow((&flow).into())
+        .await?;
+    let result = ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[],
+        &WorkspaceParamsKey::Profile,
+        &profile_other,
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileDeleteStatesNotClean {
+                profile,
+                item_ids,
+            }))
+            if profile == &profile_other && item_ids == &[VecCopyItem::ID_DEFAULT.clone()]
+        ),
+        "result was: {result:?}"
+    );
+
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow, &profile_other).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+        CleanCmd::exec(&mut cmd_ctx).await?;
+    }
+    let mut cmd_ctx = CmdCtxMpsf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .with_flow((&flow).into())
+        .await?;
+    ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[],
+        &WorkspaceParamsKey::Profile,
+        &profile_other,
+    )
+    .await?;
+
+    assert!(!ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_other)).exists());
+    assert_eq!(&[profile_active], cmd_ctx.fields().profiles());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deletes_profile_only_when_states_of_every_flow_are_clean(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_active = profile!("profile_active");
+    let profile_other = profile!("profile_other");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_active.clone(), profile_other.clone()],
+        None,
+    )
+    .await?;
+    let flow_clean = flow(FlowId::new("flow_clean")?);
+    let flow_ensured = flow(FlowId::new("flow_ensured")?);
+    for flow in [&flow_clean, &flow_ensured] {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, flow, &profile_active).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow_clean, &profile_other).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow_ensured, &profile_other).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+        EnsureCmd::exec(&mut cmd_ctx).await?;
+    }
+
+    let mut cmd_ctx = cmd_ctx_mpsf(&workspace, &flow_clean).await?;
+    let result = ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[],
+        &WorkspaceParamsKey::Profile,
+        &profile_other,
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileDeleteFlowsNotChecked {
+                profile,
+                flow_ids,
+            }))
+            if profile == &profile_other && flow_ids == &[flow_ensured.flow_id().clone()]
+        ),
+        "result was: {result:?}"
+    );
+
+    let cmd_ctx_flow_ensured = cmd_ctx_mpsf(&workspace, &flow_ensured).await?;
+    let result = ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[&cmd_ctx_flow_ensured],
+        &WorkspaceParamsKey::Profile,
+        &profile_other,
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileDeleteStatesNotClean {
+                profile,
+                item_ids,
+            }))
+            if profile == &profile_other && item_ids == &[VecCopyItem::ID_DEFAULT.clone()]
+        ),
+        "result was: {result:?}"
+    );
+    assert!(ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_other)).exists());
+
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow_ensured, &profile_other).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+        CleanCmd::exec(&mut cmd_ctx).await?;
+    }
+    let cmd_ctx_flow_ensured = cmd_ctx_mpsf(&workspace, &flow_ensured).await?;
+    ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[&cmd_ctx_flow_ensured],
+        &WorkspaceParamsKey::Profile,
+        &profile_other,
+    )
+    .await?;
+
+    assert!(!ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_other)).exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_delete_profile_when_stored_states_contain_items_not_in_flow(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_active = profile!("profile_active");
+    let profile_other = profile!("profile_other");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_active.clone(), profile_other.clone()],
+        None,
+    )
+    .await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    for profile in [&profile_active, &profile_other] {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow, profile).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+
+    // Simulate an item that was removed from the flow after it was ensured.
+    let profile_dir = ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_other));
+    let flow_dir = FlowDir::from((&profile_dir, flow.flow_id()));
+    let states_current_file = StatesCurrentFile::from(&flow_dir);
+    let mut states_current_yaml = tokio::fs::read_to_string(&states_current_file).await?;
+    states_current_yaml.push_str("item_removed: [1, 2]\n");
+    tokio::fs::write(&states_current_file, states_current_yaml).await?;
+
+    let mut cmd_ctx = cmd_ctx_mpsf(&workspace, &flow).await?;
+    let result = ProfileDeleteCmd::exec(
+        &mut cmd_ctx,
+        &[],
+        &WorkspaceParamsKey::Profile,
+        &profile_other,
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileDeleteStatesNotClean {
+                profile,
+                item_ids,
+            }))
+            if profile == &profile_other && item_ids == &[item_id!("item_removed")]
+        ),
+        "result was: {result:?}"
+    );
+    assert!(profile_dir.exists());
+
+    Ok(())
+}
+
+fn flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
+
+async fn cmd_ctx_mpsf<'ctx>(
+    workspace: &'ctx Workspace,
+    flow: &'ctx Flow<PeaceTestError>,
+) -> Result<CmdCtxMpsf<'ctx, TestCctProfileCmd>, Box<dyn std::error::Error>> {
+    let cmd_ctx = CmdCtxMpsf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace(workspace.into())
+        .with_flow(flow.into())
+        .await?;
+
+    Ok(cmd_ctx)
+}
+
+async fn cmd_ctx_spsf<'ctx>(
+    workspace: &'ctx Workspace,
+    flow: &'ctx Flow<PeaceTestError>,
+    profile: &Profile,
+) -> Result<CmdCtxSpsf<'ctx, TestCctProfileCmd>, Box<dyn std::error::Error>> {
+    let cmd_ctx = CmdCtxSpsf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace(workspace.into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow(flow.into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+
+    Ok(cmd_ctx)
+}
@@ -0,0 +1,113 @@
+use peace::{
+    cfg::app_name,
+    cmd_ctx::{CmdCtxMpnf, CmdCtxSpsf, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    profile_model::{profile, Profile},
+    rt::cmds::{ProfileExportCmd, StatesDiscoverCmd},
+    rt_model::{Error as PeaceRtError, ProfileBundle, ProfileBundleFlowManifest, Workspace},
+};
+
+use crate::{
+    peace_cmd_ctx_types::TestCctProfileCmd, test_support::workspace_with, NoOpOutput,
+    PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn exports_profile_params_and_flow_files() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let flow_other = self::flow(FlowId::new("flow_other")?);
+    {
+        let mut cmd_ctx = cmd_ctx_spsf(&workspace, &flow, &profile).await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+
+    let bundle_path = tempdir.path().join("bundle.yaml");
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let manifest =
+        ProfileExportCmd::exec(&cmd_ctx, &[&flow, &flow_other], &profile, &bundle_path).await?;
+
+    assert_eq!(ProfileBundle::VERSION, manifest.version);
+    assert_eq!(&app_name!(), &manifest.app_name);
+    assert_eq!(profile, manifest.profile);
+    assert_eq!(
+        vec![ProfileBundleFlowManifest {
+            flow_id: flow.flow_id().clone(),
+            item_ids: vec![VecCopyItem::ID_DEFAULT.clone()],
+        }],
+        manifest.flows
+    );
+
+    let profile_bundle =
+        serde_yaml::from_str::<ProfileBundle>(&tokio::fs::read_to_string(&bundle_path).await?)?;
+    assert_eq!(manifest, profile_bundle.manifest);
+    assert!(profile_bundle.profile_params.is_some());
+    let bundle_flow = &profile_bundle.flows[0];
+    assert!(bundle_flow.params_specs.is_some());
+    assert!(bundle_flow.states_current.is_some());
+    assert!(bundle_flow.states_goal.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_not_exists() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let profile_other = profile!("profile_other");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+
+    let bundle_path = tempdir.path().join("bundle.yaml");
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileExportCmd::exec(&cmd_ctx, &[&flow], &profile_other, &bundle_path).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileNotInScope { profile, .. }))
+            if profile == &profile_other
+        ),
+        "result was: {result:?}"
+    );
+    assert!(!bundle_path.exists());
+
+    Ok(())
+}
+
+fn flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
+
+async fn cmd_ctx_spsf<'ctx>(
+    workspace: &'ctx Workspace,
+    flow: &'ctx Flow<PeaceTestError>,
+    profile: &Profile,
+) -> Result<CmdCtxSpsf<'ctx, TestCctProfileCmd>, Box<dyn std::error::Error>> {
+    let cmd_ctx = CmdCtxSpsf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace(workspace.into())
+        .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+        .with_flow(flow.into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+
+    Ok(cmd_ctx)
+}
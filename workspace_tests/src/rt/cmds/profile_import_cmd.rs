@@ -0,0 +1,335 @@
+use peace::{
+    cfg::{app_name, AppName},
+    cmd_ctx::{CmdCtxMpnf, CmdCtxSpsf, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::item_id,
+    profile_model::{profile, Profile},
+    resource_rt::paths::{FlowDir, ParamsSpecsFile, ProfileDir, StatesCurrentFile},
+    rt::cmds::{ProfileExportCmd, ProfileImportCmd, StatesDiscoverCmd},
+    rt_model::{Error as PeaceRtError, ProfileBundle, Workspace},
+};
+
+use crate::{
+    cmd_ctx::ProfileParamsKey, peace_cmd_ctx_types::TestCctProfileCmd,
+    test_support::workspace_with, NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn imports_exported_profile_with_rename() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let profile_imported = profile!("profile_imported");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let profile_returned = ProfileImportCmd::exec(
+        &mut cmd_ctx,
+        &[&flow],
+        &bundle_path,
+        Some(profile_imported.clone()),
+    )
+    .await?;
+
+    assert_eq!(profile_imported, profile_returned);
+    let profile_dir = ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_imported));
+    let flow_dir = FlowDir::from((&profile_dir, flow.flow_id()));
+    assert!(ParamsSpecsFile::from(&flow_dir).exists());
+    assert!(StatesCurrentFile::from(&flow_dir).exists());
+    assert_eq!(
+        &[profile_imported.clone(), profile],
+        cmd_ctx.fields().profiles()
+    );
+    assert_eq!(
+        Some(&1u32),
+        cmd_ctx
+            .fields()
+            .profile_to_profile_params()
+            .get(&profile_imported)
+            .and_then(|profile_params| profile_params.get(&ProfileParamsKey::U32Param))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_exists() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileImportCmd::exec(&mut cmd_ctx, &[&flow], &bundle_path, None).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileExists { profile: profile_existing }))
+            if profile_existing == &profile
+        ),
+        "result was: {result:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_flow_not_registered() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let flow_other = self::flow(FlowId::new("flow_other")?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileImportCmd::exec(
+        &mut cmd_ctx,
+        &[&flow_other],
+        &bundle_path,
+        Some(profile!("profile_imported")),
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileBundleFlowNotRegistered {
+                flow_id,
+                flow_ids_registered,
+            }))
+            if flow_id == flow.flow_id()
+            && flow_ids_registered == &[flow_other.flow_id().clone()]
+        ),
+        "result was: {result:?}"
+    );
+    assert_eq!(&[profile], cmd_ctx.fields().profiles());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_app_name_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+    let mut profile_bundle =
+        serde_yaml::from_str::<ProfileBundle>(&tokio::fs::read_to_string(&bundle_path).await?)?;
+    let app_name_other = AppName::new("other_app")?;
+    profile_bundle.manifest.app_name = app_name_other.clone();
+    tokio::fs::write(&bundle_path, serde_yaml::to_string(&profile_bundle)?).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileImportCmd::exec(
+        &mut cmd_ctx,
+        &[&flow],
+        &bundle_path,
+        Some(profile!("profile_imported")),
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileBundleAppNameMismatch {
+                app_name_bundle,
+                app_name,
+            }))
+            if app_name_bundle == &app_name_other
+            && app_name == &app_name!()
+        ),
+        "result was: {result:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_states_contain_items_not_in_flow(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+    let mut profile_bundle =
+        serde_yaml::from_str::<ProfileBundle>(&tokio::fs::read_to_string(&bundle_path).await?)?;
+    let Some(serde_yaml::Value::Mapping(states_current)) =
+        profile_bundle.flows[0].states_current.as_mut()
+    else {
+        panic!("Expected exported `states_current` to be a map.");
+    };
+    states_current.insert(
+        serde_yaml::Value::from("item_unknown"),
+        serde_yaml::Value::from(1),
+    );
+    tokio::fs::write(&bundle_path, serde_yaml::to_string(&profile_bundle)?).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let profile_imported = profile!("profile_imported");
+    let result = ProfileImportCmd::exec(
+        &mut cmd_ctx,
+        &[&flow],
+        &bundle_path,
+        Some(profile_imported.clone()),
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileBundleItemsNotInFlow {
+                flow_id,
+                item_ids,
+            }))
+            if flow_id == flow.flow_id()
+            && item_ids == &[item_id!("item_unknown")]
+        ),
+        "result was: {result:?}"
+    );
+    assert!(!ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_imported)).exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_params_specs_not_keyed_by_item_id(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+    let mut profile_bundle =
+        serde_yaml::from_str::<ProfileBundle>(&tokio::fs::read_to_string(&bundle_path).await?)?;
+    profile_bundle.flows[0].params_specs = Some(serde_yaml::Value::Sequence(Vec::new()));
+    tokio::fs::write(&bundle_path, serde_yaml::to_string(&profile_bundle)?).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let profile_imported = profile!("profile_imported");
+    let result = ProfileImportCmd::exec(
+        &mut cmd_ctx,
+        &[&flow],
+        &bundle_path,
+        Some(profile_imported.clone()),
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileBundleFlowFileInvalid {
+                flow_id,
+                file_name,
+                reason: _,
+            }))
+            if flow_id == flow.flow_id()
+            && file_name == ParamsSpecsFile::NAME
+        ),
+        "result was: {result:?}"
+    );
+    assert!(!ProfileDir::from((workspace.dirs().peace_app_dir(), &profile_imported)).exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_bundle_profile_is_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile = profile!("test_profile");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile.clone()], None).await?;
+    let flow = flow(FlowId::new(crate::fn_name_short!())?);
+    let bundle_path = bundle_export(&tempdir, &workspace, &flow, &profile).await?;
+    let mut profile_bundle =
+        serde_yaml::from_str::<ProfileBundle>(&tokio::fs::read_to_string(&bundle_path).await?)?;
+    profile_bundle.manifest.profile = Profile::new_unchecked("../../escaped");
+    tokio::fs::write(&bundle_path, serde_yaml::to_string(&profile_bundle)?).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileImportCmd::exec(&mut cmd_ctx, &[&flow], &bundle_path, None).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileBundleProfileInvalid {
+                profile,
+                error: _,
+            }))
+            if profile == "../../escaped"
+        ),
+        "result was: {result:?}"
+    );
+    assert!(!workspace
+        .dirs()
+        .peace_app_dir()
+        .join("../../escaped")
+        .exists());
+
+    Ok(())
+}
+
+async fn bundle_export(
+    tempdir: &tempfile::TempDir,
+    workspace: &Workspace,
+    flow: &Flow<PeaceTestError>,
+    profile: &Profile,
+) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    {
+        let mut cmd_ctx = CmdCtxSpsf::<TestCctProfileCmd>::builder()
+            .with_output(NoOpOutput.into())
+            .with_workspace(workspace.into())
+            .with_profile_selection(ProfileSelection::Specified(profile.clone()))
+            .with_flow(flow.into())
+            .with_item_params::<VecCopyItem>(
+                VecCopyItem::ID_DEFAULT.clone(),
+                VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+            )
+            .await?;
+        StatesDiscoverCmd::current_and_goal(&mut cmd_ctx).await?;
+    }
+
+    let bundle_path = tempdir.path().join("bundle.yaml");
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace(workspace.into())
+        .await?;
+    ProfileExportCmd::exec(&cmd_ctx, &[flow], profile, &bundle_path).await?;
+
+    Ok(bundle_path)
+}
+
+fn flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
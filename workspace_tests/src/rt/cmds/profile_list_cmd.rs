@@ -0,0 +1,38 @@
+use peace::{cfg::app_name, cmd_ctx::CmdCtxMpnf, profile_model::profile, rt::cmds::ProfileListCmd};
+
+use crate::{peace_cmd_ctx_types::TestCctProfileCmd, test_support::workspace_with, NoOpOutput};
+
+#[tokio::test]
+async fn lists_profiles_in_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_b = profile!("profile_b");
+    let profile_a = profile!("profile_a");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_b.clone(), profile_a.clone()],
+        None,
+    )
+    .await?;
+
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+
+    assert_eq!(
+        vec![profile_a, profile_b],
+        ProfileListCmd::<TestCctProfileCmd>::exec(&cmd_ctx)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!("{:?}", ProfileListCmd::<TestCctProfileCmd>::default());
+    assert_eq!(
+        r#"ProfileListCmd(PhantomData<workspace_tests::peace_cmd_ctx_types::test_cct_profile_cmd::TestCctProfileCmd>)"#,
+        debug_str,
+    );
+}
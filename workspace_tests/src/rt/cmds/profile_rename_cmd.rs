@@ -0,0 +1,87 @@
+use peace::{
+    cfg::app_name,
+    cmd_ctx::CmdCtxMpnf,
+    profile_model::{profile, Profile},
+    resource_rt::paths::ProfileDir,
+    rt::cmds::ProfileRenameCmd,
+    rt_model::Error as PeaceRtError,
+};
+
+use crate::{
+    cmd_ctx::WorkspaceParamsKey, peace_cmd_ctx_types::TestCctProfileCmd,
+    test_support::workspace_with, NoOpOutput, PeaceTestError,
+};
+
+#[tokio::test]
+async fn renames_active_profile_and_updates_workspace_param(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_from = profile!("profile_from");
+    let profile_to = profile!("profile_to");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile_from.clone()], None).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    ProfileRenameCmd::exec(
+        &mut cmd_ctx,
+        WorkspaceParamsKey::Profile,
+        &profile_from,
+        profile_to.clone(),
+    )
+    .await?;
+
+    let peace_app_dir = workspace.dirs().peace_app_dir();
+    assert!(!ProfileDir::from((peace_app_dir, &profile_from)).exists());
+    assert!(ProfileDir::from((peace_app_dir, &profile_to)).exists());
+
+    // Re-read from storage.
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    assert_eq!(&[profile_to.clone()], cmd_ctx.fields().profiles());
+    assert_eq!(
+        Some(&profile_to),
+        cmd_ctx
+            .fields()
+            .workspace_params()
+            .get::<Profile, _>(&WorkspaceParamsKey::Profile)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_from_does_not_exist() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let profile_existing = profile!("profile_existing");
+    let profile_missing = profile!("profile_missing");
+    let workspace =
+        workspace_with(&tempdir, app_name!(), &[profile_existing.clone()], None).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileRenameCmd::exec(
+        &mut cmd_ctx,
+        WorkspaceParamsKey::Profile,
+        &profile_missing,
+        profile!("profile_to"),
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileNotInScope { profile, .. }))
+            if profile == &profile_missing
+        ),
+        "result was: {result:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use peace::{
+    cfg::app_name,
+    cmd_ctx::CmdCtxMpnf,
+    profile_model::{profile, Profile},
+    rt::cmds::ProfileSwitchCmd,
+    rt_model::Error as PeaceRtError,
+};
+
+use crate::{
+    cmd_ctx::WorkspaceParamsKey, peace_cmd_ctx_types::TestCctProfileCmd,
+    test_support::workspace_with, NoOpOutput, PeaceTestError,
+};
+
+#[tokio::test]
+async fn switches_active_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_a = profile!("profile_a");
+    let profile_b = profile!("profile_b");
+    let workspace = workspace_with(
+        &tempdir,
+        app_name!(),
+        &[profile_a.clone(), profile_b.clone()],
+        None,
+    )
+    .await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    ProfileSwitchCmd::exec(&mut cmd_ctx, WorkspaceParamsKey::Profile, profile_b.clone()).await?;
+
+    // Re-read from storage.
+    let cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    assert_eq!(
+        Some(&profile_b),
+        cmd_ctx
+            .fields()
+            .workspace_params()
+            .get::<Profile, _>(&WorkspaceParamsKey::Profile)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_does_not_exist() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let profile_a = profile!("profile_a");
+    let profile_missing = profile!("profile_missing");
+    let workspace = workspace_with(&tempdir, app_name!(), &[profile_a.clone()], None).await?;
+
+    let mut cmd_ctx = CmdCtxMpnf::<TestCctProfileCmd>::builder()
+        .with_output(NoOpOutput.into())
+        .with_workspace((&workspace).into())
+        .await?;
+    let result = ProfileSwitchCmd::exec(
+        &mut cmd_ctx,
+        WorkspaceParamsKey::Profile,
+        profile_missing.clone(),
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRt(PeaceRtError::ProfileNotInScope { profile, .. }))
+            if profile == &profile_missing
+        ),
+        "result was: {result:?}"
+    );
+    assert_eq!(
+        Some(&profile_a),
+        cmd_ctx
+            .fields()
+            .workspace_params()
+            .get::<Profile, _>(&WorkspaceParamsKey::Profile)
+    );
+
+    Ok(())
+}
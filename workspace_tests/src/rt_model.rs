@@ -1,13 +1,20 @@
+mod cmd_checkpoint;
 #[cfg(feature = "error_reporting")]
 mod error;
 #[cfg(feature = "output_in_memory")]
 mod in_memory_text_output;
 mod item_boxed;
+mod item_enable_condition;
+mod item_fan_out;
 mod item_graph;
 mod item_graph_builder;
+mod item_ids_disabled;
+mod item_ids_orphaned;
 mod item_wrapper;
+mod items_retired;
 mod native;
 mod outcomes;
 mod params;
+mod params_applied;
 mod storage;
 mod workspace_dirs_builder;
@@ -0,0 +1,71 @@
+use peace::{
+    cmd_model::CmdExecutionId,
+    item_model::item_id,
+    resource_rt::paths::CmdCheckpointFile,
+    rt_model::{CmdCheckpoint, CmdCheckpointSerializer, Storage},
+};
+
+use crate::PeaceTestError;
+
+#[test]
+fn item_completed_insert_records_states_before_and_after_apply() {
+    let mut cmd_checkpoint = CmdCheckpoint::new(CmdExecutionId::new(1), "EnsureCmd".to_string());
+    assert!(cmd_checkpoint.is_empty());
+
+    cmd_checkpoint.item_completed_insert(
+        item_id!("a"),
+        Some(serde_yaml::Value::from(0)),
+        serde_yaml::Value::from(1),
+    );
+    cmd_checkpoint.item_completed_insert(item_id!("b"), None, serde_yaml::Value::from(2));
+
+    assert!(!cmd_checkpoint.is_empty());
+    assert_eq!(CmdExecutionId::new(1), cmd_checkpoint.cmd_execution_id());
+    assert_eq!("EnsureCmd", cmd_checkpoint.cmd_name());
+    assert_eq!(
+        vec![&item_id!("a"), &item_id!("b")],
+        cmd_checkpoint.item_ids_completed().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(&serde_yaml::Value::from(0)),
+        cmd_checkpoint.states_current_stored().get(&item_id!("a"))
+    );
+    assert_eq!(
+        None,
+        cmd_checkpoint.states_current_stored().get(&item_id!("b"))
+    );
+    assert_eq!(
+        Some(&serde_yaml::Value::from(2)),
+        cmd_checkpoint.states_completed().get(&item_id!("b"))
+    );
+}
+
+#[tokio::test]
+async fn serialize_deserialize_and_remove_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let cmd_checkpoint_file = CmdCheckpointFile::new(tempdir.path().join(CmdCheckpointFile::NAME));
+    let mut cmd_checkpoint = CmdCheckpoint::new(CmdExecutionId::new(1), "EnsureCmd".to_string());
+    cmd_checkpoint.item_completed_insert(item_id!("a"), None, serde_yaml::Value::from(1));
+
+    CmdCheckpointSerializer::<PeaceTestError>::serialize(
+        &Storage,
+        &cmd_checkpoint,
+        &cmd_checkpoint_file,
+    )
+    .await?;
+    let cmd_checkpoint_deserialized =
+        CmdCheckpointSerializer::<PeaceTestError>::deserialize_opt(&Storage, &cmd_checkpoint_file)
+            .await?;
+    assert_eq!(Some(cmd_checkpoint), cmd_checkpoint_deserialized);
+
+    CmdCheckpointSerializer::<PeaceTestError>::remove(&Storage, &cmd_checkpoint_file).await?;
+    let cmd_checkpoint_deserialized =
+        CmdCheckpointSerializer::<PeaceTestError>::deserialize_opt(&Storage, &cmd_checkpoint_file)
+            .await?;
+    assert_eq!(None, cmd_checkpoint_deserialized);
+
+    // Removing a checkpoint that does not exist is not an error.
+    CmdCheckpointSerializer::<PeaceTestError>::remove(&Storage, &cmd_checkpoint_file).await?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use peace::{resource_rt::Resources, rt_model::ItemEnableCondition};
+
+#[test]
+fn is_enabled_evaluates_function() {
+    let resources = Resources::new();
+
+    assert!(ItemEnableCondition::new(|_| true).is_enabled(&resources));
+    assert!(!ItemEnableCondition::new(|_| false).is_enabled(&resources));
+}
+
+#[test]
+fn param_eq_is_enabled_when_param_value_equal() {
+    let mut resources = Resources::new();
+    resources.insert(3u8);
+
+    assert!(ItemEnableCondition::param_eq(3u8).is_enabled(&resources));
+    assert!(!ItemEnableCondition::param_eq(4u8).is_enabled(&resources));
+}
+
+#[test]
+fn param_eq_is_disabled_when_param_value_absent() {
+    let resources = Resources::new();
+
+    assert!(!ItemEnableCondition::param_eq(3u8).is_enabled(&resources));
+}
+
+#[test]
+fn clone() {
+    let item_enable_condition = ItemEnableCondition::new(|_| true);
+
+    assert!(Clone::clone(&item_enable_condition).is_enabled(&Resources::new()));
+}
+
+#[test]
+fn debug() {
+    let item_enable_condition = ItemEnableCondition::new(|_| true);
+
+    assert_eq!(
+        r#"ItemEnableCondition("Fn(&Resources<Empty>) -> bool")"#,
+        format!("{item_enable_condition:?}")
+    );
+}
@@ -0,0 +1,192 @@
+use peace::{
+    flow_rt::{ItemFanOut, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
+    rt_model::Error as PeaceRtError,
+};
+use peace_items::blank::BlankItem;
+
+use crate::PeaceTestError;
+
+#[test]
+fn item_id_appends_element_key_to_prefix() -> Result<(), PeaceTestError> {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+
+    assert_eq!(&item_id!("artifact"), item_fan_out.item_id_prefix());
+    assert_eq!(
+        item_id!("artifact__web_app"),
+        item_fan_out.item_id("web_app")?
+    );
+    assert_eq!(item_id!("artifact__0"), item_fan_out.item_id("0")?);
+    assert_eq!(
+        vec![
+            item_id!("artifact__web_app"),
+            item_id!("artifact__db"),
+            item_id!("artifact__cli"),
+        ],
+        item_fan_out.item_ids(&["web_app", "db", "cli"])?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn item_id_returns_error_when_element_key_invalid() {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+
+    ["", "web-app", "web__app", "_web", "web_", "web app"]
+        .into_iter()
+        .for_each(|element_key_invalid| {
+            let result = item_fan_out.item_id(element_key_invalid);
+            assert!(
+                matches!(
+                    &result,
+                    Err(PeaceRtError::ItemFanOutElementKeyInvalid {
+                        item_id_prefix,
+                        element_key,
+                    })
+                    if item_id_prefix == &item_id!("artifact")
+                    && element_key == element_key_invalid
+                ),
+                "result was: {result:?}"
+            );
+        });
+}
+
+#[test]
+fn item_ids_returns_error_when_element_key_duplicated() {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+
+    let result = item_fan_out.item_ids(&["web_app", "db", "web_app"]);
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceRtError::ItemFanOutElementKeyDuplicate {
+                item_id_prefix,
+                element_key,
+            })
+            if item_id_prefix == &item_id!("artifact")
+            && element_key == "web_app"
+        ),
+        "result was: {result:?}"
+    );
+}
+
+#[test]
+fn element_key_returns_key_for_derived_item_ids() {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+
+    assert_eq!(
+        Some("0"),
+        item_fan_out.element_key(&item_id!("artifact__0"))
+    );
+    assert_eq!(
+        Some("web_app"),
+        item_fan_out.element_key(&item_id!("artifact__web_app"))
+    );
+    assert_eq!(None, item_fan_out.element_key(&item_id!("artifact")));
+    assert_eq!(None, item_fan_out.element_key(&item_id!("artifact__")));
+    assert_eq!(
+        None,
+        item_fan_out.element_key(&item_id!("artifact__web__app"))
+    );
+    assert_eq!(
+        None,
+        item_fan_out.element_key(&item_id!("artifact_upload__0"))
+    );
+    assert_eq!(None, item_fan_out.element_key(&item_id!("other__0")));
+}
+
+#[test]
+fn add_to_adds_item_per_element() -> Result<(), PeaceTestError> {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    let item_fan_out_fn_ids = item_fan_out.add_to(&mut builder, &["web_app", "db"], &[])?;
+    let item_graph = builder.build();
+
+    let item_ids = item_graph
+        .iter_insertion()
+        .map(|item| item.id().clone())
+        .collect::<Vec<ItemId>>();
+    assert_eq!(
+        vec![item_id!("artifact__web_app"), item_id!("artifact__db")],
+        item_ids
+    );
+    assert_eq!(2, item_fan_out_fn_ids.fn_ids.len());
+    assert!(item_fan_out_fn_ids.fn_ids_removed.is_empty());
+    assert!(item_graph
+        .iter_insertion()
+        .all(|item| !item.state_goal_is_clean()));
+
+    Ok(())
+}
+
+#[test]
+fn add_to_adds_clean_items_for_removed_elements() -> Result<(), PeaceTestError> {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+    let item_ids_stored = [
+        item_id!("artifact__web_app"),
+        item_id!("artifact__db"),
+        item_id!("other"),
+        item_id!("artifact__cli"),
+        item_id!("artifact__api"),
+    ];
+
+    // `db` is removed from the middle of the list, and `docs` is added. `cli`
+    // and `api` are removed from the end.
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    let item_fan_out_fn_ids =
+        item_fan_out.add_to(&mut builder, &["web_app", "docs"], &item_ids_stored)?;
+    let item_graph = builder.build();
+
+    let item_ids_and_clean = item_graph
+        .iter_insertion()
+        .map(|item| (item.id().clone(), item.state_goal_is_clean()))
+        .collect::<Vec<(ItemId, bool)>>();
+    assert_eq!(
+        vec![
+            (item_id!("artifact__web_app"), false),
+            (item_id!("artifact__docs"), false),
+            (item_id!("artifact__api"), true),
+            (item_id!("artifact__cli"), true),
+            (item_id!("artifact__db"), true),
+        ],
+        item_ids_and_clean
+    );
+    assert_eq!(2, item_fan_out_fn_ids.fn_ids.len());
+    assert_eq!(3, item_fan_out_fn_ids.fn_ids_removed.len());
+
+    Ok(())
+}
+
+#[test]
+fn add_to_keeps_item_ids_when_elements_are_reordered() -> Result<(), PeaceTestError> {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+    let item_ids_stored = [item_id!("artifact__web_app"), item_id!("artifact__db")];
+
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    let item_fan_out_fn_ids =
+        item_fan_out.add_to(&mut builder, &["db", "web_app"], &item_ids_stored)?;
+    let item_graph = builder.build();
+
+    let item_ids = item_graph
+        .iter_insertion()
+        .map(|item| item.id().clone())
+        .collect::<Vec<ItemId>>();
+    assert_eq!(
+        vec![item_id!("artifact__db"), item_id!("artifact__web_app")],
+        item_ids
+    );
+    assert!(item_fan_out_fn_ids.fn_ids_removed.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let item_fan_out = ItemFanOut::new(item_id!("artifact"), BlankItem::<()>::new);
+
+    assert!(format!("{item_fan_out:?}")
+        .starts_with(r#"ItemFanOut { item_id_prefix: ItemId("artifact"), item_new: "#));
+}
@@ -1,7 +1,13 @@
 use peace::{
-    flow_rt::ItemGraphBuilder,
+    data::fn_graph::{daggy::Walker, Edge, GraphInfo, WouldCycle},
+    flow_model::{flow_id, SubFlowSpecInfo},
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::{item_id, ItemId},
     rt_model::{fn_graph::FnGraphBuilder, Error, ItemBoxed},
 };
+use peace_items::blank::BlankItem;
+
+use crate::PeaceTestError;
 
 #[test]
 fn debug() {
@@ -34,3 +40,136 @@ fn from() {
     let fn_graph_builder = FnGraphBuilder::<ItemBoxed<Error>>::new();
     let _builder = ItemGraphBuilder::<Error>::from(fn_graph_builder);
 }
+
+#[test]
+fn add_sub_flow_namespaces_item_ids_and_copies_edges() -> Result<(), WouldCycle<Edge>> {
+    let sub_flow = sub_flow()?;
+
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    let fn_id_before = builder.add_fn(BlankItem::<()>::new(item_id!("before")).into());
+    let sub_flow_fn_ids = builder.add_flow(&sub_flow);
+    let fn_id_after = builder.add_fn(BlankItem::<()>::new(item_id!("after")).into());
+    builder.add_logic_edge_to_sub_flow(fn_id_before, &sub_flow_fn_ids)?;
+    builder.add_logic_edge_from_sub_flow(&sub_flow_fn_ids, fn_id_after)?;
+    let item_graph = builder.build();
+
+    let item_ids = item_graph
+        .iter_insertion()
+        .map(|item| item.id().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            item_id!("before"),
+            item_id!("upload__a"),
+            item_id!("upload__b"),
+            item_id!("upload__c"),
+            item_id!("after"),
+        ],
+        item_ids
+    );
+    assert_eq!(3, sub_flow_fn_ids.fn_ids.len());
+    assert_eq!(vec![sub_flow_fn_ids.fn_ids[0]], sub_flow_fn_ids.fn_ids_root);
+    assert_eq!(
+        vec![sub_flow_fn_ids.fn_ids[1], sub_flow_fn_ids.fn_ids[2]],
+        sub_flow_fn_ids.fn_ids_leaf
+    );
+    assert_eq!(
+        &[SubFlowSpecInfo::new(
+            flow_id!("upload"),
+            vec![
+                item_id!("upload__a"),
+                item_id!("upload__b"),
+                item_id!("upload__c"),
+            ],
+        )],
+        item_graph.sub_flows()
+    );
+
+    // before -> a -> {b, c} -> after
+    let graph_info = GraphInfo::from_graph(&item_graph, |item| item.id().clone());
+    assert_eq!(
+        vec![item_id!("upload__a")],
+        item_ids_children(&graph_info, "before")
+    );
+    assert_eq!(
+        vec![item_id!("upload__b"), item_id!("upload__c")],
+        item_ids_children(&graph_info, "upload__a")
+    );
+    assert_eq!(
+        vec![item_id!("after")],
+        item_ids_children(&graph_info, "upload__b")
+    );
+    assert_eq!(
+        vec![item_id!("after")],
+        item_ids_children(&graph_info, "upload__c")
+    );
+    Ok(())
+}
+
+#[test]
+fn add_logic_edge_between_sub_flows_links_leaves_to_roots() -> Result<(), WouldCycle<Edge>> {
+    let sub_flow = sub_flow()?;
+
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    let sub_flow_fn_ids_first = builder.add_sub_flow(flow_id!("first"), sub_flow.graph());
+    let sub_flow_fn_ids_second = builder.add_sub_flow(flow_id!("second"), sub_flow.graph());
+    builder.add_logic_edge_between_sub_flows(&sub_flow_fn_ids_first, &sub_flow_fn_ids_second)?;
+    let item_graph = builder.build();
+
+    let graph_info = GraphInfo::from_graph(&item_graph, |item| item.id().clone());
+    let node_index_second_a = graph_info
+        .iter_insertion_with_indices()
+        .find_map(|(node_index, item_id)| (item_id.as_str() == "second__a").then_some(node_index))
+        .unwrap();
+    let mut item_ids_parents = graph_info
+        .parents(node_index_second_a)
+        .iter(&graph_info)
+        .filter(|(edge_index, _)| matches!(graph_info.edge_weight(*edge_index), Some(Edge::Logic)))
+        .map(|(_edge_index, node_index_parent)| graph_info[node_index_parent].clone())
+        .collect::<Vec<_>>();
+    item_ids_parents.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    assert_eq!(
+        vec![item_id!("first__b"), item_id!("first__c")],
+        item_ids_parents
+    );
+    assert_eq!(2, item_graph.sub_flows().len());
+    Ok(())
+}
+
+/// Returns the IDs of the items that have a logic edge from the given item.
+fn item_ids_children(graph_info: &GraphInfo<ItemId>, item_id: &str) -> Vec<ItemId> {
+    let node_index = graph_info
+        .iter_insertion_with_indices()
+        .find_map(|(node_index, item_id_node)| {
+            (item_id_node.as_str() == item_id).then_some(node_index)
+        })
+        .unwrap();
+    let mut item_ids_children = graph_info
+        .children(node_index)
+        .iter(graph_info)
+        .filter(|(edge_index, _)| matches!(graph_info.edge_weight(*edge_index), Some(Edge::Logic)))
+        .map(|(_edge_index, node_index_child)| graph_info[node_index_child].clone())
+        .collect::<Vec<_>>();
+    item_ids_children.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    item_ids_children
+}
+
+/// Returns a flow with the following items:
+///
+/// ```text
+/// a -> b
+///   \-> c
+/// ```
+fn sub_flow() -> Result<Flow<PeaceTestError>, WouldCycle<Edge>> {
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    let [fn_id_a, fn_id_b, fn_id_c] = builder.add_fns([
+        BlankItem::<()>::new(item_id!("a")).into(),
+        BlankItem::<()>::new(item_id!("b")).into(),
+        BlankItem::<()>::new(item_id!("c")).into(),
+    ]);
+    builder.add_logic_edge(fn_id_a, fn_id_b)?;
+    builder.add_logic_edge(fn_id_a, fn_id_c)?;
+
+    Ok(Flow::new(flow_id!("upload"), builder.build()))
+}
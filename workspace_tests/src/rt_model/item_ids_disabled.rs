@@ -0,0 +1,41 @@
+use peace::{item_model::item_id, rt_model::ItemIdsDisabled};
+
+#[test]
+fn is_skipped_returns_true_for_disabled_items() {
+    let item_ids_disabled = ItemIdsDisabled::new(
+        vec![item_id!("a"), item_id!("b")],
+        vec![item_id!("b")],
+        false,
+    );
+
+    assert!(item_ids_disabled.contains(&item_id!("a")));
+    assert!(item_ids_disabled.is_skipped(&item_id!("a")));
+    assert!(item_ids_disabled.is_skipped(&item_id!("b")));
+    assert!(!item_ids_disabled.is_skipped(&item_id!("c")));
+    assert!(!item_ids_disabled.is_cleaned(&item_id!("b")));
+}
+
+#[test]
+fn is_cleaned_returns_true_for_stored_not_clean_items_when_clean_requested() {
+    let item_ids_disabled = ItemIdsDisabled::new(
+        vec![item_id!("a"), item_id!("b")],
+        vec![item_id!("b")],
+        true,
+    );
+
+    assert!(item_ids_disabled.stored_not_clean_clean());
+    assert_eq!(
+        &[item_id!("b")],
+        item_ids_disabled.item_ids_stored_not_clean()
+    );
+    assert!(item_ids_disabled.is_skipped(&item_id!("a")));
+    assert!(!item_ids_disabled.is_cleaned(&item_id!("a")));
+    assert!(!item_ids_disabled.is_skipped(&item_id!("b")));
+    assert!(item_ids_disabled.is_cleaned(&item_id!("b")));
+}
+
+#[test]
+fn is_empty_returns_true_when_no_items_disabled() {
+    assert!(ItemIdsDisabled::default().is_empty());
+    assert!(!ItemIdsDisabled::new(vec![item_id!("a")], Vec::new(), false).is_empty());
+}
@@ -0,0 +1,49 @@
+use peace::{item_model::item_id, rt_model::ItemIdsOrphaned};
+
+#[test]
+fn is_skipped_returns_true_for_retired_items_when_not_cleaned() {
+    let item_ids_orphaned = ItemIdsOrphaned::new(
+        vec![item_id!("removed"), item_id!("retired")],
+        vec![item_id!("retired")],
+    );
+
+    assert!(!item_ids_orphaned.clean());
+    assert!(item_ids_orphaned.contains(&item_id!("removed")));
+    assert!(!item_ids_orphaned.is_skipped(&item_id!("removed")));
+    assert!(item_ids_orphaned.is_skipped(&item_id!("retired")));
+    assert!(!item_ids_orphaned.is_cleaned(&item_id!("retired")));
+}
+
+#[test]
+fn is_cleaned_returns_true_for_retired_items_when_cleaned() {
+    let mut item_ids_orphaned = ItemIdsOrphaned::new(
+        vec![item_id!("removed"), item_id!("retired")],
+        vec![item_id!("retired")],
+    );
+    item_ids_orphaned.set_clean(true);
+
+    assert!(item_ids_orphaned.clean());
+    assert!(!item_ids_orphaned.is_cleaned(&item_id!("removed")));
+    assert!(item_ids_orphaned.is_cleaned(&item_id!("retired")));
+    assert!(!item_ids_orphaned.is_skipped(&item_id!("retired")));
+}
+
+#[test]
+fn item_ids_unretired_returns_orphans_that_are_not_retired() {
+    let item_ids_orphaned = ItemIdsOrphaned::new(
+        vec![item_id!("removed"), item_id!("retired")],
+        vec![item_id!("retired")],
+    );
+
+    assert_eq!(
+        vec![&item_id!("removed")],
+        item_ids_orphaned.item_ids_unretired().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        &[item_id!("removed"), item_id!("retired")],
+        item_ids_orphaned.item_ids()
+    );
+    assert_eq!(&[item_id!("retired")], item_ids_orphaned.item_ids_retired());
+    assert!(!item_ids_orphaned.is_empty());
+    assert!(ItemIdsOrphaned::default().is_empty());
+}
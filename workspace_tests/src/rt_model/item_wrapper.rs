@@ -305,6 +305,48 @@ async fn state_goal_try_exec() -> Result<(), VecCopyError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn state_goal_try_exec_returns_state_clean_when_state_goal_is_clean(
+) -> Result<(), VecCopyError> {
+    let vec_copy_item = VecCopyItem::default();
+    let item_wrapper = ItemWrapper::<_, VecCopyError>::from(vec_copy_item);
+    let (params_specs, mapping_fn_reg, resources) = resources_set_up(&item_wrapper).await?;
+    let item_rt = <dyn ItemRt<_>>::with_state_goal_clean(&item_wrapper);
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(
+                VecCopyItem::ID_DEFAULT,
+                &progress_tx,
+            );
+        }
+    }
+    let fn_ctx = FnCtx::new(
+        VecCopyItem::ID_DEFAULT,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    let state_goal = item_rt
+        .state_goal_try_exec(&params_specs, &mapping_fn_reg, &resources, fn_ctx)
+        .await?
+        .unwrap();
+
+    assert!(!item_wrapper.state_goal_is_clean());
+    assert!(item_rt.state_goal_is_clean());
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        BoxDataTypeDowncast::<VecCopyState>::downcast_ref(&state_goal)
+    );
+    // Automatic `Goal<State>` insertion.
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        resources.borrow::<Goal<VecCopyState>>().as_ref()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn state_diff_exec() -> Result<(), VecCopyError> {
     let vec_copy_item = VecCopyItem::default();
@@ -0,0 +1,67 @@
+use peace::{
+    flow_rt::{ItemGraphBuilder, ItemsRetired},
+    item_model::{item_id, ItemId},
+};
+use peace_items::blank::BlankItem;
+
+use crate::PeaceTestError;
+
+#[test]
+fn get_returns_registered_item() {
+    let items_retired = ItemsRetired::<PeaceTestError>::new()
+        .with_item(BlankItem::<()>::new(item_id!("one")))
+        .with_item(BlankItem::<()>::new(item_id!("two")));
+
+    assert_eq!(2, items_retired.len());
+    assert!(!items_retired.is_empty());
+    assert_eq!(
+        vec![&item_id!("one"), &item_id!("two")],
+        items_retired.item_ids().collect::<Vec<&ItemId>>()
+    );
+    assert_eq!(
+        Some(&item_id!("two")),
+        items_retired
+            .get(&item_id!("two"))
+            .map(|item_boxed| item_boxed.id())
+    );
+    assert!(items_retired.get(&item_id!("three")).is_none());
+}
+
+#[test]
+fn add_to_adds_retired_items_with_stored_state() {
+    let items_retired = ItemsRetired::<PeaceTestError>::new()
+        .with_item(BlankItem::<()>::new(item_id!("one")))
+        .with_item(BlankItem::<()>::new(item_id!("two")))
+        .with_item(BlankItem::<()>::new(item_id!("three")));
+    let item_ids_stored = [item_id!("present"), item_id!("three"), item_id!("one")];
+
+    let mut builder = ItemGraphBuilder::<PeaceTestError>::new();
+    builder.add_fn(BlankItem::<()>::new(item_id!("present")).into());
+    let fn_ids_retired = items_retired.add_to(&mut builder, &item_ids_stored);
+    let item_graph = builder.build();
+
+    let item_ids_and_clean = item_graph
+        .iter_insertion()
+        .map(|item| (item.id().clone(), item.state_goal_is_clean()))
+        .collect::<Vec<(ItemId, bool)>>();
+    assert_eq!(
+        vec![
+            (item_id!("present"), false),
+            (item_id!("one"), true),
+            (item_id!("three"), true),
+        ],
+        item_ids_and_clean
+    );
+    assert_eq!(2, fn_ids_retired.len());
+    assert_eq!(
+        &[item_id!("one"), item_id!("three")],
+        item_graph.item_ids_retired()
+    );
+}
+
+#[test]
+fn debug() {
+    let items_retired = ItemsRetired::<PeaceTestError>::new();
+
+    assert_eq!("ItemsRetired([])", format!("{items_retired:?}"));
+}
@@ -0,0 +1,145 @@
+use peace::{
+    params::Params,
+    rt_model::{ParamsApplied, ParamsFieldDiff},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Params, Serialize, Deserialize)]
+pub struct ServerParams {
+    name: String,
+    port: u16,
+    #[params(secret)]
+    password: String,
+}
+
+fn params_applied(server_params: &ServerParams) -> ParamsApplied {
+    let params_spec = ServerParams::field_wise_spec()
+        .with_name(server_params.name.clone())
+        .with_port(server_params.port)
+        .with_password(server_params.password.clone())
+        .build();
+
+    ParamsApplied::new(
+        serde_yaml::to_value(&params_spec).unwrap(),
+        Some(serde_yaml::to_value(server_params).unwrap()),
+    )
+}
+
+fn server_params() -> ServerParams {
+    ServerParams {
+        name: String::from("server_1"),
+        port: 80,
+        password: String::from("hunter2"),
+    }
+}
+
+#[test]
+fn field_names_secret_returns_fields_with_secret_attribute() {
+    assert_eq!(vec!["password"], ServerParams::field_names_secret());
+}
+
+#[test]
+fn field_diffs_returns_empty_when_params_unchanged() {
+    let params_applied_previous = params_applied(&server_params());
+    let params_applied_next = params_applied(&server_params());
+
+    let field_diffs = params_applied_next.field_diffs(
+        Some(&params_applied_previous),
+        &ServerParams::field_names_secret(),
+    );
+
+    assert!(field_diffs.is_empty(), "was {field_diffs:?}");
+}
+
+#[test]
+fn field_diffs_returns_changed_fields_with_secrets_masked() {
+    let params_applied_previous = params_applied(&server_params());
+    let params_applied_next = params_applied(&ServerParams {
+        port: 8080,
+        password: String::from("correct horse"),
+        ..server_params()
+    });
+
+    let field_diffs = params_applied_next.field_diffs(
+        Some(&params_applied_previous),
+        &ServerParams::field_names_secret(),
+    );
+
+    assert_eq!(
+        vec![
+            ParamsFieldDiff::new(
+                Some(String::from("port")),
+                Some(String::from("80")),
+                Some(String::from("8080")),
+            ),
+            ParamsFieldDiff::new(
+                Some(String::from("password")),
+                Some(String::from("***")),
+                Some(String::from("***")),
+            ),
+        ],
+        field_diffs
+    );
+}
+
+#[test]
+fn field_diffs_returns_all_fields_when_not_previously_applied() {
+    let params_applied_next = params_applied(&server_params());
+
+    let field_diffs = params_applied_next.field_diffs(None, &ServerParams::field_names_secret());
+
+    assert_eq!(
+        vec![
+            ParamsFieldDiff::new(
+                Some(String::from("name")),
+                None,
+                Some(String::from("\"server_1\"")),
+            ),
+            ParamsFieldDiff::new(Some(String::from("port")), None, Some(String::from("80"))),
+            ParamsFieldDiff::new(
+                Some(String::from("password")),
+                None,
+                Some(String::from("***")),
+            ),
+        ],
+        field_diffs
+    );
+}
+
+#[test]
+fn field_diffs_compares_params_specs_when_params_not_resolved() {
+    let params_applied_previous = params_applied(&server_params());
+    let params_applied_next = {
+        let mut params_applied_next = params_applied(&ServerParams {
+            name: String::from("server_2"),
+            ..server_params()
+        });
+        params_applied_next.params = None;
+        params_applied_next
+    };
+
+    let field_diffs = params_applied_next.field_diffs(
+        Some(&params_applied_previous),
+        &ServerParams::field_names_secret(),
+    );
+
+    assert_eq!(
+        vec![ParamsFieldDiff::new(
+            Some(String::from("name")),
+            Some(String::from("\"server_1\"")),
+            Some(String::from("\"server_2\"")),
+        )],
+        field_diffs
+    );
+}
+
+#[test]
+fn params_field_diff_display() {
+    let params_field_diff = ParamsFieldDiff::new(
+        Some(String::from("port")),
+        Some(String::from("80")),
+        Some(String::from("8080")),
+    );
+
+    assert_eq!("port: 80 -> 8080", params_field_diff.to_string());
+}
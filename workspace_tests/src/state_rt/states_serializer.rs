@@ -193,3 +193,176 @@ async fn deserialize_stored_error_maps_byte_indices() -> Result<(), Box<dyn std:
 
     Ok(())
 }
+
+#[tokio::test]
+async fn deserialize_item_ids_opt() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage;
+    let states_current_file = StatesCurrentFile::new(tempdir.path().join("states_current.yaml"));
+
+    let item_ids_none = StatesSerializer::<PeaceTestError>::deserialize_item_ids_opt(
+        &storage,
+        &states_current_file,
+    )
+    .await?;
+    assert_eq!(None, item_ids_none);
+
+    tokio::fs::write(&states_current_file, "one:\n  - 1\ntwo: 2\nthree: null\n").await?;
+    let item_ids = StatesSerializer::<PeaceTestError>::deserialize_item_ids_opt(
+        &storage,
+        &states_current_file,
+    )
+    .await?;
+
+    assert_eq!(
+        Some(vec![item_id!("one"), item_id!("two"), item_id!("three")]),
+        item_ids
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deserialize_item_ids_opt_returns_error_when_not_map(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage;
+    let states_current_file = StatesCurrentFile::new(tempdir.path().join("states_current.yaml"));
+    tokio::fs::write(&states_current_file, "- one\n- two\n").await?;
+
+    let error = StatesSerializer::<PeaceTestError>::deserialize_item_ids_opt(
+        &storage,
+        &states_current_file,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            PeaceTestError::PeaceRt(Error::StatesItemIdsDeserialize { path, error: _ })
+            if **path == *states_current_file
+        ),
+        "was {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialize_retains_orphaned_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage;
+    let states_current_file = StatesCurrentFile::new(tempdir.path().join("states_current.yaml"));
+    tokio::fs::write(&states_current_file, "one:\n  - 0\nremoved: 3\n").await?;
+
+    let item_one = item_id!("one");
+    let item_graph = {
+        let mut item_graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        item_graph_builder.add_fn(VecCopyItem::new(item_one.clone()).into());
+        item_graph_builder.build()
+    };
+    let states = {
+        let mut states_mut = StatesMut::new();
+        states_mut.insert(item_one.clone(), VecCopyState::from(vec![1u8]));
+        StatesCurrentStored::from(states_mut)
+    };
+    StatesSerializer::<PeaceTestError>::serialize(
+        &storage,
+        &item_graph,
+        &states,
+        &states_current_file,
+    )
+    .await?;
+
+    let serialized = tokio::fs::read_to_string(states_current_file).await?;
+    assert_eq!(
+        "\
+        one:\n\
+          - 1\n\
+        removed: 3\n\
+        ",
+        serialized
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialize_omits_retired_items_without_state() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage;
+    let states_current_file = StatesCurrentFile::new(tempdir.path().join("states_current.yaml"));
+    tokio::fs::write(&states_current_file, "one:\n  - 0\nretired: 3\n").await?;
+
+    let item_one = item_id!("one");
+    let item_graph = {
+        let mut item_graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        item_graph_builder.add_fn(VecCopyItem::new(item_one.clone()).into());
+        item_graph_builder.add_retired(MockItem::<()>::new(item_id!("retired")).into());
+        item_graph_builder.build()
+    };
+    let states = {
+        let mut states_mut = StatesMut::new();
+        states_mut.insert(item_one.clone(), VecCopyState::from(vec![1u8]));
+        StatesCurrentStored::from(states_mut)
+    };
+    StatesSerializer::<PeaceTestError>::serialize(
+        &storage,
+        &item_graph,
+        &states,
+        &states_current_file,
+    )
+    .await?;
+
+    let serialized = tokio::fs::read_to_string(states_current_file).await?;
+    assert_eq!(
+        "\
+        one:\n\
+          - 1\n\
+        ",
+        serialized
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deserialize_item_ids_orphaned_opt() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage;
+    let states_current_file = StatesCurrentFile::new(tempdir.path().join("states_current.yaml"));
+    let item_graph = {
+        let mut item_graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        item_graph_builder.add_fn(VecCopyItem::new(item_id!("one")).into());
+        item_graph_builder.add_retired(MockItem::<()>::new(item_id!("retired")).into());
+        item_graph_builder.build()
+    };
+
+    let item_ids_none = StatesSerializer::<PeaceTestError>::deserialize_item_ids_orphaned_opt(
+        &storage,
+        &item_graph,
+        &states_current_file,
+    )
+    .await?;
+    assert_eq!(None, item_ids_none);
+
+    tokio::fs::write(
+        &states_current_file,
+        "one:\n  - 1\nremoved: 2\nretired: 3\n",
+    )
+    .await?;
+    let item_ids_orphaned = StatesSerializer::<PeaceTestError>::deserialize_item_ids_orphaned_opt(
+        &storage,
+        &item_graph,
+        &states_current_file,
+    )
+    .await?;
+
+    assert_eq!(
+        Some(vec![item_id!("removed"), item_id!("retired")]),
+        item_ids_orphaned
+    );
+
+    Ok(())
+}
@@ -11,7 +11,7 @@ use peace::{
         Data,
     },
     item_model::{item_id, ItemId},
-    params::Params,
+    params::{ItemOutputs, Params},
     resource_rt::{resources::ts::Empty, states::StatesCurrentStored, Resources},
     rt_model::ItemWrapper,
 };
@@ -215,6 +215,10 @@ impl Item for VecCopyItem {
         Ok(state_target.clone())
     }
 
+    fn outputs(state: &Self::State) -> ItemOutputs {
+        ItemOutputs::new().with_output("len", state.len())
+    }
+
     async fn setup(&self, resources: &mut Resources<Empty>) -> Result<(), VecCopyError> {
         let vec_b = {
             let states_current_stored =
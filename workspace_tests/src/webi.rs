@@ -1 +1,6 @@
+mod flow_diagram_format;
 
+#[cfg(feature = "ssr")]
+mod flow_diagram_renderer;
+#[cfg(feature = "ssr")]
+mod flow_diagrams_export_cmd;
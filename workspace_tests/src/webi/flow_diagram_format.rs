@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use peace::webi_model::{FlowDiagramFormat, FlowDiagramFormatParseError};
+
+#[test]
+fn from_str_returns_ok_for_valid_formats() {
+    assert_eq!(
+        Ok(FlowDiagramFormat::Dot),
+        FlowDiagramFormat::from_str("dot")
+    );
+    assert_eq!(
+        Ok(FlowDiagramFormat::Mermaid),
+        FlowDiagramFormat::from_str("mermaid")
+    );
+    assert_eq!(
+        Ok(FlowDiagramFormat::Svg),
+        FlowDiagramFormat::from_str("svg")
+    );
+}
+
+#[test]
+fn from_str_returns_err_for_invalid_format() {
+    assert_eq!(
+        Err(FlowDiagramFormatParseError(String::from("png"))),
+        FlowDiagramFormat::from_str("png")
+    );
+}
+
+#[test]
+fn file_extension() {
+    assert_eq!("dot", FlowDiagramFormat::Dot.file_extension());
+    assert_eq!("mmd", FlowDiagramFormat::Mermaid.file_extension());
+    assert_eq!("svg", FlowDiagramFormat::Svg.file_extension());
+}
+
+#[test]
+fn parse_error_display_lists_valid_values() {
+    assert_eq!(
+        r#"Failed to parse flow diagram format from string: `"png"`. Valid values are ["dot", "mermaid", "svg"]"#,
+        FlowDiagramFormatParseError(String::from("png")).to_string()
+    );
+}
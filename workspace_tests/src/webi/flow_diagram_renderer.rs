@@ -0,0 +1,124 @@
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    item_model::item_id,
+    rt_model::{Workspace, WorkspaceSpec},
+    webi::output::{FlowDiagramRenderer, FlowDiagrams},
+    webi_model::{FlowDiagramFormat, WebiError},
+};
+
+use crate::{
+    mock_item::{MockItem, MockSrc},
+    peace_cmd_ctx_types::TestCctNoOpOutput,
+    NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn dot_renders_nodes_and_edges() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_diagrams = flow_diagrams(crate::fn_name_short!()).await?;
+
+    let dot_src = FlowDiagramRenderer::dot(&flow_diagrams.item_graph);
+
+    assert!(dot_src.starts_with("digraph "), "dot_src was:\n{dot_src}");
+    assert!(dot_src.contains("    mock [\n"), "dot_src was:\n{dot_src}");
+    assert!(dot_src.contains("    end [\n"), "dot_src was:\n{dot_src}");
+    assert!(dot_src.contains("mock -> end"), "dot_src was:\n{dot_src}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mermaid_renders_nodes_and_edges_with_prefixed_node_ids(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let flow_diagrams = flow_diagrams(crate::fn_name_short!()).await?;
+
+    let mermaid_src = FlowDiagramRenderer::mermaid(&flow_diagrams.item_graph);
+
+    assert_eq!(
+        r#"flowchart TB
+    node_mock["mock"]
+    node_end["end"]
+    node_mock --> node_end
+"#,
+        mermaid_src
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn render_returns_source_in_given_format() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_diagrams = flow_diagrams(crate::fn_name_short!()).await?;
+    let item_graph = &flow_diagrams.item_graph;
+
+    assert_eq!(
+        FlowDiagramRenderer::dot(item_graph),
+        FlowDiagramRenderer::render(item_graph, FlowDiagramFormat::Dot).await?
+    );
+    assert_eq!(
+        FlowDiagramRenderer::mermaid(item_graph),
+        FlowDiagramRenderer::render(item_graph, FlowDiagramFormat::Mermaid).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn svg_returns_svg_or_dot_not_found_error() -> Result<(), Box<dyn std::error::Error>> {
+    let flow_diagrams = flow_diagrams(crate::fn_name_short!()).await?;
+
+    // Whether Graphviz is installed depends on the environment the tests are
+    // run in.
+    match FlowDiagramRenderer::svg(&flow_diagrams.item_graph).await {
+        Ok(svg_src) => assert!(svg_src.contains("<svg"), "svg_src was:\n{svg_src}"),
+        Err(error) => assert!(
+            matches!(error, WebiError::FlowDiagramDotNotFound),
+            "error was: {error:?}"
+        ),
+    }
+
+    Ok(())
+}
+
+/// Returns the diagrams for a flow with a `mock` item followed by an item
+/// whose ID is the Mermaid keyword `end`.
+async fn flow_diagrams(flow_id: &'static str) -> Result<FlowDiagrams, Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        let mock_id = graph_builder.add_fn(MockItem::<()>::default().into());
+        let end_id = graph_builder.add_fn(VecCopyItem::new(item_id!("end")).into());
+        graph_builder.add_logic_edge(mock_id, end_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(flow_id)?, graph);
+    let output = &mut NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<MockItem<()>>(MockItem::<()>::ID_DEFAULT.clone(), MockSrc(1).into())
+        .with_item_params::<VecCopyItem>(item_id!("end"), VecA(vec![0, 1, 2]).into())
+        .await?;
+
+    let CmdCtxSpsfFields {
+        flow,
+        params_specs,
+        mapping_fn_reg,
+        resources,
+        ..
+    } = cmd_ctx.fields();
+    Ok(FlowDiagrams::calculate(
+        flow,
+        params_specs,
+        mapping_fn_reg,
+        resources,
+    ))
+}
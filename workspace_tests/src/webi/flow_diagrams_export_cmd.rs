@@ -0,0 +1,79 @@
+use peace::{
+    cfg::{app_name, profile},
+    cmd_ctx::{CmdCtxSpsf, CmdCtxSpsfFields, ProfileSelection},
+    flow_model::FlowId,
+    flow_rt::{Flow, ItemGraphBuilder},
+    rt_model::{Workspace, WorkspaceSpec},
+    webi::output::{FlowDiagramRenderer, FlowDiagrams, FlowDiagramsExportCmd},
+    webi_model::FlowDiagramFormat,
+};
+
+use crate::{
+    peace_cmd_ctx_types::TestCctNoOpOutput, NoOpOutput, PeaceTestError, VecA, VecCopyItem,
+};
+
+#[tokio::test]
+async fn exec_writes_each_diagram_in_each_format() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItem::default().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let output = &mut NoOpOutput;
+    let cmd_ctx = CmdCtxSpsf::<TestCctNoOpOutput>::builder()
+        .with_output(output.into())
+        .with_workspace((&workspace).into())
+        .with_profile_selection(ProfileSelection::Specified(profile!("test_profile")))
+        .with_flow((&flow).into())
+        .with_item_params::<VecCopyItem>(
+            VecCopyItem::ID_DEFAULT.clone(),
+            VecA(vec![0, 1, 2, 3, 4, 5, 6, 7]).into(),
+        )
+        .await?;
+    // The directory is created if it does not exist.
+    let diagram_dir = tempdir.path().join("doc").join("diagrams");
+
+    let diagram_paths = FlowDiagramsExportCmd::exec(
+        &cmd_ctx,
+        &diagram_dir,
+        &[FlowDiagramFormat::Dot, FlowDiagramFormat::Mermaid],
+    )
+    .await?;
+
+    let CmdCtxSpsfFields {
+        flow,
+        params_specs,
+        mapping_fn_reg,
+        resources,
+        ..
+    } = cmd_ctx.fields();
+    let flow_diagrams = FlowDiagrams::calculate(flow, params_specs, mapping_fn_reg, resources);
+    let diagram_dir = &diagram_dir;
+    let diagram_paths_expected = flow_diagrams
+        .iter()
+        .flat_map(|(diagram_name, _info_graph)| {
+            ["dot", "mmd"].into_iter().map(move |extension| {
+                diagram_dir.join(format!(
+                    "exec_writes_each_diagram_in_each_format.{diagram_name}.{extension}"
+                ))
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(diagram_paths_expected, diagram_paths);
+    assert_eq!(
+        FlowDiagramRenderer::dot(&flow_diagrams.item_graph),
+        tokio::fs::read_to_string(&diagram_paths[0]).await?
+    );
+    assert_eq!(
+        FlowDiagramRenderer::mermaid(&flow_diagrams.item_graph),
+        tokio::fs::read_to_string(&diagram_paths[1]).await?
+    );
+
+    Ok(())
+}